@@ -0,0 +1,47 @@
+//! Benchmarks of the workloads from `asn1_rs::bench`
+//!
+//! Run with `cargo bench` from the `bench` directory. Before measuring, each workload is run once with a
+//! counting allocator, and the number of allocations is checked against its declared maximum.
+
+use asn1_rs::bench::workloads;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn bench_workloads(c: &mut Criterion) {
+    for w in workloads() {
+        let input = w.input().expect("input generation failed");
+        let before = ALLOCATIONS.load(Ordering::Relaxed);
+        let res = w.run(&input);
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+        res.expect("workload failed");
+        w.assert_allocations(allocations);
+        c.bench_function(w.name, |b| b.iter(|| w.run(black_box(&input))));
+    }
+}
+
+criterion_group!(benches, bench_workloads);
+criterion_main!(benches);
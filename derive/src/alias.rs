@@ -3,7 +3,8 @@ use proc_macro2::Span;
 use quote::quote;
 use syn::{Data, Ident};
 
-pub fn derive_ber_alias(s: synstructure::Structure) -> proc_macro2::TokenStream {
+pub fn derive_ber_alias(mut s: synstructure::Structure) -> proc_macro2::TokenStream {
+    s.underscore_const(true);
     let ast = s.ast();
 
     let container = match &ast.data {
@@ -30,7 +31,8 @@ pub fn derive_ber_alias(s: synstructure::Structure) -> proc_macro2::TokenStream
     ts
 }
 
-pub fn derive_der_alias(s: synstructure::Structure) -> proc_macro2::TokenStream {
+pub fn derive_der_alias(mut s: synstructure::Structure) -> proc_macro2::TokenStream {
+    s.underscore_const(true);
     let ast = s.ast();
 
     let container = match &ast.data {
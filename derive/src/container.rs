@@ -1,8 +1,11 @@
 use proc_macro2::{Literal, Span, TokenStream};
 use quote::{quote, ToTokens};
 use syn::{
-    parse::ParseStream, parse_quote, spanned::Spanned, Attribute, DataStruct, DeriveInput, Field,
-    Fields, Ident, Lifetime, LitInt, Meta, Type, WherePredicate,
+    parse::{ParseStream, Parser},
+    parse_quote,
+    spanned::Spanned,
+    Attribute, DataStruct, DeriveInput, Field, Fields, Ident, Lifetime, Lit, LitInt, Meta,
+    NestedMeta, Type, WherePredicate,
 };
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -70,6 +73,10 @@ pub struct Container {
     pub fields: Vec<FieldInfo>,
     pub where_predicates: Vec<WherePredicate>,
     pub error: Option<Attribute>,
+    /// Accept content remaining after the last field
+    pub allow_trailing: bool,
+    /// Reject `DEFAULT` values explicitly encoded with their default value (DER parsers only)
+    pub strict_default: bool,
 
     is_any: bool,
 }
@@ -80,6 +87,15 @@ impl Container {
         ast: &DeriveInput,
         container_type: ContainerType,
     ) -> Self {
+        // container attributes can select SET or SEQUENCE
+        let attrs = parse_container_attrs(ast);
+        let container_type = match (container_type, attrs.container_type) {
+            (ContainerType::Alias, Some(_)) => {
+                panic!("#[asn1(sequence)] and #[asn1(set)] cannot be used on aliases")
+            }
+            (_, Some(ty)) => ty,
+            (ty, None) => ty,
+        };
         let mut is_any = false;
         match (container_type, &ds.fields) {
             (ContainerType::Alias, Fields::Unnamed(f)) => {
@@ -128,14 +144,26 @@ impl Container {
             fields,
             where_predicates,
             error,
+            allow_trailing: attrs.allow_trailing,
+            strict_default: attrs.strict_default,
             is_any,
         }
     }
 
     pub fn gen_tryfrom(&self) -> TokenStream {
         let field_names = &self.fields.iter().map(|f| &f.name).collect::<Vec<_>>();
-        let parse_content =
-            derive_ber_sequence_content(&self.fields, Asn1Type::Ber, self.error.is_some());
+        let parse_content = derive_ber_container_content(
+            self.container_type,
+            &self.fields,
+            Asn1Type::Ber,
+            self.error.is_some(),
+            false,
+        );
+        let check_consumed = if self.allow_trailing {
+            quote! { let _ = i; }
+        } else {
+            quote! { any.header.assert_consumed(i)?; }
+        };
         let lifetime = Lifetime::new("'ber", Span::call_site());
         let wh = &self.where_predicates;
         let error = if let Some(attr) = &self.error {
@@ -164,12 +192,27 @@ impl Container {
                 //
                 #parse_content
                 //
-                let _ = i; // XXX check if empty?
+                #check_consumed
                 Ok(Self{#(#field_names),*})
             }
         };
         // note: `gen impl` in synstructure takes care of appending extra where clauses if any, and removing
         // the `where` statement if there are none.
+        if self.container_type == ContainerType::Alias {
+            return quote! {
+                use asn1_rs::{Any, FromBer};
+                use core::convert::TryFrom;
+
+                gen impl<#lifetime> TryFrom<Any<#lifetime>> for @Self where #(#wh)+* {
+                    type Error = #error;
+
+                    fn try_from(any: Any<#lifetime>) -> asn1_rs::Result<Self, #error> {
+                        #fn_content
+                    }
+                }
+            };
+        }
+        // sequences and sets only read the content: parse by reference, so the `Any` can be kept
         quote! {
             use asn1_rs::{Any, FromBer};
             use core::convert::TryFrom;
@@ -178,6 +221,14 @@ impl Container {
                 type Error = #error;
 
                 fn try_from(any: Any<#lifetime>) -> asn1_rs::Result<Self, #error> {
+                    TryFrom::try_from(&any)
+                }
+            }
+
+            gen impl<'any, #lifetime> TryFrom<&'any Any<#lifetime>> for @Self where #(#wh)+* {
+                type Error = #error;
+
+                fn try_from(any: &'any Any<#lifetime>) -> asn1_rs::Result<Self, #error> {
                     #fn_content
                 }
             }
@@ -217,7 +268,7 @@ impl Container {
             let ty = &self.fields[0].type_;
             quote! {
                 any.tag().assert_eq(Self::TAG)?;
-                <#ty>::check_constraints(any)
+                <#ty>::check_constraints_ctx(any, ctx)
             }
         } else {
             let check_fields: Vec<_> = self
@@ -227,14 +278,22 @@ impl Container {
                     let ty = &field.type_;
                     quote! {
                         let (rem, any) = Any::from_der(rem)?;
-                        <#ty as CheckDerConstraints>::check_constraints(&any)?;
+                        <#ty as CheckDerConstraints>::check_constraints_ctx(&any, ctx)?;
                     }
                 })
                 .collect();
+            let check_consumed = if self.allow_trailing {
+                quote! { let _ = (header, rem); }
+            } else {
+                quote! { header.assert_consumed(rem)?; }
+            };
             quote! {
                 any.tag().assert_eq(Self::TAG)?;
+                let header = &any.header;
+                let ctx = ctx.enter()?;
                 let rem = &any.data;
                 #(#check_fields)*
+                #check_consumed
                 Ok(())
             }
         };
@@ -242,9 +301,13 @@ impl Container {
         // note: `gen impl` in synstructure takes care of appending extra where clauses if any, and removing
         // the `where` statement if there are none.
         quote! {
-            use asn1_rs::{CheckDerConstraints, Tagged};
+            use asn1_rs::{CheckDerConstraints, DerCheckContext, Tagged};
             gen impl<#lifetime> CheckDerConstraints for @Self where #(#wh)+* {
                 fn check_constraints(any: &Any) -> asn1_rs::Result<()> {
+                    Self::check_constraints_ctx(any, DerCheckContext::new())
+                }
+
+                fn check_constraints_ctx(any: &Any, ctx: DerCheckContext) -> asn1_rs::Result<()> {
                     #fn_content
                 }
             }
@@ -255,8 +318,20 @@ impl Container {
         let lifetime = Lifetime::new("'ber", Span::call_site());
         let wh = &self.where_predicates;
         let field_names = &self.fields.iter().map(|f| &f.name).collect::<Vec<_>>();
-        let parse_content =
-            derive_ber_sequence_content(&self.fields, Asn1Type::Der, self.error.is_some());
+        let parse_content = derive_ber_container_content(
+            self.container_type,
+            &self.fields,
+            Asn1Type::Der,
+            self.error.is_some(),
+            self.strict_default,
+        );
+        let check_consumed = if self.allow_trailing {
+            quote! { let _ = i; }
+        } else {
+            quote! {
+                any.header.assert_consumed(i).map_err(|e| asn1_rs::nom::Err::Error(e.into()))?;
+            }
+        };
         let error = if let Some(attr) = &self.error {
             get_attribute_meta(attr).expect("Invalid error attribute format")
         } else {
@@ -286,7 +361,7 @@ impl Container {
                 //
                 #parse_content
                 //
-                // let _ = i; // XXX check if empty?
+                #check_consumed
                 Ok((rem,Self{#(#field_names),*}))
             }
         };
@@ -302,6 +377,110 @@ impl Container {
             }
         }
     }
+
+    /// Generate a `ToDer` implementation, using the tag from the `Tagged` implementation
+    pub fn gen_toder(&self) -> TokenStream {
+        let len_stmts: Vec<_> = self
+            .fields
+            .iter()
+            .map(|f| {
+                f.gen_serialize(|value| {
+                    quote! { len += asn1_rs::ToDer::to_der_len(&#value)?; }
+                })
+            })
+            .collect();
+        let content_len = quote! {{
+            let mut len = 0usize;
+            #(#len_stmts)*
+            len
+        }};
+        let push_stmts: Vec<_> = self
+            .fields
+            .iter()
+            .map(|f| {
+                f.gen_serialize(|value| {
+                    quote! { items.push(asn1_rs::ToDer::to_der_vec(&#value)?); }
+                })
+            })
+            .collect();
+        let write_stmts: Vec<_> = self
+            .fields
+            .iter()
+            .map(|f| {
+                f.gen_serialize(|value| {
+                    quote! { num_bytes += asn1_rs::ToDer::write_der(&#value, writer)?; }
+                })
+            })
+            .collect();
+        let push_encoded_stmts: Vec<_> = self
+            .fields
+            .iter()
+            .map(|f| {
+                f.gen_serialize(|value| {
+                    quote! { items.push(asn1_rs::ToDer::to_encoded_vec(&#value, rules)?); }
+                })
+            })
+            .collect();
+        quote! {
+            gen impl asn1_rs::ToDer for @Self {
+                fn to_der_len(&self) -> asn1_rs::Result<usize> {
+                    let len = #content_len;
+                    let header = asn1_rs::Header::new(asn1_rs::Class::Universal, true, <Self as asn1_rs::Tagged>::TAG, asn1_rs::Length::Definite(len));
+                    Ok(asn1_rs::ToDer::to_der_len(&header)? + len)
+                }
+
+                fn write_der_header(&self, writer: &mut dyn std::io::Write) -> asn1_rs::SerializeResult<usize> {
+                    let len = #content_len;
+                    let header = asn1_rs::Header::new(asn1_rs::Class::Universal, true, <Self as asn1_rs::Tagged>::TAG, asn1_rs::Length::Definite(len));
+                    asn1_rs::ToDer::write_der_header(&header, writer)
+                }
+
+                fn write_der_content(&self, writer: &mut dyn std::io::Write) -> asn1_rs::SerializeResult<usize> {
+                    let mut num_bytes = 0;
+                    if <Self as asn1_rs::Tagged>::TAG == asn1_rs::Tag::Set {
+                        // X.690 section 10.3: SET components are sorted by tag (class, then
+                        // number), ignoring the constructed bit
+                        let mut items = Vec::new();
+                        #(#push_stmts)*
+                        items.sort_by_key(|item| {
+                            <asn1_rs::Header as asn1_rs::FromBer>::from_ber(item).map(|(_, header)| (header.class(), header.tag())).ok()
+                        });
+                        for item in &items {
+                            writer.write_all(item)?;
+                            num_bytes += item.len();
+                        }
+                    } else {
+                        #(#write_stmts)*
+                    }
+                    Ok(num_bytes)
+                }
+
+                fn write_encoded(&self, rules: asn1_rs::EncodingRules, writer: &mut dyn std::io::Write) -> asn1_rs::SerializeResult<usize> {
+                    if rules != asn1_rs::EncodingRules::Cer {
+                        return asn1_rs::ToDer::write_der(self, writer);
+                    }
+                    // CER: fields are encoded using their own type (X.690 section 9), in a
+                    // constructed object with the indefinite length form
+                    let mut items = Vec::new();
+                    #(#push_encoded_stmts)*
+                    if <Self as asn1_rs::Tagged>::TAG == asn1_rs::Tag::Set {
+                        // SET: items are ordered by tag (X.690 section 9.3)
+                        items.sort_by_key(|item| {
+                            <asn1_rs::Header as asn1_rs::FromBer>::from_ber(item).map(|(_, header)| (header.class(), header.tag())).ok()
+                        });
+                    }
+                    let header = asn1_rs::Header::new(asn1_rs::Class::Universal, true, <Self as asn1_rs::Tagged>::TAG, asn1_rs::Length::Indefinite);
+                    let mut num_bytes = asn1_rs::ToDer::write_der_raw(&header, writer)?;
+                    for item in &items {
+                        writer.write_all(item)?;
+                        num_bytes += item.len();
+                    }
+                    writer.write_all(&[0, 0])?;
+                    Ok(num_bytes + 2)
+                }
+            }
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -309,9 +488,104 @@ pub struct FieldInfo {
     pub name: Ident,
     pub type_: Type,
     pub default: Option<TokenStream>,
+    /// The `DEFAULT` value is compared using `DerDefault`
+    pub der_default: bool,
     pub optional: bool,
     pub tag: Option<(Asn1TagKind, Asn1TagClass, u16)>,
     pub map_err: Option<TokenStream>,
+    pub skip_serializing_if: Option<TokenStream>,
+}
+
+impl FieldInfo {
+    /// Generate the code serializing this field, using `f` to build the statement for a value
+    ///
+    /// The field is omitted if it is equal to its default value (X.690 section 11.5), if the
+    /// `skip_serializing_if` function returns true, or if it is `OPTIONAL`, tagged and absent.
+    fn gen_serialize<F>(&self, f: F) -> TokenStream
+    where
+        F: Fn(TokenStream) -> TokenStream,
+    {
+        let name = &self.name;
+        let mut skip = Vec::new();
+        if let Some(is_default) = self.gen_is_default(quote! { &self.#name }) {
+            skip.push(is_default);
+        }
+        if let Some(path) = &self.skip_serializing_if {
+            skip.push(quote! { #path(&self.#name) });
+        }
+        // OPTIONAL fields without DEFAULT are stored as `Option`
+        let is_option = self.optional && self.default.is_none();
+        let stmt = match self.tag {
+            Some((tag_kind, class, n)) => {
+                let tag = Literal::u16_unsuffixed(n);
+                let ctor = match tag_kind {
+                    Asn1TagKind::Explicit => quote! { explicit },
+                    Asn1TagKind::Implicit => quote! { implicit },
+                };
+                let tagged = |v: TokenStream| {
+                    quote! {
+                        asn1_rs::TaggedValue::<_, asn1_rs::Error, #tag_kind, {#class}, #tag>::#ctor(#v)
+                    }
+                };
+                if is_option {
+                    let stmt = f(tagged(quote! { value }));
+                    quote! {
+                        if let Some(value) = &self.#name {
+                            #stmt
+                        }
+                    }
+                } else {
+                    f(tagged(quote! { &self.#name }))
+                }
+            }
+            None => f(quote! { self.#name }),
+        };
+        if skip.is_empty() {
+            stmt
+        } else {
+            quote! {
+                if !(#(#skip)||*) {
+                    #stmt
+                }
+            }
+        }
+    }
+
+    /// Generate the expression testing if `header` has the tag of this field
+    ///
+    /// Return `None` if the field accepts any tag (untagged `Any` fields).
+    fn gen_tag_matches(&self, header: TokenStream) -> Option<TokenStream> {
+        if let Some((_, class, n)) = self.tag {
+            let tag = Literal::u16_unsuffixed(n);
+            return Some(quote! {
+                (#header.tag().0 == #tag && #header.class() as u8 == #class)
+            });
+        }
+        // OPTIONAL fields without DEFAULT are stored as `Option`
+        let ty = if self.optional && self.default.is_none() {
+            option_inner_type(&self.type_).unwrap_or(&self.type_)
+        } else {
+            &self.type_
+        };
+        if ty.to_token_stream().to_string().starts_with("Any") {
+            return None;
+        }
+        Some(quote! {
+            (#header.class() == asn1_rs::Class::Universal && #header.tag() == <#ty as asn1_rs::Tagged>::TAG)
+        })
+    }
+
+    /// Generate the expression testing if `value` (a reference) is equal to the `DEFAULT` value,
+    /// if the field has one
+    fn gen_is_default(&self, value: TokenStream) -> Option<TokenStream> {
+        let ty = &self.type_;
+        let default = self.default.as_ref()?;
+        if self.der_default {
+            Some(quote! { <#ty as asn1_rs::DerDefault>::is_default(#value) })
+        } else {
+            Some(quote! { { let default: #ty = #default; *#value == default } })
+        }
+    }
 }
 
 impl From<&Field> for FieldInfo {
@@ -321,6 +595,8 @@ impl From<&Field> for FieldInfo {
         let mut tag = None;
         let mut map_err = None;
         let mut default = None;
+        let mut der_default = false;
+        let mut skip_serializing_if = None;
         let name = field
             .ident
             .as_ref()
@@ -338,6 +614,7 @@ impl From<&Field> for FieldInfo {
                 "default" => {
                     let expr: syn::Expr = attr.parse_args().expect("could not parse default");
                     default = Some(quote! { #expr });
+                    der_default = false;
                     optional = true;
                 }
                 "optional" => optional = true,
@@ -355,6 +632,28 @@ impl From<&Field> for FieldInfo {
                     let (class, value) = attr.parse_args_with(parse_tag_args).unwrap();
                     tag = Some((Asn1TagKind::Implicit, class, value));
                 }
+                "asn1" => {
+                    let attrs = parse_field_attrs(attr);
+                    if attrs.optional {
+                        optional = true;
+                    }
+                    if let Some(expr) = attrs.default {
+                        default = Some(expr);
+                        der_default = attrs.der_default;
+                        optional = true;
+                    }
+                    if attrs.skip_serializing_if.is_some() {
+                        skip_serializing_if = attrs.skip_serializing_if;
+                    }
+                    match (attrs.tag, attrs.tag_kind) {
+                        (Some(_), _) if tag.is_some() => panic!("tag cannot be set twice!"),
+                        (Some((class, value)), kind) => {
+                            tag = Some((kind.unwrap_or(Asn1TagKind::Explicit), class, value));
+                        }
+                        (None, Some(_)) => panic!("explicit/implicit require a tag"),
+                        (None, None) => (),
+                    }
+                }
                 // ignore unknown attributes
                 _ => (),
             }
@@ -363,11 +662,129 @@ impl From<&Field> for FieldInfo {
             name,
             type_: field.ty.clone(),
             default,
+            der_default,
             optional,
             tag,
             map_err,
+            skip_serializing_if,
+        }
+    }
+}
+
+/// Options from a `#[asn1(...)]` field attribute
+#[derive(Default)]
+struct Asn1FieldAttrs {
+    tag: Option<(Asn1TagClass, u16)>,
+    tag_kind: Option<Asn1TagKind>,
+    optional: bool,
+    default: Option<TokenStream>,
+    der_default: bool,
+    skip_serializing_if: Option<TokenStream>,
+}
+
+/// Return the items of a `#[asn1(...)]` attribute
+fn get_asn1_items(attr: &Attribute) -> Vec<NestedMeta> {
+    match attr.parse_meta() {
+        Ok(Meta::List(meta)) => meta.nested.into_iter().collect(),
+        _ => panic!("Invalid asn1 attribute format, expected #[asn1(...)]"),
+    }
+}
+
+/// Parse a `#[asn1(...)]` field attribute, for ex.
+/// `#[asn1(tag = "0", explicit, optional, default = "Default::default")]`
+///
+/// `default` without a value uses `Default::default()`, compared using `DerDefault`
+fn parse_field_attrs(attr: &Attribute) -> Asn1FieldAttrs {
+    let mut attrs = Asn1FieldAttrs::default();
+    for item in get_asn1_items(attr) {
+        match item {
+            NestedMeta::Meta(Meta::Path(path)) => {
+                let kind = match path.get_ident().map(|i| i.to_string()).as_deref() {
+                    Some("optional") => {
+                        attrs.optional = true;
+                        continue;
+                    }
+                    Some("default") => {
+                        attrs.default = Some(quote! { ::core::default::Default::default() });
+                        attrs.der_default = true;
+                        continue;
+                    }
+                    Some("explicit") => Asn1TagKind::Explicit,
+                    Some("implicit") => Asn1TagKind::Implicit,
+                    _ => panic!("Unknown asn1 attribute: {}", path.into_token_stream()),
+                };
+                if attrs.tag_kind.is_some() {
+                    panic!("explicit/implicit cannot be set twice!");
+                }
+                attrs.tag_kind = Some(kind);
+            }
+            NestedMeta::Meta(Meta::NameValue(nv)) => {
+                let value = match &nv.lit {
+                    Lit::Str(s) => s,
+                    _ => panic!("asn1 attribute values must be strings"),
+                };
+                match nv.path.get_ident().map(|i| i.to_string()).as_deref() {
+                    Some("tag") => {
+                        let tag = parse_tag_args
+                            .parse_str(&value.value())
+                            .expect("could not parse tag");
+                        attrs.tag = Some(tag);
+                    }
+                    Some("default") => {
+                        let expr: syn::Expr = value.parse().expect("could not parse default");
+                        attrs.default = Some(quote! { #expr });
+                        attrs.der_default = false;
+                    }
+                    Some("skip_serializing_if") => {
+                        let path: syn::ExprPath =
+                            value.parse().expect("could not parse skip_serializing_if");
+                        attrs.skip_serializing_if = Some(quote! { #path });
+                    }
+                    _ => panic!("Unknown asn1 attribute: {}", nv.path.into_token_stream()),
+                }
+            }
+            _ => panic!("Invalid asn1 attribute format"),
         }
     }
+    attrs
+}
+
+/// Options from `#[asn1(...)]` container attributes
+#[derive(Default)]
+struct Asn1ContainerAttrs {
+    container_type: Option<ContainerType>,
+    allow_trailing: bool,
+    strict_default: bool,
+}
+
+/// Parse `#[asn1(...)]` container attributes: `sequence`, `set`, `allow_trailing` and
+/// `strict_default`
+fn parse_container_attrs(ast: &DeriveInput) -> Asn1ContainerAttrs {
+    let mut attrs = Asn1ContainerAttrs::default();
+    for attr in ast.attrs.iter().filter(|attr| attr.path.is_ident("asn1")) {
+        for item in get_asn1_items(attr) {
+            let ty = match item {
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("sequence") => {
+                    ContainerType::Sequence
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("set") => ContainerType::Set,
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("allow_trailing") => {
+                    attrs.allow_trailing = true;
+                    continue;
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("strict_default") => {
+                    attrs.strict_default = true;
+                    continue;
+                }
+                _ => panic!("Unknown asn1 container attribute"),
+            };
+            if attrs.container_type.is_some() {
+                panic!("container type cannot be set twice!");
+            }
+            attrs.container_type = Some(ty);
+        }
+    }
+    attrs
 }
 
 fn parse_tag_args(stream: ParseStream) -> Result<(Asn1TagClass, u16), syn::Error> {
@@ -391,14 +808,29 @@ fn parse_tag_args(stream: ParseStream) -> Result<(Asn1TagClass, u16), syn::Error
     Ok((tag_class, value))
 }
 
+fn derive_ber_container_content(
+    container_type: ContainerType,
+    fields: &[FieldInfo],
+    asn1_type: Asn1Type,
+    custom_errors: bool,
+    strict_default: bool,
+) -> TokenStream {
+    if container_type == ContainerType::Set {
+        derive_ber_set_content(fields, asn1_type, custom_errors, strict_default)
+    } else {
+        derive_ber_sequence_content(fields, asn1_type, custom_errors, strict_default)
+    }
+}
+
 fn derive_ber_sequence_content(
     fields: &[FieldInfo],
     asn1_type: Asn1Type,
     custom_errors: bool,
+    strict_default: bool,
 ) -> TokenStream {
     let field_parsers: Vec<_> = fields
         .iter()
-        .map(|f| get_field_parser(f, asn1_type, custom_errors))
+        .map(|f| get_field_parser(f, asn1_type, custom_errors, strict_default))
         .collect();
 
     quote! {
@@ -406,17 +838,120 @@ fn derive_ber_sequence_content(
     }
 }
 
-fn get_field_parser(f: &FieldInfo, asn1_type: Asn1Type, custom_errors: bool) -> TokenStream {
+/// Parse the components of a SET, which can appear in any order (X.680 section 27.3)
+///
+/// Each component is assigned to the first field not yet parsed with a matching tag. Parsing
+/// stops at the first component not matching any field: the remaining fields are then parsed
+/// from this position, so a missing required field reports the same error as for a SEQUENCE,
+/// and the component is reported as trailing content otherwise.
+fn derive_ber_set_content(
+    fields: &[FieldInfo],
+    asn1_type: Asn1Type,
+    custom_errors: bool,
+    strict_default: bool,
+) -> TokenStream {
+    let from = match asn1_type {
+        Asn1Type::Ber => quote! {FromBer::from_ber},
+        Asn1Type::Der => quote! {FromDer::from_der},
+    };
+    let map_err = default_map_err(asn1_type, custom_errors);
+    let slots: Vec<_> = fields
+        .iter()
+        .map(|f| Ident::new(&format!("__set_{}", f.name), Span::call_site()))
+        .collect();
+    let mut arms = Vec::new();
+    let mut wildcard_arms = Vec::new();
+    let mut finals = Vec::new();
+    for (f, slot) in fields.iter().zip(&slots) {
+        let name = &f.name;
+        let parser = get_field_parser(f, asn1_type, custom_errors, strict_default);
+        let parse_value = quote! {
+            let (rem, value) = {
+                #parser
+                (i, #name)
+            };
+            i = rem;
+        };
+        match f.gen_tag_matches(quote! { header }) {
+            Some(matches) => arms.push(quote! {
+                if #slot.is_none() && #matches {
+                    #parse_value
+                    #slot = Some(value);
+                    continue;
+                }
+            }),
+            None => wildcard_arms.push(quote! {
+                if #slot.is_none() {
+                    #parse_value
+                    #slot = Some(value);
+                    continue;
+                }
+            }),
+        }
+        finals.push(quote! {
+            let #name = match #slot {
+                Some(value) => value,
+                None => {
+                    #parse_value
+                    value
+                }
+            };
+        });
+    }
+    quote! {
+        let mut i = i;
+        #(let mut #slots = None;)*
+        while !i.is_empty() {
+            let (_, header): (_, asn1_rs::Header) = #from(i)#map_err?;
+            #(#arms)*
+            #(#wildcard_arms)*
+            break;
+        }
+        #(#finals)*
+    }
+}
+
+/// Error conversion for parsers of fields without `map_err`
+fn default_map_err(asn1_type: Asn1Type, custom_errors: bool) -> Option<TokenStream> {
+    // add mapping functions only if custom errors are used
+    if !custom_errors {
+        return None;
+    }
+    if asn1_type == Asn1Type::Ber {
+        Some(quote! { .finish() })
+    } else {
+        Some(quote! { .map_err(nom::Err::convert) })
+    }
+}
+
+fn get_field_parser(
+    f: &FieldInfo,
+    asn1_type: Asn1Type,
+    custom_errors: bool,
+    strict_default: bool,
+) -> TokenStream {
     let from = match asn1_type {
         Asn1Type::Ber => quote! {FromBer::from_ber},
         Asn1Type::Der => quote! {FromDer::from_der},
     };
     let name = &f.name;
+    // DER: a value equal to its DEFAULT must not be encoded (X.690 section 11.5)
+    let check_default = match f.gen_is_default(quote! { value }) {
+        Some(is_default) if strict_default && asn1_type == Asn1Type::Der => Some(quote! {
+            if let Some(value) = &#name {
+                if #is_default {
+                    let e = asn1_rs::Error::DerConstraintFailed(asn1_rs::DerConstraint::DefaultValueEncoded);
+                    return Err(asn1_rs::nom::Err::Error(e.into()));
+                }
+            }
+        }),
+        _ => None,
+    };
     let default = f
         .default
         .as_ref()
         // use a type hint, otherwise compiler will not know what type provides .unwrap_or
-        .map(|x| quote! {let #name: Option<_> = #name; let #name = #name.unwrap_or(#x);});
+        .map(|x| quote! {let #name: Option<_> = #name; #check_default let #name = #name.unwrap_or(#x);});
     let map_err = if let Some(tt) = f.map_err.as_ref() {
         if asn1_type == Asn1Type::Ber {
             Some(quote! { .finish().map_err(#tt) })
@@ -425,16 +960,7 @@ fn get_field_parser(f: &FieldInfo, asn1_type: Asn1Type, custom_errors: bool) ->
             Some(quote! { .map_err(|err| err.map(#tt)) })
         }
     } else {
-        // add mapping functions only if custom errors are used
-        if custom_errors {
-            if asn1_type == Asn1Type::Ber {
-                Some(quote! { .finish() })
-            } else {
-                Some(quote! { .map_err(nom::Err::convert) })
-            }
-        } else {
-            None
-        }
+        default_map_err(asn1_type, custom_errors)
     };
     if let Some((tag_kind, class, n)) = f.tag {
         let tag = Literal::u16_unsuffixed(n);
@@ -446,7 +972,7 @@ fn get_field_parser(f: &FieldInfo, asn1_type: Asn1Type, custom_errors: bool) ->
                         (i, None)
                     } else {
                         let (_, header): (_, asn1_rs::Header) = #from(i)#map_err?;
-                        if header.tag().0 == #tag {
+                        if header.tag().0 == #tag && header.class() as u8 == #class {
                             let (i, t): (_, asn1_rs::TaggedValue::<_, _, #tag_kind, {#class}, #tag>) = #from(i)#map_err?;
                             (i, Some(t.into_inner()))
                         } else {
@@ -475,6 +1001,24 @@ fn get_field_parser(f: &FieldInfo, asn1_type: Asn1Type, custom_errors: bool) ->
     }
 }
 
+/// Return `T` if `ty` is `Option<T>`
+fn option_inner_type(ty: &Type) -> Option<&Type> {
+    let segment = match ty {
+        Type::Path(type_path) => type_path.path.segments.last()?,
+        _ => return None,
+    };
+    if segment.ident != "Option" {
+        return None;
+    }
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => match args.args.first()? {
+            syn::GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 fn get_attribute_meta(attr: &Attribute) -> Result<TokenStream, syn::Error> {
     if let Ok(Meta::List(meta)) = attr.parse_meta() {
         let content = &meta.nested;
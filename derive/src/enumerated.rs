@@ -0,0 +1,387 @@
+use proc_macro2::{Literal, Span, TokenStream};
+use quote::{quote, ToTokens};
+use std::convert::TryFrom;
+use syn::{
+    Attribute, Data, DataEnum, DeriveInput, Expr, ExprLit, ExprUnary, Fields, Ident, Lit, Meta,
+    NestedMeta, Type, UnOp,
+};
+
+/// A variant of an enum mapped to `ENUMERATED` or `INTEGER` values
+struct EnumVariant {
+    ident: Ident,
+    /// The value, or `None` for the catch-all variant
+    value: Option<i64>,
+}
+
+/// An enum mapped to `ENUMERATED` (or `INTEGER`) values
+pub struct Asn1Enum {
+    ident: Ident,
+    variants: Vec<EnumVariant>,
+    /// Use `INTEGER` instead of `ENUMERATED`
+    integer: bool,
+}
+
+/// Return the items of `#[asn1(...)]` attributes
+fn asn1_items(attrs: &[Attribute]) -> Vec<NestedMeta> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("asn1"))
+        .flat_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(meta)) => meta.nested.into_iter(),
+            _ => panic!("Invalid asn1 attribute format, expected #[asn1(...)]"),
+        })
+        .collect()
+}
+
+fn parse_value(lit: &Lit) -> i64 {
+    let value = match lit {
+        Lit::Int(i) => i.base10_parse::<i64>(),
+        Lit::Str(s) => s
+            .value()
+            .parse::<i64>()
+            .map_err(|e| syn::Error::new(s.span(), e)),
+        _ => panic!("enumerated values must be integers"),
+    };
+    value.expect("enumerated values must be in the range of i64")
+}
+
+impl Asn1Enum {
+    pub fn from_ast(ast: &DeriveInput) -> Self {
+        let de = match &ast.data {
+            Data::Enum(de) => de,
+            _ => panic!("Unsupported type, cannot derive (expected enum)"),
+        };
+        if !ast.generics.params.is_empty() {
+            panic!("Generic enums are not supported");
+        }
+        let mut integer = false;
+        for item in asn1_items(&ast.attrs) {
+            match item {
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("integer") => integer = true,
+                _ => panic!("Unknown asn1 enum attribute"),
+            }
+        }
+        Asn1Enum {
+            ident: ast.ident.clone(),
+            variants: parse_variants(de, integer),
+            integer,
+        }
+    }
+
+    /// The Rust type of the raw values: `u32` for `ENUMERATED`, `i64` for `INTEGER`
+    fn raw_type(&self) -> TokenStream {
+        if self.integer {
+            quote! { i64 }
+        } else {
+            quote! { u32 }
+        }
+    }
+
+    /// The value `n`, as a literal of the raw type
+    fn raw_literal(&self, n: i64) -> Literal {
+        if self.integer {
+            Literal::i64_unsuffixed(n)
+        } else {
+            Literal::u32_unsuffixed(n as u32)
+        }
+    }
+
+    fn other_variant(&self) -> Option<&Ident> {
+        self.variants
+            .iter()
+            .find(|v| v.value.is_none())
+            .map(|v| &v.ident)
+    }
+
+    fn tag(&self) -> TokenStream {
+        if self.integer {
+            quote! { asn1_rs::Tag::Integer }
+        } else {
+            quote! { asn1_rs::Tag::Enumerated }
+        }
+    }
+
+    /// The type used to parse and encode values
+    fn value_type(&self) -> TokenStream {
+        if self.integer {
+            quote! { i64 }
+        } else {
+            quote! { asn1_rs::Enumerated }
+        }
+    }
+
+    /// Generate conversions from and to the raw type (`u32` or `i64`), `TryFrom<Any>` and
+    /// `Tagged`
+    pub fn gen_ber(&self) -> TokenStream {
+        let name = &self.ident;
+        let tag = self.tag();
+        let value_type = self.value_type();
+        let raw = self.raw_type();
+        let from_arms: Vec<_> = self
+            .variants
+            .iter()
+            .filter_map(|v| {
+                let ident = &v.ident;
+                v.value.map(|n| {
+                    let n = self.raw_literal(n);
+                    quote! { #n => Ok(#name::#ident), }
+                })
+            })
+            .collect();
+        let fallback = match self.other_variant() {
+            Some(ident) => quote! { v => Ok(#name::#ident(v)), },
+            None => quote! { _ => Err(#tag.invalid_value("unknown enumerated value")), },
+        };
+        let to_arms: Vec<_> = self
+            .variants
+            .iter()
+            .map(|v| {
+                let ident = &v.ident;
+                match v.value {
+                    Some(n) => {
+                        let n = self.raw_literal(n);
+                        quote! { #name::#ident => #n, }
+                    }
+                    None => quote! { #name::#ident(v) => *v, },
+                }
+            })
+            .collect();
+        let to_raw = if self.integer {
+            quote! { let value: i64 = TryFrom::try_from(any)?; }
+        } else {
+            quote! { let value = <#value_type as TryFrom<&Any>>::try_from(any)?.0; }
+        };
+        quote! {
+            use asn1_rs::Any;
+            use core::convert::TryFrom;
+
+            impl TryFrom<#raw> for #name {
+                type Error = asn1_rs::Error;
+
+                fn try_from(value: #raw) -> asn1_rs::Result<Self> {
+                    match value {
+                        #(#from_arms)*
+                        #fallback
+                    }
+                }
+            }
+
+            impl From<&#name> for #raw {
+                fn from(value: &#name) -> #raw {
+                    match value {
+                        #(#to_arms)*
+                    }
+                }
+            }
+
+            impl<'ber> TryFrom<Any<'ber>> for #name {
+                type Error = asn1_rs::Error;
+
+                fn try_from(any: Any<'ber>) -> asn1_rs::Result<Self> {
+                    TryFrom::try_from(&any)
+                }
+            }
+
+            impl<'any, 'ber> TryFrom<&'any Any<'ber>> for #name {
+                type Error = asn1_rs::Error;
+
+                fn try_from(any: &'any Any<'ber>) -> asn1_rs::Result<Self> {
+                    #to_raw
+                    TryFrom::try_from(value)
+                }
+            }
+
+            impl asn1_rs::Tagged for #name {
+                const TAG: asn1_rs::Tag = #tag;
+            }
+        }
+    }
+
+    /// Generate `CheckDerConstraints` and `DerAutoDerive`
+    pub fn gen_der(&self) -> TokenStream {
+        let name = &self.ident;
+        let value_type = self.value_type();
+        quote! {
+            impl asn1_rs::CheckDerConstraints for #name {
+                fn check_constraints(any: &asn1_rs::Any) -> asn1_rs::Result<()> {
+                    <#value_type as asn1_rs::CheckDerConstraints>::check_constraints(any)
+                }
+            }
+
+            impl asn1_rs::DerAutoDerive for #name {}
+        }
+    }
+
+    /// Generate `ToDer`, encoding the value of the variant
+    ///
+    /// The catch-all variant cannot hold the value of a named variant, since it would be parsed
+    /// as the named variant: encoding it returns an error.
+    pub fn gen_toder(&self) -> TokenStream {
+        let name = &self.ident;
+        let tag = self.tag();
+        let raw = self.raw_type();
+        let value = if self.integer {
+            quote! { i64::from(self) }
+        } else {
+            quote! { asn1_rs::Enumerated(u32::from(self)) }
+        };
+        let (check_fn, check) = match self.other_variant() {
+            Some(ident) => (
+                quote! {
+                    fn check_other(value: &#name) -> asn1_rs::Result<()> {
+                        match value {
+                            #name::#ident(v) if !matches!(<#name as core::convert::TryFrom<#raw>>::try_from(*v), Ok(#name::#ident(_))) => {
+                                Err(#tag.invalid_value("catch-all variant holds the value of a named variant"))
+                            }
+                            _ => Ok(()),
+                        }
+                    }
+                },
+                quote! { check_other(self)?; },
+            ),
+            None => (quote! {}, quote! {}),
+        };
+        quote! {
+            #check_fn
+
+            impl asn1_rs::ToDer for #name {
+                fn to_der_len(&self) -> asn1_rs::Result<usize> {
+                    #check
+                    asn1_rs::ToDer::to_der_len(&#value)
+                }
+
+                fn write_der_header(&self, writer: &mut dyn std::io::Write) -> asn1_rs::SerializeResult<usize> {
+                    #check
+                    asn1_rs::ToDer::write_der_header(&#value, writer)
+                }
+
+                fn write_der_content(&self, writer: &mut dyn std::io::Write) -> asn1_rs::SerializeResult<usize> {
+                    #check
+                    asn1_rs::ToDer::write_der_content(&#value, writer)
+                }
+            }
+        }
+    }
+}
+
+/// Parse variants: values are given by `#[asn1(value = n)]`, or by the discriminant (or the
+/// previous value plus one, as for Rust discriminants). One variant can be marked
+/// `#[asn1(other)]`, with a single field holding unknown values (`u32` for `ENUMERATED`, `i64`
+/// for `INTEGER`).
+fn parse_variants(de: &DataEnum, integer: bool) -> Vec<EnumVariant> {
+    let raw = if integer { "i64" } else { "u32" };
+    let mut next = 0i64;
+    let mut has_other = false;
+    de.variants
+        .iter()
+        .map(|v| {
+            let mut value = None;
+            let mut other = false;
+            for item in asn1_items(&v.attrs) {
+                match item {
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("other") => other = true,
+                    NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("value") => {
+                        value = Some(parse_value(&nv.lit));
+                    }
+                    _ => panic!("Unknown asn1 variant attribute"),
+                }
+            }
+            if other {
+                if has_other {
+                    panic!("Only one variant can be marked #[asn1(other)]");
+                }
+                has_other = true;
+                match &v.fields {
+                    Fields::Unnamed(f) if f.unnamed.len() == 1 => match &f.unnamed[0].ty {
+                        Type::Path(p) if p.qself.is_none() && p.path.is_ident(raw) => (),
+                        _ => panic!("#[asn1(other)] field must have type {}", raw),
+                    },
+                    _ => panic!("#[asn1(other)] variant must have a single ({}) field", raw),
+                }
+                return EnumVariant {
+                    ident: v.ident.clone(),
+                    value: None,
+                };
+            }
+            if !matches!(v.fields, Fields::Unit) {
+                panic!("Only the #[asn1(other)] variant can have fields");
+            }
+            let value = match (value, &v.discriminant) {
+                (Some(n), _) => n,
+                (None, Some((_, expr))) => discriminant_value(expr).unwrap_or_else(|| {
+                    panic!(
+                        "Unsupported discriminant {}, use #[asn1(value = n)]",
+                        expr.into_token_stream()
+                    )
+                }),
+                (None, None) => next,
+            };
+            if !integer && u32::try_from(value).is_err() {
+                panic!("enumerated values must be in the range of u32");
+            }
+            next = value.wrapping_add(1);
+            EnumVariant {
+                ident: v.ident.clone(),
+                value: Some(value),
+            }
+        })
+        .collect()
+}
+
+/// Return the value of a discriminant, if it is an integer literal (possibly negated)
+fn discriminant_value(expr: &Expr) -> Option<i64> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: lit @ Lit::Int(_),
+            ..
+        }) => Some(parse_value(lit)),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => discriminant_value(expr).map(|n| -n),
+        _ => None,
+    }
+}
+
+fn print_debug(ast: &DeriveInput, ts: &TokenStream) {
+    let debug_derive = ast.attrs.iter().any(|attr| {
+        attr.path
+            .is_ident(&Ident::new("debug_derive", Span::call_site()))
+    });
+    if debug_derive {
+        eprintln!("{}", ts);
+    }
+}
+
+pub fn derive_ber_enumerated(s: synstructure::Structure) -> TokenStream {
+    let ast = s.ast();
+    let asn1_enum = Asn1Enum::from_ast(ast);
+    let impl_ber = asn1_enum.gen_ber();
+    let ts = quote! {
+        const _: () = {
+            extern crate asn1_rs;
+
+            #impl_ber
+        };
+    };
+    print_debug(ast, &ts);
+    ts
+}
+
+pub fn derive_der_enumerated(s: synstructure::Structure) -> TokenStream {
+    let ast = s.ast();
+    let asn1_enum = Asn1Enum::from_ast(ast);
+    let impl_ber = asn1_enum.gen_ber();
+    let impl_der = asn1_enum.gen_der();
+    let ts = quote! {
+        const _: () = {
+            extern crate asn1_rs;
+
+            #impl_ber
+            #impl_der
+        };
+    };
+    print_debug(ast, &ts);
+    ts
+}
@@ -1,12 +1,19 @@
 mod alias;
 mod container;
+mod enumerated;
 mod sequence;
 mod set;
+mod to_der;
+mod to_static;
 use alias::*;
+use enumerated::*;
 use sequence::*;
 use set::*;
+use to_der::*;
+use to_static::*;
 
 synstructure::decl_derive!([BerAlias, attributes(
+    asn1,
     debug_derive,
     default,
     optional,
@@ -16,6 +23,7 @@ synstructure::decl_derive!([BerAlias, attributes(
     map_err
 )] => derive_ber_alias);
 synstructure::decl_derive!([DerAlias, attributes(
+    asn1,
     debug_derive,
     default,
     optional,
@@ -26,6 +34,7 @@ synstructure::decl_derive!([DerAlias, attributes(
 )] => derive_der_alias);
 
 synstructure::decl_derive!([BerSequence, attributes(
+    asn1,
     debug_derive,
     default,
     optional,
@@ -35,6 +44,7 @@ synstructure::decl_derive!([BerSequence, attributes(
     map_err
 )] => derive_ber_sequence);
 synstructure::decl_derive!([DerSequence, attributes(
+    asn1,
     debug_derive,
     default,
     optional,
@@ -45,6 +55,7 @@ synstructure::decl_derive!([DerSequence, attributes(
 )] => derive_der_sequence);
 
 synstructure::decl_derive!([BerSet, attributes(
+    asn1,
     debug_derive,
     default,
     optional,
@@ -54,6 +65,7 @@ synstructure::decl_derive!([BerSet, attributes(
     map_err
 )] => derive_ber_set);
 synstructure::decl_derive!([DerSet, attributes(
+    asn1,
     debug_derive,
     default,
     optional,
@@ -62,3 +74,17 @@ synstructure::decl_derive!([DerSet, attributes(
     error,
     map_err
 )] => derive_der_set);
+
+synstructure::decl_derive!([BerEnumerated, attributes(asn1, debug_derive)] => derive_ber_enumerated);
+synstructure::decl_derive!([DerEnumerated, attributes(asn1, debug_derive)] => derive_der_enumerated);
+
+synstructure::decl_derive!([ToDer, attributes(
+    asn1,
+    debug_derive,
+    default,
+    optional,
+    tag_explicit,
+    tag_implicit
+)] => derive_to_der);
+
+synstructure::decl_derive!([ToStatic, attributes(debug_derive)] => derive_to_static);
@@ -3,7 +3,8 @@ use proc_macro2::Span;
 use quote::quote;
 use syn::{Data, Ident};
 
-pub fn derive_ber_set(s: synstructure::Structure) -> proc_macro2::TokenStream {
+pub fn derive_ber_set(mut s: synstructure::Structure) -> proc_macro2::TokenStream {
+    s.underscore_const(true);
     let ast = s.ast();
 
     let container = match &ast.data {
@@ -30,7 +31,8 @@ pub fn derive_ber_set(s: synstructure::Structure) -> proc_macro2::TokenStream {
     ts
 }
 
-pub fn derive_der_set(s: synstructure::Structure) -> proc_macro2::TokenStream {
+pub fn derive_der_set(mut s: synstructure::Structure) -> proc_macro2::TokenStream {
+    s.underscore_const(true);
     let ast = s.ast();
 
     let container = match &ast.data {
@@ -0,0 +1,42 @@
+use crate::container::*;
+use crate::enumerated::Asn1Enum;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{Data, Ident};
+
+pub fn derive_to_der(mut s: synstructure::Structure) -> proc_macro2::TokenStream {
+    s.underscore_const(true);
+    let ast = s.ast();
+
+    let debug_derive = ast.attrs.iter().any(|attr| {
+        attr.path
+            .is_ident(&Ident::new("debug_derive", Span::call_site()))
+    });
+    let ts = match &ast.data {
+        Data::Struct(ds) => {
+            let container = Container::from_datastruct(ds, ast, ContainerType::Sequence);
+            let impl_toder = container.gen_toder();
+            s.gen_impl(quote! {
+                extern crate asn1_rs;
+
+                #impl_toder
+            })
+        }
+        // enums mapped to ENUMERATED or INTEGER values (see `DerEnumerated`)
+        Data::Enum(_) => {
+            let impl_toder = Asn1Enum::from_ast(ast).gen_toder();
+            quote! {
+                const _: () = {
+                    extern crate asn1_rs;
+
+                    #impl_toder
+                };
+            }
+        }
+        _ => panic!("Unsupported type, cannot derive"),
+    };
+    if debug_derive {
+        eprintln!("{}", ts);
+    }
+    ts
+}
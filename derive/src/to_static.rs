@@ -0,0 +1,67 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{GenericParam, Ident};
+use synstructure::AddBounds;
+
+pub fn derive_to_static(mut s: synstructure::Structure) -> proc_macro2::TokenStream {
+    s.underscore_const(true);
+    let ast = s.ast();
+
+    let debug_derive = ast.attrs.iter().any(|attr| {
+        attr.path
+            .is_ident(&Ident::new("debug_derive", Span::call_site()))
+    });
+
+    // the owned type: all lifetimes are replaced by 'static, and type parameters by their
+    // owned type
+    let name = &ast.ident;
+    let params: Vec<TokenStream> = ast
+        .generics
+        .params
+        .iter()
+        .map(|p| match p {
+            GenericParam::Lifetime(_) => quote! { 'static },
+            GenericParam::Type(t) => {
+                let ident = &t.ident;
+                quote! { <#ident as asn1_rs::ToStatic>::Owned }
+            }
+            GenericParam::Const(c) => {
+                let ident = &c.ident;
+                quote! { #ident }
+            }
+        })
+        .collect();
+    let owned = if params.is_empty() {
+        quote! { #name }
+    } else {
+        quote! { #name<#(#params),*> }
+    };
+
+    // type parameters must implement ToStatic
+    s.add_bounds(AddBounds::Generics);
+    let body = s.each_variant(|vi| {
+        let bindings = vi.bindings();
+        vi.construct(|_, i| {
+            let binding = &bindings[i];
+            quote! { asn1_rs::ToStatic::to_static(#binding) }
+        })
+    });
+
+    let ts = s.gen_impl(quote! {
+        extern crate asn1_rs;
+
+        gen impl asn1_rs::ToStatic for @Self {
+            type Owned = #owned;
+
+            fn to_static(&self) -> Self::Owned {
+                match *self {
+                    #body
+                }
+            }
+        }
+    });
+    if debug_derive {
+        eprintln!("{}", ts);
+    }
+    ts
+}
@@ -1,20 +1,28 @@
 extern crate proc_macro;
-use proc_macro::{Span, TokenStream};
+use proc_macro::{Delimiter, Span, TokenStream, TokenTree};
 use syn::{parse_macro_input, Error, LitInt};
 
 #[proc_macro]
 pub fn encode_oid(input: TokenStream) -> TokenStream {
-    let token_stream = input.to_string();
-    let (s, relative) = if token_stream.starts_with("rel ") {
-        (&token_stream[4..], true)
+    let (items, relative) = if is_arc_expression(&input) {
+        match parse_arc_expression(input) {
+            Ok(res) => res,
+            Err(msg) => return create_error(msg),
+        }
     } else {
-        (token_stream.as_ref(), false)
-    };
-    let items: Result<Vec<_>, _> = s.split('.').map(|x| x.trim().parse::<u128>()).collect();
-    let mut items: &[_] = match items.as_ref() {
-        Ok(v) => v.as_ref(),
-        Err(_) => return create_error("Could not parse OID"),
+        let token_stream = input.to_string();
+        let (s, relative) = if token_stream.starts_with("rel ") {
+            (&token_stream[4..], true)
+        } else {
+            (token_stream.as_ref(), false)
+        };
+        let items: Result<Vec<_>, _> = s.split('.').map(|x| x.trim().parse::<u128>()).collect();
+        match items {
+            Ok(v) => (v, relative),
+            Err(_) => return create_error("Could not parse OID"),
+        }
     };
+    let mut items: &[_] = &items;
     let mut v = Vec::new();
     if !relative {
         if items.len() < 2 {
@@ -44,6 +52,62 @@ pub fn encode_oid(input: TokenStream) -> TokenStream {
     s.parse().unwrap()
 }
 
+/// Return true if the input uses the ASN.1 value notation (for ex. `iso(1) member-body(2)`),
+/// with at least one parenthesized number
+fn is_arc_expression(input: &TokenStream) -> bool {
+    input.clone().into_iter().any(|tt| match tt {
+        TokenTree::Group(g) => g.delimiter() == Delimiter::Parenthesis,
+        _ => false,
+    })
+}
+
+/// Parse a list of arcs using the ASN.1 value notation (X.680 section 32.3)
+///
+/// Each arc is either a name followed by a parenthesized number (`member-body(2)`), or a number.
+/// Names are ignored. A leading `rel` keyword (not followed by a number) denotes a relative OID.
+fn parse_arc_expression(input: TokenStream) -> Result<(Vec<u128>, bool), &'static str> {
+    let tokens: Vec<_> = input.into_iter().collect();
+    let relative = match tokens.as_slice() {
+        [TokenTree::Ident(id), next, ..] if id.to_string() == "rel" => match next {
+            TokenTree::Group(g) => g.delimiter() != Delimiter::Parenthesis,
+            _ => true,
+        },
+        _ => false,
+    };
+    let tokens = if relative { &tokens[1..] } else { &tokens[..] };
+    let mut items = Vec::new();
+    // true if the previous token is part of a name
+    let mut in_name = false;
+    for tt in tokens {
+        match tt {
+            TokenTree::Ident(_) => in_name = true,
+            TokenTree::Punct(p) if p.as_char() == '-' && in_name => (),
+            TokenTree::Group(g) if g.delimiter() == Delimiter::Parenthesis && in_name => {
+                let n = g
+                    .stream()
+                    .to_string()
+                    .trim()
+                    .parse::<u128>()
+                    .map_err(|_| "Could not parse arc number")?;
+                items.push(n);
+                in_name = false;
+            }
+            TokenTree::Literal(l) if !in_name => {
+                let n = l
+                    .to_string()
+                    .parse::<u128>()
+                    .map_err(|_| "Could not parse arc number")?;
+                items.push(n);
+            }
+            _ => return Err("Invalid arc expression"),
+        }
+    }
+    if in_name {
+        return Err("Missing number for named arc");
+    }
+    Ok((items, relative))
+}
+
 #[inline]
 fn create_error(msg: &str) -> TokenStream {
     let s = format!("Invalid OID({})", msg);
@@ -0,0 +1,229 @@
+//! Copies and reassembly of object content using a custom allocator
+//!
+//! With the `allocator-copy` feature, the content of objects can be copied, and the content of
+//! constructed strings reassembled, to vectors allocated using any allocator implementing the
+//! [`Allocator`] trait from the `allocator-api2` crate (for ex. a memory pool).
+//!
+//! The `from_ber_in` functions parse objects, returning their content as an [`AllocCow`]: the
+//! content of primitive objects is borrowed from the input, and the content of constructed
+//! objects is reassembled to an [`AllocVec`] allocated using the provided allocator.
+//!
+//! Library types are not parameterized by an allocator: the owned data of parsed objects (for
+//! ex. the `Owned` variants of the `Cow` fields of `OctetString` or string types) always uses
+//! the global allocator.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use asn1_rs::allocator::Global;
+//! use asn1_rs::{AllocCow, OctetString};
+//!
+//! // constructed OCTET STRING, with 2 fragments
+//! let bytes = &[0x24, 0x80, 0x04, 0x02, 0x61, 0x62, 0x04, 0x01, 0x63, 0x00, 0x00];
+//! let (_, data) = OctetString::from_ber_in(bytes, Global).expect("parsing failed");
+//! assert!(matches!(data, AllocCow::Owned(_)));
+//! assert_eq!(&data[..], b"abc");
+//! // primitive OCTET STRING: content is borrowed
+//! let (_, data) = OctetString::from_ber_in(&[0x04, 0x01, 0x61], Global).expect("parsing failed");
+//! assert!(matches!(data, AllocCow::Borrowed(b"a")));
+//! ```
+
+use crate::asn1_types::reassemble_bitstring_with;
+use crate::ber::MAX_RECURSION;
+use crate::{Any, BitString, Error, FromBer, OctetString, ParseResult, Result, Tag, Tagged};
+pub use allocator_api2::alloc::{Allocator, Global};
+pub use allocator_api2::vec::Vec as AllocVec;
+use core::fmt;
+use core::ops::Deref;
+
+/// Content of an object, borrowed from the input or owned using a custom allocator
+///
+/// This is the equivalent of `Cow<'a, [u8]>`, for vectors allocated using an [`Allocator`].
+pub enum AllocCow<'a, A: Allocator> {
+    /// Content borrowed from the input
+    Borrowed(&'a [u8]),
+    /// Content allocated using `A`
+    Owned(AllocVec<u8, A>),
+}
+
+impl<A: Allocator> AllocCow<'_, A> {
+    /// Return true if the content is borrowed from the input
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self, AllocCow::Borrowed(_))
+    }
+}
+
+impl<A: Allocator> Deref for AllocCow<'_, A> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            AllocCow::Borrowed(b) => b,
+            AllocCow::Owned(v) => v,
+        }
+    }
+}
+
+impl<A: Allocator> AsRef<[u8]> for AllocCow<'_, A> {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl<A: Allocator + Clone> Clone for AllocCow<'_, A> {
+    fn clone(&self) -> Self {
+        match self {
+            AllocCow::Borrowed(b) => AllocCow::Borrowed(b),
+            AllocCow::Owned(v) => AllocCow::Owned(v.clone()),
+        }
+    }
+}
+
+impl<A: Allocator> fmt::Debug for AllocCow<'_, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AllocCow::Borrowed(b) => f.debug_tuple("Borrowed").field(b).finish(),
+            AllocCow::Owned(v) => f.debug_tuple("Owned").field(&&v[..]).finish(),
+        }
+    }
+}
+
+impl<A: Allocator, B: Allocator> PartialEq<AllocCow<'_, B>> for AllocCow<'_, A> {
+    fn eq(&self, other: &AllocCow<'_, B>) -> bool {
+        self[..] == other[..]
+    }
+}
+
+impl<A: Allocator> Eq for AllocCow<'_, A> {}
+
+impl<'a> Any<'a> {
+    /// Copy the content of this object to a vector allocated using `alloc`
+    pub fn data_to_vec_in<A: Allocator>(&self, alloc: A) -> AllocVec<u8, A> {
+        let mut v = AllocVec::with_capacity_in(self.data.len(), alloc);
+        v.extend_from_slice(self.data);
+        v
+    }
+
+    /// Get the content of a string object, reassembling fragments if the object is
+    /// constructed (BER only, X.690 section 8.7.3), to a vector allocated using `alloc`
+    ///
+    /// See [`Any::string_fragments`] for the accepted objects.
+    pub fn string_content_in<A: Allocator>(&self, alloc: A) -> Result<AllocVec<u8, A>> {
+        let mut v = AllocVec::new_in(alloc);
+        for fragment in self.string_fragments() {
+            v.extend_from_slice(fragment?);
+        }
+        Ok(v)
+    }
+
+    /// Get the content of a string object, borrowed if the object is primitive, or reassembled
+    /// to a vector allocated using `alloc` if it is constructed (BER only, X.690 section 8.7.3)
+    ///
+    /// See [`Any::string_fragments`] for the accepted objects.
+    pub fn string_content_cow_in<A: Allocator>(&self, alloc: A) -> Result<AllocCow<'a, A>> {
+        if self.header.is_primitive() && self.tag() != Tag::BitString {
+            return Ok(AllocCow::Borrowed(self.data));
+        }
+        self.string_content_in(alloc).map(AllocCow::Owned)
+    }
+}
+
+impl<'a> OctetString<'a> {
+    /// Parse a BER-encoded `OCTET STRING`, returning its content
+    ///
+    /// The content is borrowed if the object is primitive, or reassembled to a vector allocated
+    /// using `alloc` if it is constructed.
+    pub fn from_ber_in<A: Allocator>(
+        bytes: &'a [u8],
+        alloc: A,
+    ) -> ParseResult<'a, AllocCow<'a, A>> {
+        let (rem, any) = Any::from_ber(bytes)?;
+        any.tag().assert_eq(Self::TAG)?;
+        let data = any.string_content_cow_in(alloc)?;
+        Ok((rem, data))
+    }
+
+    /// Copy the content of this object to a vector allocated using `alloc`
+    pub fn to_vec_in<A: Allocator>(&self, alloc: A) -> AllocVec<u8, A> {
+        let data: &[u8] = self.as_ref();
+        let mut v = AllocVec::with_capacity_in(data.len(), alloc);
+        v.extend_from_slice(data);
+        v
+    }
+}
+
+impl<'a> BitString<'a> {
+    /// Parse a BER-encoded `BIT STRING`, returning the number of unused bits and the content
+    ///
+    /// The content is borrowed if the object is primitive, or reassembled to a vector allocated
+    /// using `alloc` if it is constructed (X.690 section 8.6.4).
+    pub fn from_ber_in<A: Allocator>(
+        bytes: &'a [u8],
+        alloc: A,
+    ) -> ParseResult<'a, (u8, AllocCow<'a, A>)> {
+        let (rem, any) = Any::from_ber(bytes)?;
+        any.tag().assert_eq(Self::TAG)?;
+        if any.header.is_constructed() {
+            let mut v = AllocVec::new_in(alloc);
+            let unused =
+                reassemble_bitstring_with(&any, MAX_RECURSION, |data| v.extend_from_slice(data))?;
+            return Ok((rem, (unused, AllocCow::Owned(v))));
+        }
+        let (unused, data) = any.data.split_first().ok_or(Error::InvalidLength)?;
+        Ok((rem, (*unused, AllocCow::Borrowed(data))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Global;
+    use crate::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn allocator_string_content() {
+        // primitive
+        let (_, any) = Any::from_ber(&hex!("0c 03 61 62 63")).expect("parsing failed");
+        let v = any.string_content_in(Global).expect("invalid string");
+        assert_eq!(&v[..], b"abc");
+        assert_eq!(&any.data_to_vec_in(Global)[..], b"abc");
+        // nested fragments
+        let input = &hex!("24 09 24 03 04 01 61 04 02 62 63");
+        let (_, any) = Any::from_ber(input).expect("parsing failed");
+        let v = any.string_content_in(Global).expect("invalid string");
+        assert_eq!(&v[..], b"abc");
+        // invalid fragment type
+        let input = &hex!("24 05 0c 03 61 62 63");
+        let (_, any) = Any::from_ber(input).expect("parsing failed");
+        assert!(any.string_content_in(Global).is_err());
+        // octet string
+        let s = OctetString::new(b"xyz");
+        assert_eq!(&s.to_vec_in(Global)[..], b"xyz");
+    }
+
+    #[test]
+    fn allocator_parse_in() {
+        // primitive: borrowed
+        let (_, any) = Any::from_ber(&hex!("04 02 61 62")).expect("parsing failed");
+        let data = any.string_content_cow_in(Global).expect("invalid string");
+        assert!(data.is_borrowed());
+        let (rem, data) = OctetString::from_ber_in(&hex!("04 01 61 ff"), Global).unwrap();
+        assert_eq!((rem, &data[..]), (&[0xff][..], &b"a"[..]));
+        assert!(data.is_borrowed());
+        // constructed: owned
+        let input = &hex!("24 09 24 03 04 01 61 04 02 62 63");
+        let (_, data) = OctetString::from_ber_in(input, Global).expect("parsing failed");
+        assert_eq!(data, AllocCow::<Global>::Borrowed(b"abc"));
+        assert!(!data.is_borrowed());
+        assert!(OctetString::from_ber_in(&hex!("0c 01 61"), Global).is_err());
+        // bit strings
+        let (_, (unused, data)) = BitString::from_ber_in(&hex!("03 02 04 f0"), Global).unwrap();
+        assert_eq!((unused, &data[..]), (4, &[0xf0][..]));
+        assert!(data.is_borrowed());
+        let input = &hex!("23 08 03 02 00 0a 03 02 04 f0");
+        let (_, (unused, data)) = BitString::from_ber_in(input, Global).expect("parsing failed");
+        assert_eq!((unused, &data[..]), (4, &[0x0a, 0xf0][..]));
+        assert!(!data.is_borrowed());
+        assert!(BitString::from_ber_in(&hex!("03 00"), Global).is_err());
+    }
+}
@@ -0,0 +1,132 @@
+//! Arena allocation of parsed objects
+//!
+//! Parsed objects usually borrow the input buffer. To keep them after the input is released, the
+//! `to_owned`/`to_static` methods copy the data, using one heap allocation per object.
+//!
+//! With the `bumpalo` feature, objects can instead be copied into a [`Bump`] arena using the
+//! `to_owned_in` methods: allocations are very cheap, and all objects are freed at once when the
+//! arena is dropped or reset. This is useful for servers parsing many messages, each message
+//! using its own arena.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use asn1_rs::{Any, Bump, FromDer};
+//!
+//! let bump = Bump::new();
+//! let any = {
+//!     let input = vec![0x04, 0x03, 0x01, 0x02, 0x03];
+//!     let (_, any) = Any::from_der(&input).expect("parsing failed");
+//!     // copy into arena: the result does not borrow `input`
+//!     any.to_owned_in(&bump)
+//! };
+//! assert_eq!(any.data, &[1, 2, 3]);
+//! ```
+
+use crate::*;
+use alloc::borrow::Cow;
+pub use bumpalo::Bump;
+
+impl<'a> Header<'a> {
+    /// Copy this header into the arena `bump`
+    pub fn to_owned_in<'b>(&self, bump: &'b Bump) -> Header<'b> {
+        let raw_tag = self
+            .raw_tag
+            .as_ref()
+            .map(|b| Cow::Borrowed(&*bump.alloc_slice_copy(&b[..])));
+        Header {
+            class: self.class,
+            constructed: self.constructed,
+            tag: self.tag,
+            length: self.length,
+            raw_tag,
+            length_encoding: self.length_encoding,
+        }
+    }
+}
+
+impl<'a> Any<'a> {
+    /// Copy this object (header and data) into the arena `bump`
+    pub fn to_owned_in<'b>(&self, bump: &'b Bump) -> Any<'b> {
+        Any {
+            header: self.header.to_owned_in(bump),
+            data: bump.alloc_slice_copy(self.data),
+        }
+    }
+}
+
+impl<'a> OctetString<'a> {
+    /// Copy this object into the arena `bump`
+    pub fn to_owned_in<'b>(&self, bump: &'b Bump) -> OctetString<'b> {
+        OctetString::new(bump.alloc_slice_copy(self.as_ref()))
+    }
+}
+
+impl<'a> BitString<'a> {
+    /// Copy this object into the arena `bump`
+    pub fn to_owned_in<'b>(&self, bump: &'b Bump) -> BitString<'b> {
+        BitString::new(self.unused_bits, bump.alloc_slice_copy(&self.data[..]))
+    }
+}
+
+macro_rules! impl_string_to_owned_in {
+    ($($name:ident),* $(,)?) => {
+        $(
+            impl<'a> $name<'a> {
+                /// Copy this object into the arena `bump`
+                pub fn to_owned_in<'b>(&self, bump: &'b Bump) -> $name<'b> {
+                    $name::new(bump.alloc_str(&self.data))
+                }
+            }
+        )*
+    };
+}
+
+impl_string_to_owned_in!(
+    BmpString,
+    GeneralString,
+    GraphicString,
+    Ia5String,
+    NumericString,
+    PrintableString,
+    TeletexString,
+    UniversalString,
+    Utf8String,
+    VideotexString,
+    VisibleString,
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn arena_any() {
+        let bump = Bump::new();
+        let input = hex!("30 06 02 01 02 0c 01 61").to_vec();
+        let (_, any) = Any::from_ber(&input).expect("parsing failed");
+        let owned = any.to_owned_in(&bump);
+        drop(input);
+        assert_eq!(owned.tag(), Tag::Sequence);
+        assert_eq!(owned.data, &hex!("02 01 02 0c 01 61"));
+        assert_eq!(owned.header.length(), Length::Definite(6));
+    }
+
+    #[test]
+    fn arena_strings() {
+        let bump = Bump::new();
+        let (s, o) = {
+            let input = hex!("0c 03 61 62 63 04 02 01 02").to_vec();
+            let (rem, s) = Utf8String::from_der(&input).expect("parsing failed");
+            let (_, o) = OctetString::from_der(rem).expect("parsing failed");
+            (s.to_owned_in(&bump), o.to_owned_in(&bump))
+        };
+        assert_eq!(s.as_ref(), "abc");
+        assert_eq!(o.as_ref(), &[1, 2]);
+        // BmpString is decoded to UTF-8 (owned), and copied to the arena
+        let input = &hex!("1e 04 00 61 00 62");
+        let (_, b) = BmpString::from_der(input).expect("parsing failed");
+        assert_eq!(b.to_owned_in(&bump).as_ref(), "ab");
+    }
+}
@@ -0,0 +1,144 @@
+use crate::*;
+use alloc::string::ToString;
+use core::convert::TryFrom;
+
+/// The `AlgorithmIdentifier` type, used in many standards (X.509, PKCS#8, CMS, etc.)
+///
+/// <pre>
+/// AlgorithmIdentifier  ::=  SEQUENCE  {
+///      algorithm               OBJECT IDENTIFIER,
+///      parameters              ANY DEFINED BY algorithm OPTIONAL  }
+/// </pre>
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{oid, AlgorithmIdentifier, FromDer};
+///
+/// // sha256WithRSAEncryption, with NULL parameters
+/// let bytes = &[
+///     0x30, 0x0d, 0x06, 0x09, 0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0b, 0x05, 0x00,
+/// ];
+/// let (_, alg) = AlgorithmIdentifier::from_der(bytes).expect("parsing failed");
+/// assert_eq!(alg.algorithm, oid!(1.2.840.113549.1.1.11));
+/// assert!(alg.assert_null_params().is_ok());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AlgorithmIdentifier<'a> {
+    pub algorithm: Oid<'a>,
+    pub parameters: Option<Any<'a>>,
+}
+
+impl<'a> AlgorithmIdentifier<'a> {
+    /// Build a new `AlgorithmIdentifier`
+    pub const fn new(algorithm: Oid<'a>, parameters: Option<Any<'a>>) -> Self {
+        AlgorithmIdentifier {
+            algorithm,
+            parameters,
+        }
+    }
+
+    /// Build a new `AlgorithmIdentifier` with `NULL` parameters
+    pub const fn with_null_params(algorithm: Oid<'a>) -> Self {
+        let null = Any::from_tag_and_data(Tag::Null, &[]);
+        Self::new(algorithm, Some(null))
+    }
+
+    /// Return error if parameters are not present, or are not `NULL`
+    pub fn assert_null_params(&self) -> Result<()> {
+        match &self.parameters {
+            Some(any) => {
+                any.header.assert_tag(Tag::Null)?;
+                if !any.data.is_empty() {
+                    return Err(Error::InvalidLength);
+                }
+                Ok(())
+            }
+            None => Err(Error::invalid_value(
+                Tag::Null,
+                "missing NULL parameters".to_string(),
+            )),
+        }
+    }
+
+    /// Attempt to convert parameters to type `T`
+    ///
+    /// Returns `None` if parameters are absent.
+    pub fn params_as<T>(&self) -> Result<Option<T>>
+    where
+        T: TryFrom<Any<'a>, Error = Error>,
+    {
+        self.parameters.clone().map(T::try_from).transpose()
+    }
+}
+
+impl<'a> TryFrom<Any<'a>> for AlgorithmIdentifier<'a> {
+    type Error = Error;
+
+    fn try_from(any: Any<'a>) -> Result<Self> {
+        TryFrom::try_from(&any)
+    }
+}
+
+impl<'a, 'b> TryFrom<&'b Any<'a>> for AlgorithmIdentifier<'a> {
+    type Error = Error;
+
+    fn try_from(any: &'b Any<'a>) -> Result<Self> {
+        any.tag().assert_eq(Self::TAG)?;
+        any.header.assert_constructed()?;
+        let (rem, algorithm) = Oid::from_ber(any.data)?;
+        let parameters = if rem.is_empty() {
+            None
+        } else {
+            let (rem, params) = Any::from_ber(rem)?;
+            any.header.assert_consumed(rem)?;
+            Some(params)
+        };
+        Ok(AlgorithmIdentifier {
+            algorithm,
+            parameters,
+        })
+    }
+}
+
+impl<'a> CheckDerConstraints for AlgorithmIdentifier<'a> {
+    fn check_constraints(any: &Any) -> Result<()> {
+        any.tag().assert_eq(Self::TAG)?;
+        any.header.assert_constructed()?;
+        let (rem, algorithm) = Any::from_der(any.data)?;
+        Oid::check_constraints(&algorithm)?;
+        if !rem.is_empty() {
+            let (rem, params) = Any::from_der(rem)?;
+            any.header.assert_consumed(rem)?;
+            Any::check_constraints(&params)?;
+        }
+        Ok(())
+    }
+}
+
+impl DerAutoDerive for AlgorithmIdentifier<'_> {}
+
+impl<'a> Tagged for AlgorithmIdentifier<'a> {
+    const TAG: Tag = Tag::Sequence;
+}
+
+#[cfg(feature = "std")]
+impl ToDer for AlgorithmIdentifier<'_> {
+    fn to_der_len(&self) -> Result<usize> {
+        let sz = self.algorithm.to_der_len()? + self.parameters.to_der_len()?;
+        let header = Header::new(Class::Universal, true, Self::TAG, Length::Definite(sz));
+        Ok(header.to_der_len()? + sz)
+    }
+
+    fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        let sz = self.algorithm.to_der_len()? + self.parameters.to_der_len()?;
+        let header = Header::new(Class::Universal, true, Self::TAG, Length::Definite(sz));
+        header.write_der_header(writer)
+    }
+
+    fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        let sz = self.algorithm.write_der(writer)?;
+        let sz = sz + self.parameters.write_der(writer)?;
+        Ok(sz)
+    }
+}
@@ -1,8 +1,13 @@
 use crate::ber::*;
+use crate::profile::check_universal_der;
 use crate::*;
 use alloc::borrow::Cow;
 use alloc::string::String;
+use alloc::vec::Vec;
+use core::cell::{Ref, RefCell};
 use core::convert::{TryFrom, TryInto};
+use core::marker::PhantomData;
+use core::ops::Deref;
 
 /// The `Any` object is not strictly an ASN.1 type, but holds a generic description of any object
 /// that could be encoded.
@@ -36,11 +41,80 @@ impl<'a> Any<'a> {
                 class: Class::Universal,
                 length: Length::Definite(data.len()),
                 raw_tag: None,
+                length_encoding: None,
             },
             data,
         }
     }
 
+    /// Create a new `Any` from class, constructed flag, tag and content, checking that they are
+    /// coherent
+    ///
+    /// The length is set to the length of `data`. This function returns an error if the
+    /// constructed flag is not allowed for this tag (see [`Header::check_form`]), or if the
+    /// object is an end-of-contents marker with content.
+    ///
+    /// This is the checked version of [`Any::from_tag_and_data`], for any class.
+    ///
+    /// ```rust
+    /// use asn1_rs::{Any, Class, Error, Tag};
+    ///
+    /// let any = Any::try_from_tag_and_data(Class::ContextSpecific, true, Tag(2), &[0x05, 0x00]);
+    /// assert!(any.is_ok());
+    /// // INTEGER is primitive
+    /// let any = Any::try_from_tag_and_data(Class::Universal, true, Tag::Integer, &[0x01]);
+    /// assert_eq!(any, Err(Error::ConstructUnexpected));
+    /// ```
+    pub fn try_from_tag_and_data(
+        class: Class,
+        constructed: bool,
+        tag: Tag,
+        data: &'a [u8],
+    ) -> Result<Self> {
+        let header = Header::new(class, constructed, tag, Length::Definite(data.len()));
+        let any = Any { header, data };
+        any.check_form()?;
+        Ok(any)
+    }
+
+    /// Check that the header and content of this object are coherent
+    ///
+    /// See [`Any::try_from_tag_and_data`].
+    pub fn check_form(&self) -> Result<()> {
+        self.header.check_form()?;
+        if self.header.is_universal()
+            && self.header.tag == Tag::EndOfContent
+            && !self.data.is_empty()
+        {
+            return Err(Error::InvalidEndOfContent);
+        }
+        Ok(())
+    }
+
+    /// Update the class of the current object, checking that the result is coherent
+    pub fn try_with_class(self, class: Class) -> Result<Self> {
+        let any = self.with_class(class);
+        any.check_form()?;
+        Ok(any)
+    }
+
+    /// Update the tag of the current object, checking that the result is coherent
+    pub fn try_with_tag(self, tag: Tag) -> Result<Self> {
+        let any = self.with_tag(tag);
+        any.check_form()?;
+        Ok(any)
+    }
+
+    /// Update the constructed flag of the current object, checking that the result is coherent
+    pub fn try_with_constructed(self, constructed: bool) -> Result<Self> {
+        let any = Any {
+            header: self.header.with_constructed(constructed),
+            data: self.data,
+        };
+        any.check_form()?;
+        Ok(any)
+    }
+
     /// Return the `Class` of this object
     #[inline]
     pub const fn class(&self) -> Class {
@@ -101,10 +175,10 @@ impl<'a> Any<'a> {
         let (rem, any) = Any::from_ber(bytes).map_err(Err::convert)?;
         any.tag()
             .assert_eq(Tag(tag))
-            .map_err(|e| nom::Err::Error(e.into()))?;
+            .map_err(|e| Err::Error(e.into()))?;
         any.class()
             .assert_eq(class)
-            .map_err(|e| nom::Err::Error(e.into()))?;
+            .map_err(|e| Err::Error(e.into()))?;
         let (_, res) = op(any.data)?;
         Ok((rem, res))
     }
@@ -125,10 +199,10 @@ impl<'a> Any<'a> {
         let (rem, any) = Any::from_der(bytes).map_err(Err::convert)?;
         any.tag()
             .assert_eq(Tag(tag))
-            .map_err(|e| nom::Err::Error(e.into()))?;
+            .map_err(|e| Err::Error(e.into()))?;
         any.class()
             .assert_eq(class)
-            .map_err(|e| nom::Err::Error(e.into()))?;
+            .map_err(|e| Err::Error(e.into()))?;
         let (_, res) = op(any.data)?;
         Ok((rem, res))
     }
@@ -153,6 +227,67 @@ impl<'a> Any<'a> {
         header.assert_definite()?;
         DerParser::get_object_content(i, header, 8)
     }
+
+    /// Skip the first object of `input` (parsed as BER), and return the remaining bytes
+    ///
+    /// The header is parsed, and the content is skipped without building `Any` values for nested
+    /// objects. Objects using the indefinite form of length are walked until their end-of-contents
+    /// marker, at all nesting levels. This is useful for scanners only extracting a few fields
+    /// of large records.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use asn1_rs::Any;
+    ///
+    /// // SEQUENCE (indefinite) { NULL }, INTEGER 5
+    /// let bytes = &[0x30, 0x80, 0x05, 0x00, 0x00, 0x00, 0x02, 0x01, 0x05];
+    /// let rem = Any::skip(bytes).expect("invalid object");
+    /// assert_eq!(rem, &[0x02, 0x01, 0x05]);
+    /// ```
+    pub fn skip(input: &[u8]) -> Result<&[u8]> {
+        let (i, header) = Header::from_ber(input)?;
+        let (rem, _) = BerParser::get_object_content(i, &header, MAX_RECURSION)?;
+        Ok(rem)
+    }
+
+    /// Skip the first `n` objects of `input` (parsed as BER), and return the remaining bytes
+    ///
+    /// See [`Any::skip`]. Returns an error if `input` contains less than `n` objects.
+    pub fn skip_n(input: &[u8], n: usize) -> Result<&[u8]> {
+        let mut i = input;
+        for _ in 0..n {
+            i = Any::skip(i)?;
+        }
+        Ok(i)
+    }
+
+    /// Get the inner object of a context-specific `EXPLICIT` tagged object
+    ///
+    /// This function checks that the current object has class `ContextSpecific` and tag
+    /// `expected_tag`, and is constructed. The content must contain exactly one object (parsed as
+    /// BER), which is returned. Trailing bytes in the content are an error
+    /// (`Error::TrailingContent`).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use asn1_rs::{Any, FromBer};
+    ///
+    /// // [0] EXPLICIT INTEGER 2
+    /// let bytes = &[0xa0, 0x03, 0x02, 0x01, 0x02];
+    /// let (_, any) = Any::from_ber(bytes).expect("parsing failed");
+    /// let inner = any.explicit_inner(0).expect("invalid tagged object");
+    /// assert_eq!(inner.as_u32(), Ok(2));
+    /// ```
+    pub fn explicit_inner(&self, expected_tag: u32) -> Result<Any<'a>> {
+        self.header.assert_class(Class::ContextSpecific)?;
+        self.tag().assert_eq(Tag(expected_tag))?;
+        self.header.assert_constructed()?;
+        let (rem, inner) = Any::from_ber(self.data)?;
+        self.header.assert_consumed(rem)?;
+        Ok(inner)
+    }
 }
 
 macro_rules! impl_any_into {
@@ -199,6 +334,7 @@ impl<'a> Any<'a> {
     impl_any_into!(enumerated => Enumerated, "ENUMERATED");
     impl_any_into!(generalizedtime => GeneralizedTime, "GeneralizedTime");
     impl_any_into!(generalstring => GeneralString<'a>, "GeneralString");
+    impl_any_into!(generalstring_utf8 => GeneralStringUtf8<'a>, "GeneralString");
     impl_any_into!(graphicstring => GraphicString<'a>, "GraphicString");
     impl_any_into!(i8 => i8, "INTEGER");
     impl_any_into!(i16 => i16, "INTEGER");
@@ -236,49 +372,50 @@ impl<'a> Any<'a> {
     impl_any_into!(videotexstring => VideotexString<'a>, "VideotexString");
     impl_any_into!(visiblestring => VisibleString<'a>, "VisibleString");
 
-    impl_any_as!(as_bitstring => BitString, "BITSTRING");
+    impl_any_as!(as_bitstring => BitString<'_>, "BITSTRING");
     impl_any_as!(as_bool => bool, "BOOLEAN");
     impl_any_as!(as_boolean => Boolean, "BOOLEAN");
-    impl_any_as!(as_embedded_pdv => EmbeddedPdv, "EMBEDDED PDV");
+    impl_any_as!(as_embedded_pdv => EmbeddedPdv<'_>, "EMBEDDED PDV");
     impl_any_as!(as_endofcontent => EndOfContent, "END OF CONTENT (not a real ASN.1 type)");
     impl_any_as!(as_enumerated => Enumerated, "ENUMERATED");
     impl_any_as!(as_generalizedtime => GeneralizedTime, "GeneralizedTime");
     impl_any_as!(as_generalstring => GeneralizedTime, "GeneralString");
-    impl_any_as!(as_graphicstring => GraphicString, "GraphicString");
+    impl_any_as!(as_generalstring_utf8 => GeneralStringUtf8<'_>, "GeneralString");
+    impl_any_as!(as_graphicstring => GraphicString<'_>, "GraphicString");
     impl_any_as!(as_i8 => i8, "INTEGER");
     impl_any_as!(as_i16 => i16, "INTEGER");
     impl_any_as!(as_i32 => i32, "INTEGER");
     impl_any_as!(as_i64 => i64, "INTEGER");
     impl_any_as!(as_i128 => i128, "INTEGER");
-    impl_any_as!(as_ia5string => Ia5String, "IA5String");
-    impl_any_as!(as_integer => Integer, "INTEGER");
+    impl_any_as!(as_ia5string => Ia5String<'_>, "IA5String");
+    impl_any_as!(as_integer => Integer<'_>, "INTEGER");
     impl_any_as!(as_null => Null, "NULL");
-    impl_any_as!(as_numericstring => NumericString, "NumericString");
-    impl_any_as!(as_objectdescriptor => ObjectDescriptor, "OBJECT IDENTIFIER");
-    impl_any_as!(as_octetstring => OctetString, "OCTET STRING");
-    impl_any_as!(as_oid => Oid, "OBJECT IDENTIFIER");
+    impl_any_as!(as_numericstring => NumericString<'_>, "NumericString");
+    impl_any_as!(as_objectdescriptor => ObjectDescriptor<'_>, "OBJECT IDENTIFIER");
+    impl_any_as!(as_octetstring => OctetString<'_>, "OCTET STRING");
+    impl_any_as!(as_oid => Oid<'_>, "OBJECT IDENTIFIER");
     /// Attempt to create ASN.1 type `RELATIVE-OID` from this object.
     pub fn as_relative_oid(&self) -> Result<Oid<'a>> {
         self.header.assert_tag(Tag::RelativeOid)?;
         let asn1 = Cow::Borrowed(self.data);
         Ok(Oid::new_relative(asn1))
     }
-    impl_any_as!(as_printablestring => PrintableString, "PrintableString");
-    impl_any_as!(as_sequence => Sequence, "SEQUENCE");
-    impl_any_as!(as_set => Set, "SET");
+    impl_any_as!(as_printablestring => PrintableString<'_>, "PrintableString");
+    impl_any_as!(as_sequence => Sequence<'_>, "SEQUENCE");
+    impl_any_as!(as_set => Set<'_>, "SET");
     impl_any_as!(as_str => &str, "UTF8String");
     impl_any_as!(as_string => String, "UTF8String");
-    impl_any_as!(as_teletexstring => TeletexString, "TeletexString");
+    impl_any_as!(as_teletexstring => TeletexString<'_>, "TeletexString");
     impl_any_as!(as_u8 => u8, "INTEGER");
     impl_any_as!(as_u16 => u16, "INTEGER");
     impl_any_as!(as_u32 => u32, "INTEGER");
     impl_any_as!(as_u64 => u64, "INTEGER");
     impl_any_as!(as_u128 => u128, "INTEGER");
-    impl_any_as!(as_universalstring => UniversalString, "UniversalString");
+    impl_any_as!(as_universalstring => UniversalString<'_>, "UniversalString");
     impl_any_as!(as_utctime => UtcTime, "UTCTime");
-    impl_any_as!(as_utf8string => Utf8String, "UTF8String");
-    impl_any_as!(as_videotexstring => VideotexString, "VideotexString");
-    impl_any_as!(as_visiblestring => VisibleString, "VisibleString");
+    impl_any_as!(as_utf8string => Utf8String<'_>, "UTF8String");
+    impl_any_as!(as_videotexstring => VideotexString<'_>, "VideotexString");
+    impl_any_as!(as_visiblestring => VisibleString<'_>, "VisibleString");
 
     /// Attempt to create an `Option<T>` from this object.
     pub fn as_optional<'b, T>(&'b self) -> Result<Option<T>>
@@ -315,22 +452,325 @@ impl<'a> Any<'a> {
     {
         TryFrom::try_from(self)
     }
+
+    /// Return an iterator over the objects contained in this constructed object, parsed as BER
+    ///
+    /// Returns `Error::ConstructExpected` if this object is primitive.
+    pub fn ber_children(&self) -> Result<AnyChildren<'a, BerParser>> {
+        self.header.assert_constructed()?;
+        Ok(AnyChildren::new(self.data))
+    }
+
+    /// Return an iterator over the objects contained in this constructed object, parsed as DER
+    ///
+    /// The length of this object must be definite. The DER rules for lengths are enforced at all
+    /// nesting levels: constructed children are checked to contain only definite lengths, so
+    /// objects using the indefinite form cannot be returned even if nested deeply.
+    ///
+    /// Returns `Error::ConstructExpected` if this object is primitive.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use asn1_rs::{Any, FromDer};
+    ///
+    /// // SEQUENCE { SEQUENCE (indefinite length) { NULL } }
+    /// let bytes = &[0x30, 0x06, 0x30, 0x80, 0x05, 0x00, 0x00, 0x00];
+    /// let (_, any) = Any::from_der(bytes).expect("parsing failed");
+    /// let mut children = any.der_children().expect("not constructed");
+    /// assert!(children.next().unwrap().is_err());
+    /// ```
+    pub fn der_children(&self) -> Result<AnyChildren<'a, DerParser>> {
+        self.header.assert_constructed()?;
+        self.header.assert_definite()?;
+        Ok(AnyChildren::new(self.data))
+    }
+
+    /// Wrap this object in a [`CachedAny`], memoizing its children (parsed as BER)
+    pub fn ber_cached(self) -> CachedAny<'a, BerParser> {
+        CachedAny::new(self)
+    }
+
+    /// Wrap this object in a [`CachedAny`], memoizing its children (parsed as DER)
+    pub fn der_cached(self) -> CachedAny<'a, DerParser> {
+        CachedAny::new(self)
+    }
+}
+
+/// An iterator over the objects contained in a constructed object
+///
+/// The encoding rules (BER or DER) are specified using the generic parameter `F`, and apply to
+/// all returned objects. See [`Any::ber_children`] and [`Any::der_children`].
+///
+/// The iterator stops after the first error.
+#[derive(Debug)]
+pub struct AnyChildren<'a, F>
+where
+    F: ASN1Parser,
+{
+    data: &'a [u8],
+    has_error: bool,
+    _f: PhantomData<F>,
+}
+
+impl<'a, F> AnyChildren<'a, F>
+where
+    F: ASN1Parser,
+{
+    /// Build an iterator over the objects in `data` (the *content* of a constructed object)
+    pub const fn new(data: &'a [u8]) -> Self {
+        AnyChildren {
+            data,
+            has_error: false,
+            _f: PhantomData,
+        }
+    }
+
+    fn next_with<P>(&mut self, parse: P) -> Option<Result<Any<'a>>>
+    where
+        P: FnOnce(&'a [u8]) -> ParseResult<'a, Any<'a>>,
+    {
+        if self.has_error || self.data.is_empty() {
+            return None;
+        }
+        match parse(self.data) {
+            Ok((rem, any)) => {
+                self.data = rem;
+                Some(Ok(any))
+            }
+            Err(e) => {
+                self.has_error = true;
+                Some(Err(e.into()))
+            }
+        }
+    }
+}
+
+impl<'a> Iterator for AnyChildren<'a, BerParser> {
+    type Item = Result<Any<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_with(|i| {
+            let (rem, any) = Any::from_ber(i)?;
+            // end-of-contents octets are not part of the content (X.690 section 8.1.5)
+            if any.header.is_universal() && any.tag() == Tag::EndOfContent {
+                return Err(Err::Error(Error::UnexpectedEndOfContent));
+            }
+            Ok((rem, any))
+        })
+    }
+}
+
+impl<'a> Iterator for AnyChildren<'a, DerParser> {
+    type Item = Result<Any<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_with(|i| {
+            let (rem, any) = Any::from_der(i)?;
+            if any.header.is_constructed() {
+                check_definite_lengths(any.data, MAX_RECURSION)?;
+            }
+            Ok((rem, any))
+        })
+    }
+}
+
+/// An `Any` object, with a memoized list of children
+///
+/// When a constructed object is accessed several times (for ex. by a validator, then by an
+/// extractor), walking its content again each time can be expensive for large payloads. This
+/// wrapper parses the children on the first call to [`children_cached`](Self::children_cached),
+/// and returns the same list (or the same error) for subsequent calls.
+///
+/// The encoding rules (BER or DER) are specified using the generic parameter `F`, see
+/// [`Any::ber_cached`] and [`Any::der_cached`]. This type dereferences to the inner `Any`.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{Any, FromDer};
+///
+/// // SEQUENCE { INTEGER 1, NULL }
+/// let bytes = &[0x30, 0x05, 0x02, 0x01, 0x01, 0x05, 0x00];
+/// let (_, any) = Any::from_der(bytes).expect("parsing failed");
+/// let cached = any.der_cached();
+/// // validation
+/// assert_eq!(cached.children_cached().expect("invalid children").len(), 2);
+/// // extraction: content is not parsed again
+/// let children = cached.children_cached().expect("invalid children");
+/// assert_eq!(children[0].as_u32(), Ok(1));
+/// ```
+#[derive(Debug)]
+pub struct CachedAny<'a, F>
+where
+    F: ASN1Parser,
+{
+    any: Any<'a>,
+    children: RefCell<Option<Result<Vec<Any<'a>>>>>,
+    _f: PhantomData<F>,
+}
+
+impl<'a, F> CachedAny<'a, F>
+where
+    F: ASN1Parser,
+{
+    /// Wrap `any`. Children are not parsed until requested.
+    pub const fn new(any: Any<'a>) -> Self {
+        CachedAny {
+            any,
+            children: RefCell::new(None),
+            _f: PhantomData,
+        }
+    }
+
+    /// Return a reference to the inner object
+    pub const fn any(&self) -> &Any<'a> {
+        &self.any
+    }
+
+    /// Return the inner object, dropping the cache
+    pub fn into_inner(self) -> Any<'a> {
+        self.any
+    }
+
+    /// Return true if the children have already been parsed
+    pub fn is_cached(&self) -> bool {
+        self.children.borrow().is_some()
+    }
+
+    fn children_with<P>(&self, parse: P) -> Result<Ref<'_, [Any<'a>]>>
+    where
+        P: FnOnce(&Any<'a>) -> Result<Vec<Any<'a>>>,
+    {
+        if !self.is_cached() {
+            let res = parse(&self.any);
+            *self.children.borrow_mut() = Some(res);
+        }
+        let cache = self.children.borrow();
+        if let Some(Err(e)) = &*cache {
+            return Err(e.clone());
+        }
+        Ok(Ref::map(cache, |c| match c {
+            Some(Ok(v)) => v.as_slice(),
+            _ => &[],
+        }))
+    }
+}
+
+impl<'a> CachedAny<'a, BerParser> {
+    /// Return the objects contained in this constructed object, parsed as BER on the first call
+    ///
+    /// See [`Any::ber_children`]. The first error is cached and returned for all calls.
+    pub fn children_cached(&self) -> Result<Ref<'_, [Any<'a>]>> {
+        self.children_with(|any| any.ber_children()?.collect())
+    }
+}
+
+impl<'a> CachedAny<'a, DerParser> {
+    /// Return the objects contained in this constructed object, parsed as DER on the first call
+    ///
+    /// See [`Any::der_children`]. The first error is cached and returned for all calls.
+    pub fn children_cached(&self) -> Result<Ref<'_, [Any<'a>]>> {
+        self.children_with(|any| any.der_children()?.collect())
+    }
+}
+
+impl<'a, F> Deref for CachedAny<'a, F>
+where
+    F: ASN1Parser,
+{
+    type Target = Any<'a>;
+
+    fn deref(&self) -> &Any<'a> {
+        &self.any
+    }
+}
+
+impl<'a, F> From<Any<'a>> for CachedAny<'a, F>
+where
+    F: ASN1Parser,
+{
+    fn from(any: Any<'a>) -> Self {
+        CachedAny::new(any)
+    }
+}
+
+/// Check that all objects in `data` (recursively) use the definite form of length
+fn check_definite_lengths(data: &[u8], max_depth: usize) -> Result<()> {
+    if max_depth == 0 {
+        return Err(Error::BerMaxDepth);
+    }
+    let mut i = data;
+    while !i.is_empty() {
+        let (rem, any) = Any::from_der(i)?;
+        if any.header.is_constructed() {
+            check_definite_lengths(any.data, max_depth - 1)?;
+        }
+        i = rem;
+    }
+    Ok(())
+}
+
+/// Check the DER constraints of `any` and of all nested objects
+///
+/// For universal types, the DER constraints of the type are checked. `visit` is called for each
+/// object, after its constraints are checked.
+pub(crate) fn check_der_tree(
+    any: &Any,
+    ctx: DerCheckContext,
+    visit: &mut dyn FnMut(&Any) -> Result<()>,
+) -> Result<()> {
+    Any::check_constraints(any)?;
+    if any.header.is_universal() {
+        check_universal_der(any)?;
+    }
+    visit(any)?;
+    if any.header.is_constructed() {
+        let ctx = ctx.enter()?;
+        let mut i = any.data;
+        while !i.is_empty() {
+            let (rem, item) = Any::from_der(i)?;
+            check_der_tree(&item, ctx, visit)?;
+            i = rem;
+        }
+    }
+    Ok(())
 }
 
 impl<'a> FromBer<'a> for Any<'a> {
-    fn from_ber(bytes: &'a [u8]) -> ParseResult<Self> {
-        let (i, header) = Header::from_ber(bytes)?;
-        let (i, data) = BerParser::get_object_content(i, &header, MAX_RECURSION)?;
+    fn from_ber(bytes: &'a [u8]) -> ParseResult<'a, Self> {
+        let (i, header) = debug_on_err!(
+            Header::from_ber(bytes),
+            input_len = bytes.len(),
+            "invalid BER header"
+        )?;
+        let (i, data) = debug_on_err!(
+            BerParser::get_object_content(i, &header, MAX_RECURSION),
+            tag = header.tag().0,
+            header_len = bytes.len() - i.len(),
+            length = ?header.length(),
+            "invalid BER content"
+        )?;
         Ok((i, Any { header, data }))
     }
 }
 
 impl<'a> FromDer<'a> for Any<'a> {
-    fn from_der(bytes: &'a [u8]) -> ParseResult<Self> {
-        let (i, header) = Header::from_der(bytes)?;
+    fn from_der(bytes: &'a [u8]) -> ParseResult<'a, Self> {
+        let (i, header) = debug_on_err!(
+            Header::from_der(bytes),
+            input_len = bytes.len(),
+            "invalid DER header"
+        )?;
         // X.690 section 10.1: The definite form of length encoding shall be used
         header.length.assert_definite()?;
-        let (i, data) = DerParser::get_object_content(i, &header, MAX_RECURSION)?;
+        let (i, data) = debug_on_err!(
+            DerParser::get_object_content(i, &header, MAX_RECURSION),
+            tag = header.tag().0,
+            header_len = bytes.len() - i.len(),
+            length = ?header.length(),
+            "invalid DER content"
+        )?;
         Ok((i, Any { header, data }))
     }
 }
@@ -341,6 +781,14 @@ impl CheckDerConstraints for Any<'_> {
         // if len < 128, must use short form (10.1: minimum number of octets)
         Ok(())
     }
+
+    /// Check the DER constraints of `any` and, if it is constructed, of all nested objects
+    ///
+    /// For universal types, the DER constraints of the type are checked (for ex. boolean value,
+    /// integer encoding, unused bits of bit strings).
+    fn check_constraints_ctx(any: &Any, ctx: DerCheckContext) -> Result<()> {
+        check_der_tree(any, ctx, &mut |_| Ok(()))
+    }
 }
 
 impl DerAutoDerive for Any<'_> {}
@@ -393,18 +841,156 @@ impl ToDer for Any<'_> {
     }
 }
 
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl Any<'_> {
+    /// Encode this object using a new header (class, tag and constructed bit), followed by the
+    /// original content
+    ///
+    /// The content is not copied: the header and content slices are written to `writer` using
+    /// vectored I/O. This is useful to apply implicit tagging to large objects, for ex. in
+    /// proxies.
+    ///
+    /// Return the number of bytes written.
+    ///
+    /// ```rust
+    /// use asn1_rs::{Any, Class, FromDer, Tag};
+    ///
+    /// // OCTET STRING "ab", re-tagged as [1] IMPLICIT
+    /// let (_, any) = Any::from_der(&[0x04, 0x02, 0x61, 0x62]).expect("parsing failed");
+    /// let mut v = Vec::new();
+    /// let sz = any
+    ///     .encode_retagged(Class::ContextSpecific, Tag(1), false, &mut v)
+    ///     .expect("serialization failed");
+    /// assert_eq!(sz, 4);
+    /// assert_eq!(&v, &[0x81, 0x02, 0x61, 0x62]);
+    /// ```
+    pub fn encode_retagged(
+        &self,
+        class: Class,
+        tag: Tag,
+        constructed: bool,
+        writer: &mut dyn std::io::Write,
+    ) -> SerializeResult<usize> {
+        let header = Header::new(class, constructed, tag, Length::Definite(self.data.len()));
+        // header is at most 6 (tag) + 9 (length) bytes
+        let mut hdr = [0u8; 16];
+        let mut cursor = std::io::Cursor::new(&mut hdr[..]);
+        let hdr_len = header.write_der_header(&mut cursor)?;
+        write_all_vectored(writer, &[&hdr[..hdr_len], self.data]).map_err(Into::into)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::*;
     use hex_literal::hex;
 
+    #[test]
+    fn any_children() {
+        // SEQUENCE { INTEGER 1, SEQUENCE (indefinite) { NULL } }
+        let input = &hex!("30 80 02 01 01 30 80 05 00 00 00 00 00");
+        let (_, any) = Any::from_ber(input).expect("parsing failed");
+        assert!(any.der_children().is_err());
+        let children: Vec<_> = any
+            .ber_children()
+            .expect("not constructed")
+            .collect::<Result<_>>()
+            .expect("invalid children");
+        assert_eq!(children.len(), 2);
+        assert_eq!(children[0].as_u32(), Ok(1));
+        assert_eq!(children[1].ber_children().unwrap().count(), 1);
+        // indefinite length nested in a definite length object
+        let input = &hex!("30 0b 02 01 01 30 06 30 80 05 00 00 00");
+        let (_, any) = Any::from_der(input).expect("parsing failed");
+        let mut iter = any.der_children().expect("not constructed");
+        assert_eq!(iter.next().map(|r| r.is_ok()), Some(true));
+        assert_eq!(
+            iter.next(),
+            Some(Err(Error::DerConstraintFailed(
+                DerConstraint::IndefiniteLength
+            )))
+        );
+        assert!(iter.next().is_none());
+        assert_eq!(any.ber_children().unwrap().count(), 2);
+        // primitive objects have no children
+        let (_, any) = Any::from_der(&hex!("02 01 01")).unwrap();
+        assert_eq!(any.der_children().err(), Some(Error::ConstructExpected));
+    }
+
+    #[test]
+    fn any_skip() {
+        // SEQUENCE (indefinite) { SEQUENCE (indefinite) { NULL } }, INTEGER 1, BOOLEAN
+        let input = &hex!("30 80 30 80 05 00 00 00 00 00 02 01 01 01 01 ff");
+        let rem = Any::skip(input).expect("invalid object");
+        assert_eq!(rem, &hex!("02 01 01 01 01 ff"));
+        assert_eq!(Any::skip_n(input, 2), Ok(&hex!("01 01 ff")[..]));
+        assert_eq!(Any::skip_n(input, 3), Ok(&[][..]));
+        assert_eq!(Any::skip_n(input, 0), Ok(&input[..]));
+        // same as parsing
+        let (rem2, _) = Any::from_ber(input).unwrap();
+        assert_eq!(rem, rem2);
+        // not enough objects, or incomplete
+        assert!(Any::skip_n(input, 4).is_err());
+        assert!(Any::skip(&hex!("30 80 05 00")).is_err());
+        assert!(Any::skip(&hex!("02 03 01")).is_err());
+    }
+
+    #[test]
+    fn any_tryfrom_ref() {
+        use core::convert::TryFrom;
+
+        // SEQUENCE { INTEGER 1, REAL 0, NULL }
+        let input = &hex!("30 07 02 01 01 09 00 05 00");
+        let (_, any) = Any::from_ber(input).expect("parsing failed");
+        let v = <(u32, Real, ())>::try_from(&any).expect("conversion failed");
+        assert_eq!(v, (1, Real::Zero, ()));
+        let v = Vec::<Any>::try_from(&any).expect("conversion failed");
+        assert_eq!(v.len(), 3);
+        assert_eq!(f64::try_from(&v[1]), Ok(0.0));
+        assert!(<&[u8]>::try_from(&v[0]).is_err());
+        // the object is still available, for ex. to re-encode it
+        let seq = SequenceOf::<Any>::try_from(&any).expect("conversion failed");
+        assert_eq!(seq.len(), 3);
+        assert_eq!(any.data.len(), 7);
+    }
+
+    #[test]
+    fn any_children_cached() {
+        // SEQUENCE { INTEGER 1, NULL }
+        let input = &hex!("30 05 02 01 01 05 00");
+        let (_, any) = Any::from_der(input).expect("parsing failed");
+        let cached = any.clone().der_cached();
+        assert!(!cached.is_cached());
+        let ptr = {
+            let children = cached.children_cached().expect("invalid children");
+            assert_eq!(children.len(), 2);
+            assert_eq!(children[1].tag(), Tag::Null);
+            children.as_ptr()
+        };
+        assert!(cached.is_cached());
+        // same list is returned
+        let children = cached.children_cached().expect("invalid children");
+        assert_eq!(children.as_ptr(), ptr);
+        assert_eq!(cached.tag(), Tag::Sequence);
+        drop(children);
+        assert_eq!(cached.into_inner(), any);
+        // errors are cached too
+        let (_, any) = Any::from_ber(&hex!("30 80 02 01 01 00 00")).unwrap();
+        let cached = any.clone().der_cached();
+        assert!(cached.children_cached().is_err());
+        assert!(cached.is_cached());
+        assert!(cached.children_cached().is_err());
+        assert_eq!(any.ber_cached().children_cached().unwrap().len(), 1);
+    }
+
     #[test]
     fn methods_any() {
         let header = Header::new_simple(Tag::Integer);
         let any = Any::new(header, &[])
             .with_class(Class::ContextSpecific)
             .with_tag(Tag(0));
-        assert_eq!(any.as_bytes(), &[]);
+        assert_eq!(any.as_bytes(), &[] as &[u8]);
 
         let input = &hex! {"80 03 02 01 01"};
         let (_, any) = Any::from_ber(input).expect("parsing failed");
@@ -426,4 +1012,89 @@ mod tests {
         let int = any.integer().unwrap();
         assert_eq!(int.as_u16(), Ok(1));
     }
+
+    #[test]
+    fn any_explicit_inner() {
+        let (_, any) = Any::from_ber(&hex!("a1 03 02 01 02")).expect("parsing failed");
+        let inner = any.explicit_inner(1).expect("invalid tagged object");
+        assert_eq!(inner.tag(), Tag::Integer);
+        assert_eq!(inner.data, &[2]);
+        // wrong tag
+        assert_eq!(
+            any.explicit_inner(0),
+            Err(Error::unexpected_tag(Some(Tag(0)), Tag(1)))
+        );
+        // wrong class
+        let (_, any) = Any::from_ber(&hex!("61 03 02 01 02")).expect("parsing failed");
+        assert!(any.explicit_inner(1).is_err());
+        // primitive
+        let (_, any) = Any::from_ber(&hex!("81 03 02 01 02")).expect("parsing failed");
+        assert_eq!(any.explicit_inner(1), Err(Error::ConstructExpected));
+        // trailing bytes
+        let (_, any) = Any::from_ber(&hex!("a1 05 02 01 02 05 00")).expect("parsing failed");
+        assert_eq!(
+            any.explicit_inner(1),
+            Err(Error::TrailingContent {
+                tag: Tag(1),
+                remaining: 2
+            })
+        );
+    }
+
+    #[test]
+    fn any_try_from_tag_and_data() {
+        let any = Any::try_from_tag_and_data(Class::Universal, true, Tag::Sequence, &hex!("05 00"))
+            .expect("invalid parts");
+        assert_eq!(any.header.length(), Length::Definite(2));
+        assert_eq!(
+            Any::try_from_tag_and_data(Class::Universal, false, Tag::Set, &[]),
+            Err(Error::ConstructExpected)
+        );
+        assert_eq!(
+            Any::try_from_tag_and_data(Class::Universal, false, Tag::EndOfContent, &[0]),
+            Err(Error::InvalidEndOfContent)
+        );
+        // strings can be constructed (BER)
+        assert!(Any::try_from_tag_and_data(Class::Universal, true, Tag::OctetString, &[]).is_ok());
+        // builder methods
+        let any = Any::try_from_tag_and_data(Class::Universal, false, Tag::Integer, &[1]).unwrap();
+        assert_eq!(
+            any.clone().try_with_constructed(true),
+            Err(Error::ConstructUnexpected)
+        );
+        let any = any
+            .try_with_class(Class::ContextSpecific)
+            .and_then(|any| any.try_with_constructed(true))
+            .expect("invalid tagged object");
+        assert!(any.header.is_constructed());
+        assert_eq!(
+            any.try_with_class(Class::Universal),
+            Err(Error::ConstructUnexpected)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn any_encode_retagged() {
+        // SEQUENCE { INTEGER 1 } re-tagged as [APPLICATION 3] (constructed)
+        let input = &hex!("30 03 02 01 01");
+        let (_, any) = Any::from_der(input).expect("parsing failed");
+        let mut v = Vec::new();
+        let sz = any
+            .encode_retagged(Class::Application, Tag(3), true, &mut v)
+            .expect("serialization failed");
+        assert_eq!(sz, 5);
+        assert_eq!(&v, &hex!("63 03 02 01 01"));
+        // long content and high tag number
+        let content = vec![0xaa; 300];
+        let any = Any::try_from_tag_and_data(Class::Universal, false, Tag::OctetString, &content)
+            .unwrap();
+        let mut v = Vec::new();
+        let sz = any
+            .encode_retagged(Class::ContextSpecific, Tag(31), false, &mut v)
+            .expect("serialization failed");
+        assert_eq!(sz, 2 + 3 + 300);
+        assert_eq!(&v[..5], &hex!("9f 1f 82 01 2c"));
+        assert_eq!(&v[5..], &content[..]);
+    }
 }
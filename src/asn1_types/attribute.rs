@@ -0,0 +1,505 @@
+use crate::*;
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+/// Decode an object of one of the string types allowed in directory names
+///
+/// This accepts the `DirectoryString` choices (`TeletexString`, `PrintableString`,
+/// `UniversalString`, `UTF8String`, `BMPString`), and the `IA5String`, `VisibleString` and
+/// `NumericString` types which are often used in attribute values (for ex. `emailAddress`).
+///
+/// Strings are borrowed from the input when possible.
+fn any_as_directory_string<'a>(any: &Any<'a>) -> Result<Cow<'a, str>> {
+    let s = match any.tag() {
+        Tag::Utf8String => Utf8String::try_from(any)?.data,
+        Tag::PrintableString => PrintableString::try_from(any)?.data,
+        Tag::TeletexString => TeletexString::try_from(any)?.data,
+        Tag::Ia5String => Ia5String::try_from(any)?.data,
+        Tag::VisibleString => VisibleString::try_from(any)?.data,
+        Tag::NumericString => NumericString::try_from(any)?.data,
+        Tag::BmpString => BmpString::try_from(any)?.data,
+        Tag::UniversalString => UniversalString::try_from(any)?.data,
+        tag => return Err(Error::unexpected_tag(None, tag)),
+    };
+    Ok(s)
+}
+
+/// The `AttributeTypeAndValue` type, used in X.501 names (X.509 `Name`, `RelativeDistinguishedName`)
+///
+/// <pre>
+/// AttributeTypeAndValue ::= SEQUENCE {
+///      type     AttributeType,
+///      value    AttributeValue }
+///
+/// AttributeType ::= OBJECT IDENTIFIER
+///
+/// AttributeValue ::= ANY -- DEFINED BY AttributeType
+/// </pre>
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{oid, AttributeTypeAndValue, FromDer};
+///
+/// // commonName = "abc"
+/// let bytes = &[
+///     0x30, 0x0a, 0x06, 0x03, 0x55, 0x04, 0x03, 0x0c, 0x03, 0x61, 0x62, 0x63,
+/// ];
+/// let (_, attr) = AttributeTypeAndValue::from_der(bytes).expect("parsing failed");
+/// assert_eq!(attr.oid, oid!(2.5.4.3));
+/// assert_eq!(attr.as_directory_string().as_deref(), Ok("abc"));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AttributeTypeAndValue<'a> {
+    pub oid: Oid<'a>,
+    pub value: Any<'a>,
+}
+
+impl<'a> AttributeTypeAndValue<'a> {
+    /// Build a new `AttributeTypeAndValue`
+    pub const fn new(oid: Oid<'a>, value: Any<'a>) -> Self {
+        AttributeTypeAndValue { oid, value }
+    }
+
+    /// Attempt to decode value as a directory string
+    ///
+    /// See [`Attribute::values_as_directory_strings`] for the list of accepted string types.
+    pub fn as_directory_string(&self) -> Result<Cow<'a, str>> {
+        any_as_directory_string(&self.value)
+    }
+
+    /// Attempt to convert value to type `T`
+    pub fn value_as<T>(&self) -> Result<T>
+    where
+        T: TryFrom<Any<'a>, Error = Error>,
+    {
+        T::try_from(self.value.clone())
+    }
+}
+
+impl<'a> TryFrom<Any<'a>> for AttributeTypeAndValue<'a> {
+    type Error = Error;
+
+    fn try_from(any: Any<'a>) -> Result<Self> {
+        TryFrom::try_from(&any)
+    }
+}
+
+impl<'a, 'b> TryFrom<&'b Any<'a>> for AttributeTypeAndValue<'a> {
+    type Error = Error;
+
+    fn try_from(any: &'b Any<'a>) -> Result<Self> {
+        any.tag().assert_eq(Self::TAG)?;
+        any.header.assert_constructed()?;
+        let (rem, oid) = Oid::from_ber(any.data)?;
+        let (rem, value) = Any::from_ber(rem)?;
+        any.header.assert_consumed(rem)?;
+        Ok(AttributeTypeAndValue { oid, value })
+    }
+}
+
+impl<'a> CheckDerConstraints for AttributeTypeAndValue<'a> {
+    fn check_constraints(any: &Any) -> Result<()> {
+        any.tag().assert_eq(Self::TAG)?;
+        any.header.assert_constructed()?;
+        let (rem, oid) = Any::from_der(any.data)?;
+        Oid::check_constraints(&oid)?;
+        let (rem, value) = Any::from_der(rem)?;
+        any.header.assert_consumed(rem)?;
+        Any::check_constraints(&value)?;
+        Ok(())
+    }
+}
+
+impl DerAutoDerive for AttributeTypeAndValue<'_> {}
+
+impl<'a> Tagged for AttributeTypeAndValue<'a> {
+    const TAG: Tag = Tag::Sequence;
+}
+
+#[cfg(feature = "std")]
+impl ToDer for AttributeTypeAndValue<'_> {
+    fn to_der_len(&self) -> Result<usize> {
+        let sz = self.oid.to_der_len()? + self.value.to_der_len()?;
+        let header = Header::new(Class::Universal, true, Self::TAG, Length::Definite(sz));
+        Ok(header.to_der_len()? + sz)
+    }
+
+    fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        let sz = self.oid.to_der_len()? + self.value.to_der_len()?;
+        let header = Header::new(Class::Universal, true, Self::TAG, Length::Definite(sz));
+        header.write_der_header(writer)
+    }
+
+    fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        let sz = self.oid.write_der(writer)?;
+        let sz = sz + self.value.write_der(writer)?;
+        Ok(sz)
+    }
+}
+
+/// The `Attribute` type, used in X.501 and in many standards (CMS `signedAttrs`, PKCS#10, etc.)
+///
+/// <pre>
+/// Attribute ::= SEQUENCE {
+///      type     AttributeType,
+///      values   SET OF AttributeValue }
+/// </pre>
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{oid, Attribute, FromDer};
+///
+/// // contentType = id-data
+/// let bytes = &[
+///     0x30, 0x18, 0x06, 0x09, 0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x09, 0x03, 0x31, 0x0b,
+///     0x06, 0x09, 0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x07, 0x01,
+/// ];
+/// let (_, attr) = Attribute::from_der(bytes).expect("parsing failed");
+/// assert_eq!(attr.oid, oid!(1.2.840.113549.1.9.3));
+/// let value = attr.single_value().expect("expected a single value");
+/// assert_eq!(value.as_oid(), Ok(oid!(1.2.840.113549.1.7.1)));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Attribute<'a> {
+    pub oid: Oid<'a>,
+    pub values: SetOf<Any<'a>>,
+}
+
+impl<'a> Attribute<'a> {
+    /// Build a new `Attribute`
+    pub const fn new(oid: Oid<'a>, values: SetOf<Any<'a>>) -> Self {
+        Attribute { oid, values }
+    }
+
+    /// Return the value of this attribute, if it has exactly one value
+    ///
+    /// Many attributes are defined as single-valued (for ex. CMS `contentType` or
+    /// `messageDigest`). This function returns an error if the set of values is empty or
+    /// contains more than one value.
+    pub fn single_value(&self) -> Result<&Any<'a>> {
+        match self.values.as_ref() {
+            [value] => Ok(value),
+            _ => Err(Error::InvalidLength),
+        }
+    }
+
+    /// Attempt to decode all values as directory strings
+    ///
+    /// Accepted types are `TeletexString`, `PrintableString`, `UniversalString`, `UTF8String`,
+    /// `BMPString` (the `DirectoryString` choices), `IA5String`, `VisibleString` and
+    /// `NumericString`. Strings are borrowed from the input when possible.
+    pub fn values_as_directory_strings(&self) -> Result<Vec<Cow<'a, str>>> {
+        self.values.iter().map(any_as_directory_string).collect()
+    }
+}
+
+impl<'a> TryFrom<Any<'a>> for Attribute<'a> {
+    type Error = Error;
+
+    fn try_from(any: Any<'a>) -> Result<Self> {
+        TryFrom::try_from(&any)
+    }
+}
+
+impl<'a, 'b> TryFrom<&'b Any<'a>> for Attribute<'a> {
+    type Error = Error;
+
+    fn try_from(any: &'b Any<'a>) -> Result<Self> {
+        any.tag().assert_eq(Self::TAG)?;
+        any.header.assert_constructed()?;
+        let (rem, oid) = Oid::from_ber(any.data)?;
+        let (rem, values) = SetOf::<Any>::from_ber(rem)?;
+        any.header.assert_consumed(rem)?;
+        Ok(Attribute { oid, values })
+    }
+}
+
+impl<'a> CheckDerConstraints for Attribute<'a> {
+    fn check_constraints(any: &Any) -> Result<()> {
+        any.tag().assert_eq(Self::TAG)?;
+        any.header.assert_constructed()?;
+        let (rem, oid) = Any::from_der(any.data)?;
+        Oid::check_constraints(&oid)?;
+        let (rem, values) = Any::from_der(rem)?;
+        any.header.assert_consumed(rem)?;
+        <SetOf<Any>>::check_constraints(&values)?;
+        Ok(())
+    }
+}
+
+impl DerAutoDerive for Attribute<'_> {}
+
+impl<'a> Tagged for Attribute<'a> {
+    const TAG: Tag = Tag::Sequence;
+}
+
+#[cfg(feature = "std")]
+impl ToDer for Attribute<'_> {
+    fn to_der_len(&self) -> Result<usize> {
+        let sz = self.oid.to_der_len()? + self.values.to_der_len()?;
+        let header = Header::new(Class::Universal, true, Self::TAG, Length::Definite(sz));
+        Ok(header.to_der_len()? + sz)
+    }
+
+    fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        let sz = self.oid.to_der_len()? + self.values.to_der_len()?;
+        let header = Header::new(Class::Universal, true, Self::TAG, Length::Definite(sz));
+        header.write_der_header(writer)
+    }
+
+    fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        let sz = self.oid.write_der(writer)?;
+        let sz = sz + self.values.write_der(writer)?;
+        Ok(sz)
+    }
+}
+
+/// Encode CMS signed attributes, as input of the message digest of the signature
+///
+/// In a CMS `SignerInfo`, signed attributes are encoded as `[0] IMPLICIT SET OF Attribute`, but
+/// the signature is computed over the DER encoding using the universal `SET OF` tag (RFC 5652
+/// section 5.4). Computing the digest over the `[0]` encoding is a common mistake, producing
+/// signatures that cannot be verified.
+///
+/// Attributes, and the values of each attribute, are sorted as required by DER. See
+/// [`signed_attrs_to_der`] for the encoding to include in the `SignerInfo`: both encodings only
+/// differ by the first octet.
+///
+/// When verifying a signature, the digest must be computed over the received encoding, which
+/// may not be sorted: use [`signed_attrs_retag`] instead of re-encoding the parsed attributes.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{oid, signed_attrs_digest_input, signed_attrs_to_der, Any, Attribute, SetOf, Tag};
+///
+/// // contentType = id-data
+/// let id_data = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x07, 0x01];
+/// let content_type = Any::from_tag_and_data(Tag::Oid, id_data);
+/// let attr = Attribute::new(oid!(1.2.840.113549.1.9.3), SetOf::new(vec![content_type]));
+/// let attrs = [attr];
+/// let digest_input = signed_attrs_digest_input(&attrs).expect("serialization failed");
+/// assert_eq!(digest_input[0], 0x31);
+/// let signer_info_attrs = signed_attrs_to_der(&attrs).expect("serialization failed");
+/// assert_eq!(signer_info_attrs[0], 0xa0);
+/// assert_eq!(digest_input[1..], signer_info_attrs[1..]);
+/// ```
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn signed_attrs_digest_input(attrs: &[Attribute]) -> SerializeResult<Vec<u8>> {
+    encode_signed_attrs(attrs, Class::Universal, Tag::Set)
+}
+
+/// Encode CMS signed attributes, as included in a `SignerInfo` (`[0] IMPLICIT SET OF Attribute`)
+///
+/// Attributes, and the values of each attribute, are sorted as required by DER. See
+/// [`signed_attrs_digest_input`] for the encoding used to compute the signature.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn signed_attrs_to_der(attrs: &[Attribute]) -> SerializeResult<Vec<u8>> {
+    encode_signed_attrs(attrs, Class::ContextSpecific, Tag(0))
+}
+
+/// Convert the received encoding of CMS signed attributes (`[0] IMPLICIT SET OF Attribute`) to
+/// the input of the message digest of the signature
+///
+/// Only the identifier octet is changed to the universal `SET` tag (RFC 5652 section 5.4): the
+/// rest of the encoding is kept as received. Bytes following the object are ignored.
+///
+/// The digest input must be a DER encoding: an error is returned if the object, or any nested
+/// object, has an indefinite length.
+///
+/// ```rust
+/// use asn1_rs::signed_attrs_retag;
+///
+/// let received = &[0xa0, 0x04, 0x30, 0x02, 0x05, 0x00];
+/// let digest_input = signed_attrs_retag(received).expect("invalid signed attributes");
+/// assert_eq!(digest_input, vec![0x31, 0x04, 0x30, 0x02, 0x05, 0x00]);
+/// ```
+pub fn signed_attrs_retag(signer_info_attrs: &[u8]) -> Result<Vec<u8>> {
+    let (rem, any) = Any::from_ber(signer_info_attrs)?;
+    any.header.assert_class(Class::ContextSpecific)?;
+    any.tag().assert_eq(Tag(0))?;
+    any.header.assert_constructed()?;
+    // the identifier of [0] (constructed) is a single octet, unless encoded in long form
+    if signer_info_attrs[0] != 0xa0 {
+        return Err(Error::InvalidTag);
+    }
+    let encoding = &signer_info_attrs[..signer_info_attrs.len() - rem.len()];
+    for event in TlvTokenizer::new(encoding) {
+        if let TlvEvent::BeginConstructed(header) = event? {
+            if !header.length().is_definite() {
+                return Err(Error::DerConstraintFailed(DerConstraint::IndefiniteLength));
+            }
+        }
+    }
+    let mut v = encoding.to_vec();
+    v[0] = 0x31;
+    Ok(v)
+}
+
+#[cfg(feature = "std")]
+fn encode_signed_attrs(attrs: &[Attribute], class: Class, tag: Tag) -> SerializeResult<Vec<u8>> {
+    let items = attrs
+        .iter()
+        .map(attribute_to_der_sorted)
+        .collect::<SerializeResult<Vec<_>>>()?;
+    let mut out = Vec::new();
+    write_der_sorted_set(class, tag, items, &mut out)?;
+    Ok(out)
+}
+
+/// Encode an attribute, with its values sorted in DER order
+#[cfg(feature = "std")]
+fn attribute_to_der_sorted(attr: &Attribute) -> SerializeResult<Vec<u8>> {
+    let values = attr
+        .values
+        .iter()
+        .map(|v| v.to_der_vec())
+        .collect::<SerializeResult<Vec<_>>>()?;
+    let mut content = attr.oid.to_der_vec()?;
+    write_der_sorted_set(Class::Universal, Tag::Set, values, &mut content)?;
+    let header = Header::new(
+        Class::Universal,
+        true,
+        Tag::Sequence,
+        Length::Definite(content.len()),
+    );
+    let mut out = header.to_der_vec()?;
+    out.extend_from_slice(&content);
+    Ok(out)
+}
+
+/// Write a constructed object containing the encoded `items`, sorted in DER order
+#[cfg(feature = "std")]
+fn write_der_sorted_set(
+    class: Class,
+    tag: Tag,
+    mut items: Vec<Vec<u8>>,
+    out: &mut Vec<u8>,
+) -> SerializeResult<()> {
+    items.sort_by(|a, b| der_encoding_cmp(a, b));
+    let len = items.iter().map(Vec::len).sum();
+    let header = Header::new(class, true, tag, Length::Definite(len));
+    header.write_der_header(out)?;
+    for item in &items {
+        out.extend_from_slice(item);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use alloc::vec;
+    use core::convert::TryFrom;
+    use hex_literal::hex;
+
+    #[test]
+    fn attribute_type_and_value() {
+        // countryName = "FR", as PrintableString
+        let input = &hex!("30 09 06 03 55 04 06 13 02 46 52");
+        let (rem, attr) = AttributeTypeAndValue::from_der(input).expect("parsing failed");
+        assert!(rem.is_empty());
+        assert_eq!(attr.oid, oid!(2.5.4 .6));
+        assert_eq!(attr.as_directory_string().as_deref(), Ok("FR"));
+        let s = attr
+            .value_as::<PrintableString>()
+            .expect("not a PrintableString");
+        assert_eq!(s.as_ref(), "FR");
+        // BMPString values are converted
+        let input = &hex!("30 0b 06 03 55 04 03 1e 04 00 61 00 62");
+        let (_, attr) = AttributeTypeAndValue::from_der(input).expect("parsing failed");
+        assert_eq!(attr.as_directory_string().as_deref(), Ok("ab"));
+        // not a string
+        let input = &hex!("30 08 06 03 55 04 03 02 01 2a");
+        let (_, attr) = AttributeTypeAndValue::from_der(input).expect("parsing failed");
+        assert_eq!(
+            attr.as_directory_string(),
+            Err(Error::unexpected_tag(None, Tag::Integer))
+        );
+    }
+
+    #[test]
+    fn attribute_values() {
+        // organizationalUnitName = { "a", "b" }
+        let input = &hex!("30 0d 06 03 55 04 0b 31 06 0c 01 61 13 01 62");
+        let (rem, attr) = Attribute::from_der(input).expect("parsing failed");
+        assert!(rem.is_empty());
+        assert_eq!(attr.oid, oid!(2.5.4 .11));
+        assert_eq!(attr.values.len(), 2);
+        assert_eq!(attr.single_value(), Err(Error::InvalidLength));
+        let values = attr.values_as_directory_strings().expect("invalid values");
+        assert_eq!(values, vec!["a", "b"]);
+        // values must be a SET
+        let input = &hex!("30 0d 06 03 55 04 0b 30 06 0c 01 61 13 01 62");
+        let res = Attribute::from_der(input);
+        assert!(res.is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn attribute_to_der() {
+        let value = Any::from_tag_and_data(Tag::Utf8String, b"abc");
+        let atv = AttributeTypeAndValue::new(oid!(2.5.4 .3), value.clone());
+        let v = atv.to_der_vec().expect("serialization failed");
+        assert_eq!(&v, &hex!("30 0a 06 03 55 04 03 0c 03 61 62 63"));
+        let attr = Attribute::new(oid!(2.5.4 .3), SetOf::new(vec![value]));
+        let v = attr.to_der_vec().expect("serialization failed");
+        assert_eq!(&v, &hex!("30 0c 06 03 55 04 03 31 05 0c 03 61 62 63"));
+        let (_, attr2) = Attribute::from_der(&v).expect("parsing failed");
+        assert_eq!(attr2, attr);
+        assert_eq!(Attribute::try_from(Any::from_der(&v).unwrap().1), Ok(attr));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn signed_attrs_encoding() {
+        // contentType (26 bytes), then messageDigest (21 bytes): encodings must be sorted
+        let id_data = &hex!("2a 86 48 86 f7 0d 01 07 01");
+        let content_type = Any::from_tag_and_data(Tag::Oid, id_data);
+        let content_type = Attribute::new(
+            oid!(1.2.840 .113549 .1 .9 .3),
+            SetOf::new(vec![content_type]),
+        );
+        let digest = Any::from_tag_and_data(Tag::OctetString, &[0xaa; 4]);
+        let message_digest =
+            Attribute::new(oid!(1.2.840 .113549 .1 .9 .4), SetOf::new(vec![digest]));
+        let attrs = [content_type.clone(), message_digest.clone()];
+        let v = signed_attrs_digest_input(&attrs).expect("serialization failed");
+        let mut expected = hex!("31 2f").to_vec();
+        expected.extend_from_slice(&message_digest.to_der_vec().unwrap());
+        expected.extend_from_slice(&content_type.to_der_vec().unwrap());
+        assert_eq!(v, expected);
+        let (_, set) = Set::from_der(&v).expect("not valid DER");
+        assert_eq!(set.check_der_order(), Ok(None));
+        let v2 = signed_attrs_to_der(&attrs).expect("serialization failed");
+        assert_eq!(v2[0], 0xa0);
+        assert_eq!(v2[1..], v[1..]);
+        assert_eq!(signed_attrs_retag(&v2), Ok(v.clone()));
+        // the received encoding is kept, even if not sorted
+        let mut received = hex!("a0 2f").to_vec();
+        received.extend_from_slice(&content_type.to_der_vec().unwrap());
+        received.extend_from_slice(&message_digest.to_der_vec().unwrap());
+        let v3 = signed_attrs_retag(&received).expect("invalid signed attributes");
+        assert_eq!(v3[0], 0x31);
+        assert_eq!(v3[1..], received[1..]);
+        assert!(signed_attrs_retag(&v).is_err());
+        assert!(signed_attrs_retag(&hex!("80 00")).is_err());
+        // indefinite lengths cannot be kept in the DER digest input
+        let err = Err(Error::DerConstraintFailed(DerConstraint::IndefiniteLength));
+        assert_eq!(signed_attrs_retag(&hex!("a0 80 30 00 00 00")), err);
+        assert_eq!(signed_attrs_retag(&hex!("a0 06 30 80 05 00 00 00")), err);
+        // values of each attribute are also sorted
+        let values = SetOf::new(vec![
+            Any::from_tag_and_data(Tag::Integer, &[2]),
+            Any::from_tag_and_data(Tag::Integer, &[1]),
+        ]);
+        let attr = Attribute::new(oid!(1.2.3), values);
+        let v = signed_attrs_digest_input(&[attr]).expect("serialization failed");
+        assert_eq!(&v, &hex!("31 0e 30 0c 06 02 2a 03 31 06 02 01 01 02 01 02"));
+    }
+}
@@ -1,10 +1,20 @@
+use crate::ber::MAX_RECURSION;
 use crate::*;
 use alloc::borrow::Cow;
+use alloc::vec;
+use alloc::vec::Vec;
 #[cfg(feature = "bits")]
-use bitvec::{order::Msb0, slice::BitSlice};
+use bitvec::{order::Msb0, slice::BitSlice, vec::BitVec};
 use core::convert::TryFrom;
 
 /// ASN.1 `BITSTRING` type
+///
+/// Bits are numbered following X.690 section 8.6.2: bit 0 is the most significant bit of the
+/// first content octet, and the `unused_bits` last bits of the last octet are not part of the
+/// value.
+///
+/// In BER, the value can be encoded using the constructed form (X.690 section 8.6.4): fragments
+/// are reassembled when parsing, and the result is an owned `BitString`.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct BitString<'a> {
     pub unused_bits: u8,
@@ -35,6 +45,117 @@ impl<'a> BitString<'a> {
     pub fn as_bitslice(&self) -> Option<&BitSlice<u8, Msb0>> {
         BitSlice::<_, Msb0>::try_from_slice(&self.data).ok()
     }
+
+    /// Return the number of bits of this object (excluding unused bits)
+    pub fn bit_len(&self) -> usize {
+        (self.data.len() * 8).saturating_sub(self.unused_bits as usize)
+    }
+
+    /// Build a `BitString` from the `num_bits` least significant bits of `value`
+    ///
+    /// Bits are stored most significant first: bit 0 of the `BitString` is bit `num_bits - 1`
+    /// of `value`. This is the inverse of [`BitString::as_u64`].
+    ///
+    /// Returns an error if `num_bits` is greater than 64, or if `value` does not fit in
+    /// `num_bits` bits.
+    ///
+    /// ```rust
+    /// use asn1_rs::BitString;
+    ///
+    /// let b = BitString::from_u64_msb(0b101, 3).unwrap();
+    /// assert_eq!(b.unused_bits, 5);
+    /// assert_eq!(b.as_ref(), &[0b1010_0000]);
+    /// assert!(b.is_set(0));
+    /// assert!(!b.is_set(1));
+    /// ```
+    pub fn from_u64_msb(value: u64, num_bits: usize) -> Result<BitString<'static>> {
+        if num_bits > 64 || (num_bits < 64 && value >> num_bits != 0) {
+            return Err(Error::IntegerTooLarge);
+        }
+        let num_bytes = (num_bits + 7) / 8;
+        let unused_bits = num_bytes * 8 - num_bits;
+        let bytes = (value << unused_bits).to_be_bytes();
+        Ok(BitString {
+            unused_bits: unused_bits as u8,
+            data: Cow::Owned(bytes[8 - num_bytes..].to_vec()),
+        })
+    }
+
+    /// Interpret the bits of this object as an unsigned integer, most significant bit first
+    ///
+    /// Bit 0 of the `BitString` is the most significant bit of the result, and the last bit is
+    /// the least significant bit. Unused bits are ignored.
+    ///
+    /// Returns an error if the object contains more than 64 bits.
+    ///
+    /// ```rust
+    /// use asn1_rs::BitString;
+    ///
+    /// let b = BitString::new(5, &[0b1010_0000]);
+    /// assert_eq!(b.as_u64(), Ok(0b101));
+    /// ```
+    pub fn as_u64(&self) -> Result<u64> {
+        if self.unused_bits > 7 || self.bit_len() > 64 {
+            return Err(Error::IntegerTooLarge);
+        }
+        let value = self
+            .data
+            .iter()
+            .fold(0u64, |acc, &b| (acc << 8) | u64::from(b));
+        Ok(value >> self.unused_bits)
+    }
+
+    /// Convert this object to an `OctetString` with the same content octets
+    ///
+    /// Fails if the value is not a whole number of octets (`unused_bits` must be zero).
+    ///
+    /// ```rust
+    /// use asn1_rs::BitString;
+    ///
+    /// let b = BitString::new(0, &[0x01, 0x02]);
+    /// let o = b.try_into_octets().expect("not octet-aligned");
+    /// assert_eq!(o.as_ref(), &[0x01, 0x02]);
+    /// assert!(BitString::new(4, &[0x10]).try_into_octets().is_err());
+    /// ```
+    pub fn try_into_octets(self) -> Result<OctetString<'a>> {
+        if self.unused_bits != 0 {
+            return Err(Self::TAG.invalid_value("value is not octet-aligned (unused bits)"));
+        }
+        let o = match self.data {
+            Cow::Borrowed(b) => OctetString::new(b),
+            Cow::Owned(v) => OctetString::from(v),
+        };
+        Ok(o)
+    }
+
+    /// Return the bits of this object (excluding unused bits) as a `BitVec`
+    ///
+    /// Bit 0 of the `BitVec` is bit 0 of the `BitString` (most significant bit of the first
+    /// octet).
+    #[cfg(feature = "bits")]
+    pub fn to_bitvec(&self) -> BitVec<u8, Msb0> {
+        let mut bv = BitVec::<u8, Msb0>::from_slice(&self.data);
+        bv.truncate(self.bit_len());
+        bv
+    }
+}
+
+#[cfg(feature = "bits")]
+impl<'a> From<&BitSlice<u8, Msb0>> for BitString<'a> {
+    /// Build a `BitString` from a `BitSlice`, using the same bit ordering
+    ///
+    /// Unused bits are set to zero, as required by DER.
+    fn from(bits: &BitSlice<u8, Msb0>) -> Self {
+        let mut data = vec![0u8; (bits.len() + 7) / 8];
+        for i in bits.iter_ones() {
+            data[i / 8] |= 0x80 >> (i % 8);
+        }
+        let unused_bits = (data.len() * 8 - bits.len()) as u8;
+        BitString {
+            unused_bits,
+            data: Cow::Owned(data),
+        }
+    }
 }
 
 impl<'a> AsRef<[u8]> for BitString<'a> {
@@ -57,6 +178,13 @@ impl<'a, 'b> TryFrom<&'b Any<'a>> for BitString<'a> {
 
     fn try_from(any: &'b Any<'a>) -> Result<BitString<'a>> {
         any.tag().assert_eq(Self::TAG)?;
+        if any.header.is_constructed() {
+            let (unused_bits, data) = reassemble_bitstring(any, MAX_RECURSION)?;
+            return Ok(BitString {
+                unused_bits,
+                data: Cow::Owned(data),
+            });
+        }
         if any.data.is_empty() {
             return Err(Error::InvalidLength);
         }
@@ -66,36 +194,97 @@ impl<'a, 'b> TryFrom<&'b Any<'a>> for BitString<'a> {
     }
 }
 
+/// Reassemble the fragments of a constructed `BIT STRING` (X.690 section 8.6.4)
+///
+/// Each fragment starts with its number of unused bits, which must be 0 except for the last one,
+/// so fragments can be concatenated on octet boundaries. Fragments can also be constructed, up
+/// to `max_depth` levels.
+///
+/// Returns the number of unused bits of the last fragment, and the concatenated bits.
+pub(crate) fn reassemble_bitstring(any: &Any, max_depth: usize) -> Result<(u8, Vec<u8>)> {
+    let mut bits = Vec::new();
+    let unused = reassemble_bitstring_with(any, max_depth, |data| bits.extend_from_slice(data))?;
+    Ok((unused, bits))
+}
+
+/// Reassemble the fragments of a constructed `BIT STRING`, passing the bits of each fragment to
+/// `push`
+///
+/// See [`reassemble_bitstring`]. Returns the number of unused bits of the last fragment.
+pub(crate) fn reassemble_bitstring_with(
+    any: &Any,
+    max_depth: usize,
+    mut push: impl FnMut(&[u8]),
+) -> Result<u8> {
+    let mut unused = 0;
+    let mut stack = vec![any.data];
+    while let Some(top) = stack.last_mut() {
+        if top.is_empty() {
+            stack.pop();
+            continue;
+        }
+        if unused != 0 {
+            // only the last fragment can have unused bits
+            return Err(Error::InvalidLength);
+        }
+        let (rem, fragment) = Any::from_ber(top)?;
+        *top = rem;
+        fragment.header.assert_class(Class::Universal)?;
+        fragment.tag().assert_eq(Tag::BitString)?;
+        if fragment.header.is_constructed() {
+            if stack.len() >= max_depth {
+                return Err(Error::BerMaxDepth);
+            }
+            stack.push(fragment.data);
+            continue;
+        }
+        let (n, data) = fragment.data.split_first().ok_or(Error::InvalidLength)?;
+        if *n > 7 || (data.is_empty() && *n != 0) {
+            return Err(Error::InvalidLength);
+        }
+        unused = *n;
+        push(data);
+    }
+    Ok(unused)
+}
+
 impl<'a> CheckDerConstraints for BitString<'a> {
     fn check_constraints(any: &Any) -> Result<()> {
         // X.690 section 10.2
-        any.header.assert_primitive()?;
-        // Check that padding bits are all 0 (X.690 section 11.2.1)
-        match any.data.len() {
-            0 => Err(Error::InvalidLength),
-            1 => {
-                // X.690 section 11.2.2 Note 2
-                if any.data[0] == 0 {
-                    Ok(())
-                } else {
-                    Err(Error::InvalidLength)
-                }
-            }
-            len => {
-                let unused_bits = any.data[0];
-                let last_byte = any.data[len - 1];
-                if last_byte.trailing_zeros() < unused_bits as u32 {
-                    return Err(Error::DerConstraintFailed(DerConstraint::UnusedBitsNotZero));
-                }
-
-                Ok(())
+        any.header.assert_der_primitive()?;
+        let (unused_bits, data) = any.data.split_first().ok_or(Error::InvalidLength)?;
+        // X.690 section 8.6.2.2
+        if *unused_bits > 7 {
+            return Err(Error::InvalidLength);
+        }
+        match data.last() {
+            // X.690 section 11.2.2 Note 2
+            None if *unused_bits != 0 => Err(Error::DerConstraintFailed(
+                DerConstraint::EmptyBitStringUnusedBits,
+            )),
+            None => Ok(()),
+            // Check that padding bits are all 0 (X.690 section 11.2.1)
+            Some(last_byte) if last_byte.trailing_zeros() < u32::from(*unused_bits) => {
+                Err(Error::DerConstraintFailed(DerConstraint::UnusedBitsNotZero))
             }
+            Some(_) => Ok(()),
         }
     }
 }
 
 impl DerAutoDerive for BitString<'_> {}
 
+impl ToStatic for BitString<'_> {
+    type Owned = BitString<'static>;
+
+    fn to_static(&self) -> Self::Owned {
+        BitString {
+            unused_bits: self.unused_bits,
+            data: Cow::Owned(self.data.to_vec()),
+        }
+    }
+}
+
 impl<'a> Tagged for BitString<'a> {
     const TAG: Tag = Tag::BitString;
 }
@@ -129,11 +318,68 @@ impl ToDer for BitString<'_> {
         let sz = sz + writer.write(&self.data)?;
         Ok(sz)
     }
+
+    fn to_der_vectored(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        // header (at most 1 (tag) + 9 (length) bytes) and unused bits
+        let mut header = [0u8; 16];
+        let mut cursor = std::io::Cursor::new(&mut header[..]);
+        let hdr_len = self.write_der_header(&mut cursor)?;
+        header[hdr_len] = self.unused_bits;
+        write_all_vectored(writer, &[&header[..=hdr_len], &self.data]).map_err(Into::into)
+    }
+}
+
+impl<'a> TryFrom<BitString<'a>> for OctetString<'a> {
+    type Error = Error;
+
+    /// See [`BitString::try_into_octets`]
+    fn try_from(b: BitString<'a>) -> Result<Self> {
+        b.try_into_octets()
+    }
+}
+
+impl<'a> From<OctetString<'a>> for BitString<'a> {
+    /// See [`OctetString::into_bitstring`]
+    fn from(o: OctetString<'a>) -> Self {
+        o.into_bitstring()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::BitString;
+    use crate::{Err, Error, FromBer, FromDer};
+    use alloc::borrow::Cow;
+    use hex_literal::hex;
+
+    #[test]
+    fn test_bitstring_constructed() {
+        // constructed BIT STRING { '0A3B'H, '5F291CD'H }, X.690 section 8.6.4.2
+        let input = &hex!("23 80 03 03 00 0a 3b 03 05 04 5f 29 1c d0 00 00");
+        let (rem, b) = BitString::from_ber(input).expect("parsing failed");
+        assert!(rem.is_empty());
+        assert_eq!(b.unused_bits, 4);
+        assert_eq!(b.data.as_ref(), &hex!("0a 3b 5f 29 1c d0"));
+        assert!(matches!(b.data, Cow::Owned(_)));
+        assert_eq!(b.bit_len(), 44);
+        // nested and empty fragments
+        let input = &hex!("23 0d 03 01 00 23 04 03 02 00 ff 03 02 06 80");
+        let (_, b) = BitString::from_ber(input).expect("parsing failed");
+        assert_eq!(b.unused_bits, 6);
+        assert_eq!(b.data.as_ref(), &hex!("ff 80"));
+        // intermediate fragment with unused bits
+        let input = &hex!("23 08 03 02 04 a0 03 02 00 ff");
+        assert_eq!(
+            BitString::from_ber(input),
+            Err(Err::Error(Error::InvalidLength))
+        );
+        // fragment with wrong tag
+        let input = &hex!("23 04 04 02 00 ff");
+        assert!(BitString::from_ber(input).is_err());
+        // not allowed in DER
+        let input = &hex!("23 04 03 02 00 ff");
+        assert!(BitString::from_der(input).is_err());
+    }
 
     #[test]
     fn test_bitstring_is_set() {
@@ -144,6 +390,28 @@ mod tests {
         assert!(obj.is_set(17));
     }
 
+    #[test]
+    fn test_bitstring_octetstring() {
+        use crate::{OctetString, Tag};
+        use core::convert::TryFrom;
+        let obj = BitString::new(0, &[0x0f, 0x40]);
+        let o = obj.clone().try_into_octets().expect("conversion failed");
+        assert_eq!(o.as_ref(), &[0x0f, 0x40]);
+        assert_eq!(BitString::from(o), obj);
+        // empty value
+        let o = OctetString::try_from(BitString::new(0, &[])).expect("conversion failed");
+        assert!(o.as_ref().is_empty());
+        // unused bits
+        let err = BitString::new(4, &[0x0f, 0x40]).try_into_octets();
+        assert!(matches!(
+            err,
+            Err(Error::InvalidValue {
+                tag: Tag::BitString,
+                ..
+            })
+        ));
+    }
+
     #[cfg(feature = "bits")]
     #[test]
     fn test_bitstring_to_bitvec() {
@@ -154,4 +422,41 @@ mod tests {
         assert_eq!(bv.get(9).as_deref(), Some(&false));
         assert_eq!(bv.get(17).as_deref(), Some(&true));
     }
+
+    #[test]
+    fn test_bitstring_u64() {
+        let obj = BitString::new(4, &[0x0f, 0x40]);
+        assert_eq!(obj.bit_len(), 12);
+        assert_eq!(obj.as_u64(), Ok(0x0f4));
+        let obj2 = BitString::from_u64_msb(0x0f4, 12).expect("from_u64_msb failed");
+        assert_eq!(obj, obj2);
+        // all 64 bits
+        let obj = BitString::from_u64_msb(u64::MAX, 64).expect("from_u64_msb failed");
+        assert_eq!(obj.as_u64(), Ok(u64::MAX));
+        // empty
+        let obj = BitString::from_u64_msb(0, 0).expect("from_u64_msb failed");
+        assert_eq!(obj.bit_len(), 0);
+        assert_eq!(obj.as_u64(), Ok(0));
+        // errors
+        assert_eq!(BitString::from_u64_msb(8, 3), Err(Error::IntegerTooLarge));
+        assert_eq!(BitString::from_u64_msb(0, 65), Err(Error::IntegerTooLarge));
+        let obj = BitString::new(0, &[0; 9]);
+        assert_eq!(obj.as_u64(), Err(Error::IntegerTooLarge));
+    }
+
+    #[cfg(feature = "bits")]
+    #[test]
+    fn test_bitstring_bitslice_conversions() {
+        use bitvec::prelude::*;
+        let obj = BitString::new(4, &[0x0f, 0x4f]);
+        let bv = obj.to_bitvec();
+        assert_eq!(bv.len(), 12);
+        assert_eq!(
+            bv.as_bitslice(),
+            bits![u8, Msb0; 0, 0, 0, 0, 1, 1, 1, 1, 0, 1, 0, 0]
+        );
+        // unused bits are cleared
+        let obj2 = BitString::from(bv.as_bitslice());
+        assert_eq!(obj2, BitString::new(4, &[0x0f, 0x40]));
+    }
 }
@@ -6,7 +6,7 @@ use core::convert::TryFrom;
 /// BER objects consider any non-zero value as `true`, and `0` as `false`.
 ///
 /// DER objects must use value `0x0` (`false`) or `0xff` (`true`).
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Boolean {
     pub value: u8,
 }
@@ -30,6 +30,29 @@ impl Boolean {
     }
 }
 
+impl Default for Boolean {
+    /// Return `BOOLEAN` object for value `false`
+    fn default() -> Self {
+        Boolean::FALSE
+    }
+}
+
+impl From<bool> for Boolean {
+    fn from(b: bool) -> Self {
+        if b {
+            Boolean::TRUE
+        } else {
+            Boolean::FALSE
+        }
+    }
+}
+
+impl From<Boolean> for bool {
+    fn from(b: Boolean) -> Self {
+        b.bool()
+    }
+}
+
 impl<'a> TryFrom<Any<'a>> for Boolean {
     type Error = Error;
 
@@ -46,22 +69,26 @@ impl<'a, 'b> TryFrom<&'b Any<'a>> for Boolean {
         any.tag().assert_eq(Self::TAG)?;
         // X.690 section 8.2.1:
         // The encoding of a boolean value shall be primitive. The contents octets shall consist of a single octet
-        if any.header.length != Length::Definite(1) {
-            return Err(Error::InvalidLength);
+        match (any.header.length, any.data) {
+            (Length::Definite(1), &[value]) => Ok(Boolean { value }),
+            _ => Err(Error::InvalidLength),
         }
-        let value = any.data[0];
-        Ok(Boolean { value })
     }
 }
 
 impl CheckDerConstraints for Boolean {
     fn check_constraints(any: &Any) -> Result<()> {
-        let c = any.data[0];
+        // check the tag first, so other types are reported as `UnexpectedTag` (for ex. when
+        // parsing an optional value)
+        any.tag().assert_eq(Tag::Boolean)?;
         // X.690 section 11.1
-        if !(c == 0 || c == 0xff) {
-            return Err(Error::DerConstraintFailed(DerConstraint::InvalidBoolean));
+        match any.data {
+            [0] | [0xff] => Ok(()),
+            [_] => Err(Error::DerConstraintFailed(
+                DerConstraint::NonCanonicalBoolean,
+            )),
+            _ => Err(Error::InvalidLength),
         }
-        Ok(())
     }
 }
 
@@ -74,12 +101,11 @@ impl Tagged for Boolean {
 #[cfg(feature = "std")]
 impl ToDer for Boolean {
     fn to_der_len(&self) -> Result<usize> {
-        // 3 = 1 (tag) + 1 (length) + 1 (value)
-        Ok(3)
+        primitive_der_len(Self::TAG, 1)
     }
 
     fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
-        writer.write(&[Self::TAG.0 as u8, 0x01]).map_err(Into::into)
+        write_header(Self::TAG, 1, writer)
     }
 
     fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
@@ -89,7 +115,8 @@ impl ToDer for Boolean {
 
     /// Similar to using `to_der`, but uses header without computing length value
     fn write_der_raw(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
-        let sz = writer.write(&[Self::TAG.0 as u8, 0x01, self.value])?;
+        let sz = write_header(Self::TAG, 1, writer)?;
+        let sz = sz + writer.write(&[self.value])?;
         Ok(sz)
     }
 }
@@ -114,12 +141,17 @@ impl<'a, 'b> TryFrom<&'b Any<'a>> for bool {
 
 impl CheckDerConstraints for bool {
     fn check_constraints(any: &Any) -> Result<()> {
-        let c = any.data[0];
+        // check the tag first, so other types are reported as `UnexpectedTag` (for ex. when
+        // parsing an optional value)
+        any.tag().assert_eq(Tag::Boolean)?;
         // X.690 section 11.1
-        if !(c == 0 || c == 0xff) {
-            return Err(Error::DerConstraintFailed(DerConstraint::InvalidBoolean));
+        match any.data {
+            [0] | [0xff] => Ok(()),
+            [_] => Err(Error::DerConstraintFailed(
+                DerConstraint::NonCanonicalBoolean,
+            )),
+            _ => Err(Error::InvalidLength),
         }
-        Ok(())
     }
 }
 
@@ -132,12 +164,11 @@ impl Tagged for bool {
 #[cfg(feature = "std")]
 impl ToDer for bool {
     fn to_der_len(&self) -> Result<usize> {
-        // 3 = 1 (tag) + 1 (length) + 1 (value)
-        Ok(3)
+        primitive_der_len(Self::TAG, 1)
     }
 
     fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
-        writer.write(&[Self::TAG.0 as u8, 0x01]).map_err(Into::into)
+        write_header(Self::TAG, 1, writer)
     }
 
     fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
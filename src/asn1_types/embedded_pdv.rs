@@ -1,14 +1,14 @@
 use crate::*;
 use core::convert::TryFrom;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct EmbeddedPdv<'a> {
     pub identification: PdvIdentification<'a>,
     pub data_value_descriptor: Option<ObjectDescriptor<'a>>,
     pub data_value: &'a [u8],
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum PdvIdentification<'a> {
     Syntaxes {
         s_abstract: Oid<'a>,
@@ -50,7 +50,8 @@ impl<'a, 'b> TryFrom<&'b Any<'a>> for EmbeddedPdv<'a> {
                 // },
                 // AUTOMATIC tags -> implicit! Hopefully, Oid does not check tag value!
                 let (rem, s_abstract) = Oid::from_ber(inner.data)?;
-                let (_, s_transfer) = Oid::from_ber(rem)?;
+                let (rem, s_transfer) = Oid::from_ber(rem)?;
+                inner.header.assert_consumed(rem)?;
                 PdvIdentification::Syntaxes {
                     s_abstract,
                     s_transfer,
@@ -74,7 +75,8 @@ impl<'a, 'b> TryFrom<&'b Any<'a>> for EmbeddedPdv<'a> {
                 // AUTOMATIC tags -> implicit!
                 let (rem, any) = Any::from_ber(inner.data)?;
                 let presentation_context_id = Integer::new(any.data);
-                let (_, presentation_syntax) = Oid::from_ber(rem)?;
+                let (rem, presentation_syntax) = Oid::from_ber(rem)?;
+                inner.header.assert_consumed(rem)?;
                 PdvIdentification::ContextNegotiation {
                     presentation_context_id,
                     presentation_syntax,
@@ -102,8 +104,9 @@ impl<'a, 'b> TryFrom<&'b Any<'a>> for EmbeddedPdv<'a> {
         //     TaggedOptional::from(1).parse_der(rem, |_, inner| ObjectDescriptor::from_ber(inner))?;
         let (rem, data_value_descriptor) = (rem, None);
         // [2] -> data-value OCTET STRING
-        let (_, data_value) =
+        let (rem, data_value) =
             TaggedParser::<Implicit, &[u8]>::parse_ber(Class::ContextSpecific, Tag(2), rem)?;
+        any.header.assert_consumed(rem)?;
         let data_value = data_value.inner;
         let obj = EmbeddedPdv {
             identification,
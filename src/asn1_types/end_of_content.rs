@@ -1,4 +1,4 @@
-use crate::{Any, Error, Result, Tag, Tagged};
+use crate::{Any, Error, FromBer, Header, ParseResult, Result, Tag, Tagged};
 use core::convert::TryFrom;
 
 /// End-of-contents octets
@@ -7,13 +7,59 @@ use core::convert::TryFrom;
 /// of an object, when the length is `Indefinite` (see X.690 section 8.1.5).
 ///
 /// This type cannot exist in DER, and so provides no `FromDer`/`ToDer` implementation.
-#[derive(Debug)]
+///
+/// Parsers consume the end-of-contents octets of indefinite length objects (they are not part of
+/// the content returned in [`Any::data`]). Parsing this type directly can be used when reading
+/// the elements of an indefinite length object manually: see [`EndOfContent::parse_ber`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub struct EndOfContent {}
 
 impl EndOfContent {
+    /// Encoding of the end-of-contents octets
+    pub const BYTES: [u8; 2] = [0x00, 0x00];
+
     pub const fn new() -> Self {
         EndOfContent {}
     }
+
+    /// Parse the end-of-contents octets, if they are the next bytes of input
+    ///
+    /// Returns `Some(EndOfContent)` and consumes the octets if the next object is an
+    /// end-of-contents marker, and `None` (without consuming input) if it is another object. This
+    /// allows reading the elements of indefinite length content until the marker is reached.
+    ///
+    /// Returns [`Error::InvalidEndOfContent`] if the next object has tag 0 (universal), but is
+    /// not a valid marker.
+    ///
+    /// ```rust
+    /// use asn1_rs::{EndOfContent, Integer, FromBer};
+    ///
+    /// // content of an indefinite length SEQUENCE { INTEGER 1 }
+    /// let mut i: &[u8] = &[0x02, 0x01, 0x01, 0x00, 0x00, 0xff];
+    /// let mut count = 0;
+    /// loop {
+    ///     let (rem, eoc) = EndOfContent::parse_ber(i).expect("parsing failed");
+    ///     i = rem;
+    ///     if eoc.is_some() {
+    ///         break;
+    ///     }
+    ///     let (rem, _) = Integer::from_ber(i).expect("parsing failed");
+    ///     i = rem;
+    ///     count += 1;
+    /// }
+    /// assert_eq!(count, 1);
+    /// assert_eq!(i, &[0xff]);
+    /// ```
+    pub fn parse_ber(i: &[u8]) -> ParseResult<'_, Option<EndOfContent>> {
+        let (rem, header) = Header::from_ber(i)?;
+        if !header.is_universal() || header.tag() != Self::TAG {
+            return Ok((i, None));
+        }
+        if !header.is_end_of_content() {
+            return Err(nom::Err::Error(Error::InvalidEndOfContent));
+        }
+        Ok((rem, Some(EndOfContent {})))
+    }
 }
 
 impl<'a> TryFrom<Any<'a>> for EndOfContent {
@@ -32,6 +78,8 @@ impl<'a, 'b> TryFrom<&'b Any<'a>> for EndOfContent {
         if !any.header.length.is_null() {
             return Err(Error::InvalidLength);
         }
+        any.header.assert_class(crate::Class::Universal)?;
+        any.header.assert_primitive()?;
         Ok(EndOfContent {})
     }
 }
@@ -7,13 +7,43 @@ use core::convert::TryFrom;
 /// # Limitations
 ///
 /// Supported values are limited to 0 .. 2^32
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct Enumerated(pub u32);
 
 impl Enumerated {
     pub const fn new(value: u32) -> Self {
         Enumerated(value)
     }
+
+    /// Return the value as an `u32`
+    pub const fn as_u32(&self) -> u32 {
+        self.0
+    }
+
+    /// Attempt to convert the value to an `i32`
+    ///
+    /// Returns [`Error::IntegerOutOfRange`] if the value is greater than `i32::MAX`.
+    pub fn as_i32(&self) -> Result<i32> {
+        i32::try_from(self.0).map_err(|_| Error::IntegerOutOfRange {
+            bits: 33 - self.0.leading_zeros() as usize,
+            max: 32,
+        })
+    }
+}
+
+impl From<&Enumerated> for u32 {
+    fn from(e: &Enumerated) -> u32 {
+        e.0
+    }
+}
+
+impl TryFrom<&Enumerated> for i32 {
+    type Error = Error;
+
+    fn try_from(e: &Enumerated) -> Result<i32> {
+        e.as_i32()
+    }
 }
 
 impl<'a> TryFrom<Any<'a>> for Enumerated {
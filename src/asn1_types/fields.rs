@@ -0,0 +1,297 @@
+use crate::*;
+use std::collections::HashMap;
+
+/// Description of an expected field, for dynamic decoding of `SEQUENCE` and `SET` objects
+///
+/// See [`parse_der_sequence_fields`] and [`parse_der_set_fields`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FieldDescriptor<'n> {
+    /// Field name, used as key in the returned map
+    pub name: &'n str,
+    /// Expected class
+    pub class: Class,
+    /// Expected tag
+    pub tag: Tag,
+    /// True if the field must be present
+    pub required: bool,
+}
+
+impl<'n> FieldDescriptor<'n> {
+    /// Build a descriptor for a required field, with `Universal` class
+    pub const fn required(name: &'n str, tag: Tag) -> Self {
+        FieldDescriptor {
+            name,
+            class: Class::Universal,
+            tag,
+            required: true,
+        }
+    }
+
+    /// Build a descriptor for an optional field, with `Universal` class
+    pub const fn optional(name: &'n str, tag: Tag) -> Self {
+        FieldDescriptor {
+            name,
+            class: Class::Universal,
+            tag,
+            required: false,
+        }
+    }
+
+    /// Set the expected class of this field
+    pub const fn with_class(self, class: Class) -> Self {
+        FieldDescriptor { class, ..self }
+    }
+
+    #[inline]
+    fn matches(&self, any: &Any) -> bool {
+        self.class == any.class() && self.tag == any.tag()
+    }
+}
+
+/// The result of dynamic decoding: objects indexed by field name
+pub type FieldMap<'n, 'a> = HashMap<&'n str, Any<'a>>;
+
+/// Match items of a `SEQUENCE` to fields, in order
+fn match_sequence_fields<'a, 'n, I>(
+    items: I,
+    fields: &[FieldDescriptor<'n>],
+) -> Result<FieldMap<'n, 'a>>
+where
+    I: Iterator<Item = Result<Any<'a>>>,
+{
+    let mut map = HashMap::new();
+    let mut idx = 0;
+    for item in items {
+        let item = item?;
+        // find next matching field, skipping optional fields
+        let pos = fields[idx..].iter().position(|f| f.matches(&item));
+        match pos {
+            Some(pos) => {
+                if let Some(missing) = fields[idx..idx + pos].iter().find(|f| f.required) {
+                    return Err(Error::MissingField(missing.name.to_string()));
+                }
+                map.insert(fields[idx + pos].name, item);
+                idx += pos + 1;
+            }
+            None => {
+                if let Some(previous) = fields[..idx].iter().rev().find(|f| f.matches(&item)) {
+                    // a previous field can be absent only if it is optional
+                    if map.contains_key(previous.name) {
+                        return Err(Error::DuplicateField(previous.name.to_string()));
+                    }
+                    return Err(Error::UnorderedField(previous.name.to_string()));
+                }
+                return Err(Error::UnexpectedField {
+                    class: item.class(),
+                    tag: item.tag(),
+                });
+            }
+        }
+    }
+    if let Some(missing) = fields[idx..].iter().find(|f| f.required) {
+        return Err(Error::MissingField(missing.name.to_string()));
+    }
+    Ok(map)
+}
+
+/// Match items of a `SET` to fields, in any order
+fn match_set_fields<'a, 'n, I>(items: I, fields: &[FieldDescriptor<'n>]) -> Result<FieldMap<'n, 'a>>
+where
+    I: Iterator<Item = Result<Any<'a>>>,
+{
+    let mut map = HashMap::new();
+    for item in items {
+        let item = item?;
+        let field = fields
+            .iter()
+            .find(|f| f.matches(&item))
+            .ok_or(Error::UnexpectedField {
+                class: item.class(),
+                tag: item.tag(),
+            })?;
+        if map.insert(field.name, item).is_some() {
+            return Err(Error::DuplicateField(field.name.to_string()));
+        }
+    }
+    if let Some(missing) = fields
+        .iter()
+        .find(|f| f.required && !map.contains_key(f.name))
+    {
+        return Err(Error::MissingField(missing.name.to_string()));
+    }
+    Ok(map)
+}
+
+/// Parse a BER `SEQUENCE`, and return its items indexed by field name
+///
+/// Items must appear in the order of `fields`. Optional fields can be absent.
+///
+/// This function returns an error if a required field is missing ([`Error::MissingField`]),
+/// if a field is present more than once ([`Error::DuplicateField`]), if an optional field
+/// appears after a following field ([`Error::UnorderedField`]), or if an item does not match
+/// any expected field ([`Error::UnexpectedField`]).
+pub fn parse_ber_sequence_fields<'a, 'n>(
+    bytes: &'a [u8],
+    fields: &[FieldDescriptor<'n>],
+) -> ParseResult<'a, FieldMap<'n, 'a>> {
+    let (rem, any) = Any::from_ber(bytes)?;
+    any.tag().assert_eq(Tag::Sequence)?;
+    any.header.assert_constructed()?;
+    let items = SequenceIterator::<Any, BerParser>::new(any.data);
+    let map = match_sequence_fields(items, fields)?;
+    Ok((rem, map))
+}
+
+/// Parse a DER `SEQUENCE`, and return its items indexed by field name
+///
+/// Items must appear in the order of `fields`. Optional fields can be absent.
+///
+/// This function returns an error if a required field is missing ([`Error::MissingField`]),
+/// if a field is present more than once ([`Error::DuplicateField`]), if an optional field
+/// appears after a following field ([`Error::UnorderedField`]), or if an item does not match
+/// any expected field ([`Error::UnexpectedField`]).
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{parse_der_sequence_fields, Class, FieldDescriptor, Tag};
+///
+/// // SEQUENCE { version [0] INTEGER OPTIONAL, serial INTEGER, name UTF8String }
+/// let fields = [
+///     FieldDescriptor::optional("version", Tag(0)).with_class(Class::ContextSpecific),
+///     FieldDescriptor::required("serial", Tag::Integer),
+///     FieldDescriptor::required("name", Tag::Utf8String),
+/// ];
+///
+/// let bytes = &[0x30, 0x08, 0x02, 0x01, 0x2a, 0x0c, 0x03, 0x61, 0x62, 0x63];
+/// let (_, map) = parse_der_sequence_fields(bytes, &fields).expect("parsing failed");
+/// assert!(!map.contains_key("version"));
+/// assert_eq!(map["serial"].as_u32(), Ok(42));
+/// assert_eq!(map["name"].as_str(), Ok("abc"));
+/// ```
+pub fn parse_der_sequence_fields<'a, 'n>(
+    bytes: &'a [u8],
+    fields: &[FieldDescriptor<'n>],
+) -> ParseResult<'a, FieldMap<'n, 'a>> {
+    let (rem, any) = Any::from_der(bytes)?;
+    any.tag().assert_eq(Tag::Sequence)?;
+    any.header.assert_constructed()?;
+    let items = SequenceIterator::<Any, DerParser>::new(any.data);
+    let map = match_sequence_fields(items, fields)?;
+    Ok((rem, map))
+}
+
+/// Parse a BER `SET`, and return its items indexed by field name
+///
+/// Items can appear in any order. Optional fields can be absent.
+///
+/// This function returns an error if a required field is missing ([`Error::MissingField`]),
+/// if a field is present more than once ([`Error::DuplicateField`]), or if an item does not
+/// match any expected field ([`Error::UnexpectedField`]).
+pub fn parse_ber_set_fields<'a, 'n>(
+    bytes: &'a [u8],
+    fields: &[FieldDescriptor<'n>],
+) -> ParseResult<'a, FieldMap<'n, 'a>> {
+    let (rem, any) = Any::from_ber(bytes)?;
+    any.tag().assert_eq(Tag::Set)?;
+    any.header.assert_constructed()?;
+    let items = SetIterator::<Any, BerParser>::new(any.data);
+    let map = match_set_fields(items, fields)?;
+    Ok((rem, map))
+}
+
+/// Parse a DER `SET`, and return its items indexed by field name
+///
+/// Items can appear in any order. Optional fields can be absent.
+///
+/// This function returns an error if a required field is missing ([`Error::MissingField`]),
+/// if a field is present more than once ([`Error::DuplicateField`]), or if an item does not
+/// match any expected field ([`Error::UnexpectedField`]).
+pub fn parse_der_set_fields<'a, 'n>(
+    bytes: &'a [u8],
+    fields: &[FieldDescriptor<'n>],
+) -> ParseResult<'a, FieldMap<'n, 'a>> {
+    let (rem, any) = Any::from_der(bytes)?;
+    any.tag().assert_eq(Tag::Set)?;
+    any.header.assert_constructed()?;
+    let items = SetIterator::<Any, DerParser>::new(any.data);
+    let map = match_set_fields(items, fields)?;
+    Ok((rem, map))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use hex_literal::hex;
+
+    const FIELDS: &[FieldDescriptor] = &[
+        FieldDescriptor::optional("version", Tag(0)).with_class(Class::ContextSpecific),
+        FieldDescriptor::required("serial", Tag::Integer),
+        FieldDescriptor::required("name", Tag::Utf8String),
+        FieldDescriptor::optional("flag", Tag::Boolean),
+    ];
+
+    #[test]
+    fn sequence_fields() {
+        let input = &hex!("30 0d a0 03 02 01 02 02 01 2a 0c 03 61 62 63");
+        let (rem, map) = parse_der_sequence_fields(input, FIELDS).expect("parsing failed");
+        assert!(rem.is_empty());
+        assert_eq!(map.len(), 3);
+        assert_eq!(map["serial"].as_u32(), Ok(42));
+        assert!(!map.contains_key("flag"));
+        // missing required field
+        let input = &hex!("30 05 0c 03 61 62 63");
+        let res = parse_der_sequence_fields(input, FIELDS);
+        assert_eq!(
+            res,
+            Err(Err::Error(Error::MissingField("serial".to_string())))
+        );
+        // duplicate field
+        let input = &hex!("30 0b 02 01 2a 0c 03 61 62 63 02 01 2a");
+        let res = parse_der_sequence_fields(input, FIELDS);
+        assert_eq!(
+            res,
+            Err(Err::Error(Error::DuplicateField("serial".to_string())))
+        );
+        // optional field after a following field
+        let input = &hex!("30 0d 02 01 2a a0 03 02 01 02 0c 03 61 62 63");
+        let res = parse_der_sequence_fields(input, FIELDS);
+        assert_eq!(
+            res,
+            Err(Err::Error(Error::UnorderedField("version".to_string())))
+        );
+        // unexpected field
+        let input = &hex!("30 0a 02 01 2a 0c 03 61 62 63 05 00");
+        let res = parse_ber_sequence_fields(input, FIELDS);
+        assert_eq!(
+            res,
+            Err(Err::Error(Error::UnexpectedField {
+                class: Class::Universal,
+                tag: Tag::Null
+            }))
+        );
+    }
+
+    #[test]
+    fn set_fields() {
+        let input = &hex!("31 0b 01 01 ff 0c 03 61 62 63 02 01 2a");
+        let (rem, map) = parse_der_set_fields(input, FIELDS).expect("parsing failed");
+        assert!(rem.is_empty());
+        assert_eq!(map.len(), 3);
+        assert_eq!(map["flag"].as_bool(), Ok(true));
+        // duplicate field
+        let input = &hex!("31 0b 02 01 2a 0c 03 61 62 63 02 01 2a");
+        let res = parse_ber_set_fields(input, FIELDS);
+        assert_eq!(
+            res,
+            Err(Err::Error(Error::DuplicateField("serial".to_string())))
+        );
+        // missing required field
+        let input = &hex!("31 03 02 01 2a");
+        let res = parse_der_set_fields(input, FIELDS);
+        assert_eq!(
+            res,
+            Err(Err::Error(Error::MissingField("name".to_string())))
+        );
+    }
+}
@@ -1,21 +1,90 @@
-use crate::datetime::decode_decimal;
+#[cfg(feature = "std")]
+use crate::datetime::encode_time_fields;
+use crate::datetime::{
+    check_calendar_date, decode_decimal, decode_decimal4, decode_fraction_millis,
+};
 use crate::*;
 use alloc::format;
 use alloc::string::String;
+use core::cmp::Ordering;
 use core::convert::TryFrom;
 use core::fmt;
 #[cfg(feature = "datetime")]
 use time::OffsetDateTime;
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub struct GeneralizedTime(pub ASN1DateTime);
+/// ASN.1 `GeneralizedTime` type
+///
+/// When parsed, the object also records the format of the original time string (see
+/// [`GeneralizedTime::format`]). The format is not part of the value: it is ignored when
+/// comparing objects.
+#[derive(Clone, Debug)]
+pub struct GeneralizedTime(pub ASN1DateTime, Option<ASN1TimeFormat>);
 
 impl GeneralizedTime {
     pub const fn new(datetime: ASN1DateTime) -> Self {
-        GeneralizedTime(datetime)
+        GeneralizedTime(datetime, None)
+    }
+
+    /// Create a new `GeneralizedTime` from its components, checking that it can be encoded
+    ///
+    /// Fields are checked as in [`ASN1DateTime::validate`], and [`Error::InvalidDateTime`] is
+    /// returned if the year has more than 4 digits.
+    ///
+    /// ```rust
+    /// use asn1_rs::{ASN1TimeZone, Error, GeneralizedTime};
+    ///
+    /// let t = GeneralizedTime::try_new(2024, 2, 29, 12, 0, 0, Some(250), ASN1TimeZone::Z);
+    /// assert!(t.is_ok());
+    /// let t = GeneralizedTime::try_new(2024, 1, 1, 12, 0, 0, Some(1000), ASN1TimeZone::Z);
+    /// assert_eq!(t, Err(Error::InvalidDateTime));
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_new(
+        year: u32,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+        millisecond: Option<u16>,
+        tz: ASN1TimeZone,
+    ) -> Result<Self> {
+        if year > 9999 {
+            return Err(Error::InvalidDateTime);
+        }
+        let dt = ASN1DateTime::try_new(year, month, day, hour, minute, second, millisecond, tz)?;
+        Ok(GeneralizedTime::new(dt))
+    }
+
+    /// Return the format of the original time string, if this object was parsed
+    #[inline]
+    pub const fn format(&self) -> Option<ASN1TimeFormat> {
+        self.1
+    }
+
+    /// Set the format used by [`GeneralizedTime::write_ber_original`]
+    #[inline]
+    pub fn with_format(self, format: ASN1TimeFormat) -> Self {
+        GeneralizedTime(self.0, Some(format))
     }
 
+    /// Parse the content of a `GeneralizedTime` object
+    ///
+    /// The date is checked using the calendar (number of days per month, leap years), and
+    /// [`Error::InvalidCalendarDate`] is returned if it does not exist. See
+    /// [`GeneralizedTime::from_bytes_lenient`] to skip this check.
     pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let t = Self::from_bytes_lenient(bytes)?;
+        check_calendar_date(t.0.year, t.0.month, t.0.day)?;
+        Ok(t)
+    }
+
+    /// Parse the content of a `GeneralizedTime` object, without checking that the date exists
+    ///
+    /// Fields are only checked for their range (for ex. day must be at most 31), so invalid dates
+    /// like February 31st are accepted. This can be used to parse data from non-conforming
+    /// BER encoders.
+    pub fn from_bytes_lenient(bytes: &[u8]) -> Result<Self> {
         // X.680 section 42 defines a GeneralizedTime as a VisibleString restricted to:
         //
         // a) a string representing the calendar date, as specified in ISO 8601, with a four-digit representation of the
@@ -29,9 +98,10 @@ impl GeneralizedTime {
         let (year, month, day, hour, minute, rem) = match bytes {
             [year1, year2, year3, year4, mon1, mon2, day1, day2, hour1, hour2, min1, min2, rem @ ..] =>
             {
-                let year_hi = decode_decimal(Self::TAG, *year1, *year2)?;
-                let year_lo = decode_decimal(Self::TAG, *year3, *year4)?;
-                let year = (year_hi as u32) * 100 + (year_lo as u32);
+                let year = u32::from(decode_decimal4(
+                    Self::TAG,
+                    &[*year1, *year2, *year3, *year4],
+                )?);
                 let month = decode_decimal(Self::TAG, *mon1, *mon2)?;
                 let day = decode_decimal(Self::TAG, *day1, *day2)?;
                 let hour = decode_decimal(Self::TAG, *hour1, *hour2)?;
@@ -44,12 +114,16 @@ impl GeneralizedTime {
             return Err(Self::TAG.invalid_value("malformed time string"));
         }
         // check for seconds
-        let (second, rem) = match rem {
-            [sec1, sec2, rem @ ..] => {
+        let (second, has_seconds, rem) = match rem {
+            [sec1, sec2, rem @ ..] if sec1.is_ascii_digit() && sec2.is_ascii_digit() => {
                 let second = decode_decimal(Self::TAG, *sec1, *sec2)?;
-                (second, rem)
+                (second, true, rem)
             }
-            _ => (0, rem),
+            _ => (0, false, rem),
+        };
+        let mut format = ASN1TimeFormat {
+            seconds: has_seconds,
+            ..ASN1TimeFormat::new()
         };
         if month > 12 || day > 31 || hour > 23 || minute > 59 || second > 59 {
             // eprintln!("GeneralizedTime: time checks failed");
@@ -62,7 +136,7 @@ impl GeneralizedTime {
         }
         if rem.is_empty() {
             // case a): no fractional seconds part, and no terminating Z
-            return Ok(GeneralizedTime(ASN1DateTime::new(
+            let dt = ASN1DateTime::new(
                 year,
                 month,
                 day,
@@ -71,54 +145,23 @@ impl GeneralizedTime {
                 second,
                 None,
                 ASN1TimeZone::Undefined,
-            )));
+            );
+            return Ok(GeneralizedTime(dt, Some(format)));
         }
         // check for fractional seconds
         let (millisecond, rem) = match rem {
-            [b'.' | b',', rem @ ..] => {
-                let mut fsecond = 0;
-                let mut rem = rem;
-                let mut digits = 0;
-                for idx in 0..=4 {
-                    if rem.is_empty() {
-                        if idx == 0 {
-                            // dot or comma, but no following digit
-                            return Err(Self::TAG.invalid_value(
-                                "malformed time string (dot or comma but no digits)",
-                            ));
-                        }
-                        digits = idx;
-                        break;
-                    }
-                    if idx == 4 {
-                        return Err(
-                            Self::TAG.invalid_value("malformed time string (invalid milliseconds)")
-                        );
-                    }
-                    match rem[0] {
-                        b'0'..=b'9' => {
-                            // cannot overflow, max 4 digits will be read
-                            fsecond = fsecond * 10 + (rem[0] - b'0') as u16;
-                        }
-                        b'Z' | b'+' | b'-' => {
-                            digits = idx;
-                            break;
-                        }
-                        _ => {
-                            return Err(Self::TAG.invalid_value(
-                                "malformed time string (invalid milliseconds/timezone)",
-                            ))
-                        }
-                    }
-                    rem = &rem[1..];
+            [sep @ b'.', rem @ ..] | [sep @ b',', rem @ ..] => {
+                format.decimal_comma = *sep == b',';
+                let digits = rem.iter().take_while(|b| b.is_ascii_digit()).count();
+                if digits == 0 {
+                    return Err(Self::TAG
+                        .invalid_value("malformed time string (dot or comma but no digits)"));
                 }
-                // fix fractional seconds depending on the number of digits
-                // for ex, date "xxxx.3" means 3000 milliseconds, not 3
-                let fsecond = match digits {
-                    1 => fsecond * 100,
-                    2 => fsecond * 10,
-                    _ => fsecond,
-                };
+                let (fraction, rem) = rem.split_at(digits);
+                // fractional seconds are scaled depending on the number of digits
+                // for ex, date "xxxx.3" means 300 milliseconds, not 3
+                let fsecond = decode_fraction_millis(Self::TAG, fraction)?;
+                format.fraction_digits = digits as u8;
                 (Some(fsecond), rem)
             }
             _ => (None, rem),
@@ -126,7 +169,7 @@ impl GeneralizedTime {
         // check timezone
         if rem.is_empty() {
             // case a): fractional seconds part, and no terminating Z
-            return Ok(GeneralizedTime(ASN1DateTime::new(
+            let dt = ASN1DateTime::new(
                 year,
                 month,
                 day,
@@ -135,40 +178,116 @@ impl GeneralizedTime {
                 second,
                 millisecond,
                 ASN1TimeZone::Undefined,
-            )));
+            );
+            return Ok(GeneralizedTime(dt, Some(format)));
         }
         let tz = match rem {
             [b'Z'] => ASN1TimeZone::Z,
-            [b'+', h1, h2, m1, m2] => {
-                let hh = decode_decimal(Self::TAG, *h1, *h2)?;
-                let mm = decode_decimal(Self::TAG, *m1, *m2)?;
-                ASN1TimeZone::Offset(hh as i8, mm as i8)
-            }
-            [b'-', h1, h2, m1, m2] => {
+            [sign @ b'+', h1, h2, m1, m2] | [sign @ b'-', h1, h2, m1, m2] => {
                 let hh = decode_decimal(Self::TAG, *h1, *h2)?;
                 let mm = decode_decimal(Self::TAG, *m1, *m2)?;
-                ASN1TimeZone::Offset(-(hh as i8), mm as i8)
+                ASN1TimeZone::from_offset(*sign == b'-', hh, mm)
             }
             _ => return Err(Self::TAG.invalid_value("malformed time string: no time zone")),
         };
-        Ok(GeneralizedTime(ASN1DateTime::new(
-            year,
-            month,
-            day,
-            hour,
-            minute,
-            second,
-            millisecond,
-            tz,
-        )))
+        let dt = ASN1DateTime::new(year, month, day, hour, minute, second, millisecond, tz);
+        Ok(GeneralizedTime(dt, Some(format)))
+    }
+
+    /// Serialize this object using BER, with the format of the original time string
+    ///
+    /// If the object was parsed (or if a format was set using
+    /// [`GeneralizedTime::with_format`]), the time string is written using the same
+    /// representation (seconds, fractional seconds digits and separator, time zone), so parsing
+    /// and serializing an object gives the same encoding. Otherwise, seconds are always written,
+    /// and milliseconds (if any) using 3 digits.
+    ///
+    /// ```rust
+    /// use asn1_rs::{FromBer, GeneralizedTime};
+    ///
+    /// // fractional seconds with trailing zero and a comma, no time zone
+    /// let input = b"\x18\x1120240101123000,50";
+    /// let (_, t) = GeneralizedTime::from_ber(input).expect("parsing failed");
+    /// assert_eq!(t.to_ber_vec_original().expect("serialization failed"), input);
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn write_ber_original(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        let dt = &self.0;
+        let format = self.1.unwrap_or(ASN1TimeFormat {
+            fraction_digits: if dt.millisecond.is_some() { 3 } else { 0 },
+            ..ASN1TimeFormat::new()
+        });
+        let tz = match dt.tz {
+            ASN1TimeZone::Offset(..) => dt.tz.offset_string(),
+            ASN1TimeZone::Z => "Z".to_string(),
+            ASN1TimeZone::Undefined => String::new(),
+        };
+        let mut content = encode_year(dt)?.to_vec();
+        content.extend_from_slice(&encode_time_fields(Self::TAG, dt, format.seconds)?);
+        content.extend_from_slice(&format.fraction(Self::TAG, dt.millisecond)?);
+        content.extend_from_slice(tz.as_bytes());
+        let sz = write_header(Self::TAG, content.len(), writer)?;
+        writer.write_all(&content)?;
+        Ok(sz + content.len())
+    }
+
+    /// Serialize this object using BER to a new `Vec<u8>`, with the format of the original time
+    /// string
+    ///
+    /// See [`GeneralizedTime::write_ber_original`].
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn to_ber_vec_original(&self) -> SerializeResult<Vec<u8>> {
+        let mut v = Vec::new();
+        self.write_ber_original(&mut v)?;
+        Ok(v)
+    }
+
+    /// Return true if this time, used as an expiry time, is strictly before `now`
+    ///
+    /// See [`ASN1DateTime::is_expired_at`].
+    #[inline]
+    pub fn is_expired_at(&self, now: &ASN1DateTime) -> bool {
+        self.0.is_expired_at(now)
+    }
+
+    /// Return the same instant, converted to UTC (see [`ASN1DateTime::to_utc`])
+    ///
+    /// Local times (without time zone) are returned unchanged.
+    pub fn to_utc(&self) -> Result<GeneralizedTime> {
+        let dt = self.0.to_utc()?;
+        Ok(GeneralizedTime(dt, self.1))
     }
 
     /// Return a ISO 8601 combined date and time with time zone.
+    ///
+    /// Local times (without time zone) are assumed to be UTC, see
+    /// [`utc_datetime_with`](Self::utc_datetime_with).
     #[cfg(feature = "datetime")]
     #[cfg_attr(docsrs, doc(cfg(feature = "datetime")))]
     pub fn utc_datetime(&self) -> Result<OffsetDateTime> {
         self.0.to_datetime()
     }
+
+    /// Return a ISO 8601 combined date and time with time zone, resolving the offset of local
+    /// times (without time zone) using `zone`
+    ///
+    /// ```rust
+    /// use asn1_rs::{FixedOffset, GeneralizedTime};
+    ///
+    /// let t = GeneralizedTime::from_bytes(b"20240101013000").expect("invalid time");
+    /// let dt = t.utc_datetime_with(&FixedOffset(2, 0)).expect("invalid offset");
+    /// assert_eq!(dt.unix_timestamp(), 1_704_065_400);
+    /// ```
+    #[cfg(feature = "datetime")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "datetime")))]
+    pub fn utc_datetime_with<Z>(&self, zone: &Z) -> Result<OffsetDateTime>
+    where
+        Z: LocalTimeZone + ?Sized,
+    {
+        self.0.resolve_local(zone)?.to_datetime()
+    }
 }
 
 impl<'a> TryFrom<Any<'a>> for GeneralizedTime {
@@ -214,18 +333,42 @@ impl fmt::Display for GeneralizedTime {
                 "{:04}-{:02}-{:02} {:02}:{:02}:{:02}{}Z",
                 dt.year, dt.month, dt.day, dt.hour, dt.minute, dt.second, fsec
             ),
-            ASN1TimeZone::Offset(hh, mm) => {
-                let (s, hh) = if hh > 0 { ('+', hh) } else { ('-', -hh) };
-                write!(
-                    f,
-                    "{:04}-{:02}-{:02} {:02}:{:02}:{:02}{}{}{:02}{:02}",
-                    dt.year, dt.month, dt.day, dt.hour, dt.minute, dt.second, fsec, s, hh, mm
-                )
-            }
+            ASN1TimeZone::Offset(..) => write!(
+                f,
+                "{:04}-{:02}-{:02} {:02}:{:02}:{:02}{}{}",
+                dt.year,
+                dt.month,
+                dt.day,
+                dt.hour,
+                dt.minute,
+                dt.second,
+                fsec,
+                dt.tz.offset_string()
+            ),
         }
     }
 }
 
+impl PartialEq for GeneralizedTime {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for GeneralizedTime {}
+
+impl PartialOrd for GeneralizedTime {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for GeneralizedTime {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
 impl CheckDerConstraints for GeneralizedTime {
     fn check_constraints(any: &Any) -> Result<()> {
         // X.690 section 11.7.1: The encoding shall terminate with a "Z"
@@ -236,7 +379,9 @@ impl CheckDerConstraints for GeneralizedTime {
         // XXX
         // X.690 section 11.7.4: The decimal point element, if present, shall be the point option "."
         if any.data.iter().any(|&b| b == b',') {
-            return Err(Error::DerConstraintFailed(DerConstraint::MissingSeconds));
+            return Err(Error::DerConstraintFailed(
+                DerConstraint::InvalidDecimalPoint,
+            ));
         }
         Ok(())
     }
@@ -248,6 +393,30 @@ impl Tagged for GeneralizedTime {
     const TAG: Tag = Tag::GeneralizedTime;
 }
 
+/// Return the length of the fractional seconds in DER, including the separator (trailing zeros
+/// are removed, X.690 section 11.7.3)
+#[cfg(feature = "std")]
+fn der_fraction_len(millisecond: Option<u16>) -> usize {
+    match millisecond {
+        None | Some(0) => 0,
+        Some(ms) if ms % 100 == 0 => 2,
+        Some(ms) if ms % 10 == 0 => 3,
+        Some(_) => 4,
+    }
+}
+
+/// Return the four digits of the year of `dt`, as written in a `GeneralizedTime`
+#[cfg(feature = "std")]
+fn encode_year(dt: &ASN1DateTime) -> SerializeResult<[u8; 4]> {
+    match u16::try_from(dt.year) {
+        Ok(year) if year <= 9999 => Ok(encode_decimal4(Tag::GeneralizedTime, year)?),
+        _ => Err(SerializeError::ValueOutOfRange {
+            tag: Tag::GeneralizedTime,
+            msg: "year must have at most 4 digits",
+        }),
+    }
+}
+
 #[cfg(feature = "std")]
 impl ToDer for GeneralizedTime {
     fn to_der_len(&self) -> Result<usize> {
@@ -257,47 +426,28 @@ impl ToDer for GeneralizedTime {
         // - (variable) the fractional part, without trailing zeros, with a point "."
         // - 1 for the character Z in DER (X.690 section 11.7.1)
         // data length: 15 + fractional part
-        //
-        // thus, length will always be on 1 byte (short length) and
-        // class+structure+tag also on 1
-        //
-        // total: = 1 (class+constructed+tag) + 1 (length) + 15 + fractional
-        let num_digits = match self.0.millisecond {
-            None => 0,
-            Some(v) => 1 + v.to_string().len(),
-        };
-        Ok(2 + 15 + num_digits)
+        primitive_der_len(Self::TAG, 15 + der_fraction_len(self.0.millisecond))
     }
 
     fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        // fail before writing anything if the year cannot be represented
+        encode_year(&self.to_utc()?.0)?;
         // see above for length value
-        let num_digits = match self.0.millisecond {
-            None => 0,
-            Some(v) => 1 + v.to_string().len() as u8,
-        };
-        writer
-            .write(&[Self::TAG.0 as u8, 15 + num_digits])
-            .map_err(Into::into)
+        write_header(Self::TAG, 15 + der_fraction_len(self.0.millisecond), writer)
     }
 
     fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
-        let fractional = match self.0.millisecond {
-            None => "".to_string(),
-            Some(v) => format!(".{}", v),
+        // DER requires UTC (X.690 section 11.7.1): convert if the time zone is an offset
+        let dt = &self.to_utc()?.0;
+        let format = ASN1TimeFormat {
+            fraction_digits: der_fraction_len(dt.millisecond).saturating_sub(1) as u8,
+            ..ASN1TimeFormat::new()
         };
-        let num_digits = fractional.len();
-        write!(
-            writer,
-            "{:04}{:02}{:02}{:02}{:02}{:02}{}Z",
-            self.0.year,
-            self.0.month,
-            self.0.day,
-            self.0.hour,
-            self.0.minute,
-            self.0.second,
-            fractional,
-        )?;
-        // write_fmt returns (), see above for length value
-        Ok(15 + num_digits)
+        let mut content = encode_year(dt)?.to_vec();
+        content.extend_from_slice(&encode_time_fields(Self::TAG, dt, true)?);
+        content.extend_from_slice(&format.fraction(Self::TAG, dt.millisecond)?);
+        content.push(b'Z');
+        writer.write_all(&content)?;
+        Ok(content.len())
     }
 }
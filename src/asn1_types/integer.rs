@@ -1,12 +1,34 @@
 use crate::*;
 use alloc::borrow::Cow;
 use alloc::vec;
+use alloc::vec::Vec;
 use core::convert::{TryFrom, TryInto};
 
 #[cfg(feature = "bigint")]
 #[cfg_attr(docsrs, doc(cfg(feature = "bigint")))]
 pub use num_bigint::{BigInt, BigUint, Sign};
 
+/// Default maximum length (in bytes) of the content of parsed `INTEGER` objects
+///
+/// This is enough for RSA keys of up to 32768 bits. Parsing an `INTEGER` with a longer content
+/// returns [`Error::IntegerLengthLimit`].
+///
+/// This limit is applied by all conversions from [`Any`] (`TryFrom<Any>`, and so `FromBer` and
+/// `FromDer`) to [`Integer`], [`Uint`] and primitive integer types. To set a different limit, use
+/// [`Integer::from_ber_with_max_len`] or [`Integer::from_der_with_max_len`], or set
+/// [`ParserProfile::max_integer_len`] to apply a lower limit to all nested objects.
+pub const MAX_INTEGER_LEN: usize = 4096;
+
+/// Check that the content of `INTEGER` object is at most `max_len` bytes
+#[inline]
+pub(crate) fn check_int_len(any: &Any, max_len: usize) -> Result<()> {
+    let len = any.data.len();
+    if len > max_len {
+        return Err(Error::IntegerLengthLimit { len, max: max_len });
+    }
+    Ok(())
+}
+
 /// Decode an unsigned integer into a big endian byte slice with all leading
 /// zeroes removed (if positive) and extra 0xff remove (if negative)
 fn trim_slice<'a>(any: &'a Any<'_>) -> Result<&'a [u8]> {
@@ -42,6 +64,7 @@ fn trim_slice<'a>(any: &'a Any<'_>) -> Result<&'a [u8]> {
 /// Decode an unsigned integer into a byte array of the requested size
 /// containing a big endian integer.
 fn decode_array_uint<const N: usize>(any: &Any<'_>) -> Result<[u8; N]> {
+    check_int_len(any, MAX_INTEGER_LEN)?;
     if is_highest_bit_set(any.data) {
         return Err(Error::IntegerNegative);
     }
@@ -73,6 +96,18 @@ fn decode_array_int<const N: usize>(any: &Any<'_>) -> Result<[u8; N]> {
     Ok(output)
 }
 
+/// Replace an `IntegerTooLarge` error by an `IntegerOutOfRange` error, containing the number of
+/// bits required by the value `bytes` (content of an `INTEGER`), and the size of the target type
+fn range_error(e: Error, bytes: &[u8], max: u32, signed: bool) -> Error {
+    match e {
+        Error::IntegerTooLarge => Error::IntegerOutOfRange {
+            bits: Integer::new(bytes).bit_len() + signed as usize,
+            max: max as usize,
+        },
+        e => e,
+    }
+}
+
 /// Is the highest bit of the first byte in the slice 1? (if present)
 #[inline]
 fn is_highest_bit_set(bytes: &[u8]) -> bool {
@@ -99,11 +134,11 @@ macro_rules! impl_int {
                 any.tag().assert_eq(Self::TAG)?;
                 any.header.assert_primitive()?;
                 let result = if is_highest_bit_set(any.as_bytes()) {
-                    <$uint>::from_be_bytes(decode_array_int(&any)?) as $int
+                    decode_array_int(&any).map(|b| <$uint>::from_be_bytes(b) as $int)
                 } else {
-                    Self::from_be_bytes(decode_array_uint(&any)?)
+                    decode_array_uint(&any).map(Self::from_be_bytes)
                 };
-                Ok(result)
+                result.map_err(|e| range_error(e, any.data, <$int>::BITS, true))
             }
         }
 
@@ -159,8 +194,9 @@ macro_rules! impl_uint {
             fn try_from(any: &'b Any<'a>) -> Result<Self> {
                 any.tag().assert_eq(Self::TAG)?;
                 any.header.assert_primitive()?;
-                let result = Self::from_be_bytes(decode_array_uint(any)?);
-                Ok(result)
+                decode_array_uint(any)
+                    .map(Self::from_be_bytes)
+                    .map_err(|e| range_error(e, any.data, <$ty>::BITS, false))
             }
         }
         impl CheckDerConstraints for $ty {
@@ -237,7 +273,7 @@ impl_int!(u128 => i128);
 /// assert_eq!(i.as_ref(), &[4]);
 /// // signed
 /// let j = Integer::from(-2);
-/// assert_eq!(j.as_ref(), &[0x0, 0xff, 0xff, 0xff, 0xfe]);
+/// assert_eq!(j.as_ref(), &[0xfe]);
 /// ```
 ///
 /// Converting an `Integer` to a primitive type (using the `TryInto` trait)
@@ -252,7 +288,10 @@ impl_int!(u128 => i128);
 ///
 /// // Same, but converting to an u16: will fail, value cannot fit into an u16
 /// let i = Integer::new(&[0x12, 0x34, 0x56, 0x78]);
-/// assert_eq!(i.try_into() as Result<u16, _>, Err(Error::IntegerTooLarge));
+/// assert_eq!(
+///     i.try_into() as Result<u16, _>,
+///     Err(Error::IntegerOutOfRange { bits: 29, max: 16 })
+/// );
 /// ```
 ///
 /// Encoding an `Integer` to DER
@@ -268,7 +307,7 @@ impl_int!(u128 => i128);
 /// let v = 4.to_der_vec().unwrap();
 /// assert_eq!(&v, &[2, 1, 4]);
 /// ```
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Integer<'a> {
     pub(crate) data: Cow<'a, [u8]>,
 }
@@ -282,12 +321,130 @@ impl<'a> Integer<'a> {
         }
     }
 
+    /// Creates a new `Integer` from its two's complement representation, without checking it
+    ///
+    /// The bytes are stored as-is, so redundant leading `0x00` or `0xff` octets are kept and
+    /// will be written when encoding (producing invalid DER). This is the same as
+    /// [`Integer::new`]; use [`Integer::new_canonical`] for values which may not be minimal.
+    #[inline]
+    pub const fn from_raw_unchecked(s: &'a [u8]) -> Self {
+        Self::new(s)
+    }
+
+    /// Creates a new `Integer` from its two's complement representation, removing redundant
+    /// leading octets
+    ///
+    /// Leading `0x00` octets followed by an octet with the highest bit unset, and leading `0xff`
+    /// octets followed by an octet with the highest bit set, are removed (X.690 section 8.3.2),
+    /// so the value always re-encodes canonically. This is useful for values taken from other
+    /// encodings, for ex. fixed-width numbers in cryptographic formats. Note that the sign is
+    /// preserved: `[0x00, 0x80]` (128) is kept as-is, to avoid turning it into -128.
+    ///
+    /// An empty input is converted to zero.
+    ///
+    /// ```rust
+    /// use asn1_rs::Integer;
+    ///
+    /// let i = Integer::new_canonical(&[0x00, 0x00, 0x01, 0x00]);
+    /// assert_eq!(i.as_ref(), &[0x01, 0x00]);
+    /// let i = Integer::new_canonical(&[0xff, 0xff, 0x80]);
+    /// assert_eq!(i.as_ref(), &[0x80]);
+    /// let i = Integer::new_canonical(&[0x00, 0x00, 0x80]);
+    /// assert_eq!(i.as_ref(), &[0x00, 0x80]);
+    /// ```
+    pub fn new_canonical(s: &'a [u8]) -> Self {
+        if s.is_empty() {
+            return Integer {
+                data: Cow::Borrowed(&[0]),
+            };
+        }
+        let mut s = s;
+        while let [first, second, ..] = s {
+            let redundant =
+                (*first == 0x00 && *second < 0x80) || (*first == 0xff && *second >= 0x80);
+            if !redundant {
+                break;
+            }
+            s = &s[1..];
+        }
+        Self::new(s)
+    }
+
     /// Creates a borrowed `Any` for this object
     #[inline]
     pub fn any(&'a self) -> Any<'a> {
         Any::from_tag_and_data(Self::TAG, &self.data)
     }
 
+    /// Returns true if this value is negative
+    #[inline]
+    pub fn is_negative(&self) -> bool {
+        is_highest_bit_set(&self.data)
+    }
+
+    /// Returns true if this value is even
+    ///
+    /// An empty content is considered as zero.
+    #[inline]
+    pub fn is_even(&self) -> bool {
+        self.data.last().map(|b| b & 1 == 0).unwrap_or(true)
+    }
+
+    /// Returns the number of bits of the minimal two's complement representation of this value,
+    /// excluding the sign bit
+    ///
+    /// For positive values, this is the number of significant bits of the value (for ex. the
+    /// size of an RSA modulus). Zero has a bit length of 0, and so has -1.
+    ///
+    /// ```rust
+    /// use asn1_rs::Integer;
+    ///
+    /// assert_eq!(Integer::new(&[0x00, 0x80]).bit_len(), 8);
+    /// assert_eq!(Integer::new(&[0x00, 0x00, 0x01, 0x00]).bit_len(), 9);
+    /// assert_eq!(Integer::new(&[0x80]).bit_len(), 7);
+    /// ```
+    pub fn bit_len(&self) -> usize {
+        // for negative values, count the bits of the one's complement
+        let mask = if self.is_negative() { 0xff } else { 0x00 };
+        let mut bytes = self.data.iter().map(|b| b ^ mask).skip_while(|&b| b == 0);
+        match bytes.next() {
+            Some(first) => (8 - first.leading_zeros() as usize) + 8 * bytes.count(),
+            None => 0,
+        }
+    }
+
+    /// Returns the big-endian representation of this (non-negative) value, left-padded with zeroes
+    /// to `width` bytes
+    ///
+    /// This is useful to extract fixed-width values, for ex. the components of an ECDSA
+    /// signature. Returns [`Error::IntegerNegative`] if the value is negative, and
+    /// [`Error::IntegerTooLarge`] if it does not fit in `width` bytes.
+    ///
+    /// ```rust
+    /// use asn1_rs::{Error, Integer};
+    ///
+    /// let i = Integer::new(&[0x00, 0xff, 0x01]);
+    /// assert_eq!(i.to_fixed_width_be(4), Ok(vec![0x00, 0x00, 0xff, 0x01]));
+    /// assert_eq!(i.to_fixed_width_be(1), Err(Error::IntegerTooLarge));
+    /// ```
+    pub fn to_fixed_width_be(&self, width: usize) -> Result<Vec<u8>> {
+        if self.is_negative() {
+            return Err(Error::IntegerNegative);
+        }
+        let first = self
+            .data
+            .iter()
+            .position(|&b| b != 0)
+            .unwrap_or(self.data.len());
+        let value = &self.data[first..];
+        if value.len() > width {
+            return Err(Error::IntegerTooLarge);
+        }
+        let mut out = vec![0; width - value.len()];
+        out.extend_from_slice(value);
+        Ok(out)
+    }
+
     /// Returns a `BigInt` built from this `Integer` value.
     #[cfg(feature = "bigint")]
     #[cfg_attr(docsrs, doc(cfg(feature = "bigint")))]
@@ -306,7 +463,47 @@ impl<'a> Integer<'a> {
         }
     }
 
+    /// Parse a BER-encoded `INTEGER`, accepting a content of at most `max_len` bytes
+    ///
+    /// Other parsing functions use the default limit [`MAX_INTEGER_LEN`].
+    ///
+    /// ```rust
+    /// use asn1_rs::{Err, Error, Integer};
+    ///
+    /// let input = &[0x02, 0x03, 0x01, 0x00, 0x01];
+    /// let (_, i) = Integer::from_ber_with_max_len(input, 4).expect("parsing failed");
+    /// assert_eq!(i.as_u32(), Ok(0x10001));
+    /// let res = Integer::from_ber_with_max_len(input, 2);
+    /// assert_eq!(res, Err(Err::Error(Error::IntegerLengthLimit { len: 3, max: 2 })));
+    /// ```
+    pub fn from_ber_with_max_len(bytes: &'a [u8], max_len: usize) -> ParseResult<'a, Self> {
+        let (rem, any) = Any::from_ber(bytes)?;
+        let int = Self::try_from_any_with_max_len(&any, max_len)?;
+        Ok((rem, int))
+    }
+
+    /// Parse a DER-encoded `INTEGER`, accepting a content of at most `max_len` bytes
+    ///
+    /// Other parsing functions use the default limit [`MAX_INTEGER_LEN`].
+    pub fn from_der_with_max_len(bytes: &'a [u8], max_len: usize) -> ParseResult<'a, Self> {
+        let (rem, any) = Any::from_der(bytes)?;
+        check_der_int_constraints(&any)?;
+        let int = Self::try_from_any_with_max_len(&any, max_len)?;
+        Ok((rem, int))
+    }
+
+    fn try_from_any_with_max_len(any: &Any<'a>, max_len: usize) -> Result<Self> {
+        any.tag().assert_eq(Self::TAG)?;
+        check_int_len(any, max_len)?;
+        Ok(Integer {
+            data: Cow::Borrowed(any.data),
+        })
+    }
+
     /// Build an `Integer` from a constant array of bytes representation of an integer.
+    ///
+    /// The array is the big-endian representation of a non-negative integer: leading zeroes
+    /// are removed, and a zero byte is added if the highest bit of the value is set.
     pub fn from_const_array<const N: usize>(b: [u8; N]) -> Self {
         let mut idx = 0;
         // skip leading 0s
@@ -321,6 +518,14 @@ impl<'a> Integer<'a> {
             Integer {
                 data: Cow::Borrowed(&[0]),
             }
+        } else if b[idx] & 0x80 != 0 {
+            // add a sign octet, the value is not negative
+            let mut out = Vec::with_capacity(1 + N - idx);
+            out.push(0);
+            out.extend_from_slice(&b[idx..]);
+            Integer {
+                data: Cow::Owned(out),
+            }
         } else {
             Integer {
                 data: Cow::Owned(b[idx..].to_vec()),
@@ -329,11 +534,13 @@ impl<'a> Integer<'a> {
     }
 
     fn from_const_array_negative<const N: usize>(b: [u8; N]) -> Self {
-        let mut out = vec![0];
-        out.extend_from_slice(&b);
-
+        let mut idx = 0;
+        // skip leading 0xff, if the next byte keeps the sign bit
+        while idx + 1 < b.len() && b[idx] == 0xff && b[idx + 1] & 0x80 != 0 {
+            idx += 1;
+        }
         Integer {
-            data: Cow::Owned(out),
+            data: Cow::Owned(b[idx..].to_vec()),
         }
     }
 }
@@ -354,12 +561,20 @@ macro_rules! impl_from_to {
             }
         }
 
+        impl TryFrom<&Integer<'_>> for $ty {
+            type Error = Error;
+
+            fn try_from(value: &Integer<'_>) -> Result<Self> {
+                value.$to()
+            }
+        }
+
         impl Integer<'_> {
             #[doc = "Attempts to convert an `Integer` to a `"]
             #[doc = $sty]
             #[doc = "`."]
             #[doc = ""]
-            #[doc = "This function returns an `IntegerTooLarge` error if the integer will not fit into the output type."]
+            #[doc = "This function returns an `IntegerOutOfRange` error (containing the number of bits required) if the integer will not fit into the output type, or `IntegerNegative` if the integer is negative and the output type is unsigned."]
             pub fn $to(&self) -> Result<$ty> {
                 self.any().try_into()
             }
@@ -436,10 +651,7 @@ impl<'a, 'b> TryFrom<&'b Any<'a>> for Integer<'a> {
     type Error = Error;
 
     fn try_from(any: &'b Any<'a>) -> Result<Integer<'a>> {
-        any.tag().assert_eq(Self::TAG)?;
-        Ok(Integer {
-            data: Cow::Borrowed(any.data),
-        })
+        Self::try_from_any_with_max_len(any, MAX_INTEGER_LEN)
     }
 }
 
@@ -469,6 +681,16 @@ fn check_der_int_constraints(any: &Any) -> Result<()> {
 
 impl DerAutoDerive for Integer<'_> {}
 
+impl ToStatic for Integer<'_> {
+    type Owned = Integer<'static>;
+
+    fn to_static(&self) -> Self::Owned {
+        Integer {
+            data: Cow::Owned(self.data.to_vec()),
+        }
+    }
+}
+
 impl<'a> Tagged for Integer<'a> {
     const TAG: Tag = Tag::Integer;
 }
@@ -503,6 +725,172 @@ impl ToDer for Integer<'_> {
     }
 }
 
+/// A non-negative ASN.1 `INTEGER` (for ex. a certificate serial number, or an RSA modulus)
+///
+/// The value is stored as its big-endian magnitude, without leading zeroes: the sign octet
+/// required by the encoding of values whose most significant bit is set (X.690 section 8.3.2) is
+/// removed when parsing, and added back when encoding. Zero is stored as `[0]`.
+///
+/// Parsing returns [`Error::IntegerNegative`] for negative values.
+///
+/// ```rust
+/// use asn1_rs::{Error, FromDer, ToDer, Uint};
+///
+/// // INTEGER 0xff01 (with sign octet)
+/// let (_, u) = Uint::from_der(&[0x02, 0x03, 0x00, 0xff, 0x01]).expect("parsing failed");
+/// assert_eq!(u.as_bytes(), &[0xff, 0x01]);
+/// assert_eq!(u.to_der_vec().unwrap(), &[0x02, 0x03, 0x00, 0xff, 0x01]);
+///
+/// // INTEGER -255
+/// let res = Uint::from_der(&[0x02, 0x02, 0xff, 0x01]);
+/// assert_eq!(res, Err(asn1_rs::Err::Error(Error::IntegerNegative)));
+/// ```
+#[doc(alias = "NonNegativeInteger")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Uint<'a> {
+    data: Cow<'a, [u8]>,
+}
+
+impl<'a> Uint<'a> {
+    /// Build a `Uint` from its big-endian magnitude. Leading zeroes are removed.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Uint {
+            data: Cow::Borrowed(trim_leading_zeroes(bytes)),
+        }
+    }
+
+    /// Return the big-endian magnitude, without leading zeroes (zero is returned as `[0]`)
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Return true if the value is zero
+    pub fn is_zero(&self) -> bool {
+        self.data.as_ref() == [0]
+    }
+
+    /// Returns the number of significant bits of the value (for ex. the size of an RSA modulus)
+    pub fn bit_len(&self) -> usize {
+        let first = self.data[0];
+        (8 - first.leading_zeros() as usize) + 8 * (self.data.len() - 1)
+    }
+
+    /// Return the length of the content of the `INTEGER` encoding, including the sign octet
+    fn content_len(&self) -> usize {
+        self.data.len() + is_highest_bit_set(&self.data) as usize
+    }
+}
+
+/// Remove the leading zeroes of a big-endian magnitude, keeping one octet for zero
+fn trim_leading_zeroes(bytes: &[u8]) -> &[u8] {
+    match bytes.iter().position(|&b| b != 0) {
+        Some(first) => &bytes[first..],
+        None => &[0],
+    }
+}
+
+impl AsRef<[u8]> for Uint<'_> {
+    fn as_ref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl<'a> TryFrom<Any<'a>> for Uint<'a> {
+    type Error = Error;
+
+    fn try_from(any: Any<'a>) -> Result<Uint<'a>> {
+        TryFrom::try_from(&any)
+    }
+}
+
+impl<'a, 'b> TryFrom<&'b Any<'a>> for Uint<'a> {
+    type Error = Error;
+
+    fn try_from(any: &'b Any<'a>) -> Result<Uint<'a>> {
+        any.tag().assert_eq(Self::TAG)?;
+        any.header.assert_primitive()?;
+        check_int_len(any, MAX_INTEGER_LEN)?;
+        // X.690 section 8.3.1: the content must have at least one octet
+        if any.data.is_empty() {
+            return Err(Error::InvalidLength);
+        }
+        if is_highest_bit_set(any.data) {
+            return Err(Error::IntegerNegative);
+        }
+        Ok(Uint::new(any.data))
+    }
+}
+
+impl<'a> TryFrom<Integer<'a>> for Uint<'a> {
+    type Error = Error;
+
+    fn try_from(i: Integer<'a>) -> Result<Uint<'a>> {
+        if i.is_negative() {
+            return Err(Error::IntegerNegative);
+        }
+        let data = match i.data {
+            Cow::Borrowed(b) => Cow::Borrowed(trim_leading_zeroes(b)),
+            Cow::Owned(v) => Cow::Owned(trim_leading_zeroes(&v).to_vec()),
+        };
+        Ok(Uint { data })
+    }
+}
+
+impl From<&Uint<'_>> for Integer<'static> {
+    fn from(u: &Uint<'_>) -> Self {
+        let mut data = Vec::with_capacity(u.content_len());
+        if is_highest_bit_set(&u.data) {
+            data.push(0);
+        }
+        data.extend_from_slice(&u.data);
+        Integer {
+            data: Cow::Owned(data),
+        }
+    }
+}
+
+impl CheckDerConstraints for Uint<'_> {
+    fn check_constraints(any: &Any) -> Result<()> {
+        check_der_int_constraints(any)
+    }
+}
+
+impl DerAutoDerive for Uint<'_> {}
+
+impl ToStatic for Uint<'_> {
+    type Owned = Uint<'static>;
+
+    fn to_static(&self) -> Self::Owned {
+        Uint {
+            data: Cow::Owned(self.data.to_vec()),
+        }
+    }
+}
+
+impl Tagged for Uint<'_> {
+    const TAG: Tag = Tag::Integer;
+}
+
+#[cfg(feature = "std")]
+impl ToDer for Uint<'_> {
+    fn to_der_len(&self) -> Result<usize> {
+        primitive_der_len(Self::TAG, self.content_len())
+    }
+
+    fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        write_header(Self::TAG, self.content_len(), writer)
+    }
+
+    fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        // X.690 section 8.3.2: add a sign octet if the most significant bit is set
+        if is_highest_bit_set(&self.data) {
+            writer.write_all(&[0])?;
+        }
+        writer.write_all(&self.data)?;
+        Ok(self.content_len())
+    }
+}
+
 /// Helper macro to declare integers at compile-time
 ///
 /// [`Integer`] stores the encoded representation of the integer, so declaring
@@ -538,7 +926,7 @@ macro_rules! int {
 
 #[cfg(test)]
 mod tests {
-    use crate::{Any, FromDer, Header, Tag};
+    use crate::{Any, FromBer, FromDer, Header, Tag};
     use std::convert::TryInto;
 
     // Vectors from Section 5.7 of:
@@ -602,6 +990,214 @@ mod tests {
         assert!(u16::from_der(&[0x02, 0x02, 0x00, 0x00]).is_err());
     }
 
+    #[test]
+    fn integer_length_limit() {
+        use crate::{Error, Integer, MAX_INTEGER_LEN};
+        let mut input = vec![0x02, 0x82, 0x10, 0x01, 0x01];
+        input.resize(4 + 0x1001, 0);
+        let res = Integer::from_der(&input);
+        let expected = Error::IntegerLengthLimit {
+            len: MAX_INTEGER_LEN + 1,
+            max: MAX_INTEGER_LEN,
+        };
+        assert_eq!(res, Err(nom::Err::Error(expected.clone())));
+        // primitive types use the same limit, even if value fits after removing leading zeroes
+        input[4] = 0;
+        input[4 + 0x1000] = 1;
+        assert_eq!(u8::from_ber(&input), Err(nom::Err::Error(expected)));
+        // larger limit
+        input[4] = 0x01;
+        let (rem, int) = Integer::from_der_with_max_len(&input, 0x2000).expect("parsing failed");
+        assert!(rem.is_empty());
+        assert_eq!(int.as_ref().len(), 0x1001);
+    }
+
+    #[test]
+    fn integer_new_canonical() {
+        use crate::{Integer, ToDer};
+        let cases: &[(&[u8], &[u8])] = &[
+            (&[], &[0]),
+            (&[0], &[0]),
+            (&[0, 0, 0], &[0]),
+            (&[0xff, 0xff], &[0xff]),
+            (&[0, 0x7f], &[0x7f]),
+            (&[0, 0, 0x80], &[0, 0x80]),
+            (&[0xff, 0xff, 0x7f], &[0xff, 0x7f]),
+            (&[0xff, 0x80, 0], &[0x80, 0]),
+            (&[0x12, 0x34], &[0x12, 0x34]),
+        ];
+        for (input, expected) in cases {
+            let int = Integer::new_canonical(input);
+            assert_eq!(int.as_ref(), *expected);
+            // re-encoded value is valid DER
+            let v = int.to_der_vec().expect("serialization failed");
+            let (_, int2) = Integer::from_der(&v).expect("not valid DER");
+            assert_eq!(int2, int);
+        }
+        // unchecked constructor keeps the redundant octets
+        let int = Integer::from_raw_unchecked(&[0, 0, 1]);
+        assert_eq!(int.as_ref(), &[0, 0, 1]);
+        let v = int.to_der_vec().expect("serialization failed");
+        assert!(Integer::from_der(&v).is_err());
+    }
+
+    #[test]
+    fn integer_bit_utilities() {
+        use crate::{Error, Integer};
+        let cases: &[(&[u8], usize, bool)] = &[
+            (&[], 0, true),
+            (&[0x00], 0, true),
+            (&[0x01], 1, false),
+            (&[0x7f], 7, false),
+            (&[0x00, 0x80], 8, true),
+            (&[0x00, 0x00, 0x01, 0x00], 9, true),
+            (&[0xff], 0, false),
+            (&[0x80], 7, true),
+            (&[0xff, 0x7f], 8, false),
+        ];
+        for (input, bit_len, even) in cases {
+            let int = Integer::new(input);
+            assert_eq!(int.bit_len(), *bit_len, "bit_len({:?})", input);
+            assert_eq!(int.is_even(), *even, "is_even({:?})", input);
+        }
+        let int = Integer::new(&[0x00, 0x80, 0x01]);
+        assert_eq!(int.to_fixed_width_be(2), Ok(vec![0x80, 0x01]));
+        assert_eq!(int.to_fixed_width_be(3), Ok(vec![0x00, 0x80, 0x01]));
+        assert_eq!(int.to_fixed_width_be(1), Err(Error::IntegerTooLarge));
+        assert_eq!(Integer::new(&[0]).to_fixed_width_be(2), Ok(vec![0, 0]));
+        assert_eq!(Integer::new(&[0]).to_fixed_width_be(0), Ok(vec![]));
+        assert_eq!(
+            Integer::new(&[0x80]).to_fixed_width_be(4),
+            Err(Error::IntegerNegative)
+        );
+    }
+
+    #[test]
+    fn integer_from_unsigned_sign_octet() {
+        use crate::{Integer, ToDer};
+        use std::convert::TryFrom;
+
+        // the highest bit is set: a sign octet is added
+        assert_eq!(Integer::from(0x80u8).as_ref(), &[0x00, 0x80]);
+        assert_eq!(Integer::from(0xffffu16).as_ref(), &[0x00, 0xff, 0xff]);
+        assert_eq!(Integer::from(0x8000u32).as_ref(), &[0x00, 0x80, 0x00]);
+        assert_eq!(
+            Integer::from(u64::MAX).as_ref(),
+            &[0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]
+        );
+        assert_eq!(Integer::from(u128::MAX).as_ref().len(), 17);
+        assert_eq!(Integer::from(128i32).as_ref(), &[0x00, 0x80]);
+        assert_eq!(Integer::from(i64::MAX).as_ref()[0], 0x7f);
+        // no sign octet needed
+        assert_eq!(Integer::from(0x7fu8).as_ref(), &[0x7f]);
+        assert_eq!(Integer::from(0x80u16).as_ref(), &[0x00, 0x80]);
+        assert_eq!(Integer::from(0u32).as_ref(), &[0x00]);
+        // values are not negative, and round-trip
+        assert_eq!(u8::try_from(Integer::from(0x80u8)), Ok(0x80));
+        assert_eq!(u64::try_from(Integer::from(u64::MAX)), Ok(u64::MAX));
+        assert_eq!(u128::try_from(Integer::from(u128::MAX)), Ok(u128::MAX));
+        assert_eq!(
+            Integer::from(0xffu8).to_der_vec().unwrap(),
+            &[0x02, 0x02, 0x00, 0xff]
+        );
+    }
+
+    #[test]
+    fn uint_sign_octet() {
+        use crate::{Err, Error, Integer, ToDer, Uint};
+        use std::convert::TryFrom;
+
+        let (_, u) = Uint::from_der(&[0x02, 0x03, 0x00, 0x80, 0x01]).expect("parsing failed");
+        assert_eq!(u.as_bytes(), &[0x80, 0x01]);
+        assert_eq!(u.bit_len(), 16);
+        assert_eq!(u.to_der_vec().unwrap(), &[0x02, 0x03, 0x00, 0x80, 0x01]);
+        assert_eq!(u.to_der_len(), Ok(5));
+        // no sign octet needed
+        let u = Uint::new(&[0x00, 0x00, 0x7f]);
+        assert_eq!(u.as_bytes(), &[0x7f]);
+        assert_eq!(u.to_der_vec().unwrap(), &[0x02, 0x01, 0x7f]);
+        // zero
+        let (_, u) = Uint::from_der(&[0x02, 0x01, 0x00]).expect("parsing failed");
+        assert!(u.is_zero());
+        assert_eq!(u.bit_len(), 0);
+        assert_eq!(u.to_der_vec().unwrap(), &[0x02, 0x01, 0x00]);
+        assert_eq!(Uint::new(&[]), u);
+        // BER allows leading zeroes, DER does not
+        let input = &[0x02, 0x03, 0x00, 0x00, 0x01];
+        let (_, u) = Uint::from_ber(input).expect("parsing failed");
+        assert_eq!(u.as_bytes(), &[0x01]);
+        assert!(Uint::from_der(input).is_err());
+        // negative and empty values
+        assert_eq!(
+            Uint::from_ber(&[0x02, 0x01, 0x80]),
+            Err(Err::Error(Error::IntegerNegative))
+        );
+        assert_eq!(
+            Uint::from_ber(&[0x02, 0x00]),
+            Err(Err::Error(Error::InvalidLength))
+        );
+        // conversions
+        let i = Integer::from(0x8000u32);
+        let u = Uint::try_from(i.clone()).expect("negative value");
+        assert_eq!(u.as_bytes(), &[0x80, 0x00]);
+        assert_eq!(Integer::from(&u), i);
+        assert_eq!(
+            Uint::try_from(Integer::new(&[0xff])),
+            Err(Error::IntegerNegative)
+        );
+    }
+
+    #[test]
+    fn integer_range_errors() {
+        use crate::{Any, Enumerated, Err, Error, FromBer, FromDer, Integer};
+        use std::convert::TryFrom;
+
+        let int = Integer::from(0x1_0000u32);
+        assert_eq!(int.as_u32(), Ok(0x1_0000));
+        assert_eq!(
+            int.as_u16(),
+            Err(Error::IntegerOutOfRange { bits: 17, max: 16 })
+        );
+        assert_eq!(
+            i16::try_from(&int),
+            Err(Error::IntegerOutOfRange { bits: 18, max: 16 })
+        );
+        // same error when parsing primitive types
+        let input = &[0x02, 0x03, 0x01, 0x00, 0x00];
+        assert_eq!(
+            u16::from_ber(input),
+            Err(Err::Error(Error::IntegerOutOfRange { bits: 17, max: 16 }))
+        );
+        assert_eq!(
+            i16::from_der(input),
+            Err(Err::Error(Error::IntegerOutOfRange { bits: 18, max: 16 }))
+        );
+        assert_eq!(
+            i8::try_from(Any::from_ber(&[0x02, 0x02, 0xff, 0x7f]).unwrap().1),
+            Err(Error::IntegerOutOfRange { bits: 9, max: 8 })
+        );
+        assert_eq!(Integer::from(-128).as_ref(), &[0x80]);
+        assert_eq!(Integer::from(-129).as_ref(), &[0xff, 0x7f]);
+        assert_eq!(Integer::from(-1i64).as_ref(), &[0xff]);
+        assert_eq!(i8::try_from(&Integer::from(-128)), Ok(-128));
+        assert_eq!(
+            i8::try_from(&Integer::from(-129)),
+            Err(Error::IntegerOutOfRange { bits: 9, max: 8 })
+        );
+        assert_eq!(
+            u64::try_from(&Integer::from(-1)),
+            Err(Error::IntegerNegative)
+        );
+        // ENUMERATED
+        let e = Enumerated::new(0x8000_0000);
+        assert_eq!(e.as_u32(), 0x8000_0000);
+        assert_eq!(
+            i32::try_from(&e),
+            Err(Error::IntegerOutOfRange { bits: 33, max: 32 })
+        );
+        assert_eq!(Enumerated::new(7).as_i32(), Ok(7));
+    }
+
     #[test]
     fn declare_int() {
         let int = super::int!(1234);
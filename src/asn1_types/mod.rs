@@ -1,26 +1,36 @@
+mod algorithm_identifier;
 mod any;
+mod attribute;
 mod bitstring;
 mod boolean;
 mod choice;
 mod embedded_pdv;
 mod end_of_content;
 mod enumerated;
+#[cfg(feature = "std")]
+mod fields;
 mod generalizedtime;
 mod integer;
 mod null;
 mod object_descriptor;
 mod octetstring;
 mod oid;
+mod opaque;
 mod optional;
 mod real;
 mod sequence;
 mod set;
 mod strings;
 mod tagged;
+mod universal;
 mod utctime;
 
 pub use {
-    any::*, bitstring::*, boolean::*, choice::*, embedded_pdv::*, end_of_content::*, enumerated::*,
-    generalizedtime::*, integer::*, null::*, object_descriptor::*, octetstring::*, oid::*,
-    optional::*, real::*, sequence::*, set::*, strings::*, tagged::*, utctime::*,
+    algorithm_identifier::*, any::*, attribute::*, bitstring::*, boolean::*, choice::*,
+    embedded_pdv::*, end_of_content::*, enumerated::*, generalizedtime::*, integer::*, null::*,
+    object_descriptor::*, octetstring::*, oid::*, opaque::*, optional::*, real::*, sequence::*,
+    set::*, strings::*, tagged::*, universal::*, utctime::*,
 };
+
+#[cfg(feature = "std")]
+pub use fields::*;
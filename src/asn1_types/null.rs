@@ -2,7 +2,7 @@ use crate::*;
 use core::convert::TryFrom;
 
 /// ASN.1 `NULL` type
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct Null {}
 
 impl Null {
@@ -46,11 +46,11 @@ impl Tagged for Null {
 #[cfg(feature = "std")]
 impl ToDer for Null {
     fn to_der_len(&self) -> Result<usize> {
-        Ok(2)
+        primitive_der_len(Self::TAG, 0)
     }
 
     fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
-        writer.write(&[0x05, 0x00]).map_err(Into::into)
+        write_header(Self::TAG, 0, writer)
     }
 
     fn write_der_content(&self, _writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
@@ -62,6 +62,14 @@ impl<'a> TryFrom<Any<'a>> for () {
     type Error = Error;
 
     fn try_from(any: Any<'a>) -> Result<()> {
+        TryFrom::try_from(&any)
+    }
+}
+
+impl<'a, 'b> TryFrom<&'b Any<'a>> for () {
+    type Error = Error;
+
+    fn try_from(any: &'b Any<'a>) -> Result<()> {
         any.tag().assert_eq(Self::TAG)?;
         any.header.assert_primitive()?;
         if !any.header.length.is_null() {
@@ -86,11 +94,11 @@ impl Tagged for () {
 #[cfg(feature = "std")]
 impl ToDer for () {
     fn to_der_len(&self) -> Result<usize> {
-        Ok(2)
+        primitive_der_len(Self::TAG, 0)
     }
 
     fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
-        writer.write(&[0x05, 0x00]).map_err(Into::into)
+        write_header(Self::TAG, 0, writer)
     }
 
     fn write_der_content(&self, _writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
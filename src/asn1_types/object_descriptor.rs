@@ -1,17 +1,43 @@
-use crate::{asn1_string, TestValidCharset};
-use crate::{Error, Result};
-use alloc::string::String;
+use crate::Result;
+use crate::{asn1_string, GraphicString, TestValidCharset};
 
 // X.680 section 44.3
 // ObjectDescriptor ::= [UNIVERSAL 7] IMPLICIT GraphicString
 
 asn1_string!(ObjectDescriptor);
 
+impl<'a> From<GraphicString<'a>> for ObjectDescriptor<'a> {
+    fn from(s: GraphicString<'a>) -> Self {
+        ObjectDescriptor { data: s.data }
+    }
+}
+
+impl<'a> From<ObjectDescriptor<'a>> for GraphicString<'a> {
+    fn from(s: ObjectDescriptor<'a>) -> Self {
+        GraphicString { data: s.data }
+    }
+}
+
 impl<'a> TestValidCharset for ObjectDescriptor<'a> {
     fn test_valid_charset(i: &[u8]) -> Result<()> {
-        if !i.iter().all(u8::is_ascii) {
-            return Err(Error::StringInvalidCharset);
-        }
-        Ok(())
+        GraphicString::test_valid_charset(i)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn objectdescriptor_graphicstring() {
+        let (_, d) = ObjectDescriptor::from_der(&hex!("07 03 61 62 63")).expect("parsing failed");
+        assert_eq!(d.as_ref(), "abc");
+        // same content as a GraphicString, different tag
+        assert!(ObjectDescriptor::from_der(&hex!("19 03 61 62 63")).is_err());
+        assert!(ObjectDescriptor::from_der(&hex!("07 02 61 0d")).is_err());
+        let g = GraphicString::from(d.clone());
+        assert_eq!(g.as_ref(), "abc");
+        assert_eq!(ObjectDescriptor::from(g), d);
     }
 }
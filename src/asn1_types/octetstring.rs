@@ -1,9 +1,10 @@
 use crate::*;
 use alloc::borrow::Cow;
+use alloc::vec::Vec;
 use core::convert::TryFrom;
 
 /// ASN.1 `OCTETSTRING` type
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct OctetString<'a> {
     data: Cow<'a, [u8]>,
 }
@@ -16,7 +17,7 @@ impl<'a> OctetString<'a> {
     }
 
     /// Get the bytes representation of the *content*
-    pub fn as_cow(&'a self) -> &Cow<'a, [u8]> {
+    pub fn as_cow(&'a self) -> &'a Cow<'a, [u8]> {
         &self.data
     }
 
@@ -24,6 +25,14 @@ impl<'a> OctetString<'a> {
     pub fn into_cow(self) -> Cow<'a, [u8]> {
         self.data
     }
+
+    /// Convert this object to a `BitString` with the same content octets, and no unused bits
+    pub fn into_bitstring(self) -> BitString<'a> {
+        BitString {
+            unused_bits: 0,
+            data: self.data,
+        }
+    }
 }
 
 impl<'a> AsRef<[u8]> for OctetString<'a> {
@@ -32,6 +41,13 @@ impl<'a> AsRef<[u8]> for OctetString<'a> {
     }
 }
 
+impl Default for OctetString<'_> {
+    /// Return an empty `OCTET STRING` object
+    fn default() -> Self {
+        OctetString::new(&[])
+    }
+}
+
 impl<'a> From<&'a [u8]> for OctetString<'a> {
     fn from(b: &'a [u8]) -> Self {
         OctetString {
@@ -40,6 +56,14 @@ impl<'a> From<&'a [u8]> for OctetString<'a> {
     }
 }
 
+impl From<Vec<u8>> for OctetString<'_> {
+    fn from(v: Vec<u8>) -> Self {
+        OctetString {
+            data: Cow::Owned(v),
+        }
+    }
+}
+
 impl<'a> TryFrom<Any<'a>> for OctetString<'a> {
     type Error = Error;
 
@@ -62,13 +86,23 @@ impl<'a, 'b> TryFrom<&'b Any<'a>> for OctetString<'a> {
 impl<'a> CheckDerConstraints for OctetString<'a> {
     fn check_constraints(any: &Any) -> Result<()> {
         // X.690 section 10.2
-        any.header.assert_primitive()?;
+        any.header.assert_der_primitive()?;
         Ok(())
     }
 }
 
 impl DerAutoDerive for OctetString<'_> {}
 
+impl ToStatic for OctetString<'_> {
+    type Owned = OctetString<'static>;
+
+    fn to_static(&self) -> Self::Owned {
+        OctetString {
+            data: Cow::Owned(self.data.to_vec()),
+        }
+    }
+}
+
 impl<'a> Tagged for OctetString<'a> {
     const TAG: Tag = Tag::OctetString;
 }
@@ -100,12 +134,28 @@ impl ToDer for OctetString<'_> {
     fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
         writer.write(&self.data).map_err(Into::into)
     }
+
+    fn to_der_vectored(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        // header is at most 1 (tag) + 9 (length) bytes
+        let mut header = [0u8; 16];
+        let mut cursor = std::io::Cursor::new(&mut header[..]);
+        let hdr_len = self.write_der_header(&mut cursor)?;
+        write_all_vectored(writer, &[&header[..hdr_len], &self.data]).map_err(Into::into)
+    }
 }
 
 impl<'a> TryFrom<Any<'a>> for &'a [u8] {
     type Error = Error;
 
     fn try_from(any: Any<'a>) -> Result<&'a [u8]> {
+        TryFrom::try_from(&any)
+    }
+}
+
+impl<'a, 'b> TryFrom<&'b Any<'a>> for &'a [u8] {
+    type Error = Error;
+
+    fn try_from(any: &'b Any<'a>) -> Result<&'a [u8]> {
         any.tag().assert_eq(Self::TAG)?;
         let s = OctetString::try_from(any)?;
         match s.data {
@@ -118,7 +168,7 @@ impl<'a> TryFrom<Any<'a>> for &'a [u8] {
 impl<'a> CheckDerConstraints for &'a [u8] {
     fn check_constraints(any: &Any) -> Result<()> {
         // X.690 section 10.2
-        any.header.assert_primitive()?;
+        any.header.assert_der_primitive()?;
         Ok(())
     }
 }
@@ -13,7 +13,7 @@ use num_bigint::BigUint;
 use num_traits::Num;
 
 /// An error for OID parsing functions.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub enum OidParseError {
     TooShort,
     /// Signalizes that the first or second component is too large.
@@ -66,6 +66,14 @@ impl<'a> CheckDerConstraints for Oid<'a> {
 
 impl DerAutoDerive for Oid<'_> {}
 
+impl ToStatic for Oid<'_> {
+    type Owned = Oid<'static>;
+
+    fn to_static(&self) -> Self::Owned {
+        self.to_owned()
+    }
+}
+
 impl<'a> Tagged for Oid<'a> {
     const TAG: Tag = Tag::Oid;
 }
@@ -117,7 +125,7 @@ fn encode_relative(ids: &'_ [u64]) -> impl Iterator<Item = u8> + '_ {
 impl<'a> Oid<'a> {
     /// Create an OID from the ASN.1 DER encoded form. See the [module documentation](index.html)
     /// for other ways to create oids.
-    pub const fn new(asn1: Cow<'a, [u8]>) -> Oid {
+    pub const fn new(asn1: Cow<'a, [u8]>) -> Oid<'a> {
         Oid {
             asn1,
             relative: false,
@@ -126,13 +134,19 @@ impl<'a> Oid<'a> {
 
     /// Create a relative OID from the ASN.1 DER encoded form. See the [module documentation](index.html)
     /// for other ways to create relative oids.
-    pub const fn new_relative(asn1: Cow<'a, [u8]>) -> Oid {
+    pub const fn new_relative(asn1: Cow<'a, [u8]>) -> Oid<'a> {
         Oid {
             asn1,
             relative: true,
         }
     }
 
+    /// Return true if this OID is relative
+    #[inline]
+    pub const fn is_relative(&self) -> bool {
+        self.relative
+    }
+
     /// Build an OID from an array of object identifier components.
     /// This method allocates memory on the heap.
     pub fn from<'b>(s: &'b [u64]) -> core::result::Result<Oid<'static>, OidParseError> {
@@ -298,6 +312,24 @@ impl<'a> Oid<'a> {
         Ok((rem, Oid::new_relative(asn1)))
     }
 
+    /// Build a new OID by appending the components `suffix` to this OID
+    ///
+    /// This is useful to build OIDs from a constant prefix and components known at runtime.
+    /// The returned OID is relative if this OID is relative.
+    /// This method allocates memory on the heap.
+    pub fn with_suffix(&self, suffix: &[u64]) -> Oid<'static> {
+        let asn1: Vec<u8> = self
+            .asn1
+            .iter()
+            .copied()
+            .chain(encode_relative(suffix))
+            .collect();
+        Oid {
+            asn1: Cow::from(asn1),
+            relative: self.relative,
+        }
+    }
+
     /// Returns true if `needle` is a prefix of the OID.
     pub fn starts_with(&self, needle: &Oid) -> bool {
         self.asn1.len() >= needle.asn1.len() && self.asn1.starts_with(needle.as_bytes())
@@ -413,6 +445,19 @@ impl<'a> FromStr for Oid<'a> {
 /// - `oid!(1.4.42.23)`: Create a const expression for the corresponding `Oid<'static>`
 /// - `oid!(rel 42.23)`: Create a const expression for the corresponding relative `Oid<'static>`
 /// - `oid!(raw 1.4.42.23)`/`oid!(raw rel 42.23)`: Obtain the DER encoded form as a byte array.
+/// - `oid!(iso(1) member-body(2) us(840))`: Use the ASN.1 value notation, where arcs are names
+///   followed by a parenthesized number (names are ignored). Arcs can also be plain numbers
+///   (`oid!(iso(1) 2 840)`), as long as the first arc is named. This form is accepted for
+///   `raw` and `rel` too.
+///
+/// To append components known only at runtime to a constant prefix, use [`Oid::with_suffix`]:
+/// ```
+/// use asn1_rs::{oid, Oid};
+///
+/// const PKCS1: Oid<'static> = oid!(iso(1) member-body(2) us(840) rsadsi(113549) pkcs(1) 1);
+/// let sha256_with_rsa = PKCS1.with_suffix(&[11]);
+/// assert_eq!(sha256_with_rsa, oid!(1.2.840.113549.1.1.11));
+/// ```
 ///
 /// # Comparing oids
 ///
@@ -451,14 +496,30 @@ impl<'a> FromStr for Oid<'a> {
 /// extra check might be necessary.
 #[macro_export]
 macro_rules! oid {
+    (raw $($first:ident)-+ ( $($arc:tt)* ) $($rest:tt)*) => {
+        $crate::exports::asn1_rs_impl::encode_oid!($($first)-+ ( $($arc)* ) $($rest)*)
+    };
+    (raw rel $($first:ident)-+ ( $($arc:tt)* ) $($rest:tt)*) => {
+        $crate::exports::asn1_rs_impl::encode_oid!(rel $($first)-+ ( $($arc)* ) $($rest)*)
+    };
     (raw $items:expr) => {
         $crate::exports::asn1_rs_impl::encode_oid!($items)
     };
+    (rel $($first:ident)-+ ( $($arc:tt)* ) $($rest:tt)*) => {
+        $crate::Oid::new_relative($crate::exports::borrow::Cow::Borrowed(
+            &$crate::exports::asn1_rs_impl::encode_oid!(rel $($first)-+ ( $($arc)* ) $($rest)*),
+        ))
+    };
     (rel $items:expr) => {
         $crate::Oid::new_relative($crate::exports::borrow::Cow::Borrowed(
             &$crate::exports::asn1_rs_impl::encode_oid!(rel $items),
         ))
     };
+    ($($first:ident)-+ ( $($arc:tt)* ) $($rest:tt)*) => {
+        $crate::Oid::new($crate::exports::borrow::Cow::Borrowed(
+            &$crate::oid!(raw $($first)-+ ( $($arc)* ) $($rest)*),
+        ))
+    };
     ($items:expr) => {
         $crate::Oid::new($crate::exports::borrow::Cow::Borrowed(
             &$crate::oid!(raw $items),
@@ -506,6 +567,31 @@ mod tests {
         assert_eq!(&oid, &oid2);
     }
 
+    #[test]
+    fn oid_arc_expression() {
+        const OID_RSA: Oid = oid!(iso(1) member-body(2) us(840) rsadsi(113549));
+        assert_eq!(OID_RSA, oid!(1.2.840 .113549));
+        assert_eq!(oid!(iso(1) 2 840 113549), oid!(1.2.840 .113549));
+        assert_eq!(
+            &oid!(raw joint-iso-itu-t(2) ds(5) attributeType(4) 3),
+            &oid!(raw 2.5.4.3)
+        );
+        // hyphenated name for the first arc
+        let cn = oid!(joint-iso-itu-t(2) ds(5) 4 3);
+        assert_eq!(cn, oid!(2.5.4 .3));
+        assert_eq!(cn.to_id_string(), "2.5.4.3");
+        let rel = oid!(rel pkcs(1) 1);
+        assert!(rel.is_relative());
+        assert_eq!(rel, oid!(rel 1.1));
+        // suffix
+        let oid = OID_RSA.with_suffix(&[1, 1, 11]);
+        assert_eq!(oid, oid!(1.2.840 .113549 .1 .1 .11));
+        let oid = rel.with_suffix(&[200]);
+        assert!(oid.is_relative());
+        assert_eq!(oid, oid!(rel 1.1.200));
+        assert_eq!(OID_RSA.with_suffix(&[]), OID_RSA);
+    }
+
     #[test]
     fn oid_starts_with() {
         const OID_RSA_ENCRYPTION: Oid = oid! {1.2.840.113549.1.1.1};
@@ -0,0 +1,257 @@
+use crate::ber::MAX_RECURSION;
+use crate::*;
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+/// Tag of the (legacy) `Opaque` float type, `[CONTEXT 120]` (used by net-snmp)
+const OPAQUE_FLOAT_TAG: Tag = Tag(120);
+/// Tag of the (legacy) `Opaque` double type, `[CONTEXT 121]` (used by net-snmp)
+const OPAQUE_DOUBLE_TAG: Tag = Tag(121);
+
+/// SNMP `Opaque` type (RFC 2578)
+///
+/// <pre>
+/// Opaque ::= [APPLICATION 4] IMPLICIT OCTET STRING
+/// </pre>
+///
+/// The content of an `Opaque` object is the BER encoding of another value. This type keeps the
+/// encoded content, and parses the inner value on demand using [`Opaque::inner`].
+///
+/// Some agents wrap float values in an `Opaque` object using non-standard tags
+/// (`[CONTEXT 120]` for 32-bit floats, `[CONTEXT 121]` for doubles), sometimes inside a second
+/// (nested) `Opaque` object. These encodings are supported by [`Opaque::as_f32`],
+/// [`Opaque::as_f64`], [`Opaque::from_f32`] and [`Opaque::from_f64`].
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{FromBer, Opaque};
+///
+/// // Opaque wrapping INTEGER 42
+/// let bytes = &[0x44, 0x03, 0x02, 0x01, 0x2a];
+/// let (_, opaque) = Opaque::from_ber(bytes).expect("parsing failed");
+/// let inner = opaque.inner().expect("invalid inner value");
+/// assert_eq!(inner.as_u32(), Ok(42));
+///
+/// // float value (legacy encoding)
+/// let bytes = &[0x44, 0x07, 0x9f, 0x78, 0x04, 0x3f, 0xc0, 0x00, 0x00];
+/// let (_, opaque) = Opaque::from_ber(bytes).expect("parsing failed");
+/// assert_eq!(opaque.as_f32(), Ok(1.5));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Opaque<'a> {
+    data: Cow<'a, [u8]>,
+}
+
+impl<'a> Opaque<'a> {
+    /// Tag of the `Opaque` type (in the `Application` class)
+    ///
+    /// `Opaque` does not implement [`Tagged`], since this tag is not in the `Universal` class.
+    pub const TAG: Tag = Tag(4);
+
+    /// Build a new `Opaque` object from the encoding of the inner value
+    pub const fn new(data: &'a [u8]) -> Self {
+        Opaque {
+            data: Cow::Borrowed(data),
+        }
+    }
+
+    /// Build a new `Opaque` object wrapping a float value, using the legacy encoding
+    pub fn from_f32(value: f32) -> Opaque<'static> {
+        Self::wrap_float(OPAQUE_FLOAT_TAG, &value.to_be_bytes())
+    }
+
+    /// Build a new `Opaque` object wrapping a double value, using the legacy encoding
+    pub fn from_f64(value: f64) -> Opaque<'static> {
+        Self::wrap_float(OPAQUE_DOUBLE_TAG, &value.to_be_bytes())
+    }
+
+    fn wrap_float(tag: Tag, bytes: &[u8]) -> Opaque<'static> {
+        let mut data = Vec::with_capacity(3 + bytes.len());
+        data.extend_from_slice(&[0x9f, tag.0 as u8, bytes.len() as u8]);
+        data.extend_from_slice(bytes);
+        Opaque {
+            data: Cow::Owned(data),
+        }
+    }
+
+    /// Get the encoding of the inner value
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Parse the inner value (as BER)
+    ///
+    /// If the inner value is itself an `Opaque` object (legacy nested encoding), it is unwrapped.
+    pub fn inner(&self) -> Result<Any<'_>> {
+        let mut data: &[u8] = &self.data;
+        for _ in 0..MAX_RECURSION {
+            let (_, any) = Any::from_ber(data)?;
+            if any.class() != Class::Application || any.tag() != Self::TAG {
+                return Ok(any);
+            }
+            any.header.assert_primitive()?;
+            data = any.data;
+        }
+        Err(Error::BerMaxDepth)
+    }
+
+    /// Parse the inner value as a float (legacy encoding)
+    pub fn as_f32(&self) -> Result<f32> {
+        let any = self.inner()?;
+        let bytes = Self::float_bytes(&any, OPAQUE_FLOAT_TAG)?;
+        let bytes = <[u8; 4]>::try_from(bytes).or(Err(Error::InvalidLength))?;
+        Ok(f32::from_be_bytes(bytes))
+    }
+
+    /// Parse the inner value as a double (legacy encoding)
+    pub fn as_f64(&self) -> Result<f64> {
+        let any = self.inner()?;
+        let bytes = Self::float_bytes(&any, OPAQUE_DOUBLE_TAG)?;
+        let bytes = <[u8; 8]>::try_from(bytes).or(Err(Error::InvalidLength))?;
+        Ok(f64::from_be_bytes(bytes))
+    }
+
+    fn float_bytes<'b>(any: &Any<'b>, tag: Tag) -> Result<&'b [u8]> {
+        any.header.assert_class(Class::ContextSpecific)?;
+        any.tag().assert_eq(tag)?;
+        any.header.assert_primitive()?;
+        Ok(any.data)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Opaque<'_> {
+    /// Build a new `Opaque` object, wrapping the DER encoding of `value`
+    pub fn from_value<T: ToDer>(value: &T) -> SerializeResult<Opaque<'static>> {
+        let data = value.to_der_vec()?;
+        Ok(Opaque {
+            data: Cow::Owned(data),
+        })
+    }
+}
+
+impl<'a> AsRef<[u8]> for Opaque<'a> {
+    fn as_ref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl<'a> TryFrom<Any<'a>> for Opaque<'a> {
+    type Error = Error;
+
+    fn try_from(any: Any<'a>) -> Result<Opaque<'a>> {
+        TryFrom::try_from(&any)
+    }
+}
+
+impl<'a, 'b> TryFrom<&'b Any<'a>> for Opaque<'a> {
+    type Error = Error;
+
+    fn try_from(any: &'b Any<'a>) -> Result<Opaque<'a>> {
+        any.header.assert_class(Class::Application)?;
+        any.tag().assert_eq(Self::TAG)?;
+        any.header.assert_primitive()?;
+        Ok(Opaque {
+            data: Cow::Borrowed(any.data),
+        })
+    }
+}
+
+impl<'a> CheckDerConstraints for Opaque<'a> {
+    fn check_constraints(any: &Any) -> Result<()> {
+        any.header.assert_der_primitive()?;
+        Ok(())
+    }
+}
+
+impl DerAutoDerive for Opaque<'_> {}
+
+impl DynTagged for Opaque<'_> {
+    fn tag(&self) -> Tag {
+        Self::TAG
+    }
+}
+
+#[cfg(feature = "std")]
+impl ToDer for Opaque<'_> {
+    fn to_der_len(&self) -> Result<usize> {
+        let header = Header::new(
+            Class::Application,
+            false,
+            Self::TAG,
+            Length::Definite(self.data.len()),
+        );
+        Ok(header.to_der_len()? + self.data.len())
+    }
+
+    fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        let header = Header::new(
+            Class::Application,
+            false,
+            Self::TAG,
+            Length::Definite(self.data.len()),
+        );
+        header.write_der_header(writer)
+    }
+
+    fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        writer.write(&self.data).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn opaque_inner() {
+        let input = &hex!("44 05 04 03 61 62 63");
+        let (rem, opaque) = Opaque::from_der(input).expect("parsing failed");
+        assert!(rem.is_empty());
+        assert_eq!(opaque.as_bytes(), &hex!("04 03 61 62 63"));
+        let inner = opaque.inner().expect("invalid inner value");
+        assert_eq!(
+            inner.as_octetstring().map(|s| s.as_ref().to_vec()),
+            Ok(b"abc".to_vec())
+        );
+        // wrong class
+        let input = &hex!("04 05 04 03 61 62 63");
+        assert!(Opaque::from_ber(input).is_err());
+    }
+
+    #[test]
+    fn opaque_float() {
+        let input = &hex!("44 07 9f 78 04 3f c0 00 00");
+        let (_, opaque) = Opaque::from_ber(input).expect("parsing failed");
+        assert_eq!(opaque.as_f32(), Ok(1.5));
+        assert!(opaque.as_f64().is_err());
+        // nested Opaque
+        let input = &hex!("44 09 44 07 9f 78 04 3f c0 00 00");
+        let (_, opaque) = Opaque::from_ber(input).expect("parsing failed");
+        assert_eq!(opaque.as_f32(), Ok(1.5));
+        // double
+        let opaque = Opaque::from_f64(-2.25);
+        assert_eq!(opaque.as_bytes(), &hex!("9f 79 08 c0 02 00 00 00 00 00 00"));
+        assert_eq!(opaque.as_f64(), Ok(-2.25));
+        assert_eq!(
+            Opaque::from_f32(1.5).as_bytes(),
+            &hex!("9f 78 04 3f c0 00 00")
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn opaque_to_der() {
+        let opaque = Opaque::from_value(&42u32).expect("serialization failed");
+        let v = opaque.to_der_vec().expect("serialization failed");
+        assert_eq!(&v, &hex!("44 03 02 01 2a"));
+        let opaque = Opaque::from_f32(1.5);
+        let v = opaque.to_der_vec().expect("serialization failed");
+        assert_eq!(&v, &hex!("44 07 9f 78 04 3f c0 00 00"));
+        assert_eq!(opaque.tag(), Opaque::TAG);
+    }
+}
@@ -5,17 +5,21 @@ use crate::*;
 // `src/traits.rs`, since `T` always satisfies `T: Into<Option<T>>`
 //
 // for the same reason, we cannot use a generic error type here
+//
+// an object with a different tag or class (for ex. `[0]` instead of `[PRIVATE 0]`) is not
+// the expected one, so the value is considered absent
 impl<'a, T> FromBer<'a> for Option<T>
 where
     T: FromBer<'a>,
 {
-    fn from_ber(bytes: &'a [u8]) -> ParseResult<Self> {
+    fn from_ber(bytes: &'a [u8]) -> ParseResult<'a, Self> {
         if bytes.is_empty() {
             return Ok((bytes, None));
         }
         match T::from_ber(bytes) {
             Ok((rem, t)) => Ok((rem, Some(t))),
-            Err(nom::Err::Error(Error::UnexpectedTag { .. })) => Ok((bytes, None)),
+            Err(Err::Error(Error::UnexpectedTag { .. }))
+            | Err(Err::Error(Error::UnexpectedClass { .. })) => Ok((bytes, None)),
             Err(e) => Err(e),
         }
     }
@@ -25,13 +29,14 @@ impl<'a, T> FromDer<'a> for Option<T>
 where
     T: FromDer<'a>,
 {
-    fn from_der(bytes: &'a [u8]) -> ParseResult<Self> {
+    fn from_der(bytes: &'a [u8]) -> ParseResult<'a, Self> {
         if bytes.is_empty() {
             return Ok((bytes, None));
         }
         match T::from_der(bytes) {
             Ok((rem, t)) => Ok((rem, Some(t))),
-            Err(nom::Err::Error(Error::UnexpectedTag { .. })) => Ok((bytes, None)),
+            Err(Err::Error(Error::UnexpectedTag { .. }))
+            | Err(Err::Error(Error::UnexpectedClass { .. })) => Ok((bytes, None)),
             Err(e) => Err(e),
         }
     }
@@ -44,6 +49,10 @@ where
     fn check_constraints(any: &Any) -> Result<()> {
         T::check_constraints(any)
     }
+
+    fn check_constraints_ctx(any: &Any, ctx: DerCheckContext) -> Result<()> {
+        T::check_constraints_ctx(any, ctx)
+    }
 }
 
 impl<T> DynTagged for Option<T>
@@ -84,4 +93,15 @@ where
             Some(t) => t.write_der_content(writer),
         }
     }
+
+    fn write_encoded(
+        &self,
+        rules: EncodingRules,
+        writer: &mut dyn std::io::Write,
+    ) -> SerializeResult<usize> {
+        match self {
+            None => Ok(0),
+            Some(t) => t.write_encoded(rules, writer),
+        }
+    }
 }
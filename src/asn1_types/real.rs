@@ -13,7 +13,7 @@ pub use self::f64::*;
 /// # Limitations
 ///
 /// When encoding binary values, only base 2 is supported
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Real {
     /// Non-special values
     Binary {
@@ -157,6 +157,14 @@ impl<'a> TryFrom<Any<'a>> for Real {
     type Error = Error;
 
     fn try_from(any: Any<'a>) -> Result<Self> {
+        TryFrom::try_from(&any)
+    }
+}
+
+impl<'a, 'b> TryFrom<&'b Any<'a>> for Real {
+    type Error = Error;
+
+    fn try_from(any: &'b Any<'a>) -> Result<Self> {
         any.tag().assert_eq(Self::TAG)?;
         any.header.assert_primitive()?;
         let data = &any.data;
@@ -288,8 +296,9 @@ impl Tagged for Real {
 }
 
 #[cfg(feature = "std")]
-impl ToDer for Real {
-    fn to_der_len(&self) -> Result<usize> {
+impl Real {
+    /// Length of the DER content (without header)
+    fn der_content_len(&self) -> Result<usize> {
         match self {
             Real::Zero => Ok(0),
             Real::Infinity | Real::NegInfinity => Ok(1),
@@ -302,15 +311,16 @@ impl ToDer for Real {
             }
         }
     }
+}
+
+#[cfg(feature = "std")]
+impl ToDer for Real {
+    fn to_der_len(&self) -> Result<usize> {
+        primitive_der_len(Self::TAG, self.der_content_len()?)
+    }
 
     fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
-        let header = Header::new(
-            Class::Universal,
-            false,
-            Self::TAG,
-            Length::Definite(self.to_der_len()?),
-        );
-        header.write_der_header(writer).map_err(Into::into)
+        write_header(Self::TAG, self.der_content_len()?, writer)
     }
 
     fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
@@ -5,6 +5,14 @@ impl<'a> TryFrom<Any<'a>> for f32 {
     type Error = Error;
 
     fn try_from(any: Any<'a>) -> Result<f32> {
+        TryFrom::try_from(&any)
+    }
+}
+
+impl<'a, 'b> TryFrom<&'b Any<'a>> for f32 {
+    type Error = Error;
+
+    fn try_from(any: &'b Any<'a>) -> Result<f32> {
         any.tag().assert_eq(Self::TAG)?;
         any.header.assert_primitive()?;
         let real: Real = any.try_into()?;
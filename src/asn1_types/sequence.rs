@@ -3,10 +3,13 @@ use alloc::borrow::Cow;
 use alloc::vec::Vec;
 use core::convert::TryFrom;
 
+mod field_parser;
 mod iterator;
 mod sequence_of;
+mod tuple;
 mod vec;
 
+pub use field_parser::*;
 pub use iterator::*;
 pub use sequence_of::*;
 pub use vec::*;
@@ -131,7 +134,7 @@ impl<'a> Sequence<'a> {
     /// //      b INTEGER (0..4294967296)
     /// // }
     /// // and return only `(a,b)
-    /// fn parser(i: &[u8]) -> ParseResult<(u8, u32)> {
+    /// fn parser(i: &[u8]) -> ParseResult<'_, (u8, u32)> {
     ///     Sequence::from_der_and_then(i, |i| {
     ///             let (i, a) = u8::from_der(i)?;
     ///             let (i, b) = u32::from_der(i)?;
@@ -180,7 +183,7 @@ impl<'a> Sequence<'a> {
     {
         match self.content {
             Cow::Borrowed(b) => f(b),
-            _ => Err(nom::Err::Error(Error::LifetimeError.into())),
+            _ => Err(Err::Error(Error::LifetimeError.into())),
         }
     }
 
@@ -330,6 +333,10 @@ impl<'a> CheckDerConstraints for Sequence<'a> {
         // test, for ex INTEGER encoding will not be checked
         Ok(())
     }
+
+    fn check_constraints_ctx(any: &Any, ctx: DerCheckContext) -> Result<()> {
+        Any::check_constraints_ctx(any, ctx)
+    }
 }
 
 impl<'a> DerAutoDerive for Sequence<'a> {}
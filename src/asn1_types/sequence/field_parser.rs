@@ -0,0 +1,238 @@
+use crate::*;
+use alloc::boxed::Box;
+use core::marker::PhantomData;
+
+/// A helper to parse the fields of a `SEQUENCE`, in order
+///
+/// This object holds the remaining input, and provides methods to parse the next field.
+/// Each call to a `take` method counts as one field (even if an optional field is absent), and
+/// errors are wrapped in [`Error::Field`] with the index of the field, to help finding which
+/// field could not be parsed.
+///
+/// The type of parser (BER/DER) is specified using the generic parameter `F` of this struct.
+///
+/// Note: the parser must start on the sequence *contents*, not the sequence itself.
+///
+/// # Examples
+///
+/// To parse the contents of `SEQUENCE { version [0] INTEGER OPTIONAL, serial INTEGER,
+/// critical BOOLEAN DEFAULT FALSE }`:
+///
+/// ```rust
+/// use asn1_rs::{BerParser, FieldParser, FromBer, Sequence};
+///
+/// let bytes = &[0x30, 0x08, 0xa0, 0x03, 0x02, 0x01, 0x02, 0x02, 0x01, 0x2a];
+/// let (_, seq) = Sequence::from_ber(bytes).expect("parsing sequence failed");
+///
+/// let mut parser = FieldParser::<BerParser>::new(seq.as_ref());
+/// let version = parser.take_tagged_optional::<u32>(0).expect("version");
+/// let serial = parser.take::<u32>().expect("serial");
+/// let critical = parser.take_default(false).expect("critical");
+/// assert!(parser.is_empty());
+///
+/// assert_eq!(version, Some(2));
+/// assert_eq!(serial, 42);
+/// assert!(!critical);
+/// ```
+#[derive(Debug)]
+pub struct FieldParser<'a, F>
+where
+    F: ASN1Parser,
+{
+    data: &'a [u8],
+    index: usize,
+    _f: PhantomData<F>,
+}
+
+impl<'a, F> FieldParser<'a, F>
+where
+    F: ASN1Parser,
+{
+    /// Build a new `FieldParser` from the contents of a `SEQUENCE`
+    pub const fn new(data: &'a [u8]) -> Self {
+        FieldParser {
+            data,
+            index: 0,
+            _f: PhantomData,
+        }
+    }
+
+    /// Return the remaining (unparsed) input
+    #[inline]
+    pub const fn remaining(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Return true if all input was parsed
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Return the index of the next field to parse
+    #[inline]
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Update the remaining input and the field index with the result of a parser
+    fn update<T>(&mut self, res: ParseResult<'a, T>) -> Result<T> {
+        let index = self.index;
+        self.index += 1;
+        match res {
+            Ok((rem, t)) => {
+                self.data = rem;
+                Ok(t)
+            }
+            Err(e) => Err(Error::Field {
+                index,
+                error: Box::new(e.into()),
+            }),
+        }
+    }
+}
+
+impl<'a> FieldParser<'a, BerParser> {
+    /// Parse the next field, as BER
+    pub fn take<T>(&mut self) -> Result<T>
+    where
+        T: FromBer<'a>,
+    {
+        let res = T::from_ber(self.data);
+        self.update(res)
+    }
+
+    /// Parse the next field as `T OPTIONAL`, as BER
+    ///
+    /// Returns `None` if the input is empty, or if the next object does not have the tag of `T`.
+    pub fn take_optional<T>(&mut self) -> Result<Option<T>>
+    where
+        T: FromBer<'a>,
+    {
+        self.take::<Option<T>>()
+    }
+
+    /// Parse the next field as `T DEFAULT default`, as BER
+    pub fn take_default<T>(&mut self, default: T) -> Result<T>
+    where
+        T: FromBer<'a>,
+    {
+        self.take_optional::<T>().map(|o| o.unwrap_or(default))
+    }
+
+    /// Parse the next field as `[tag] EXPLICIT T OPTIONAL`, as BER
+    ///
+    /// Returns `None` if the input is empty, or if the next object does not have the expected
+    /// tag. The tagged object must contain exactly one object of type `T`.
+    pub fn take_tagged_optional<T>(&mut self, tag: u32) -> Result<Option<T>>
+    where
+        T: FromBer<'a>,
+    {
+        let res = OptTaggedParser::from(tag).parse_ber(self.data, |header, data| {
+            let (rem, obj) = T::from_ber(data)?;
+            header.assert_consumed(rem).map_err(Err::Error)?;
+            Ok((rem, obj))
+        });
+        self.update(res)
+    }
+}
+
+impl<'a> FieldParser<'a, DerParser> {
+    /// Parse the next field, as DER
+    pub fn take<T>(&mut self) -> Result<T>
+    where
+        T: FromDer<'a>,
+    {
+        let res = T::from_der(self.data);
+        self.update(res)
+    }
+
+    /// Parse the next field as `T OPTIONAL`, as DER
+    ///
+    /// Returns `None` if the input is empty, or if the next object does not have the tag of `T`.
+    pub fn take_optional<T>(&mut self) -> Result<Option<T>>
+    where
+        T: FromDer<'a>,
+    {
+        self.take::<Option<T>>()
+    }
+
+    /// Parse the next field as `T DEFAULT default`, as DER
+    ///
+    /// Note: DER requires that a value equal to the default is not encoded, this is not
+    /// checked by this function.
+    pub fn take_default<T>(&mut self, default: T) -> Result<T>
+    where
+        T: FromDer<'a>,
+    {
+        self.take_optional::<T>().map(|o| o.unwrap_or(default))
+    }
+
+    /// Parse the next field as `[tag] EXPLICIT T OPTIONAL`, as DER
+    ///
+    /// Returns `None` if the input is empty, or if the next object does not have the expected
+    /// tag. The tagged object must contain exactly one object of type `T`.
+    pub fn take_tagged_optional<T>(&mut self, tag: u32) -> Result<Option<T>>
+    where
+        T: FromDer<'a>,
+    {
+        let res = OptTaggedParser::from(tag).parse_der(self.data, |header, data| {
+            let (rem, obj) = T::from_der(data)?;
+            header.assert_consumed(rem).map_err(Err::Error)?;
+            Ok((rem, obj))
+        });
+        self.update(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use alloc::boxed::Box;
+    use hex_literal::hex;
+
+    #[test]
+    fn field_parser_der() {
+        // SEQUENCE { [0] INTEGER OPTIONAL, INTEGER, BOOLEAN DEFAULT FALSE, [1] NULL OPTIONAL }
+        let data = &hex!("02 01 2a 01 01 ff");
+        let mut parser = FieldParser::<DerParser>::new(data);
+        assert_eq!(parser.take_tagged_optional::<u32>(0), Ok(None));
+        assert_eq!(parser.take::<u32>(), Ok(42));
+        assert_eq!(parser.take_default(false), Ok(true));
+        assert_eq!(parser.take_tagged_optional::<Null>(1), Ok(None));
+        assert_eq!(parser.index(), 4);
+        assert!(parser.is_empty());
+        // trailing content in tagged object
+        let data = &hex!("a0 05 02 01 2a 05 00");
+        let mut parser = FieldParser::<DerParser>::new(data);
+        assert_eq!(
+            parser.take_tagged_optional::<u32>(0),
+            Err(Error::Field {
+                index: 0,
+                error: Box::new(Error::TrailingContent {
+                    tag: Tag(0),
+                    remaining: 2
+                }),
+            })
+        );
+        let mut parser = FieldParser::<BerParser>::new(data);
+        assert!(parser.take_tagged_optional::<u32>(0).is_err());
+    }
+
+    #[test]
+    fn field_parser_error_index() {
+        let data = &hex!("02 01 2a 01 01 ff");
+        let mut parser = FieldParser::<BerParser>::new(data);
+        assert_eq!(parser.take::<u32>(), Ok(42));
+        let err = parser.take::<u32>().expect_err("parsing should fail");
+        assert_eq!(
+            err,
+            Error::Field {
+                index: 1,
+                error: Box::new(Error::unexpected_tag(Some(Tag::Integer), Tag::Boolean)),
+            }
+        );
+        // input is not consumed on error
+        assert_eq!(parser.remaining(), &data[3..]);
+    }
+}
@@ -58,6 +58,13 @@ where
         if self.has_error || self.data.is_empty() {
             return None;
         }
+        // the iterator reads the content of an object, without the end-of-contents octets of
+        // indefinite length objects (X.690 section 8.1.5). The header is parsed by `T`, so only
+        // check the identifier octet here (universal class, tag 0).
+        if self.data[0] & 0b1101_1111 == 0 {
+            self.has_error = true;
+            return Some(Err(Error::UnexpectedEndOfContent.into()));
+        }
         match T::from_ber(self.data) {
             Ok((rem, obj)) => {
                 self.data = rem;
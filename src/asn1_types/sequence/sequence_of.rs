@@ -1,3 +1,5 @@
+#[cfg(feature = "std")]
+use crate::encoding::write_cer_constructed;
 use crate::*;
 use alloc::vec::Vec;
 use core::convert::TryFrom;
@@ -26,7 +28,7 @@ use core::iter::FromIterator;
 /// assert_eq!(sum, 9);
 ///
 /// ```
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct SequenceOf<T> {
     pub(crate) items: Vec<T>,
 }
@@ -94,6 +96,12 @@ impl<T> FromIterator<T> for SequenceOf<T> {
     }
 }
 
+impl<T> Extend<T> for SequenceOf<T> {
+    fn extend<IT: IntoIterator<Item = T>>(&mut self, iter: IT) {
+        self.items.extend(iter)
+    }
+}
+
 impl<'a, T> TryFrom<Any<'a>> for SequenceOf<T>
 where
     T: FromBer<'a>,
@@ -101,6 +109,17 @@ where
     type Error = Error;
 
     fn try_from(any: Any<'a>) -> Result<Self> {
+        TryFrom::try_from(&any)
+    }
+}
+
+impl<'a, 'b, T> TryFrom<&'b Any<'a>> for SequenceOf<T>
+where
+    T: FromBer<'a>,
+{
+    type Error = Error;
+
+    fn try_from(any: &'b Any<'a>) -> Result<Self> {
         any.tag().assert_eq(Self::TAG)?;
         if !any.header.is_constructed() {
             return Err(Error::ConstructExpected);
@@ -115,11 +134,16 @@ where
     T: CheckDerConstraints,
 {
     fn check_constraints(any: &Any) -> Result<()> {
+        Self::check_constraints_ctx(any, DerCheckContext::new())
+    }
+
+    fn check_constraints_ctx(any: &Any, ctx: DerCheckContext) -> Result<()> {
         any.tag().assert_eq(Self::TAG)?;
         any.header.assert_constructed()?;
+        let ctx = ctx.enter()?;
         for item in SequenceIterator::<Any, DerParser>::new(any.data) {
             let item = item?;
-            T::check_constraints(&item)?;
+            T::check_constraints_ctx(&item, ctx)?;
         }
         Ok(())
     }
@@ -127,6 +151,18 @@ where
 
 impl<T> DerAutoDerive for SequenceOf<T> {}
 
+impl<T, U> ToStatic for SequenceOf<T>
+where
+    T: ToStatic<Owned = U>,
+    U: 'static,
+{
+    type Owned = SequenceOf<U>;
+
+    fn to_static(&self) -> Self::Owned {
+        SequenceOf::new(self.items.to_static())
+    }
+}
+
 impl<T> Tagged for SequenceOf<T> {
     const TAG: Tag = Tag::Sequence;
 }
@@ -147,4 +183,171 @@ where
     fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
         self.items.write_der_content(writer)
     }
+
+    fn write_encoded(
+        &self,
+        rules: EncodingRules,
+        writer: &mut dyn std::io::Write,
+    ) -> SerializeResult<usize> {
+        if rules != EncodingRules::Cer {
+            return self.write_der(writer);
+        }
+        write_cer_constructed(Class::Universal, Self::TAG, writer, |writer| {
+            let mut sz = 0;
+            for t in self.items.iter() {
+                sz += t.write_encoded(rules, writer)?;
+            }
+            Ok(sz)
+        })
+    }
+}
+
+/// Encode the items of `iter` as a DER `SEQUENCE OF`, without collecting them first
+///
+/// The iterator is cloned and traversed twice: a first pass computes the length of the
+/// content (using [`ToDer::to_der_len`]), then items are serialized one by one. This allows
+/// emitting huge lists (for ex. CRL entries) using constant memory, if items are produced on
+/// the fly.
+///
+/// Return the number of bytes written.
+///
+/// ```rust
+/// use asn1_rs::encode_sequence_of;
+///
+/// let mut v = Vec::new();
+/// let sz = encode_sequence_of((1..=3).map(|i| i as u32), &mut v).expect("serialization failed");
+/// assert_eq!(sz, 11);
+/// assert_eq!(&v, &[0x30, 0x09, 0x02, 0x01, 0x01, 0x02, 0x01, 0x02, 0x02, 0x01, 0x03]);
+/// ```
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn encode_sequence_of<I>(iter: I, writer: &mut dyn std::io::Write) -> SerializeResult<usize>
+where
+    I: IntoIterator + Clone,
+    I::Item: ToDer,
+{
+    let mut len = 0;
+    for t in iter.clone() {
+        len = usize::checked_add(len, t.to_der_len()?).ok_or(SerializeError::LengthOverflow)?;
+    }
+    let header = Header::new(Class::Universal, true, Tag::Sequence, Length::Definite(len));
+    let mut sz = header.write_der_header(writer)?;
+    for t in iter {
+        sz += t.write_der(writer)?;
+    }
+    Ok(sz)
+}
+
+/// Encode the items of `iter` as a `SEQUENCE OF` to a seekable writer, backpatching the length
+///
+/// Contrary to [`encode_sequence_of`], the items are traversed only once, and the length of the
+/// content is never computed before writing: a 4-bytes long-form length is reserved, then the
+/// items are written, and finally the length is written at the reserved position. This is
+/// useful for very large lists written to files.
+///
+/// The length is always encoded using the long form with 4 bytes. This is valid BER, but *not*
+/// DER (X.690 section 10.1 requires the minimum number of octets). The content must be
+/// smaller than 4 GiB, otherwise [`SerializeError::LengthOverflow`] is returned (after the
+/// content has been written).
+///
+/// Return the number of bytes written. The writer is left positioned at the end of the object.
+///
+/// ```rust
+/// use asn1_rs::{encode_sequence_of_seekable, FromBer, SequenceOf};
+/// use std::io::Cursor;
+///
+/// let mut cursor = Cursor::new(Vec::new());
+/// let sz = encode_sequence_of_seekable(1..=3u32, &mut cursor).expect("serialization failed");
+/// assert_eq!(sz, 15);
+/// let v = cursor.into_inner();
+/// assert_eq!(&v[..6], &[0x30, 0x84, 0x00, 0x00, 0x00, 0x09]);
+/// let (_, seq) = SequenceOf::<u32>::from_ber(&v).expect("parsing failed");
+/// assert_eq!(Vec::from(seq), vec![1, 2, 3]);
+/// ```
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn encode_sequence_of_seekable<I, W>(iter: I, writer: &mut W) -> SerializeResult<usize>
+where
+    I: IntoIterator,
+    I::Item: ToDer,
+    W: std::io::Write + std::io::Seek,
+{
+    use std::io::SeekFrom;
+
+    let start = writer.stream_position()?;
+    // identifier (universal, constructed, SEQUENCE), then long form length with 4 bytes
+    writer.write_all(&[0x30, 0x84, 0, 0, 0, 0])?;
+    let mut len = 0;
+    for t in iter {
+        len =
+            usize::checked_add(len, t.write_der(writer)?).ok_or(SerializeError::LengthOverflow)?;
+    }
+    let len32 = u32::try_from(len).map_err(|_| SerializeError::LengthOverflow)?;
+    let end = writer.stream_position()?;
+    writer.seek(SeekFrom::Start(start + 2))?;
+    writer.write_all(&len32.to_be_bytes())?;
+    writer.seek(SeekFrom::Start(end))?;
+    Ok(6 + len)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn sequence_of_extend() {
+        let mut seq: SequenceOf<u32> = (1..3).collect();
+        seq.extend(vec![3, 4]);
+        assert_eq!(Vec::from(seq), vec![1, 2, 3, 4]);
+        let mut set = SetOf::new(vec![1u32]);
+        set.extend(Some(2));
+        assert_eq!(set.len(), 2);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn encode_sequence_of_stream() {
+        let items = (0..1000u32).map(|i| i * 7);
+        let mut v = Vec::new();
+        let sz = encode_sequence_of(items.clone(), &mut v).expect("serialization failed");
+        assert_eq!(sz, v.len());
+        let seq: SequenceOf<u32> = items.collect();
+        assert_eq!(v, seq.to_der_vec().expect("serialization failed"));
+        // references
+        let items = [1u32, 2];
+        let mut v = Vec::new();
+        encode_sequence_of(items.iter(), &mut v).expect("serialization failed");
+        assert_eq!(&v, &[0x30, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x02]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn encode_sequence_of_backpatch() {
+        use std::io::Cursor;
+
+        // existing data before the object
+        let mut cursor = Cursor::new(vec![0xff, 0xff]);
+        cursor.set_position(2);
+        let items = (0..100u32).map(|i| i * 1000);
+        let sz =
+            encode_sequence_of_seekable(items.clone(), &mut cursor).expect("serialization failed");
+        assert_eq!(cursor.position() as usize, 2 + sz);
+        let v = cursor.into_inner();
+        assert_eq!(&v[..2], &[0xff, 0xff]);
+        let (rem, seq) = SequenceOf::<u32>::from_ber(&v[2..]).expect("parsing failed");
+        assert!(rem.is_empty());
+        assert_eq!(Vec::from(seq), items.collect::<Vec<_>>());
+        // values with the highest bit set are written with a sign octet
+        let mut cursor = Cursor::new(Vec::new());
+        encode_sequence_of_seekable([128u32], &mut cursor).expect("serialization failed");
+        assert_eq!(
+            cursor.into_inner(),
+            vec![0x30, 0x84, 0, 0, 0, 4, 0x02, 0x02, 0x00, 0x80]
+        );
+        // empty sequence
+        let mut cursor = Cursor::new(Vec::new());
+        let sz = encode_sequence_of_seekable(Vec::<u32>::new(), &mut cursor).unwrap();
+        assert_eq!(sz, 6);
+        assert_eq!(cursor.into_inner(), vec![0x30, 0x84, 0, 0, 0, 0]);
+    }
 }
@@ -0,0 +1,153 @@
+use crate::*;
+use core::convert::TryFrom;
+
+// Tuples are encoded as anonymous `SEQUENCE` objects, each element being a component.
+//
+// Note: `Tagged` requires all components to be `DynTagged`, to avoid conflicting with
+// the `(Class, bool, Tag)` implementation used for headers.
+//
+// As for derived sequences, trailing bytes in the sequence content are rejected
+// (`Error::TrailingContent`).
+
+macro_rules! impl_tuple {
+    ($($t:ident $v:ident $idx:tt),+) => {
+        impl<'a, $($t),+> TryFrom<Any<'a>> for ($($t,)+)
+        where
+            $($t: FromBer<'a>,)+
+        {
+            type Error = Error;
+
+            fn try_from(any: Any<'a>) -> Result<Self> {
+                TryFrom::try_from(&any)
+            }
+        }
+
+        impl<'a, 'b, $($t),+> TryFrom<&'b Any<'a>> for ($($t,)+)
+        where
+            $($t: FromBer<'a>,)+
+        {
+            type Error = Error;
+
+            fn try_from(any: &'b Any<'a>) -> Result<Self> {
+                any.tag().assert_eq(Tag::Sequence)?;
+                any.header.assert_constructed()?;
+                let rem = any.data;
+                $(
+                    let (rem, $v) = <$t>::from_ber(rem)?;
+                )+
+                any.header.assert_consumed(rem)?;
+                Ok(($($v,)+))
+            }
+        }
+
+        impl<$($t),+> CheckDerConstraints for ($($t,)+)
+        where
+            $($t: CheckDerConstraints,)+
+        {
+            fn check_constraints(any: &Any) -> Result<()> {
+                Self::check_constraints_ctx(any, DerCheckContext::new())
+            }
+
+            fn check_constraints_ctx(any: &Any, ctx: DerCheckContext) -> Result<()> {
+                any.tag().assert_eq(Tag::Sequence)?;
+                any.header.assert_constructed()?;
+                let ctx = ctx.enter()?;
+                let rem = any.data;
+                $(
+                    let (rem, $v) = Any::from_der(rem)?;
+                    <$t>::check_constraints_ctx(&$v, ctx)?;
+                )+
+                any.header.assert_consumed(rem)?;
+                Ok(())
+            }
+        }
+
+        impl<$($t),+> DerAutoDerive for ($($t,)+) {}
+
+        impl<$($t),+> Tagged for ($($t,)+)
+        where
+            $($t: DynTagged,)+
+        {
+            const TAG: Tag = Tag::Sequence;
+        }
+
+        #[cfg(feature = "std")]
+        impl<$($t),+> ToDer for ($($t,)+)
+        where
+            $($t: ToDer,)+
+        {
+            fn to_der_len(&self) -> Result<usize> {
+                let mut len = 0;
+                $(
+                    len = usize::checked_add(len, self.$idx.to_der_len()?)
+                        .ok_or(Error::LengthTooLarge)?;
+                )+
+                let header = Header::new(Class::Universal, true, Self::TAG, Length::Definite(len));
+                usize::checked_add(header.to_der_len()?, len).ok_or(Error::LengthTooLarge)
+            }
+
+            fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+                let mut len = 0;
+                $(
+                    len = usize::checked_add(len, self.$idx.to_der_len()?)
+                        .ok_or(SerializeError::LengthOverflow)?;
+                )+
+                let header = Header::new(Class::Universal, true, Self::TAG, Length::Definite(len));
+                header.write_der_header(writer).map_err(Into::into)
+            }
+
+            fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+                let mut sz = 0;
+                $(
+                    sz += self.$idx.write_der(writer)?;
+                )+
+                Ok(sz)
+            }
+        }
+    };
+}
+
+impl_tuple!(A a 0, B b 1);
+impl_tuple!(A a 0, B b 1, C c 2);
+impl_tuple!(A a 0, B b 1, C c 2, D d 3);
+impl_tuple!(A a 0, B b 1, C c 2, D d 3, E e 4);
+impl_tuple!(A a 0, B b 1, C c 2, D d 3, E e 4, F f 5);
+impl_tuple!(A a 0, B b 1, C c 2, D d 3, E e 4, F f 5, G g 6);
+impl_tuple!(A a 0, B b 1, C c 2, D d 3, E e 4, F f 5, G g 6, H h 7);
+impl_tuple!(A a 0, B b 1, C c 2, D d 3, E e 4, F f 5, G g 6, H h 7, I i 8);
+impl_tuple!(A a 0, B b 1, C c 2, D d 3, E e 4, F f 5, G g 6, H h 7, I i 8, J j 9);
+impl_tuple!(A a 0, B b 1, C c 2, D d 3, E e 4, F f 5, G g 6, H h 7, I i 8, J j 9, K k 10);
+impl_tuple!(A a 0, B b 1, C c 2, D d 3, E e 4, F f 5, G g 6, H h 7, I i 8, J j 9, K k 10, L l 11);
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::*;
+
+    /// An object with a very large encoding, which cannot be written
+    struct Huge;
+
+    impl Tagged for Huge {
+        const TAG: Tag = Tag::OctetString;
+    }
+
+    impl ToDer for Huge {
+        fn to_der_len(&self) -> Result<usize> {
+            Ok(usize::MAX / 2 + 1)
+        }
+
+        fn write_der_header(&self, _writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+            Err(SerializeError::InvalidLength)
+        }
+
+        fn write_der_content(&self, _writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+            Err(SerializeError::InvalidLength)
+        }
+    }
+
+    #[test]
+    fn tuple_to_der_len_overflow() {
+        let t = (Huge, Huge);
+        assert_eq!(t.to_der_len(), Err(Error::LengthTooLarge));
+        assert!(t.write_der_header(&mut std::io::sink()).is_err());
+    }
+}
@@ -1,3 +1,5 @@
+#[cfg(feature = "std")]
+use crate::encoding::write_cer_constructed;
 use crate::*;
 use alloc::vec::Vec;
 use core::convert::TryFrom;
@@ -49,6 +51,17 @@ where
     type Error = Error;
 
     fn try_from(any: Any<'a>) -> Result<Self> {
+        TryFrom::try_from(&any)
+    }
+}
+
+impl<'a, 'b, T> TryFrom<&'b Any<'a>> for Vec<T>
+where
+    T: FromBer<'a>,
+{
+    type Error = Error;
+
+    fn try_from(any: &'b Any<'a>) -> Result<Self> {
         any.tag().assert_eq(Self::TAG)?;
         any.header.assert_constructed()?;
         let items = SetIterator::<T, BerParser>::new(any.data).collect::<Result<Vec<T>>>()?;
@@ -61,11 +74,16 @@ where
     T: CheckDerConstraints,
 {
     fn check_constraints(any: &Any) -> Result<()> {
+        Self::check_constraints_ctx(any, DerCheckContext::new())
+    }
+
+    fn check_constraints_ctx(any: &Any, ctx: DerCheckContext) -> Result<()> {
         any.tag().assert_eq(Self::TAG)?;
         any.header.assert_constructed()?;
+        let ctx = ctx.enter()?;
         for item in SequenceIterator::<Any, DerParser>::new(any.data) {
             let item = item?;
-            <T as CheckDerConstraints>::check_constraints(&item)?;
+            <T as CheckDerConstraints>::check_constraints_ctx(&item, ctx)?;
         }
         Ok(())
     }
@@ -93,14 +111,14 @@ where
     T: FromDer<'a, E>,
     E: From<Error>,
 {
-    fn from_der(bytes: &'a [u8]) -> ParseResult<Self, E> {
+    fn from_der(bytes: &'a [u8]) -> ParseResult<'a, Self, E> {
         let (rem, any) = Any::from_der(bytes).map_err(Err::convert)?;
         any.header
             .assert_tag(Self::TAG)
-            .map_err(|e| nom::Err::Error(e.into()))?;
+            .map_err(|e| Err::Error(e.into()))?;
         let v = SequenceIterator::<T, DerParser, E>::new(any.data)
             .collect::<Result<Vec<T>, E>>()
-            .map_err(nom::Err::Error)?;
+            .map_err(Err::Error)?;
         Ok((rem, v))
     }
 }
@@ -122,7 +140,7 @@ where
     fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
         let mut len = 0;
         for t in self.iter() {
-            len += t.to_der_len().map_err(|_| SerializeError::InvalidLength)?;
+            len = usize::checked_add(len, t.to_der_len()?).ok_or(SerializeError::LengthOverflow)?;
         }
         let header = Header::new(Class::Universal, true, Self::TAG, Length::Definite(len));
         header.write_der_header(writer).map_err(Into::into)
@@ -135,4 +153,21 @@ where
         }
         Ok(sz)
     }
+
+    fn write_encoded(
+        &self,
+        rules: EncodingRules,
+        writer: &mut dyn std::io::Write,
+    ) -> SerializeResult<usize> {
+        if rules != EncodingRules::Cer {
+            return self.write_der(writer);
+        }
+        write_cer_constructed(Class::Universal, Self::TAG, writer, |writer| {
+            let mut sz = 0;
+            for t in self.iter() {
+                sz += t.write_encoded(rules, writer)?;
+            }
+            Ok(sz)
+        })
+    }
 }
@@ -4,11 +4,13 @@ use alloc::vec::Vec;
 use core::convert::TryFrom;
 
 mod btreeset;
+mod der_order;
 mod hashset;
 mod iterator;
 mod set_of;
 
 pub use btreeset::*;
+pub use der_order::*;
 #[cfg(feature = "std")]
 pub use hashset::*;
 pub use iterator::*;
@@ -134,7 +136,7 @@ impl<'a> Set<'a> {
     /// //      b INTEGER (0..4294967296)
     /// // }
     /// // and return only `(a,b)
-    /// fn parser(i: &[u8]) -> ParseResult<(u8, u32)> {
+    /// fn parser(i: &[u8]) -> ParseResult<'_, (u8, u32)> {
     ///     Set::from_der_and_then(i, |i| {
     ///             let (i, a) = u8::from_der(i)?;
     ///             let (i, b) = u32::from_der(i)?;
@@ -183,7 +185,7 @@ impl<'a> Set<'a> {
     {
         match self.content {
             Cow::Borrowed(b) => f(b),
-            _ => Err(nom::Err::Error(Error::LifetimeError.into())),
+            _ => Err(Err::Error(Error::LifetimeError.into())),
         }
     }
 
@@ -319,6 +321,10 @@ impl<'a> CheckDerConstraints for Set<'a> {
     fn check_constraints(_any: &Any) -> Result<()> {
         Ok(())
     }
+
+    fn check_constraints_ctx(any: &Any, ctx: DerCheckContext) -> Result<()> {
+        Any::check_constraints_ctx(any, ctx)
+    }
 }
 
 impl<'a> DerAutoDerive for Set<'a> {}
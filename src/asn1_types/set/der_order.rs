@@ -0,0 +1,361 @@
+use crate::*;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+/// Description of the first pair of elements of a `SET OF` not in DER order
+///
+/// See [`Set::check_der_order`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SetOrderViolation<'a> {
+    /// Index of the first element of the pair (the second element has index `index + 1`)
+    pub index: usize,
+    /// Encoding of the element at `index`
+    pub first: &'a [u8],
+    /// Encoding of the element at `index + 1`, which should come before `first`
+    pub second: &'a [u8],
+}
+
+/// Compare encodings as octet strings, the shorter one being padded with trailing 0-octets
+/// (X.690 section 11.6)
+///
+/// This is the order of elements of a DER `SET OF`. See [`der_cmp`] to compare values.
+pub fn der_encoding_cmp(a: &[u8], b: &[u8]) -> Ordering {
+    let n = a.len().min(b.len());
+    match a[..n].cmp(&b[..n]) {
+        Ordering::Equal => {
+            let a_pad = a[n..].iter().any(|&x| x != 0);
+            let b_pad = b[n..].iter().any(|&x| x != 0);
+            a_pad.cmp(&b_pad)
+        }
+        o => o,
+    }
+}
+
+/// Compare the DER encodings of two values, in the order of elements of a DER `SET OF`
+/// (X.690 section 11.6)
+///
+/// Both values are serialized once. To sort many values, see [`DerOrdered`] and
+/// [`SetOf::sort_der`], which serialize each value only once.
+///
+/// ```rust
+/// use asn1_rs::der_cmp;
+/// use std::cmp::Ordering;
+///
+/// // INTEGER 256 (02 02 01 00) comes after INTEGER 2 (02 01 02)
+/// assert_eq!(der_cmp(&256u32, &2u32).expect("serialization failed"), Ordering::Greater);
+/// // the tag is compared first
+/// assert_eq!(der_cmp(&true, &1u32).expect("serialization failed"), Ordering::Less);
+/// ```
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn der_cmp<A, B>(a: &A, b: &B) -> SerializeResult<Ordering>
+where
+    A: ToDer + ?Sized,
+    B: ToDer + ?Sized,
+{
+    let a = a.to_der_vec()?;
+    let b = b.to_der_vec()?;
+    Ok(der_encoding_cmp(&a, &b))
+}
+
+/// A value with its DER encoding, ordered as the elements of a DER `SET OF`
+/// (X.690 section 11.6)
+///
+/// The encoding is computed once, when building the object. Comparisons use the encoding, so
+/// sorting a list of `DerOrdered` values (or inserting them in a `BTreeSet`) gives the DER
+/// order, and serialization writes the stored encoding.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{DerOrdered, ToDer};
+/// use std::collections::BTreeSet;
+///
+/// let set = [256u32, 1, 2]
+///     .iter()
+///     .copied()
+///     .map(DerOrdered::new)
+///     .collect::<Result<BTreeSet<_>, _>>()
+///     .expect("serialization failed");
+/// let values = set.iter().map(|v| *v.as_inner()).collect::<Vec<_>>();
+/// assert_eq!(values, vec![1, 2, 256]);
+/// // serialized as SET OF INTEGER
+/// let v = set.to_der_vec().expect("serialization failed");
+/// assert_eq!(&v, &[0x31, 0x0a, 0x02, 0x01, 0x01, 0x02, 0x01, 0x02, 0x02, 0x02, 0x01, 0x00]);
+/// ```
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[derive(Clone, Debug)]
+pub struct DerOrdered<T> {
+    value: T,
+    encoding: Vec<u8>,
+    header_len: usize,
+    tag: Tag,
+}
+
+#[cfg(feature = "std")]
+impl<T: ToDer> DerOrdered<T> {
+    /// Serialize `value`, and build a new `DerOrdered` object
+    pub fn new(value: T) -> SerializeResult<Self> {
+        let encoding = value.to_der_vec()?;
+        let (rem, header) = Header::from_der(&encoding).map_err(Error::from)?;
+        let header_len = encoding.len() - rem.len();
+        let tag = header.tag();
+        Ok(DerOrdered {
+            value,
+            encoding,
+            header_len,
+            tag,
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> DerOrdered<T> {
+    /// Return a reference to the value
+    pub fn as_inner(&self) -> &T {
+        &self.value
+    }
+
+    /// Return the value, consuming this object
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Return the DER encoding of the value
+    pub fn encoding(&self) -> &[u8] {
+        &self.encoding
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> PartialEq for DerOrdered<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.encoding == other.encoding
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> Eq for DerOrdered<T> {}
+
+#[cfg(feature = "std")]
+impl<T> PartialOrd for DerOrdered<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> Ord for DerOrdered<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // break ties using the raw encodings, so ordering is consistent with equality
+        der_encoding_cmp(&self.encoding, &other.encoding)
+            .then_with(|| self.encoding.cmp(&other.encoding))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> DynTagged for DerOrdered<T> {
+    /// Return the tag of the encoded value
+    fn tag(&self) -> Tag {
+        self.tag
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> ToDer for DerOrdered<T> {
+    fn to_der_len(&self) -> Result<usize> {
+        Ok(self.encoding.len())
+    }
+
+    fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        writer.write_all(&self.encoding[..self.header_len])?;
+        Ok(self.header_len)
+    }
+
+    fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        let content = &self.encoding[self.header_len..];
+        writer.write_all(content)?;
+        Ok(content.len())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: ToDer> SetOf<T> {
+    /// Sort the items of this set in the order required for DER (X.690 section 11.6)
+    ///
+    /// Each item is serialized once. The sort is stable.
+    ///
+    /// ```rust
+    /// use asn1_rs::SetOf;
+    ///
+    /// let mut set = SetOf::new(vec![256u32, 2, 1]);
+    /// set.sort_der().expect("serialization failed");
+    /// assert_eq!(set.as_ref(), &[1, 2, 256]);
+    /// ```
+    pub fn sort_der(&mut self) -> SerializeResult<()> {
+        let encodings = self
+            .iter()
+            .map(|item| item.to_der_vec())
+            .collect::<SerializeResult<Vec<_>>>()?;
+        let mut items = encodings
+            .into_iter()
+            .zip(self.items_mut().drain(..))
+            .collect::<Vec<_>>();
+        items.sort_by(|(a, _), (b, _)| der_encoding_cmp(a, b));
+        self.items_mut()
+            .extend(items.into_iter().map(|(_, item)| item));
+        Ok(())
+    }
+}
+
+/// Split content into the encodings of elements
+fn der_set_of_encodings(content: &[u8]) -> Result<Vec<&[u8]>> {
+    let mut v = Vec::new();
+    let mut i = content;
+    while !i.is_empty() {
+        let (rem, _) = Any::from_der(i)?;
+        v.push(&i[..i.len() - rem.len()]);
+        i = rem;
+    }
+    Ok(v)
+}
+
+impl<'a> Set<'a> {
+    /// Check that elements of this set are sorted as required for a DER `SET OF`
+    /// (X.690 section 11.6)
+    ///
+    /// Returns `None` if all elements are in ascending order, or a description of the first
+    /// pair of elements that is out of order. Elements must be valid DER objects.
+    ///
+    /// To get the correct order, see [`Set::der_sorted_order`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use asn1_rs::{FromDer, Set};
+    ///
+    /// // SET OF INTEGER { 2, 1 }
+    /// let bytes = &[0x31, 0x06, 0x02, 0x01, 0x02, 0x02, 0x01, 0x01];
+    /// let (_, set) = Set::from_der(bytes).expect("parsing failed");
+    /// let violation = set
+    ///     .check_der_order()
+    ///     .expect("invalid set content")
+    ///     .expect("set is not sorted");
+    /// assert_eq!(violation.index, 0);
+    /// assert_eq!(violation.first, &[0x02, 0x01, 0x02]);
+    /// assert_eq!(violation.second, &[0x02, 0x01, 0x01]);
+    /// ```
+    pub fn check_der_order(&self) -> Result<Option<SetOrderViolation<'_>>> {
+        let encodings = der_set_of_encodings(&self.content)?;
+        let violation = encodings
+            .windows(2)
+            .position(|w| der_encoding_cmp(w[0], w[1]) == Ordering::Greater)
+            .map(|index| SetOrderViolation {
+                index,
+                first: encodings[index],
+                second: encodings[index + 1],
+            });
+        Ok(violation)
+    }
+
+    /// Return the indices of elements of this set, in the order required for a DER `SET OF`
+    /// (X.690 section 11.6)
+    ///
+    /// The sort is stable, so equal elements keep their relative order. Elements must be valid
+    /// DER objects.
+    ///
+    /// To rebuild a sorted set, see [`Set::repair_der_order`].
+    pub fn der_sorted_order(&self) -> Result<Vec<usize>> {
+        let encodings = der_set_of_encodings(&self.content)?;
+        let mut indices = (0..encodings.len()).collect::<Vec<_>>();
+        indices.sort_by(|&a, &b| der_encoding_cmp(encodings[a], encodings[b]));
+        Ok(indices)
+    }
+
+    /// Build a new set, with elements sorted as required for a DER `SET OF`
+    /// (X.690 section 11.6)
+    pub fn repair_der_order(&self) -> Result<Set<'static>> {
+        let mut encodings = der_set_of_encodings(&self.content)?;
+        encodings.sort_by(|a, b| der_encoding_cmp(a, b));
+        let content = encodings.concat();
+        Ok(Set::new(content.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use alloc::vec;
+    use hex_literal::hex;
+
+    #[test]
+    fn set_der_order() {
+        // SET OF INTEGER { 1, 2, 2, 256 }
+        let input = &hex!("31 0d 02 01 01 02 01 02 02 01 02 02 02 01 00");
+        let (_, set) = Set::from_der(input).expect("parsing failed");
+        assert_eq!(set.check_der_order(), Ok(None));
+        assert_eq!(set.der_sorted_order(), Ok(vec![0, 1, 2, 3]));
+        // SET OF OCTET STRING { "\x01\x00", "\x01" }: encodings are compared including the
+        // length octets, so the shorter string comes first
+        let input = &hex!("31 07 04 02 01 00 04 01 01");
+        let (_, set) = Set::from_der(input).expect("parsing failed");
+        let v = set
+            .check_der_order()
+            .expect("invalid set")
+            .expect("set is sorted");
+        assert_eq!(v.index, 0);
+        assert_eq!(v.first, &hex!("04 02 01 00"));
+        assert_eq!(v.second, &hex!("04 01 01"));
+    }
+
+    #[test]
+    fn set_der_order_repair() {
+        // SET OF INTEGER { 3, 1, 2 }
+        let input = &hex!("31 09 02 01 03 02 01 01 02 01 02");
+        let (_, set) = Set::from_der(input).expect("parsing failed");
+        let v = set
+            .check_der_order()
+            .expect("invalid set")
+            .expect("set is sorted");
+        assert_eq!(v.index, 0);
+        assert_eq!(set.der_sorted_order(), Ok(vec![1, 2, 0]));
+        let sorted = set.repair_der_order().expect("invalid set");
+        assert_eq!(sorted.as_ref(), &hex!("02 01 01 02 01 02 02 01 03"));
+        assert_eq!(sorted.check_der_order(), Ok(None));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn der_ordered() {
+        use core::cmp::Ordering;
+        assert_eq!(der_cmp(&1u32, &1u32).ok(), Some(Ordering::Equal));
+        assert_eq!(der_cmp(&2u32, &256u32).ok(), Some(Ordering::Less));
+        // OCTET STRING "\x01\x00" (04 02 01 00) comes after "\x01" (04 01 01)
+        let a = OctetString::from(&hex!("01 00")[..]);
+        let b = OctetString::from(&hex!("01")[..]);
+        assert_eq!(der_cmp(&a, &b).ok(), Some(Ordering::Greater));
+        let mut v = [DerOrdered::new(a).unwrap(), DerOrdered::new(b).unwrap()];
+        v.sort();
+        assert_eq!(v[0].encoding(), &hex!("04 01 01"));
+        assert_eq!(v[0].tag(), Tag::OctetString);
+        assert_eq!(v[0].to_der_vec().unwrap(), &hex!("04 01 01"));
+        assert_eq!(v[1].as_inner().as_ref(), &hex!("01 00"));
+        // SetOf sorting
+        let mut set = SetOf::new(vec![3u32, 1000, 1, 2]);
+        set.sort_der().unwrap();
+        assert_eq!(set.as_ref(), &[1, 2, 3, 1000]);
+        let encoded = set.to_der_vec().unwrap();
+        let (_, set) = Set::from_der(&encoded).unwrap();
+        assert_eq!(set.check_der_order(), Ok(None));
+    }
+
+    #[test]
+    fn set_der_order_padding() {
+        // trailing 0-octets do not change the order
+        use core::cmp::Ordering;
+        assert_eq!(der_encoding_cmp(&[1], &[1, 0, 0]), Ordering::Equal);
+        assert_eq!(der_encoding_cmp(&[1], &[1, 0, 1]), Ordering::Less);
+        assert_eq!(der_encoding_cmp(&[2], &[1, 0xff]), Ordering::Greater);
+    }
+}
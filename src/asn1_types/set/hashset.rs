@@ -1,4 +1,5 @@
 #![cfg(feature = "std")]
+use crate::encoding::{write_cer_constructed, write_cer_sorted};
 use crate::*;
 use std::collections::HashSet;
 use std::convert::TryFrom;
@@ -16,6 +17,18 @@ where
     type Error = Error;
 
     fn try_from(any: Any<'a>) -> Result<Self> {
+        TryFrom::try_from(&any)
+    }
+}
+
+impl<'a, 'b, T> TryFrom<&'b Any<'a>> for HashSet<T>
+where
+    T: FromBer<'a>,
+    T: Hash + Eq,
+{
+    type Error = Error;
+
+    fn try_from(any: &'b Any<'a>) -> Result<Self> {
         any.tag().assert_eq(Self::TAG)?;
         any.header.assert_constructed()?;
         let items = SetIterator::<T, BerParser>::new(any.data).collect::<Result<HashSet<T>>>()?;
@@ -28,11 +41,16 @@ where
     T: CheckDerConstraints,
 {
     fn check_constraints(any: &Any) -> Result<()> {
+        Self::check_constraints_ctx(any, DerCheckContext::new())
+    }
+
+    fn check_constraints_ctx(any: &Any, ctx: DerCheckContext) -> Result<()> {
         any.tag().assert_eq(Self::TAG)?;
         any.header.assert_constructed()?;
+        let ctx = ctx.enter()?;
         for item in SetIterator::<Any, DerParser>::new(any.data) {
             let item = item?;
-            T::check_constraints(&item)?;
+            T::check_constraints_ctx(&item, ctx)?;
         }
         Ok(())
     }
@@ -49,13 +67,13 @@ where
         let (rem, any) = Any::from_der(bytes).map_err(Err::convert)?;
         any.tag()
             .assert_eq(Self::TAG)
-            .map_err(|e| nom::Err::Error(e.into()))?;
+            .map_err(|e| Err::Error(e.into()))?;
         any.header
             .assert_constructed()
-            .map_err(|e| nom::Err::Error(e.into()))?;
+            .map_err(|e| Err::Error(e.into()))?;
         let items = SetIterator::<T, DerParser, E>::new(any.data)
             .collect::<Result<HashSet<T>, E>>()
-            .map_err(nom::Err::Error)?;
+            .map_err(Err::Error)?;
         Ok((rem, items))
     }
 }
@@ -76,7 +94,7 @@ where
     fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
         let mut len = 0;
         for t in self.iter() {
-            len += t.to_der_len().map_err(|_| SerializeError::InvalidLength)?;
+            len = usize::checked_add(len, t.to_der_len()?).ok_or(SerializeError::LengthOverflow)?;
         }
         let header = Header::new(Class::Universal, true, Self::TAG, Length::Definite(len));
         header.write_der_header(writer).map_err(Into::into)
@@ -89,6 +107,26 @@ where
         }
         Ok(sz)
     }
+
+    /// Write the encoded representation, using the given encoding rules
+    ///
+    /// For CER, items are sorted using their CER encoding (X.690 section 9.3).
+    fn write_encoded(
+        &self,
+        rules: EncodingRules,
+        writer: &mut dyn std::io::Write,
+    ) -> SerializeResult<usize> {
+        if rules != EncodingRules::Cer {
+            return self.write_der(writer);
+        }
+        let items = self
+            .iter()
+            .map(|t| t.to_encoded_vec(rules))
+            .collect::<SerializeResult<Vec<_>>>()?;
+        write_cer_constructed(Class::Universal, Self::TAG, writer, |writer| {
+            write_cer_sorted(items, writer)
+        })
+    }
 }
 
 #[cfg(test)]
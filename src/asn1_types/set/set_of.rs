@@ -1,3 +1,5 @@
+#[cfg(feature = "std")]
+use crate::encoding::{write_cer_constructed, write_cer_sorted};
 use crate::*;
 use alloc::vec::Vec;
 use core::convert::TryFrom;
@@ -26,7 +28,7 @@ use core::iter::FromIterator;
 /// assert_eq!(sum, 9);
 ///
 /// ```
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SetOf<T> {
     items: Vec<T>,
 }
@@ -55,6 +57,12 @@ impl<T> SetOf<T> {
     pub fn iter(&self) -> impl Iterator<Item = &T> {
         self.items.iter()
     }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    pub(crate) fn items_mut(&mut self) -> &mut Vec<T> {
+        &mut self.items
+    }
 }
 
 impl<T> AsRef<[T]> for SetOf<T> {
@@ -94,6 +102,12 @@ impl<T> FromIterator<T> for SetOf<T> {
     }
 }
 
+impl<T> Extend<T> for SetOf<T> {
+    fn extend<IT: IntoIterator<Item = T>>(&mut self, iter: IT) {
+        self.items.extend(iter)
+    }
+}
+
 impl<'a, T> TryFrom<Any<'a>> for SetOf<T>
 where
     T: FromBer<'a>,
@@ -101,6 +115,17 @@ where
     type Error = Error;
 
     fn try_from(any: Any<'a>) -> Result<Self> {
+        TryFrom::try_from(&any)
+    }
+}
+
+impl<'a, 'b, T> TryFrom<&'b Any<'a>> for SetOf<T>
+where
+    T: FromBer<'a>,
+{
+    type Error = Error;
+
+    fn try_from(any: &'b Any<'a>) -> Result<Self> {
         any.tag().assert_eq(Self::TAG)?;
         if !any.header.is_constructed() {
             return Err(Error::ConstructExpected);
@@ -115,11 +140,16 @@ where
     T: CheckDerConstraints,
 {
     fn check_constraints(any: &Any) -> Result<()> {
+        Self::check_constraints_ctx(any, DerCheckContext::new())
+    }
+
+    fn check_constraints_ctx(any: &Any, ctx: DerCheckContext) -> Result<()> {
         any.tag().assert_eq(Self::TAG)?;
         any.header.assert_constructed()?;
+        let ctx = ctx.enter()?;
         for item in SetIterator::<Any, DerParser>::new(any.data) {
             let item = item?;
-            T::check_constraints(&item)?;
+            T::check_constraints_ctx(&item, ctx)?;
         }
         Ok(())
     }
@@ -127,6 +157,18 @@ where
 
 impl<T> DerAutoDerive for SetOf<T> {}
 
+impl<T, U> ToStatic for SetOf<T>
+where
+    T: ToStatic<Owned = U>,
+    U: 'static,
+{
+    type Owned = SetOf<U>;
+
+    fn to_static(&self) -> Self::Owned {
+        SetOf::new(self.items.to_static())
+    }
+}
+
 impl<T> Tagged for SetOf<T> {
     const TAG: Tag = Tag::Set;
 }
@@ -137,14 +179,45 @@ where
     T: ToDer,
 {
     fn to_der_len(&self) -> Result<usize> {
-        self.items.to_der_len()
+        let mut len = 0;
+        for t in self.items.iter() {
+            len = usize::checked_add(len, t.to_der_len()?).ok_or(Error::LengthTooLarge)?;
+        }
+        let header = Header::new(Class::Universal, true, Self::TAG, Length::Definite(len));
+        usize::checked_add(header.to_der_len()?, len).ok_or(Error::LengthTooLarge)
     }
 
     fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
-        self.items.write_der_header(writer)
+        let mut len = 0;
+        for t in self.items.iter() {
+            len = usize::checked_add(len, t.to_der_len()?).ok_or(SerializeError::LengthOverflow)?;
+        }
+        let header = Header::new(Class::Universal, true, Self::TAG, Length::Definite(len));
+        header.write_der_header(writer)
     }
 
     fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
         self.items.write_der_content(writer)
     }
+
+    /// Write the encoded representation, using the given encoding rules
+    ///
+    /// For CER, items are sorted using their CER encoding (X.690 section 9.3).
+    fn write_encoded(
+        &self,
+        rules: EncodingRules,
+        writer: &mut dyn std::io::Write,
+    ) -> SerializeResult<usize> {
+        if rules != EncodingRules::Cer {
+            return self.write_der(writer);
+        }
+        let items = self
+            .items
+            .iter()
+            .map(|t| t.to_encoded_vec(rules))
+            .collect::<SerializeResult<Vec<_>>>()?;
+        write_cer_constructed(Class::Universal, Self::TAG, writer, |writer| {
+            write_cer_sorted(items, writer)
+        })
+    }
 }
@@ -1,8 +1,11 @@
+mod anystring;
 mod bmpstring;
+mod convert;
 mod generalstring;
 mod graphicstring;
 mod ia5string;
 mod numericstring;
+mod prep;
 mod printablestring;
 mod str;
 mod string;
@@ -13,11 +16,13 @@ mod videotexstring;
 mod visiblestring;
 
 pub use self::str::*;
+pub use anystring::*;
 pub use bmpstring::*;
 pub use generalstring::*;
 pub use graphicstring::*;
 pub use ia5string::*;
 pub use numericstring::*;
+pub use prep::*;
 pub use printablestring::*;
 pub use string::*;
 pub use teletexstring::*;
@@ -26,6 +31,8 @@ pub use utf8string::*;
 pub use videotexstring::*;
 pub use visiblestring::*;
 
+use alloc::vec::Vec;
+
 /// Base trait for BER string objects and character set validation
 ///
 /// This trait is implemented by several types, and is used to determine if some bytes
@@ -45,6 +52,33 @@ pub use visiblestring::*;
 pub trait TestValidCharset {
     /// Check character set for this object type.
     fn test_valid_charset(i: &[u8]) -> crate::Result<()>;
+
+    /// Check that all characters of a Rust string are valid for this object type.
+    ///
+    /// Returns [`Error::StringInvalidCharacters`](crate::Error::StringInvalidCharacters) with
+    /// the list of invalid characters (without duplicates), if any.
+    ///
+    /// ```rust
+    /// use asn1_rs::{Error, PrintableString, TestValidCharset};
+    ///
+    /// let res = PrintableString::test_valid_str("a*b*c@");
+    /// assert_eq!(res, Err(Error::StringInvalidCharacters(vec!['*', '@'])));
+    /// ```
+    fn test_valid_str(s: &str) -> crate::Result<()> {
+        let mut buf = [0u8; 4];
+        let mut invalid = Vec::new();
+        for c in s.chars() {
+            let bytes = c.encode_utf8(&mut buf).as_bytes();
+            if Self::test_valid_charset(bytes).is_err() && !invalid.contains(&c) {
+                invalid.push(c);
+            }
+        }
+        if invalid.is_empty() {
+            Ok(())
+        } else {
+            Err(crate::Error::StringInvalidCharacters(invalid))
+        }
+    }
 }
 
 #[doc(hidden)]
@@ -54,7 +88,7 @@ macro_rules! asn1_string {
         #[doc="ASN.1 restricted character string type (`"]
         #[doc = $sname]
         #[doc = "`)"]
-        #[derive(Debug, PartialEq, Eq)]
+        #[derive(Clone, Debug, PartialEq, Eq)]
         pub struct $name<'a> {
             pub(crate) data: alloc::borrow::Cow<'a, str>,
         }
@@ -66,29 +100,23 @@ macro_rules! asn1_string {
                 }
             }
 
-            pub fn string(&self) -> String {
-                use alloc::string::ToString;
-                self.data.to_string()
+            /// Build a new object, checking that all characters are valid for this type
+            pub fn try_new(s: &'a str) -> $crate::Result<Self> {
+                <Self as $crate::TestValidCharset>::test_valid_str(s)?;
+                Ok(Self::new(s))
             }
         }
 
-        impl<'a> AsRef<str> for $name<'a> {
-            fn as_ref(&self) -> &str {
-                &self.data
-            }
-        }
+        asn1_string!(TRAITS $name);
 
-        impl<'a> From<&'a str> for $name<'a> {
-            fn from(s: &'a str) -> Self {
-                Self::new(s)
-            }
-        }
+        impl core::str::FromStr for $name<'_> {
+            type Err = $crate::Error;
 
-        impl From<String> for $name<'_> {
-            fn from(s: String) -> Self {
-                Self {
-                    data: alloc::borrow::Cow::Owned(s),
-                }
+            /// Build a new object (owning a copy of `s`), checking that all characters are valid
+            /// for this type
+            fn from_str(s: &str) -> $crate::Result<Self> {
+                <Self as $crate::TestValidCharset>::test_valid_str(s)?;
+                Ok(Self::from(alloc::string::String::from(s)))
             }
         }
 
@@ -118,7 +146,7 @@ macro_rules! asn1_string {
 
         impl<'a> $crate::CheckDerConstraints for $name<'a> {
             fn check_constraints(any: &$crate::Any) -> $crate::Result<()> {
-                any.header.assert_primitive()?;
+                any.header.assert_der_primitive()?;
                 Ok(())
             }
         }
@@ -148,6 +176,9 @@ macro_rules! asn1_string {
                 writer: &mut dyn std::io::Write,
             ) -> $crate::SerializeResult<usize> {
                 use $crate::Tagged;
+                // do not produce invalid encodings if object was built from an invalid string
+                <Self as $crate::TestValidCharset>::test_valid_str(&self.data)
+                    .map_err(|e| $crate::SerializeError::from_charset_error(Self::TAG, e))?;
                 let header = $crate::Header::new(
                     $crate::Class::Universal,
                     false,
@@ -165,7 +196,168 @@ macro_rules! asn1_string {
             }
         }
     };
+    // conversions to string slices and owned values, shared with types not using this macro
+    (TRAITS $name:ident) => {
+        impl<'a> $name<'a> {
+            /// Return a copy of the string
+            pub fn string(&self) -> alloc::string::String {
+                use alloc::string::ToString;
+                self.data.to_string()
+            }
+
+            /// Return the string
+            #[inline]
+            pub fn as_str(&self) -> &str {
+                &self.data
+            }
+
+            /// Get the string, borrowed or owned
+            #[inline]
+            pub fn as_cow(&self) -> &alloc::borrow::Cow<'a, str> {
+                &self.data
+            }
+
+            /// Return the string, borrowed or owned
+            #[inline]
+            pub fn into_cow(self) -> alloc::borrow::Cow<'a, str> {
+                self.data
+            }
+
+            /// Return an object owning its data (data is copied only if borrowed)
+            pub fn into_owned(self) -> $name<'static> {
+                $name {
+                    data: alloc::borrow::Cow::Owned(self.data.into_owned()),
+                }
+            }
+        }
+
+        impl<'a> From<&'a str> for $name<'a> {
+            fn from(s: &'a str) -> Self {
+                Self::new(s)
+            }
+        }
+
+        impl From<alloc::string::String> for $name<'_> {
+            fn from(s: alloc::string::String) -> Self {
+                $name {
+                    data: alloc::borrow::Cow::Owned(s),
+                }
+            }
+        }
+
+        impl<'a> core::convert::TryFrom<$name<'a>> for &'a str {
+            type Error = $crate::Error;
+
+            /// Return the borrowed string, or `Error::LifetimeError` if data is owned (for ex.
+            /// if it was converted from another encoding when parsing)
+            fn try_from(s: $name<'a>) -> $crate::Result<&'a str> {
+                match s.data {
+                    alloc::borrow::Cow::Borrowed(s) => Ok(s),
+                    alloc::borrow::Cow::Owned(_) => Err($crate::Error::LifetimeError),
+                }
+            }
+        }
+
+        impl $crate::ToStatic for $name<'_> {
+            type Owned = $name<'static>;
+
+            fn to_static(&self) -> Self::Owned {
+                $name {
+                    data: alloc::borrow::Cow::Owned(self.data.clone().into_owned()),
+                }
+            }
+        }
+
+        impl<'a> AsRef<str> for $name<'a> {
+            fn as_ref(&self) -> &str {
+                &self.data
+            }
+        }
+
+        impl core::ops::Deref for $name<'_> {
+            type Target = str;
+
+            fn deref(&self) -> &str {
+                &self.data
+            }
+        }
+
+        impl core::borrow::Borrow<str> for $name<'_> {
+            fn borrow(&self) -> &str {
+                &self.data
+            }
+        }
+
+        impl core::fmt::Display for $name<'_> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.write_str(&self.data)
+            }
+        }
+
+        impl From<$name<'_>> for alloc::string::String {
+            fn from(s: $name<'_>) -> Self {
+                s.data.into_owned()
+            }
+        }
+    };
     ($name:ident) => {
         asn1_string!(IMPL $name, stringify!($name));
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use alloc::borrow::Cow;
+    use alloc::string::String;
+
+    #[test]
+    fn string_str_traits() {
+        use alloc::format;
+        use alloc::string::ToString;
+        use core::borrow::Borrow;
+
+        fn takes_str(s: &str) -> usize {
+            s.len()
+        }
+
+        let s = PrintableString::new("Example");
+        assert_eq!(takes_str(&s), 7);
+        assert!(s.starts_with("Ex"));
+        let b: &str = s.borrow();
+        assert_eq!(b, "Example");
+        assert_eq!(format!("{}", s), "Example");
+        assert_eq!(String::from(s), "Example");
+        let s = BmpString::new("é");
+        assert_eq!(&*s, "é");
+        assert_eq!(s.to_string(), "é");
+        let s: String = UniversalString::new("abc").into();
+        assert_eq!(s, "abc");
+        let s = GeneralStringUtf8::new("krbtgt");
+        assert_eq!(s.to_uppercase(), "KRBTGT");
+    }
+
+    #[test]
+    fn string_cow_consistency() {
+        use core::convert::TryFrom;
+
+        // borrowed data stays borrowed
+        let input = &hex_literal::hex!("16 03 61 62 63");
+        let (_, s) = Ia5String::from_ber(input).expect("parsing failed");
+        assert_eq!(s.as_str(), "abc");
+        assert!(matches!(s.as_cow(), Cow::Borrowed(_)));
+        assert_eq!(<&str>::try_from(s.clone()), Ok("abc"));
+        let owned: Ia5String<'static> = s.into_owned();
+        assert!(matches!(owned.as_cow(), Cow::Owned(_)));
+        assert_eq!(<&str>::try_from(owned), Err(Error::LifetimeError));
+        // types converted when parsing own their data
+        let input = &hex_literal::hex!("1e 04 00 61 00 62");
+        let (_, s) = BmpString::from_ber(input).expect("parsing failed");
+        assert_eq!(s.as_str(), "ab");
+        assert_eq!(s.clone().into_cow(), Cow::<str>::Owned("ab".into()));
+        assert_eq!(<&str>::try_from(s), Err(Error::LifetimeError));
+        // same surface for all types
+        assert_eq!(UniversalString::from("x").into_owned().string(), "x");
+        assert_eq!(GeneralStringUtf8::from(String::from("y")).as_str(), "y");
+    }
+}
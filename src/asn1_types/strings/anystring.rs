@@ -0,0 +1,233 @@
+use crate::*;
+use core::convert::TryFrom;
+use core::fmt;
+
+/// A `CHOICE` of string types, similar to the X.509 `DirectoryString` type
+///
+/// <pre>
+/// DirectoryString ::= CHOICE {
+///       teletexString           TeletexString (SIZE (1..MAX)),
+///       printableString         PrintableString (SIZE (1..MAX)),
+///       universalString         UniversalString (SIZE (1..MAX)),
+///       utf8String              UTF8String (SIZE (1..MAX)),
+///       bmpString               BMPString (SIZE (1..MAX)) }
+/// </pre>
+///
+/// Parsing dispatches on the tag of the object. Size constraints are not checked.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{AnyString, FromDer};
+///
+/// let (_, s) = AnyString::from_der(&[0x13, 0x03, 0x61, 0x62, 0x63]).expect("parsing failed");
+/// assert!(matches!(s, AnyString::PrintableString(_)));
+/// assert_eq!(s.as_str(), "abc");
+///
+/// let (_, s) = AnyString::from_der(&[0x0c, 0x02, 0xc3, 0xa9]).expect("parsing failed");
+/// assert_eq!(s.to_string(), "é");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AnyString<'a> {
+    /// `TeletexString` (T61String), tag 20
+    TeletexString(TeletexString<'a>),
+    /// `PrintableString`, tag 19
+    PrintableString(PrintableString<'a>),
+    /// `UniversalString` (UCS-4), tag 28
+    UniversalString(UniversalString<'a>),
+    /// `UTF8String`, tag 12
+    Utf8String(Utf8String<'a>),
+    /// `BMPString` (UCS-2), tag 30
+    BmpString(BmpString<'a>),
+}
+
+impl<'a> AnyString<'a> {
+    /// Return the string content
+    pub fn as_str(&self) -> &str {
+        match self {
+            AnyString::TeletexString(s) => s.as_ref(),
+            AnyString::PrintableString(s) => s.as_ref(),
+            AnyString::UniversalString(s) => s.as_ref(),
+            AnyString::Utf8String(s) => s.as_ref(),
+            AnyString::BmpString(s) => s.as_ref(),
+        }
+    }
+}
+
+impl AsRef<str> for AnyString<'_> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for AnyString<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<'a> TryFrom<Any<'a>> for AnyString<'a> {
+    type Error = Error;
+
+    fn try_from(any: Any<'a>) -> Result<AnyString<'a>> {
+        TryFrom::try_from(&any)
+    }
+}
+
+impl<'a, 'b> TryFrom<&'b Any<'a>> for AnyString<'a> {
+    type Error = Error;
+
+    fn try_from(any: &'b Any<'a>) -> Result<AnyString<'a>> {
+        any.header.assert_class(Class::Universal)?;
+        let s = match any.tag() {
+            Tag::TeletexString => AnyString::TeletexString(TeletexString::try_from(any)?),
+            Tag::PrintableString => AnyString::PrintableString(PrintableString::try_from(any)?),
+            Tag::UniversalString => AnyString::UniversalString(UniversalString::try_from(any)?),
+            Tag::Utf8String => AnyString::Utf8String(Utf8String::try_from(any)?),
+            Tag::BmpString => AnyString::BmpString(BmpString::try_from(any)?),
+            tag => return Err(Error::unexpected_tag(None, tag)),
+        };
+        Ok(s)
+    }
+}
+
+impl<'a> CheckDerConstraints for AnyString<'a> {
+    fn check_constraints(any: &Any) -> Result<()> {
+        match any.tag() {
+            Tag::TeletexString => TeletexString::check_constraints(any),
+            Tag::PrintableString => PrintableString::check_constraints(any),
+            Tag::UniversalString => UniversalString::check_constraints(any),
+            Tag::Utf8String => Utf8String::check_constraints(any),
+            Tag::BmpString => BmpString::check_constraints(any),
+            tag => Err(Error::unexpected_tag(None, tag)),
+        }
+    }
+}
+
+impl DerAutoDerive for AnyString<'_> {}
+
+impl Choice for AnyString<'_> {
+    fn can_decode(tag: Tag) -> bool {
+        matches!(
+            tag,
+            Tag::TeletexString
+                | Tag::PrintableString
+                | Tag::UniversalString
+                | Tag::Utf8String
+                | Tag::BmpString
+        )
+    }
+}
+
+impl<'a> BerChoice<'a> for AnyString<'a> {}
+
+impl<'a> DerChoice<'a> for AnyString<'a> {}
+
+impl ToStatic for AnyString<'_> {
+    type Owned = AnyString<'static>;
+
+    fn to_static(&self) -> Self::Owned {
+        match self {
+            AnyString::TeletexString(s) => AnyString::TeletexString(s.to_static()),
+            AnyString::PrintableString(s) => AnyString::PrintableString(s.to_static()),
+            AnyString::UniversalString(s) => AnyString::UniversalString(s.to_static()),
+            AnyString::Utf8String(s) => AnyString::Utf8String(s.to_static()),
+            AnyString::BmpString(s) => AnyString::BmpString(s.to_static()),
+        }
+    }
+}
+
+impl DynTagged for AnyString<'_> {
+    fn tag(&self) -> Tag {
+        match self {
+            AnyString::TeletexString(_) => Tag::TeletexString,
+            AnyString::PrintableString(_) => Tag::PrintableString,
+            AnyString::UniversalString(_) => Tag::UniversalString,
+            AnyString::Utf8String(_) => Tag::Utf8String,
+            AnyString::BmpString(_) => Tag::BmpString,
+        }
+    }
+}
+
+macro_rules! impl_from_string {
+    ($($name:ident),*) => {
+        $(
+            impl<'a> From<$name<'a>> for AnyString<'a> {
+                fn from(s: $name<'a>) -> Self {
+                    AnyString::$name(s)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_string!(
+    TeletexString,
+    PrintableString,
+    UniversalString,
+    Utf8String,
+    BmpString
+);
+
+#[cfg(feature = "std")]
+impl ToDer for AnyString<'_> {
+    fn to_der_len(&self) -> Result<usize> {
+        match self {
+            AnyString::TeletexString(s) => s.to_der_len(),
+            AnyString::PrintableString(s) => s.to_der_len(),
+            AnyString::UniversalString(s) => s.to_der_len(),
+            AnyString::Utf8String(s) => s.to_der_len(),
+            AnyString::BmpString(s) => s.to_der_len(),
+        }
+    }
+
+    fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        match self {
+            AnyString::TeletexString(s) => s.write_der_header(writer),
+            AnyString::PrintableString(s) => s.write_der_header(writer),
+            AnyString::UniversalString(s) => s.write_der_header(writer),
+            AnyString::Utf8String(s) => s.write_der_header(writer),
+            AnyString::BmpString(s) => s.write_der_header(writer),
+        }
+    }
+
+    fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        match self {
+            AnyString::TeletexString(s) => s.write_der_content(writer),
+            AnyString::PrintableString(s) => s.write_der_content(writer),
+            AnyString::UniversalString(s) => s.write_der_content(writer),
+            AnyString::Utf8String(s) => s.write_der_content(writer),
+            AnyString::BmpString(s) => s.write_der_content(writer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn anystring_dispatch() {
+        let (_, s) = AnyString::from_ber(&hex!("14 02 61 62")).expect("parsing failed");
+        assert!(matches!(s, AnyString::TeletexString(_)));
+        assert_eq!(s.tag(), Tag::TeletexString);
+        let (_, s) = AnyString::from_der(&hex!("1e 04 00 61 00 62")).expect("parsing failed");
+        assert!(matches!(s, AnyString::BmpString(_)));
+        assert_eq!(s.as_str(), "ab");
+        // IA5String is not a DirectoryString
+        let res = AnyString::from_der(&hex!("16 02 61 62"));
+        assert!(res.is_err());
+        assert!(AnyString::can_decode(Tag::Utf8String));
+        assert!(!AnyString::can_decode(Tag::Ia5String));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn anystring_to_der() {
+        let s = AnyString::from(Utf8String::new("ab"));
+        assert_eq!(s.to_der_vec().unwrap(), &hex!("0c 02 61 62"));
+        let s = AnyString::from(BmpString::new("ab"));
+        assert_eq!(s.to_der_vec().unwrap(), &hex!("1e 04 00 61 00 62"));
+    }
+}
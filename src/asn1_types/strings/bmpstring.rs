@@ -3,14 +3,14 @@
 
 use crate::*;
 use alloc::borrow::Cow;
-use alloc::string::{String, ToString};
+use alloc::string::String;
 use alloc::vec::Vec;
 
 /// ASN.1 `BMPSTRING` type
 ///
 /// Note: parsing a `BmpString` allocates memory since the UTF-16 to UTF-8 conversion requires a memory allocation.
 /// (see `String::from_utf16` method).
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct BmpString<'a> {
     pub(crate) data: Cow<'a, str>,
 }
@@ -22,46 +22,35 @@ impl<'a> BmpString<'a> {
         }
     }
 
-    pub fn string(&self) -> String {
-        self.data.to_string()
+    /// Build a new object, checking that all characters are in the Basic Multilingual Plane
+    pub fn try_new(s: &'a str) -> Result<Self> {
+        Self::test_valid_str(s)?;
+        Ok(Self::new(s))
     }
 }
 
-impl<'a> AsRef<str> for BmpString<'a> {
-    fn as_ref(&self) -> &str {
-        &self.data
-    }
-}
+asn1_string!(TRAITS BmpString);
 
-impl<'a> From<&'a str> for BmpString<'a> {
-    fn from(s: &'a str) -> Self {
-        Self::new(s)
-    }
-}
+impl<'a> core::convert::TryFrom<Any<'a>> for BmpString<'a> {
+    type Error = Error;
 
-impl From<String> for BmpString<'_> {
-    fn from(s: String) -> Self {
-        Self {
-            data: alloc::borrow::Cow::Owned(s),
-        }
+    fn try_from(any: Any<'a>) -> Result<BmpString<'a>> {
+        use core::convert::TryFrom;
+        TryFrom::try_from(&any)
     }
 }
 
-impl<'a> core::convert::TryFrom<Any<'a>> for BmpString<'a> {
+impl<'a, 'b> core::convert::TryFrom<&'b Any<'a>> for BmpString<'a> {
     type Error = Error;
 
-    fn try_from(any: Any<'a>) -> Result<BmpString<'a>> {
+    fn try_from(any: &'b Any<'a>) -> Result<BmpString<'a>> {
         any.tag().assert_eq(Self::TAG)?;
 
         // read slice as big-endian UTF-16 string
         let v = &any
             .data
             .chunks(2)
-            .map(|s| match s {
-                [a, b] => ((*a as u16) << 8) | (*b as u16),
-                [a] => *a as u16,
-                _ => unreachable!(),
-            })
+            .map(|s| s.iter().fold(0, |acc, b| (acc << 8) | (*b as u16)))
             .collect::<Vec<_>>();
 
         let s = String::from_utf16(v)?;
@@ -73,7 +62,7 @@ impl<'a> core::convert::TryFrom<Any<'a>> for BmpString<'a> {
 
 impl<'a> CheckDerConstraints for BmpString<'a> {
     fn check_constraints(any: &Any) -> Result<()> {
-        any.header.assert_primitive()?;
+        any.header.assert_der_primitive()?;
         Ok(())
     }
 }
@@ -89,7 +78,9 @@ impl<'a> TestValidCharset for BmpString<'a> {
         if i.len() % 2 != 0 {
             return Err(Error::StringInvalidCharset);
         }
-        let iter = i.chunks(2).map(|s| ((s[0] as u16) << 8) | (s[1] as u16));
+        let iter = i
+            .chunks_exact(2)
+            .map(|s| s.iter().fold(0, |acc, b| (acc << 8) | (*b as u16)));
         for c in char::decode_utf16(iter) {
             if c.is_err() {
                 return Err(Error::StringInvalidCharset);
@@ -97,6 +88,20 @@ impl<'a> TestValidCharset for BmpString<'a> {
         }
         Ok(())
     }
+
+    fn test_valid_str(s: &str) -> Result<()> {
+        let mut invalid = Vec::new();
+        for c in s.chars() {
+            if c as u32 > 0xffff && !invalid.contains(&c) {
+                invalid.push(c);
+            }
+        }
+        if invalid.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::StringInvalidCharacters(invalid))
+        }
+    }
 }
 
 #[cfg(feature = "std")]
@@ -115,6 +120,8 @@ impl ToDer for BmpString<'_> {
     }
 
     fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        Self::test_valid_str(&self.data)
+            .map_err(|e| SerializeError::from_charset_error(Self::TAG, e))?;
         // compute the UTF-16 length
         let l = self.data.encode_utf16().count() * 2;
         let header = Header::new(Class::Universal, false, Self::TAG, Length::Definite(l));
@@ -0,0 +1,137 @@
+use crate::*;
+use alloc::borrow::Cow;
+use alloc::string::String;
+
+/// Build string object `T` from `data`, keeping borrowed data borrowed
+fn from_cow<'a, T>(data: Cow<'a, str>) -> T
+where
+    T: From<&'a str> + From<String>,
+{
+    match data {
+        Cow::Borrowed(s) => T::from(s),
+        Cow::Owned(s) => T::from(s),
+    }
+}
+
+/// Build string object `T` from `data`, checking that all characters are valid for `T`
+fn try_from_cow<'a, T>(data: Cow<'a, str>) -> Result<T>
+where
+    T: From<&'a str> + From<String> + TestValidCharset,
+{
+    T::test_valid_str(&data)?;
+    Ok(from_cow(data))
+}
+
+macro_rules! impl_try_into {
+    ($method:ident => $target:ident for $($name:ident),* $(,)?) => {
+        $(
+            impl_try_into!(IMPL $method, $target, stringify!($target), $name);
+        )*
+    };
+    (IMPL $method:ident, $target:ident, $sname:expr, $name:ident) => {
+        impl<'a> $name<'a> {
+            #[doc = "Convert to `"]
+            #[doc = $sname]
+            #[doc = "`, checking that all characters are valid"]
+            ///
+            /// Data is not copied. Returns
+            /// [`Error::StringInvalidCharacters`](crate::Error::StringInvalidCharacters)
+            /// with the list of invalid characters, if any.
+            pub fn $method(self) -> Result<$target<'a>> {
+                try_from_cow(self.data)
+            }
+        }
+    };
+}
+
+macro_rules! impl_into_utf8 {
+    ($($name:ident),* $(,)?) => {
+        $(
+            impl<'a> $name<'a> {
+                /// Convert to `Utf8String` (all characters are valid)
+                ///
+                /// Data is not copied.
+                pub fn into_utf8(self) -> Utf8String<'a> {
+                    from_cow(self.data)
+                }
+            }
+        )*
+    };
+}
+
+impl_try_into!(try_into_printable => PrintableString for
+    BmpString, GeneralString, GraphicString, Ia5String, NumericString, TeletexString,
+    UniversalString, Utf8String, VideotexString, VisibleString,
+);
+
+impl_try_into!(try_into_ia5 => Ia5String for
+    BmpString, GeneralString, GraphicString, NumericString, PrintableString, TeletexString,
+    UniversalString, Utf8String, VideotexString, VisibleString,
+);
+
+impl_try_into!(try_into_visible => VisibleString for
+    BmpString, GeneralString, GraphicString, Ia5String, NumericString, PrintableString,
+    TeletexString, UniversalString, Utf8String, VideotexString,
+);
+
+impl_try_into!(try_into_numeric => NumericString for
+    BmpString, GeneralString, GraphicString, Ia5String, PrintableString, TeletexString,
+    UniversalString, Utf8String, VideotexString, VisibleString,
+);
+
+impl_try_into!(try_into_bmp => BmpString for
+    GeneralString, GraphicString, Ia5String, NumericString, PrintableString, TeletexString,
+    UniversalString, Utf8String, VideotexString, VisibleString,
+);
+
+impl_into_utf8!(
+    BmpString,
+    GeneralString,
+    GraphicString,
+    Ia5String,
+    NumericString,
+    PrintableString,
+    TeletexString,
+    UniversalString,
+    VideotexString,
+    VisibleString,
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use alloc::borrow::Cow;
+    use alloc::string::String;
+    use alloc::vec;
+
+    #[test]
+    fn string_conversions() {
+        let s = Ia5String::new("Example Corp");
+        let p = s.try_into_printable().expect("conversion failed");
+        assert_eq!(p.as_ref(), "Example Corp");
+        // data is still borrowed
+        assert!(matches!(p.data, Cow::Borrowed(_)));
+        let u = p.into_utf8();
+        let n = u.try_into_numeric();
+        assert_eq!(
+            n,
+            Err(Error::StringInvalidCharacters(vec![
+                'E', 'x', 'a', 'm', 'p', 'l', 'e', 'C', 'o', 'r'
+            ]))
+        );
+        // owned data is moved
+        let u = Utf8String::from(String::from("a@b"));
+        assert_eq!(
+            u.try_into_printable(),
+            Err(Error::StringInvalidCharacters(vec!['@']))
+        );
+        let u = Utf8String::from(String::from("a@b"));
+        let i = u.try_into_ia5().expect("conversion failed");
+        assert!(matches!(i.data, Cow::Owned(_)));
+        // BMP
+        let u = Utf8String::new("\u{1F600}");
+        assert!(u.try_into_bmp().is_err());
+        let u = Utf8String::new("é");
+        assert_eq!(u.try_into_bmp().unwrap().as_ref(), "é");
+    }
+}
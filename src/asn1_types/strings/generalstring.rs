@@ -1,6 +1,7 @@
-use crate::{asn1_string, TestValidCharset};
-use crate::{Error, Result};
-use alloc::string::String;
+use crate::asn1_string;
+use crate::*;
+use alloc::borrow::Cow;
+use core::convert::TryFrom;
 
 asn1_string!(GeneralString);
 
@@ -12,3 +13,126 @@ impl<'a> TestValidCharset for GeneralString<'a> {
         Ok(())
     }
 }
+
+/// Non-fatal issues found when parsing a [`GeneralStringUtf8`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GeneralStringWarning {
+    /// Content contains non-ASCII (UTF-8) characters
+    NonAsciiContent,
+}
+
+/// ASN.1 `GeneralString` type, accepting UTF-8 content
+///
+/// Some implementations (for ex. MIT Kerberos) encode UTF-8 data in `GeneralString` objects.
+/// This is not allowed by X.680, so [`GeneralString`] rejects such content.
+///
+/// This type has the same tag as [`GeneralString`], but accepts any valid UTF-8 content.
+/// Non-ASCII content is not an error, but is reported by the [`GeneralStringUtf8::warnings`]
+/// method.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{FromDer, GeneralString, GeneralStringUtf8, GeneralStringWarning};
+///
+/// let bytes = &[0x1b, 0x02, 0xc3, 0xa9];
+///
+/// assert!(GeneralString::from_der(bytes).is_err());
+///
+/// let (_, s) = GeneralStringUtf8::from_der(bytes).expect("parsing failed");
+/// assert_eq!(s.as_ref(), "é");
+/// assert_eq!(s.warnings(), &[GeneralStringWarning::NonAsciiContent]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GeneralStringUtf8<'a> {
+    pub(crate) data: Cow<'a, str>,
+}
+
+impl<'a> GeneralStringUtf8<'a> {
+    pub const fn new(s: &'a str) -> Self {
+        GeneralStringUtf8 {
+            data: Cow::Borrowed(s),
+        }
+    }
+
+    /// Return the list of non-fatal issues found in content
+    pub fn warnings(&self) -> &'static [GeneralStringWarning] {
+        if self.data.is_ascii() {
+            &[]
+        } else {
+            &[GeneralStringWarning::NonAsciiContent]
+        }
+    }
+
+    /// Attempt to convert to a strict [`GeneralString`]
+    ///
+    /// Returns a `StringInvalidCharset` error if content is not ASCII.
+    pub fn to_strict(&self) -> Result<GeneralString<'_>> {
+        if !self.data.is_ascii() {
+            return Err(Error::StringInvalidCharset);
+        }
+        Ok(GeneralString::new(&self.data))
+    }
+}
+
+asn1_string!(TRAITS GeneralStringUtf8);
+
+impl<'a> From<GeneralString<'a>> for GeneralStringUtf8<'a> {
+    fn from(s: GeneralString<'a>) -> Self {
+        GeneralStringUtf8 { data: s.data }
+    }
+}
+
+impl<'a> TryFrom<Any<'a>> for GeneralStringUtf8<'a> {
+    type Error = Error;
+
+    fn try_from(any: Any<'a>) -> Result<GeneralStringUtf8<'a>> {
+        TryFrom::try_from(&any)
+    }
+}
+
+impl<'a, 'b> TryFrom<&'b Any<'a>> for GeneralStringUtf8<'a> {
+    type Error = Error;
+
+    fn try_from(any: &'b Any<'a>) -> Result<GeneralStringUtf8<'a>> {
+        any.tag().assert_eq(Self::TAG)?;
+        let s = alloc::str::from_utf8(any.data)?;
+        let data = Cow::Borrowed(s);
+        Ok(GeneralStringUtf8 { data })
+    }
+}
+
+impl<'a> TestValidCharset for GeneralStringUtf8<'a> {
+    fn test_valid_charset(i: &[u8]) -> Result<()> {
+        let _ = core::str::from_utf8(i)?;
+        Ok(())
+    }
+}
+
+impl<'a> CheckDerConstraints for GeneralStringUtf8<'a> {
+    fn check_constraints(any: &Any) -> Result<()> {
+        any.header.assert_der_primitive()?;
+        Ok(())
+    }
+}
+
+impl DerAutoDerive for GeneralStringUtf8<'_> {}
+
+impl<'a> Tagged for GeneralStringUtf8<'a> {
+    const TAG: Tag = Tag::GeneralString;
+}
+
+#[cfg(feature = "std")]
+impl ToDer for GeneralStringUtf8<'_> {
+    fn to_der_len(&self) -> Result<usize> {
+        primitive_der_len(Self::TAG, self.data.len())
+    }
+
+    fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        write_header(Self::TAG, self.data.len(), writer)
+    }
+
+    fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        writer.write(self.data.as_bytes()).map_err(Into::into)
+    }
+}
@@ -1,14 +1,40 @@
 use crate::{asn1_string, TestValidCharset};
 use crate::{Error, Result};
-use alloc::string::String;
+
+// X.680 section 41.4
+// GraphicString: graphic characters of the registered G sets, and SPACE.
+// The content is checked against the default G0 set (ASCII): control characters are rejected,
+// except ESC which introduces ISO 2022 escape sequences (designating other G sets).
 
 asn1_string!(GraphicString);
 
 impl<'a> TestValidCharset for GraphicString<'a> {
     fn test_valid_charset(i: &[u8]) -> Result<()> {
-        if !i.iter().all(u8::is_ascii) {
+        if !i.iter().all(|b| matches!(b, 0x20..=0x7e | 0x1b)) {
             return Err(Error::StringInvalidCharset);
         }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn graphicstring_charset() {
+        let (_, s) = GraphicString::from_der(&hex!("19 03 61 20 7e")).expect("parsing failed");
+        assert_eq!(s.as_ref(), "a ~");
+        // control characters and DEL are not graphic characters
+        assert!(GraphicString::from_der(&hex!("19 02 61 0a")).is_err());
+        assert!(GraphicString::from_der(&hex!("19 02 61 7f")).is_err());
+        // ISO 2022 escape sequence (ESC ( B: designate ASCII as G0)
+        let (_, s) = GraphicString::from_der(&hex!("19 04 1b 28 42 61")).expect("parsing failed");
+        assert_eq!(s.as_ref(), "\u{1b}(Ba");
+        assert_eq!(
+            GraphicString::try_new("a\tb"),
+            Err(Error::StringInvalidCharacters(vec!['\t']))
+        );
+    }
+}
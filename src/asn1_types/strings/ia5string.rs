@@ -1,6 +1,5 @@
 use crate::{asn1_string, TestValidCharset};
 use crate::{Error, Result};
-use alloc::string::String;
 
 asn1_string!(Ia5String);
 
@@ -0,0 +1,196 @@
+use crate::{PrintableString, Utf8String};
+use alloc::string::String;
+
+/// Prepare a string for the `caseIgnoreMatch` matching rule (RFC 4518)
+///
+/// The following steps of the string preparation algorithm are applied:
+/// - map characters which are commonly mapped to nothing (control characters and code points
+///   with a control function, soft hyphens, zero-width space, variation selectors) to nothing
+///   (RFC 4518 section 2.2)
+/// - map all whitespace and separator characters to SPACE (U+0020)
+/// - fold case, using the Unicode lowercase mapping
+/// - remove leading and trailing spaces, and replace internal sequences of spaces by a single
+///   space (RFC 4518 section 2.6.1)
+///
+/// Normalization (NFKC) and the check for prohibited characters are not applied: strings using
+/// composed and decomposed forms of the same characters will not match.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::prepare_case_ignore;
+///
+/// assert_eq!(prepare_case_ignore("  Example \t  Corp. "), "example corp.");
+/// ```
+pub fn prepare_case_ignore(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut pending_space = false;
+    for c in s.chars() {
+        if is_mapped_to_nothing(c) {
+            continue;
+        }
+        if c == ' ' || is_mapped_to_space(c) {
+            pending_space = true;
+            continue;
+        }
+        if pending_space && !out.is_empty() {
+            out.push(' ');
+        }
+        pending_space = false;
+        out.extend(c.to_lowercase());
+    }
+    out
+}
+
+/// Compare two strings using the `caseIgnoreMatch` matching rule (RFC 4518)
+///
+/// See [`prepare_case_ignore`] for the string preparation steps.
+///
+/// ```rust
+/// use asn1_rs::case_ignore_match;
+///
+/// assert!(case_ignore_match("Example  Corp", " example corp "));
+/// assert!(!case_ignore_match("Example Corp", "ExampleCorp"));
+/// ```
+pub fn case_ignore_match(a: &str, b: &str) -> bool {
+    prepare_case_ignore(a) == prepare_case_ignore(b)
+}
+
+/// RFC 4518 section 2.2: characters mapped to nothing
+fn is_mapped_to_nothing(c: char) -> bool {
+    matches!(c,
+        // soft hyphens, combining grapheme joiner, variation selectors, object replacement
+        '\u{00AD}'
+        | '\u{1806}'
+        | '\u{034F}'
+        | '\u{180B}'..='\u{180D}'
+        | '\u{FE00}'..='\u{FE0F}'
+        | '\u{FFFC}'
+        // other control code points (Cc) and code points with a control function (Cf)
+        | '\u{0000}'..='\u{0008}'
+        | '\u{000E}'..='\u{001F}'
+        | '\u{007F}'..='\u{0084}'
+        | '\u{0086}'..='\u{009F}'
+        | '\u{06DD}'
+        | '\u{070F}'
+        | '\u{180E}'
+        | '\u{200C}'..='\u{200F}'
+        | '\u{202A}'..='\u{202E}'
+        | '\u{2060}'..='\u{2063}'
+        | '\u{206A}'..='\u{206F}'
+        | '\u{FEFF}'
+        | '\u{FFF9}'..='\u{FFFB}'
+        | '\u{1D173}'..='\u{1D17A}'
+        | '\u{E0001}'
+        | '\u{E0020}'..='\u{E007F}'
+        // zero width space
+        | '\u{200B}'
+    )
+}
+
+/// RFC 4518 section 2.2: characters mapped to SPACE
+fn is_mapped_to_space(c: char) -> bool {
+    matches!(c,
+        '\u{0009}'..='\u{000D}'
+        | '\u{0085}'
+        | '\u{00A0}'
+        | '\u{1680}'
+        | '\u{2000}'..='\u{200A}'
+        | '\u{2028}'
+        | '\u{2029}'
+        | '\u{202F}'
+        | '\u{205F}'
+        | '\u{3000}'
+    )
+}
+
+macro_rules! impl_case_ignore {
+    ($($name:ident),*) => {
+        $(
+            impl<'a> $name<'a> {
+                /// Return the string prepared for the `caseIgnoreMatch` matching rule
+                ///
+                /// See [`prepare_case_ignore`](crate::prepare_case_ignore).
+                pub fn to_case_ignore(&self) -> String {
+                    prepare_case_ignore(&self.data)
+                }
+
+                /// Compare with another string, using the `caseIgnoreMatch` matching rule
+                ///
+                /// See [`prepare_case_ignore`](crate::prepare_case_ignore).
+                pub fn case_ignore_eq<S: AsRef<str>>(&self, other: S) -> bool {
+                    case_ignore_match(&self.data, other.as_ref())
+                }
+            }
+        )*
+    };
+}
+
+impl_case_ignore!(PrintableString, Utf8String);
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn prepare_case_ignore_spaces() {
+        assert_eq!(prepare_case_ignore(""), "");
+        assert_eq!(prepare_case_ignore("   "), "");
+        assert_eq!(prepare_case_ignore("A"), "a");
+        assert_eq!(prepare_case_ignore("\u{00A0}a\u{3000}\u{2003}B\n"), "a b");
+        // soft hyphen and zero-width space are removed, not mapped to space
+        assert_eq!(prepare_case_ignore("Ex\u{00AD}am\u{200B}ple"), "example");
+        assert_eq!(prepare_case_ignore("ÉCOLE"), "école");
+    }
+
+    #[test]
+    fn prepare_case_ignore_mapped_to_nothing() {
+        // RFC 4518 section 2.2
+        let ranges = [
+            (0x00AD, 0x00AD),
+            (0x1806, 0x1806),
+            (0x034F, 0x034F),
+            (0x180B, 0x180D),
+            (0xFE00, 0xFE0F),
+            (0xFFFC, 0xFFFC),
+            (0x0000, 0x0008),
+            (0x000E, 0x001F),
+            (0x007F, 0x0084),
+            (0x0086, 0x009F),
+            (0x06DD, 0x06DD),
+            (0x070F, 0x070F),
+            (0x180E, 0x180E),
+            (0x200C, 0x200F),
+            (0x202A, 0x202E),
+            (0x2060, 0x2063),
+            (0x206A, 0x206F),
+            (0xFEFF, 0xFEFF),
+            (0xFFF9, 0xFFFB),
+            (0x1D173, 0x1D17A),
+            (0xE0001, 0xE0001),
+            (0xE0020, 0xE007F),
+            (0x200B, 0x200B),
+        ];
+        for &(start, end) in &ranges {
+            for c in (start..=end).filter_map(core::char::from_u32) {
+                let s = format!("a{}B", c);
+                assert_eq!(prepare_case_ignore(&s), "ab", "U+{:04X}", c as u32);
+            }
+        }
+        // neighbours are not mapped to nothing
+        assert_eq!(prepare_case_ignore("a\u{0009}b\u{0085}c"), "a b c");
+        assert_eq!(prepare_case_ignore("a\u{200A}b"), "a b");
+        assert_eq!(prepare_case_ignore("a\u{1D172}b"), "a\u{1D172}b");
+        assert_eq!(prepare_case_ignore("a\u{E0002}b"), "a\u{E0002}b");
+    }
+
+    #[test]
+    fn case_ignore_string_types() {
+        let p = PrintableString::new("Example Corp");
+        let u = Utf8String::new("  EXAMPLE   corp");
+        assert!(p.case_ignore_eq(&u));
+        assert!(u.case_ignore_eq("example corp"));
+        assert!(!p.case_ignore_eq("Example Corporation"));
+        assert_eq!(u.to_case_ignore(), "example corp");
+    }
+}
@@ -23,7 +23,7 @@ impl<'a, 'b> TryFrom<&'b Any<'a>> for String {
 impl CheckDerConstraints for String {
     fn check_constraints(any: &Any) -> Result<()> {
         // X.690 section 10.2
-        any.header.assert_primitive()?;
+        any.header.assert_der_primitive()?;
         Ok(())
     }
 }
@@ -3,15 +3,13 @@
 
 use crate::*;
 use alloc::borrow::Cow;
-use alloc::string::{String, ToString};
-use alloc::vec::Vec;
+use alloc::string::String;
 use core::convert::TryFrom;
-use core::iter::FromIterator;
 
 /// ASN.1 `UniversalString` type
 ///
 /// Note: parsing a `UniversalString` allocates memory since the UCS-4 to UTF-8 conversion requires a memory allocation.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct UniversalString<'a> {
     pub(crate) data: Cow<'a, str>,
 }
@@ -23,30 +21,25 @@ impl<'a> UniversalString<'a> {
         }
     }
 
-    pub fn string(&self) -> String {
-        self.data.to_string()
+    /// Build a new object from its UCS-4 (big-endian) encoding, checking the charset
+    ///
+    /// Return [`Error::StringInvalidCharset`] if the length of `ucs4` is not a multiple of 4, or
+    /// if it contains invalid code points (surrogates, or values above `U+10FFFF`).
+    pub fn try_new(ucs4: &[u8]) -> Result<Self> {
+        let s = decode_ucs4(ucs4)?;
+        Ok(UniversalString {
+            data: Cow::Owned(s),
+        })
     }
-}
-
-impl<'a> AsRef<str> for UniversalString<'a> {
-    fn as_ref(&self) -> &str {
-        &self.data
-    }
-}
 
-impl<'a> From<&'a str> for UniversalString<'a> {
-    fn from(s: &'a str) -> Self {
-        Self::new(s)
+    /// Length of the UCS-4 encoding of the string (4 bytes per character)
+    #[cfg(feature = "std")]
+    fn ucs4_len(&self) -> usize {
+        self.data.chars().count() * 4
     }
 }
 
-impl From<String> for UniversalString<'_> {
-    fn from(s: String) -> Self {
-        Self {
-            data: alloc::borrow::Cow::Owned(s),
-        }
-    }
-}
+asn1_string!(TRAITS UniversalString);
 
 impl<'a> TryFrom<Any<'a>> for UniversalString<'a> {
     type Error = Error;
@@ -62,37 +55,30 @@ impl<'a, 'b> TryFrom<&'b Any<'a>> for UniversalString<'a> {
     fn try_from(any: &'b Any<'a>) -> Result<UniversalString<'a>> {
         any.tag().assert_eq(Self::TAG)?;
 
-        if any.data.len() % 4 != 0 {
-            return Err(Error::StringInvalidCharset);
-        }
-
-        // read slice as big-endian UCS-4 string
-        let v = &any
-            .data
-            .chunks(4)
-            .map(|s| match s {
-                [a, b, c, d] => {
-                    let u32_val = ((*a as u32) << 24)
-                        | ((*b as u32) << 16)
-                        | ((*c as u32) << 8)
-                        | (*d as u32);
-                    char::from_u32(u32_val)
-                }
-                _ => unreachable!(),
-            })
-            .collect::<Option<Vec<_>>>()
-            .ok_or(Error::StringInvalidCharset)?;
-
-        let s = String::from_iter(v);
+        let s = decode_ucs4(any.data)?;
         let data = Cow::Owned(s);
 
         Ok(UniversalString { data })
     }
 }
 
+/// Decode a big-endian UCS-4 string
+fn decode_ucs4(data: &[u8]) -> Result<String> {
+    if data.len() % 4 != 0 {
+        return Err(Error::StringInvalidCharset);
+    }
+    data.chunks_exact(4)
+        .map(|s| {
+            let u32_val = u32::from_be_bytes([s[0], s[1], s[2], s[3]]);
+            char::from_u32(u32_val)
+        })
+        .collect::<Option<String>>()
+        .ok_or(Error::StringInvalidCharset)
+}
+
 impl<'a> CheckDerConstraints for UniversalString<'a> {
     fn check_constraints(any: &Any) -> Result<()> {
-        any.header.assert_primitive()?;
+        any.header.assert_der_primitive()?;
         Ok(())
     }
 }
@@ -106,8 +92,7 @@ impl<'a> Tagged for UniversalString<'a> {
 #[cfg(feature = "std")]
 impl ToDer for UniversalString<'_> {
     fn to_der_len(&self) -> Result<usize> {
-        // UCS-4: 4 bytes per character
-        let sz = self.data.as_bytes().len() * 4;
+        let sz = self.ucs4_len();
         if sz < 127 {
             // 1 (class+tag) + 1 (length) + len
             Ok(2 + sz)
@@ -123,7 +108,7 @@ impl ToDer for UniversalString<'_> {
             Class::Universal,
             false,
             Self::TAG,
-            Length::Definite(self.data.as_bytes().len() * 4),
+            Length::Definite(self.ucs4_len()),
         );
         header.write_der_header(writer).map_err(Into::into)
     }
@@ -132,6 +117,6 @@ impl ToDer for UniversalString<'_> {
         self.data
             .chars()
             .try_for_each(|c| writer.write(&(c as u32).to_be_bytes()[..]).map(|_| ()))?;
-        Ok(self.data.as_bytes().len() * 4)
+        Ok(self.ucs4_len())
     }
 }
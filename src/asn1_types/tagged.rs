@@ -1,6 +1,7 @@
-use crate::{Class, Error, Tag, Tagged};
+use crate::{Class, Error, Tag, Tagged, ToStatic};
 use core::marker::PhantomData;
 
+mod alias;
 mod application;
 mod builder;
 mod explicit;
@@ -9,6 +10,7 @@ mod implicit;
 mod optional;
 mod parser;
 mod private;
+mod wrapped;
 
 pub use application::*;
 pub use builder::*;
@@ -18,18 +20,20 @@ pub use implicit::*;
 pub use optional::*;
 pub use parser::*;
 pub use private::*;
+pub use wrapped::*;
 
 pub(crate) const CONTEXT_SPECIFIC: u8 = Class::ContextSpecific as u8;
 
 /// A type parameter for `IMPLICIT` tagged values.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Implicit {}
 
 /// A type parameter for `EXPLICIT` tagged values.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Explicit {}
 
 /// A type parameter for tagged values either [`Explicit`] or [`Implicit`].
+#[doc(hidden)]
 pub trait TagKind {}
 
 impl TagKind for Implicit {}
@@ -62,7 +66,7 @@ impl TagKind for Explicit {}
 ///         .unwrap();
 /// assert_eq!(tagged, TaggedValue::explicit(Integer::from(2)));
 /// ```
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct TaggedValue<T, E, TagKind, const CLASS: u8, const TAG: u32> {
     pub(crate) inner: T,
 
@@ -89,6 +93,24 @@ impl<T, E, TagKind, const CLASS: u8, const TAG: u32> TaggedValue<T, E, TagKind,
     }
 }
 
+impl<T, E, TagKind, const CLASS: u8, const TAG: u32> ToStatic
+    for TaggedValue<T, E, TagKind, CLASS, TAG>
+where
+    T: ToStatic,
+    E: 'static,
+    TagKind: 'static,
+{
+    type Owned = TaggedValue<T::Owned, E, TagKind, CLASS, TAG>;
+
+    fn to_static(&self) -> Self::Owned {
+        TaggedValue {
+            inner: self.inner.to_static(),
+            tag_kind: PhantomData,
+            _e: PhantomData,
+        }
+    }
+}
+
 impl<T, E, const CLASS: u8, const TAG: u32> TaggedValue<T, E, Explicit, CLASS, TAG> {
     /// Constructs a new `EXPLICIT TaggedParser` with the provided value
     #[inline]
@@ -0,0 +1,225 @@
+/// Define a new type for a tagged type, using the ASN.1 type assignment syntax
+///
+/// The syntax is `Name = [CLASS tag] KIND Type`, where:
+/// - `CLASS` is `APPLICATION` or `PRIVATE` (if absent, the class is `CONTEXT-SPECIFIC`)
+/// - `KIND` is `IMPLICIT` or `EXPLICIT`
+/// - `Type` is the inner type
+///
+/// If the inner type has a lifetime, it must be declared on the name (see example below).
+/// Visibility and attributes (for ex. doc comments) can be given before the name.
+///
+/// This generates a tuple struct wrapping the inner value, and implements the traits
+/// `Tagged`, `TryFrom<Any>` (and thus `FromBer`), `CheckDerConstraints` and `DerAutoDerive`
+/// (and thus `FromDer`), and `ToDer` (if the `std` feature is enabled), using the
+/// implementations of [`TaggedValue`](crate::TaggedValue).
+/// The class of the type is available as the associated constant `CLASS`.
+///
+/// The inner type must implement `Tagged`, `TryFrom<Any>`, `CheckDerConstraints` and `ToDer`,
+/// and must be a universal type for `IMPLICIT` tagging.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{asn1_alias, FromDer, OctetString, ToDer};
+///
+/// asn1_alias!(
+///     /// File identifier (ISO 7816-4)
+///     pub FileId<'a> = [APPLICATION 2] IMPLICIT OctetString<'a>
+/// );
+/// asn1_alias!(Counter = [1] EXPLICIT u32);
+///
+/// let bytes = &[0x42, 0x02, 0x3f, 0x00];
+/// let (_, file_id) = FileId::from_der(bytes).expect("parsing failed");
+/// assert_eq!(file_id.0.as_ref(), &[0x3f, 0x00]);
+///
+/// let counter = Counter(3);
+/// assert_eq!(counter.to_der_vec().unwrap(), &[0xa1, 0x03, 0x02, 0x01, 0x03]);
+/// ```
+#[macro_export]
+macro_rules! asn1_alias {
+    ($(#[$m:meta])* $vis:vis $name:ident<$lt:lifetime> = [$($tag:tt)+] $kind:ident $inner:ty) => {
+        $(#[$m])*
+        #[derive(Debug, PartialEq)]
+        $vis struct $name<$lt>(pub $inner);
+
+        $crate::asn1_alias!(@class [$($tag)+] ($name) ($name<$lt>) ($lt) ($lt) $kind $inner);
+    };
+    ($(#[$m:meta])* $vis:vis $name:ident = [$($tag:tt)+] $kind:ident $inner:ty) => {
+        $(#[$m])*
+        #[derive(Debug, PartialEq)]
+        $vis struct $name(pub $inner);
+
+        $crate::asn1_alias!(@class [$($tag)+] ($name) ($name) ('a) () $kind $inner);
+    };
+    (@class [APPLICATION $tag:literal] $($rest:tt)*) => {
+        $crate::asn1_alias!(@impl Application $tag $($rest)*);
+    };
+    (@class [PRIVATE $tag:literal] $($rest:tt)*) => {
+        $crate::asn1_alias!(@impl Private $tag $($rest)*);
+    };
+    (@class [$tag:literal] $($rest:tt)*) => {
+        $crate::asn1_alias!(@impl ContextSpecific $tag $($rest)*);
+    };
+    (@impl $class:ident $tag:literal ($name:ident) ($($ty:tt)+) ($lt:lifetime) ($($gen:tt)*)
+        IMPLICIT $inner:ty) => {
+        $crate::asn1_alias!(@common $class $tag (Implicit implicit) ($name) ($($ty)+) ($lt) ($($gen)*) $inner);
+    };
+    (@impl $class:ident $tag:literal ($name:ident) ($($ty:tt)+) ($lt:lifetime) ($($gen:tt)*)
+        EXPLICIT $inner:ty) => {
+        $crate::asn1_alias!(@common $class $tag (Explicit explicit) ($name) ($($ty)+) ($lt) ($($gen)*) $inner);
+    };
+    (@common $class:ident $tag:literal ($kind:ident $ctor:ident) ($name:ident) ($($ty:tt)+)
+        ($lt:lifetime) ($($gen:tt)*) $inner:ty) => {
+        impl<$($gen)*> $($ty)+ {
+            /// Class of this tagged type
+            pub const CLASS: $crate::Class = $crate::Class::$class;
+        }
+
+        impl<$($gen)*> $crate::Tagged for $($ty)+ {
+            const TAG: $crate::Tag = $crate::Tag($tag);
+        }
+
+        impl<$lt, '__r> core::convert::TryFrom<&'__r $crate::Any<$lt>> for $($ty)+ {
+            type Error = $crate::Error;
+
+            fn try_from(any: &'__r $crate::Any<$lt>) -> $crate::Result<Self> {
+                let tagged = <$crate::TaggedValue<
+                    $inner,
+                    $crate::Error,
+                    $crate::$kind,
+                    { $crate::Class::$class as u8 },
+                    $tag,
+                > as core::convert::TryFrom<&'__r $crate::Any<$lt>>>::try_from(any)?;
+                Ok($name(tagged.into_inner()))
+            }
+        }
+
+        impl<$lt> core::convert::TryFrom<$crate::Any<$lt>> for $($ty)+ {
+            type Error = $crate::Error;
+
+            fn try_from(any: $crate::Any<$lt>) -> $crate::Result<Self> {
+                core::convert::TryFrom::try_from(&any)
+            }
+        }
+
+        impl<$($gen)*> $crate::CheckDerConstraints for $($ty)+ {
+            fn check_constraints(any: &$crate::Any) -> $crate::Result<()> {
+                any.header.assert_class(Self::CLASS)?;
+                <$crate::TaggedValue<
+                    $inner,
+                    $crate::Error,
+                    $crate::$kind,
+                    { $crate::Class::$class as u8 },
+                    $tag,
+                > as $crate::CheckDerConstraints>::check_constraints(any)
+            }
+        }
+
+        impl<$($gen)*> $crate::DerAutoDerive for $($ty)+ {}
+
+        $crate::__asn1_alias_to_der!($kind $ctor $class $tag ($($ty)+) ($($gen)*));
+    };
+}
+
+#[doc(hidden)]
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! __asn1_alias_to_der {
+    ($kind:ident $ctor:ident $class:ident $tag:literal ($($ty:tt)+) ($($gen:tt)*)) => {
+        impl<$($gen)*> $crate::ToDer for $($ty)+ {
+            fn to_der_len(&self) -> $crate::Result<usize> {
+                let tagged = $crate::TaggedValue::<_, $crate::Error, $crate::$kind, { $crate::Class::$class as u8 }, $tag>::$ctor(&self.0);
+                $crate::ToDer::to_der_len(&tagged)
+            }
+
+            fn write_der_header(
+                &self,
+                writer: &mut dyn std::io::Write,
+            ) -> $crate::SerializeResult<usize> {
+                let tagged = $crate::TaggedValue::<_, $crate::Error, $crate::$kind, { $crate::Class::$class as u8 }, $tag>::$ctor(&self.0);
+                $crate::ToDer::write_der_header(&tagged, writer)
+            }
+
+            fn write_der_content(
+                &self,
+                writer: &mut dyn std::io::Write,
+            ) -> $crate::SerializeResult<usize> {
+                let tagged = $crate::TaggedValue::<_, $crate::Error, $crate::$kind, { $crate::Class::$class as u8 }, $tag>::$ctor(&self.0);
+                $crate::ToDer::write_der_content(&tagged, writer)
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+#[cfg(not(feature = "std"))]
+#[macro_export]
+macro_rules! __asn1_alias_to_der {
+    ($($t:tt)*) => {};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use hex_literal::hex;
+
+    asn1_alias!(Version = [PRIVATE 5] IMPLICIT u32);
+    asn1_alias!(Label<'a> = [APPLICATION 40] IMPLICIT Utf8String<'a>);
+    asn1_alias!(Wrapped = [3] EXPLICIT bool);
+    asn1_alias!(Retagged = [4] IMPLICIT Wrapped);
+
+    #[test]
+    fn alias_implicit() {
+        let (_, v) = Version::from_der(&hex!("c5 01 02")).expect("parsing failed");
+        assert_eq!(v, Version(2));
+        assert_eq!(Version::CLASS, Class::Private);
+        // wrong class
+        assert!(Version::from_ber(&hex!("85 01 02")).is_err());
+        let (_, l) = Label::from_ber(&hex!("5f 28 02 61 62")).expect("parsing failed");
+        assert_eq!(l.0.as_ref(), "ab");
+    }
+
+    #[test]
+    fn alias_explicit() {
+        let (_, w) = Wrapped::from_der(&hex!("a3 03 01 01 ff")).expect("parsing failed");
+        assert_eq!(w, Wrapped(true));
+        // trailing data
+        assert_eq!(
+            Wrapped::from_ber(&hex!("a3 05 01 01 ff 05 00")),
+            Err(Err::Error(Error::TrailingContent {
+                tag: Tag(3),
+                remaining: 2
+            }))
+        );
+        assert!(Wrapped::from_der(&hex!("a3 05 01 01 ff 05 00")).is_err());
+        // not constructed
+        assert!(Wrapped::from_ber(&hex!("83 03 01 01 ff")).is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn alias_to_der() {
+        let v = Version(2);
+        assert_eq!(v.to_der_len(), Ok(3));
+        assert_eq!(v.to_der_vec().unwrap(), &hex!("c5 01 02"));
+        let l = Label(Utf8String::new("ab"));
+        assert_eq!(l.to_der_len(), Ok(5));
+        assert_eq!(l.to_der_vec().unwrap(), &hex!("5f 28 02 61 62"));
+        // long form length
+        let s = "a".repeat(200);
+        let l = Label(Utf8String::from(s.as_str()));
+        let v = l.to_der_vec().unwrap();
+        assert_eq!(&v[..5], &hex!("5f 28 81 c8 61"));
+        assert_eq!(l.to_der_len(), Ok(v.len()));
+        let w = Wrapped(false);
+        assert_eq!(w.to_der_len(), Ok(5));
+        assert_eq!(w.to_der_vec().unwrap(), &hex!("a3 03 01 01 00"));
+        // implicit tagging of a constructed type: the encoding is constructed
+        let r = Retagged(Wrapped(true));
+        let v = r.to_der_vec().unwrap();
+        assert_eq!(v, &hex!("a4 03 01 01 ff"));
+        assert_eq!(r.to_der_len(), Ok(v.len()));
+        let (_, r2) = Retagged::from_der(&v).expect("parsing failed");
+        assert_eq!(r2, r);
+    }
+}
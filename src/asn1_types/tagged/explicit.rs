@@ -1,3 +1,5 @@
+#[cfg(feature = "std")]
+use crate::encoding::write_cer_constructed;
 use crate::*;
 use core::convert::TryFrom;
 use core::marker::PhantomData;
@@ -27,14 +29,15 @@ where
         any.tag().assert_eq(Tag(TAG))?;
         any.header.assert_constructed()?;
         if any.class() as u8 != CLASS {
-            let class = Class::try_from(CLASS).ok();
+            let class = Class::from_bits(CLASS);
             return Err(Error::unexpected_class(class, any.class()).into());
         }
-        let (_, inner) = match T::from_ber(any.data) {
+        let (rem, inner) = match T::from_ber(any.data) {
             Ok((rem, res)) => (rem, res),
             Err(Err::Error(e)) | Err(Err::Failure(e)) => return Err(e),
             Err(Err::Incomplete(n)) => return Err(Error::Incomplete(n).into()),
         };
+        any.header.assert_consumed(rem)?;
         Ok(TaggedValue::explicit(inner))
     }
 }
@@ -54,12 +57,15 @@ where
             .assert_constructed()
             .map_err(|e| Err::Error(e.into()))?;
         if any.class() as u8 != CLASS {
-            let class = Class::try_from(CLASS).ok();
+            let class = Class::from_bits(CLASS);
             return Err(Err::Error(
                 Error::unexpected_class(class, any.class()).into(),
             ));
         }
-        let (_, inner) = T::from_der(any.data)?;
+        let (inner_rem, inner) = T::from_der(any.data)?;
+        any.header
+            .assert_consumed(inner_rem)
+            .map_err(|e| Err::Error(e.into()))?;
         Ok((rem, TaggedValue::explicit(inner)))
     }
 }
@@ -70,9 +76,14 @@ where
     T: CheckDerConstraints,
 {
     fn check_constraints(any: &Any) -> Result<()> {
+        Self::check_constraints_ctx(any, DerCheckContext::new())
+    }
+
+    fn check_constraints_ctx(any: &Any, ctx: DerCheckContext) -> Result<()> {
         any.header.length.assert_definite()?;
-        let (_, inner) = Any::from_ber(any.data)?;
-        T::check_constraints(&inner)?;
+        let (rem, inner) = Any::from_ber(any.data)?;
+        any.header.assert_consumed(rem)?;
+        T::check_constraints_ctx(&inner, ctx.enter()?)?;
         Ok(())
     }
 }
@@ -84,20 +95,14 @@ where
 {
     fn to_der_len(&self) -> Result<usize> {
         let sz = self.inner.to_der_len()?;
-        if sz < 127 {
-            // 1 (class+tag) + 1 (length) + len
-            Ok(2 + sz)
-        } else {
-            // 1 (class+tag) + n (length) + len
-            let n = Length::Definite(sz).to_der_len()?;
-            Ok(1 + n + sz)
-        }
+        // class+tag (1 or more bytes) + n (length) + len
+        let n = Length::Definite(sz).to_der_len()?;
+        Ok(self.tag().encoded_len() + n + sz)
     }
 
     fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
         let inner_len = self.inner.to_der_len()?;
-        let class =
-            Class::try_from(CLASS).map_err(|_| SerializeError::InvalidClass { class: CLASS })?;
+        let class = Class::from_bits(CLASS).ok_or(SerializeError::InvalidClass { class: CLASS })?;
         let header = Header::new(class, true, self.tag(), Length::Definite(inner_len));
         header.write_der_header(writer).map_err(Into::into)
     }
@@ -105,6 +110,20 @@ where
     fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
         self.inner.write_der(writer)
     }
+
+    fn write_encoded(
+        &self,
+        rules: EncodingRules,
+        writer: &mut dyn std::io::Write,
+    ) -> SerializeResult<usize> {
+        if rules != EncodingRules::Cer {
+            return self.write_der(writer);
+        }
+        let class = Class::from_bits(CLASS).ok_or(SerializeError::InvalidClass { class: CLASS })?;
+        write_cer_constructed(class, self.tag(), writer, |writer| {
+            self.inner.write_encoded(rules, writer)
+        })
+    }
 }
 
 /// A helper object to parse `[ n ] EXPLICIT T`
@@ -112,8 +131,8 @@ where
 /// A helper object implementing [`FromBer`] and [`FromDer`], to parse tagged
 /// optional values.
 ///
-/// This helper expects context-specific tags.
-/// See [`TaggedValue`] or [`TaggedParser`] for more generic implementations if needed.
+/// This helper expects context-specific tags. For other classes, see [`PrivateExplicit`] and
+/// [`ApplicationExplicit`], or [`TaggedValue`] or [`TaggedParser`] for more generic implementations.
 ///
 /// # Examples
 ///
@@ -191,7 +210,10 @@ where
     fn from_ber(bytes: &'a [u8]) -> ParseResult<'a, Self, E> {
         let (rem, any) = Any::from_ber(bytes).map_err(Err::convert)?;
         let header = any.header;
-        let (_, inner) = T::from_ber(any.data)?;
+        let (inner_rem, inner) = T::from_ber(any.data)?;
+        header
+            .assert_consumed(inner_rem)
+            .map_err(|e| Err::Error(e.into()))?;
         let tagged = TaggedParser {
             header,
             inner,
@@ -210,7 +232,10 @@ where
     fn from_der(bytes: &'a [u8]) -> ParseResult<'a, Self, E> {
         let (rem, any) = Any::from_der(bytes).map_err(Err::convert)?;
         let header = any.header;
-        let (_, inner) = T::from_der(any.data)?;
+        let (inner_rem, inner) = T::from_der(any.data)?;
+        header
+            .assert_consumed(inner_rem)
+            .map_err(|e| Err::Error(e.into()))?;
         let tagged = TaggedParser {
             header,
             inner,
@@ -226,9 +251,14 @@ where
     T: CheckDerConstraints,
 {
     fn check_constraints(any: &Any) -> Result<()> {
+        Self::check_constraints_ctx(any, DerCheckContext::new())
+    }
+
+    fn check_constraints_ctx(any: &Any, ctx: DerCheckContext) -> Result<()> {
         any.header.length.assert_definite()?;
-        let (_, inner_any) = Any::from_der(any.data)?;
-        T::check_constraints(&inner_any)?;
+        let (rem, inner_any) = Any::from_der(any.data)?;
+        any.header.assert_consumed(rem)?;
+        T::check_constraints_ctx(&inner_any, ctx.enter()?)?;
         Ok(())
     }
 }
@@ -240,14 +270,9 @@ where
 {
     fn to_der_len(&self) -> Result<usize> {
         let sz = self.inner.to_der_len()?;
-        if sz < 127 {
-            // 1 (class+tag) + 1 (length) + len
-            Ok(2 + sz)
-        } else {
-            // 1 (class+tag) + n (length) + len
-            let n = Length::Definite(sz).to_der_len()?;
-            Ok(1 + n + sz)
-        }
+        // class+tag (1 or more bytes) + n (length) + len
+        let n = Length::Definite(sz).to_der_len()?;
+        Ok(self.tag().encoded_len() + n + sz)
     }
 
     fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
@@ -259,4 +284,17 @@ where
     fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
         self.inner.write_der(writer)
     }
+
+    fn write_encoded(
+        &self,
+        rules: EncodingRules,
+        writer: &mut dyn std::io::Write,
+    ) -> SerializeResult<usize> {
+        if rules != EncodingRules::Cer {
+            return self.write_der(writer);
+        }
+        write_cer_constructed(self.class(), self.tag(), writer, |writer| {
+            self.inner.write_encoded(rules, writer)
+        })
+    }
 }
@@ -1,3 +1,5 @@
+#[cfg(feature = "std")]
+use crate::encoding::write_cer_retagged;
 use crate::*;
 use core::convert::TryFrom;
 use core::marker::PhantomData;
@@ -30,7 +32,7 @@ where
         // XXX if input is empty, this function is not called
 
         if any.class() as u8 != CLASS {
-            let class = Class::try_from(CLASS).ok();
+            let class = Class::from_bits(CLASS);
             return Err(Error::unexpected_class(class, any.class()).into());
         }
         let any = Any {
@@ -60,7 +62,7 @@ where
             .assert_eq(Tag(TAG))
             .map_err(|e| Err::Error(e.into()))?;
         if any.class() as u8 != CLASS {
-            let class = Class::try_from(CLASS).ok();
+            let class = Class::from_bits(CLASS);
             return Err(Err::Error(
                 Error::unexpected_class(class, any.class()).into(),
             ));
@@ -74,7 +76,7 @@ where
         };
         match T::try_from(any) {
             Ok(inner) => Ok((rem, TaggedValue::implicit(inner))),
-            Err(e) => Err(nom::Err::Error(e)),
+            Err(e) => Err(Err::Error(e)),
         }
     }
 }
@@ -86,10 +88,14 @@ where
     T: Tagged,
 {
     fn check_constraints(any: &Any) -> Result<()> {
+        Self::check_constraints_ctx(any, DerCheckContext::new())
+    }
+
+    fn check_constraints_ctx(any: &Any, ctx: DerCheckContext) -> Result<()> {
         any.header.length.assert_definite()?;
         let header = any.header.clone().with_tag(T::TAG);
         let inner = Any::new(header, any.data);
-        T::check_constraints(&inner)?;
+        T::check_constraints_ctx(&inner, ctx)?;
         Ok(())
     }
 }
@@ -100,38 +106,57 @@ where
     T: ToDer,
 {
     fn to_der_len(&self) -> Result<usize> {
-        self.inner.to_der_len()
-    }
-
-    fn write_der(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
-        let class =
-            Class::try_from(CLASS).map_err(|_| SerializeError::InvalidClass { class: CLASS })?;
-        let mut v = Vec::new();
-        let inner_len = self.inner.write_der_content(&mut v)?;
-        // XXX X.690 section 8.14.3: if implicing tagging was used [...]:
-        // XXX a) the encoding shall be constructed if the base encoding is constructed, and shall be primitive otherwise
-        let constructed = matches!(self.inner.tag(), Tag::Sequence | Tag::Set);
-        let header = Header::new(class, constructed, self.tag(), Length::Definite(inner_len));
-        let sz = header.write_der_header(writer)?;
-        let sz = sz + writer.write(&v)?;
-        Ok(sz)
+        // the header of the inner object is replaced, and can have a different length
+        let (_, inner_len) = implicit_inner_header(&self.inner)?;
+        let n = Length::Definite(inner_len).to_der_len()?;
+        Ok(self.tag().encoded_len() + n + inner_len)
     }
 
     fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
-        let mut sink = std::io::sink();
-        let class =
-            Class::try_from(CLASS).map_err(|_| SerializeError::InvalidClass { class: CLASS })?;
-        let inner_len = self.inner.write_der_content(&mut sink)?;
-        // XXX X.690 section 8.14.3: if implicing tagging was used [...]:
-        // XXX a) the encoding shall be constructed if the base encoding is constructed, and shall be primitive otherwise
-        let constructed = matches!(self.inner.tag(), Tag::Sequence | Tag::Set);
+        let class = Class::from_bits(CLASS).ok_or(SerializeError::InvalidClass { class: CLASS })?;
+        let (constructed, inner_len) = implicit_inner_header(&self.inner)?;
         let header = Header::new(class, constructed, self.tag(), Length::Definite(inner_len));
-        header.write_der_header(writer).map_err(Into::into)
+        header.write_der_header(writer)
     }
 
     fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
-        self.inner.write_der(writer)
+        self.inner.write_der_content(writer)
     }
+
+    /// Write the encoded representation, using the given encoding rules
+    ///
+    /// For CER, the value is encoded using its own type before replacing the tag, so for ex.
+    /// implicitly tagged strings are fragmented (X.690 section 9.2).
+    fn write_encoded(
+        &self,
+        rules: EncodingRules,
+        writer: &mut dyn std::io::Write,
+    ) -> SerializeResult<usize> {
+        if rules != EncodingRules::Cer {
+            return self.write_der(writer);
+        }
+        let class = Class::from_bits(CLASS).ok_or(SerializeError::InvalidClass { class: CLASS })?;
+        let v = self.inner.to_encoded_vec(rules)?;
+        write_cer_retagged(class, self.tag(), &v, writer)
+    }
+}
+
+/// Return the constructed bit and the content length of the DER encoding of `inner`
+///
+/// With implicit tagging, the identifier octets of the inner object are replaced, but its
+/// content is kept, and the encoding is constructed if the base encoding is constructed (X.690
+/// section 8.14.4). Both are read from the header written by `inner`.
+#[cfg(feature = "std")]
+fn implicit_inner_header<T: ToDer>(inner: &T) -> Result<(bool, usize)> {
+    // identifier (at most 6 octets) and length (at most 1 + size_of::<usize>() octets)
+    let mut buf = [0u8; 16];
+    let mut cursor = std::io::Cursor::new(&mut buf[..]);
+    let n = inner.write_der_header(&mut cursor).map_err(|e| match e {
+        SerializeError::ASN1Error(e) => e,
+        _ => Error::InvalidLength,
+    })?;
+    let (_, header) = Header::from_der(&buf[..n])?;
+    Ok((header.is_constructed(), header.length().definite()?))
 }
 
 /// A helper object to parse `[ n ] IMPLICIT T`
@@ -139,8 +164,8 @@ where
 /// A helper object implementing [`FromBer`] and [`FromDer`], to parse tagged
 /// optional values.
 ///
-/// This helper expects context-specific tags.
-/// See [`TaggedValue`] or [`TaggedParser`] for more generic implementations if needed.
+/// This helper expects context-specific tags. For other classes, see [`PrivateImplicit`] and
+/// [`ApplicationImplicit`], or [`TaggedValue`] or [`TaggedParser`] for more generic implementations.
 ///
 /// # Examples
 ///
@@ -182,7 +207,7 @@ where
                 };
                 Ok((rem, tagged_value))
             }
-            Err(e) => Err(nom::Err::Error(e)),
+            Err(e) => Err(Err::Error(e)),
         }
     }
 }
@@ -217,7 +242,7 @@ where
             },
             data,
         };
-        T::check_constraints(&any).map_err(|e| nom::Err::Error(e.into()))?;
+        T::check_constraints(&any).map_err(|e| Err::Error(e.into()))?;
         match T::try_from(any) {
             Ok(t) => {
                 let tagged_value = TaggedParser {
@@ -228,7 +253,7 @@ where
                 };
                 Ok((rem, tagged_value))
             }
-            Err(e) => Err(nom::Err::Error(e)),
+            Err(e) => Err(Err::Error(e)),
         }
     }
 }
@@ -239,6 +264,10 @@ where
     T: Tagged,
 {
     fn check_constraints(any: &Any) -> Result<()> {
+        Self::check_constraints_ctx(any, DerCheckContext::new())
+    }
+
+    fn check_constraints_ctx(any: &Any, ctx: DerCheckContext) -> Result<()> {
         any.header.length.assert_definite()?;
         let any = Any {
             header: Header {
@@ -247,7 +276,7 @@ where
             },
             data: any.data,
         };
-        T::check_constraints(&any)?;
+        T::check_constraints_ctx(&any, ctx)?;
         Ok(())
     }
 }
@@ -258,7 +287,10 @@ where
     T: ToDer,
 {
     fn to_der_len(&self) -> Result<usize> {
-        self.inner.to_der_len()
+        // the header of the inner object is replaced, and can have a different length
+        let (_, inner_len) = implicit_inner_header(&self.inner)?;
+        let n = Length::Definite(inner_len).to_der_len()?;
+        Ok(self.tag().encoded_len() + n + inner_len)
     }
 
     fn write_der(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
@@ -284,4 +316,20 @@ where
     fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
         self.inner.write_der_content(writer)
     }
+
+    /// Write the encoded representation, using the given encoding rules
+    ///
+    /// For CER, the value is encoded using its own type before replacing the tag, so for ex.
+    /// implicitly tagged strings are fragmented (X.690 section 9.2).
+    fn write_encoded(
+        &self,
+        rules: EncodingRules,
+        writer: &mut dyn std::io::Write,
+    ) -> SerializeResult<usize> {
+        if rules != EncodingRules::Cer {
+            return self.write_der(writer);
+        }
+        let v = self.inner.to_encoded_vec(rules)?;
+        write_cer_retagged(self.class(), self.tag(), &v, writer)
+    }
 }
@@ -46,7 +46,7 @@ use crate::*;
 ///
 /// assert_eq!(tagged, Some(Integer::from(2)));
 /// ```
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug)]
 pub struct OptTaggedParser {
     /// The expected class for the object to parse
     pub class: Class,
@@ -187,8 +187,9 @@ impl From<u32> for OptTaggedParser {
 /// A helper object implementing [`FromBer`] and [`FromDer`], to parse tagged
 /// optional values.
 ///
-/// This helper expects context-specific tags.
-/// Use `Option<` [`TaggedValue`] `>` for a more generic implementation.
+/// This helper expects context-specific tags. For `PRIVATE` tags, see [`OptPrivateExplicit`]
+/// and [`OptPrivateImplicit`]. Use `Option<` [`TaggedValue`] `>` for a more generic
+/// implementation.
 ///
 /// # Examples
 ///
@@ -215,8 +216,9 @@ pub type OptTaggedExplicit<T, E, const TAG: u32> = Option<TaggedExplicit<T, E, T
 /// A helper object implementing [`FromBer`] and [`FromDer`], to parse tagged
 /// optional values.
 ///
-/// This helper expects context-specific tags.
-/// Use `Option<` [`TaggedValue`] `>` for a more generic implementation.
+/// This helper expects context-specific tags. For `PRIVATE` tags, see [`OptPrivateExplicit`]
+/// and [`OptPrivateImplicit`]. Use `Option<` [`TaggedValue`] `>` for a more generic
+/// implementation.
 ///
 /// # Examples
 ///
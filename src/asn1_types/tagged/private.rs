@@ -40,3 +40,50 @@ pub type PrivateExplicit<T, E, const TAG: u32> =
 /// ```
 pub type PrivateImplicit<T, E, const TAG: u32> =
     TaggedValue<T, E, Implicit, { Class::PRIVATE }, TAG>;
+
+/// A helper object to parse `[PRIVATE n] EXPLICIT T OPTIONAL`
+///
+/// A helper object implementing [`FromBer`](crate::FromBer) and [`FromDer`](crate::FromDer), to
+/// parse optional explicit private-tagged values.
+///
+/// # Examples
+///
+/// To parse a `[PRIVATE 0] EXPLICIT INTEGER OPTIONAL` object:
+///
+/// ```rust
+/// use asn1_rs::{Error, FromBer, Integer, OptPrivateExplicit, TaggedValue};
+///
+/// let bytes = &[0xe0, 0x03, 0x2, 0x1, 0x2];
+///
+/// let (_, tagged) = OptPrivateExplicit::<Integer, Error, 0>::from_ber(bytes).unwrap();
+/// assert_eq!(tagged, Some(TaggedValue::explicit(Integer::from(2))));
+///
+/// // If the object has a different class (here, `[0]`), parsing also succeeds (returning None):
+/// let bytes = &[0xa0, 0x03, 0x2, 0x1, 0x2];
+/// let (rem, tagged) = OptPrivateExplicit::<Integer, Error, 0>::from_ber(bytes).unwrap();
+/// assert_eq!(tagged, None);
+/// assert_eq!(rem, bytes);
+/// ```
+pub type OptPrivateExplicit<T, E, const TAG: u32> = Option<PrivateExplicit<T, E, TAG>>;
+
+/// A helper object to parse `[PRIVATE n] IMPLICIT T OPTIONAL`
+///
+/// A helper object implementing [`FromBer`](crate::FromBer) and [`FromDer`](crate::FromDer), to
+/// parse optional implicit private-tagged values.
+///
+/// # Examples
+///
+/// To parse a `[PRIVATE 0] IMPLICIT INTEGER OPTIONAL` object:
+///
+/// ```rust
+/// use asn1_rs::{Error, FromBer, Integer, OptPrivateImplicit, TaggedValue};
+///
+/// let bytes = &[0xe0, 0x1, 0x2];
+///
+/// let (_, tagged) = OptPrivateImplicit::<Integer, Error, 0>::from_ber(bytes).unwrap();
+/// assert_eq!(tagged, Some(TaggedValue::implicit(Integer::from(2_u8))));
+///
+/// let (_, tagged) = OptPrivateImplicit::<Integer, Error, 0>::from_ber(&[]).unwrap();
+/// assert_eq!(tagged, None);
+/// ```
+pub type OptPrivateImplicit<T, E, const TAG: u32> = Option<PrivateImplicit<T, E, TAG>>;
@@ -0,0 +1,127 @@
+use crate::*;
+use core::convert::TryFrom;
+
+/// An application-tagged object wrapping an OID and a payload
+///
+/// This helper parses objects of the form `[APPLICATION n] IMPLICIT SEQUENCE { oid, payload }`,
+/// accepting any application tag number. The tag number is stored in the `tag` field, so
+/// callers can check it after parsing.
+///
+/// This is the form used by GSS-API tokens (RFC 2743 section 3.1), where the payload is
+/// mechanism-specific. If the payload is not an ASN.1 object, see [`parse_gss_token_header`].
+///
+/// # Examples
+///
+/// To parse a `[APPLICATION 0]` object containing an OID and an `INTEGER`:
+///
+/// ```rust
+/// use asn1_rs::{oid, ApplicationWrapped, FromBer, Integer, Tag};
+///
+/// let bytes = &[0x60, 0x08, 0x06, 0x03, 0x2a, 0x03, 0x04, 0x02, 0x01, 0x02];
+///
+/// let (_, wrapped) = ApplicationWrapped::<Integer>::from_ber(bytes).unwrap();
+/// assert_eq!(wrapped.tag, Tag(0));
+/// assert_eq!(wrapped.oid, oid!(1.2.3.4));
+/// assert_eq!(wrapped.inner, Integer::from(2));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApplicationWrapped<'a, T> {
+    /// The application tag number
+    pub tag: Tag,
+    /// The object identifier (for ex. the mechanism type)
+    pub oid: Oid<'a>,
+    /// The wrapped payload
+    pub inner: T,
+}
+
+impl<'a, T> TryFrom<Any<'a>> for ApplicationWrapped<'a, T>
+where
+    T: FromBer<'a>,
+{
+    type Error = Error;
+
+    fn try_from(any: Any<'a>) -> Result<Self> {
+        TryFrom::try_from(&any)
+    }
+}
+
+impl<'a, 'b, T> TryFrom<&'b Any<'a>> for ApplicationWrapped<'a, T>
+where
+    T: FromBer<'a>,
+{
+    type Error = Error;
+
+    fn try_from(any: &'b Any<'a>) -> Result<Self> {
+        any.class().assert_eq(Class::Application)?;
+        any.header.assert_constructed()?;
+        let (rem, oid) = Oid::from_ber(any.data)?;
+        let (rem, inner) = T::from_ber(rem)?;
+        any.header.assert_consumed(rem)?;
+        Ok(ApplicationWrapped {
+            tag: any.tag(),
+            oid,
+            inner,
+        })
+    }
+}
+
+impl<'a, T> CheckDerConstraints for ApplicationWrapped<'a, T>
+where
+    T: CheckDerConstraints,
+{
+    fn check_constraints(any: &Any) -> Result<()> {
+        Self::check_constraints_ctx(any, DerCheckContext::new())
+    }
+
+    fn check_constraints_ctx(any: &Any, ctx: DerCheckContext) -> Result<()> {
+        any.header.length.assert_definite()?;
+        let (rem, mech) = Any::from_der(any.data)?;
+        Oid::check_constraints(&mech)?;
+        let (rem, inner) = Any::from_der(rem)?;
+        any.header.assert_consumed(rem)?;
+        T::check_constraints_ctx(&inner, ctx.enter()?)?;
+        Ok(())
+    }
+}
+
+impl<'a, T> DerAutoDerive for ApplicationWrapped<'a, T> {}
+
+/// Parse the header of a GSS-API token
+///
+/// GSS-API initial context tokens (RFC 2743 section 3.1) are encoded as:
+///
+/// ```asn1
+/// InitialContextToken ::= [APPLICATION 0] IMPLICIT SEQUENCE {
+///         thisMech MechType,
+///         innerContextToken ANY DEFINED BY thisMech
+///            -- contents mechanism-specific
+///            -- ASN.1 structure not required
+///         }
+/// ```
+///
+/// Since the inner token is not required to be an ASN.1 object, it is returned as raw bytes.
+///
+/// Returns the remaining bytes, the mechanism OID and the inner token.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{oid, parse_gss_token_header};
+///
+/// let bytes = &[0x60, 0x07, 0x06, 0x03, 0x2a, 0x03, 0x04, 0xde, 0xad];
+///
+/// let (rem, (mech, token)) = parse_gss_token_header(bytes).unwrap();
+/// assert!(rem.is_empty());
+/// assert_eq!(mech, oid!(1.2.3.4));
+/// assert_eq!(token, &[0xde, 0xad]);
+/// ```
+pub fn parse_gss_token_header(bytes: &[u8]) -> ParseResult<'_, (Oid<'_>, &[u8])> {
+    let (rem, any) = Any::from_ber(bytes)?;
+    any.class()
+        .assert_eq(Class::Application)
+        .map_err(Err::Error)?;
+    any.tag().assert_eq(Tag(0)).map_err(Err::Error)?;
+    any.header.assert_constructed().map_err(Err::Error)?;
+    let (token, mech) = Oid::from_ber(any.data)?;
+    Ok((rem, (mech, token)))
+}
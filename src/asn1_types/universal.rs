@@ -0,0 +1,176 @@
+use crate::*;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+/// A decoded object of the `Universal` class
+///
+/// This enum is returned by [`Any::decode_universal`], and can be used by generic tools (for ex.
+/// pretty-printers) to handle all universal types in one call.
+///
+/// Objects of other classes, or with universal tags without a corresponding type in this crate
+/// (for ex. `EXTERNAL`), are returned unchanged as `UniversalValue::Other`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum UniversalValue<'a> {
+    EndOfContent,
+    Boolean(bool),
+    Integer(Integer<'a>),
+    BitString(BitString<'a>),
+    OctetString(OctetString<'a>),
+    Null,
+    Oid(Oid<'a>),
+    ObjectDescriptor(ObjectDescriptor<'a>),
+    Real(Real),
+    Enumerated(Enumerated),
+    EmbeddedPdv(EmbeddedPdv<'a>),
+    Utf8String(Utf8String<'a>),
+    RelativeOid(Oid<'a>),
+    /// Items of the `SEQUENCE` (not decoded)
+    Sequence(Vec<Any<'a>>),
+    /// Items of the `SET` (not decoded)
+    Set(Vec<Any<'a>>),
+    NumericString(NumericString<'a>),
+    PrintableString(PrintableString<'a>),
+    TeletexString(TeletexString<'a>),
+    VideotexString(VideotexString<'a>),
+    Ia5String(Ia5String<'a>),
+    UtcTime(UtcTime),
+    GeneralizedTime(GeneralizedTime),
+    GraphicString(GraphicString<'a>),
+    VisibleString(VisibleString<'a>),
+    GeneralString(GeneralString<'a>),
+    UniversalString(UniversalString<'a>),
+    BmpString(BmpString<'a>),
+    /// Object of a non-universal class, or of an unsupported universal type
+    Other(Any<'a>),
+}
+
+impl<'a> Any<'a> {
+    /// Decode this object according to its universal tag
+    ///
+    /// Items of constructed types (`SEQUENCE` and `SET`) are parsed as BER, but not decoded:
+    /// this function can be called on each item to decode it.
+    ///
+    /// Objects of other classes are returned unchanged as [`UniversalValue::Other`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use asn1_rs::{Any, FromBer, UniversalValue};
+    ///
+    /// // SEQUENCE { INTEGER 42, UTF8String "a" }
+    /// let bytes = &[0x30, 0x06, 0x02, 0x01, 0x2a, 0x0c, 0x01, 0x61];
+    /// let (_, any) = Any::from_ber(bytes).expect("parsing failed");
+    /// if let Ok(UniversalValue::Sequence(items)) = any.decode_universal() {
+    ///     for item in &items {
+    ///         match item.decode_universal() {
+    ///             Ok(UniversalValue::Integer(i)) => assert_eq!(i.as_u32(), Ok(42)),
+    ///             Ok(UniversalValue::Utf8String(s)) => assert_eq!(s.as_ref(), "a"),
+    ///             _ => panic!("unexpected item"),
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn decode_universal(&self) -> Result<UniversalValue<'a>> {
+        if self.class() != Class::Universal {
+            return Ok(UniversalValue::Other(self.clone()));
+        }
+        let any = self.clone();
+        let value = match self.tag() {
+            Tag::EndOfContent => {
+                EndOfContent::try_from(any)?;
+                UniversalValue::EndOfContent
+            }
+            Tag::Boolean => UniversalValue::Boolean(bool::try_from(any)?),
+            Tag::Integer => UniversalValue::Integer(Integer::try_from(any)?),
+            Tag::BitString => UniversalValue::BitString(BitString::try_from(any)?),
+            Tag::OctetString => UniversalValue::OctetString(OctetString::try_from(any)?),
+            Tag::Null => {
+                Null::try_from(any)?;
+                UniversalValue::Null
+            }
+            Tag::Oid => UniversalValue::Oid(Oid::try_from(any)?),
+            Tag::ObjectDescriptor => {
+                UniversalValue::ObjectDescriptor(ObjectDescriptor::try_from(any)?)
+            }
+            Tag::RealType => UniversalValue::Real(Real::try_from(any)?),
+            Tag::Enumerated => UniversalValue::Enumerated(Enumerated::try_from(any)?),
+            Tag::EmbeddedPdv => UniversalValue::EmbeddedPdv(EmbeddedPdv::try_from(any)?),
+            Tag::Utf8String => UniversalValue::Utf8String(Utf8String::try_from(any)?),
+            Tag::RelativeOid => UniversalValue::RelativeOid(self.as_relative_oid()?),
+            Tag::Sequence => {
+                any.header.assert_constructed()?;
+                let items = SequenceIterator::<Any, BerParser>::new(self.data)
+                    .collect::<Result<Vec<_>>>()?;
+                UniversalValue::Sequence(items)
+            }
+            Tag::Set => {
+                any.header.assert_constructed()?;
+                let items =
+                    SetIterator::<Any, BerParser>::new(self.data).collect::<Result<Vec<_>>>()?;
+                UniversalValue::Set(items)
+            }
+            Tag::NumericString => UniversalValue::NumericString(NumericString::try_from(any)?),
+            Tag::PrintableString => {
+                UniversalValue::PrintableString(PrintableString::try_from(any)?)
+            }
+            Tag::TeletexString => UniversalValue::TeletexString(TeletexString::try_from(any)?),
+            Tag::VideotexString => UniversalValue::VideotexString(VideotexString::try_from(any)?),
+            Tag::Ia5String => UniversalValue::Ia5String(Ia5String::try_from(any)?),
+            Tag::UtcTime => UniversalValue::UtcTime(UtcTime::try_from(any)?),
+            Tag::GeneralizedTime => {
+                UniversalValue::GeneralizedTime(GeneralizedTime::try_from(any)?)
+            }
+            Tag::GraphicString => UniversalValue::GraphicString(GraphicString::try_from(any)?),
+            Tag::VisibleString => UniversalValue::VisibleString(VisibleString::try_from(any)?),
+            Tag::GeneralString => UniversalValue::GeneralString(GeneralString::try_from(any)?),
+            Tag::UniversalString => {
+                UniversalValue::UniversalString(UniversalString::try_from(any)?)
+            }
+            Tag::BmpString => UniversalValue::BmpString(BmpString::try_from(any)?),
+            _ => UniversalValue::Other(any),
+        };
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn decode_universal() {
+        let (_, any) = Any::from_ber(&hex!("01 01 ff")).expect("parsing failed");
+        assert_eq!(any.decode_universal(), Ok(UniversalValue::Boolean(true)));
+        let (_, any) = Any::from_ber(&hex!("05 00")).expect("parsing failed");
+        assert_eq!(any.decode_universal(), Ok(UniversalValue::Null));
+        let (_, any) = Any::from_ber(&hex!("06 03 55 04 03")).expect("parsing failed");
+        assert_eq!(
+            any.decode_universal(),
+            Ok(UniversalValue::Oid(oid!(2.5.4 .3)))
+        );
+        let (_, any) = Any::from_ber(&hex!("13 02 46 52")).expect("parsing failed");
+        assert_eq!(
+            any.decode_universal(),
+            Ok(UniversalValue::PrintableString(PrintableString::new("FR")))
+        );
+        // SET { INTEGER 1, NULL }
+        let (_, any) = Any::from_ber(&hex!("31 05 02 01 01 05 00")).expect("parsing failed");
+        match any.decode_universal() {
+            Ok(UniversalValue::Set(items)) => {
+                assert_eq!(items.len(), 2);
+                assert_eq!(items[1].decode_universal(), Ok(UniversalValue::Null));
+            }
+            _ => panic!("unexpected value"),
+        }
+        // non-universal class
+        let (_, any) = Any::from_ber(&hex!("80 01 00")).expect("parsing failed");
+        assert_eq!(
+            any.decode_universal(),
+            Ok(UniversalValue::Other(any.clone()))
+        );
+        // invalid content
+        let (_, any) = Any::from_ber(&hex!("13 01 40")).expect("parsing failed");
+        assert!(any.decode_universal().is_err());
+    }
+}
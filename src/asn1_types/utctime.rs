@@ -1,19 +1,100 @@
-use crate::datetime::decode_decimal;
+#[cfg(feature = "std")]
+use crate::datetime::encode_time_fields;
+use crate::datetime::{check_calendar_date, decode_decimal};
 use crate::*;
+use core::cmp::Ordering;
 use core::convert::TryFrom;
 use core::fmt;
 #[cfg(feature = "datetime")]
 use time::OffsetDateTime;
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub struct UtcTime(pub ASN1DateTime);
+/// ASN.1 `UTCTime` type
+///
+/// When parsed, the object also records the format of the original time string (see
+/// [`UtcTime::format`]). The format is not part of the value: it is ignored when comparing
+/// objects.
+///
+/// Objects are ordered by the instant they represent, interpreting two-digit years following
+/// RFC 5280 (see [`UtcTime::full_year`]).
+#[derive(Clone, Debug)]
+pub struct UtcTime(pub ASN1DateTime, Option<ASN1TimeFormat>);
 
 impl UtcTime {
     pub const fn new(datetime: ASN1DateTime) -> Self {
-        UtcTime(datetime)
+        UtcTime(datetime, None)
+    }
+
+    /// Create a new `UtcTime` from its components, checking that it can be encoded
+    ///
+    /// `year` is either the two-digit year (0 to 99), or a full year in the range 1950 to 2049
+    /// (the window defined by RFC 5280). Fields are checked as in [`ASN1DateTime::validate`],
+    /// and [`Error::InvalidDateTime`] is returned if the year cannot be represented, including
+    /// after converting an offset time zone to UTC (as required by DER).
+    ///
+    /// ```rust
+    /// use asn1_rs::{ASN1TimeZone, Error, UtcTime};
+    ///
+    /// let t = UtcTime::try_new(2049, 12, 31, 23, 0, 0, ASN1TimeZone::Z);
+    /// assert!(t.is_ok());
+    /// let t = UtcTime::try_new(2050, 1, 1, 0, 0, 0, ASN1TimeZone::Z);
+    /// assert_eq!(t, Err(Error::InvalidDateTime));
+    /// // 2050-01-01 00:30+01:00 is 2049-12-31 23:30Z
+    /// let t = UtcTime::try_new(2050, 1, 1, 0, 30, 0, ASN1TimeZone::Offset(1, 0));
+    /// assert_eq!(t, Err(Error::InvalidDateTime));
+    /// ```
+    pub fn try_new(
+        year: u32,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+        tz: ASN1TimeZone,
+    ) -> Result<Self> {
+        if !(year <= 99 || (1950..=2049).contains(&year)) {
+            return Err(Error::InvalidDateTime);
+        }
+        let dt = ASN1DateTime::try_new(year, month, day, hour, minute, second, None, tz)?;
+        let t = UtcTime::new(dt);
+        // the UTC time must also be in the window
+        let utc = t.to_utc()?;
+        if !(utc.0.year <= 99 || (1950..=2049).contains(&utc.0.year)) {
+            return Err(Error::InvalidDateTime);
+        }
+        Ok(t)
     }
 
+    /// Return the format of the original time string, if this object was parsed
+    #[inline]
+    pub const fn format(&self) -> Option<ASN1TimeFormat> {
+        self.1
+    }
+
+    /// Set the format used by [`UtcTime::write_ber_original`]
+    ///
+    /// Fractional seconds are not allowed in `UTCTime`, so `format.fraction_digits` is ignored.
+    #[inline]
+    pub fn with_format(self, format: ASN1TimeFormat) -> Self {
+        UtcTime(self.0, Some(format))
+    }
+
+    /// Parse the content of a `UTCTime` object
+    ///
+    /// The date is checked using the calendar (number of days per month, leap years), and
+    /// [`Error::InvalidCalendarDate`] is returned if it does not exist. See
+    /// [`UtcTime::from_bytes_lenient`] to skip this check.
     pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let t = Self::from_bytes_lenient(bytes)?;
+        check_calendar_date(t.0.year, t.0.month, t.0.day)?;
+        Ok(t)
+    }
+
+    /// Parse the content of a `UTCTime` object, without checking that the date exists
+    ///
+    /// Fields are only checked for their range (for ex. day must be at most 31), so invalid dates
+    /// like February 31st are accepted. This can be used to parse data from non-conforming
+    /// BER encoders.
+    pub fn from_bytes_lenient(bytes: &[u8]) -> Result<Self> {
         // X.680 section 43 defines a UniversalTime as a VisibleString restricted to:
         //
         // a) the six digits YYMMDD where YY is the two low-order digits of the Christian year, MM is the month
@@ -41,12 +122,12 @@ impl UtcTime {
             return Err(Self::TAG.invalid_value("malformed time string"));
         }
         // check for seconds
-        let (second, rem) = match rem {
-            [sec1, sec2, rem @ ..] => {
+        let (second, has_seconds, rem) = match rem {
+            [sec1, sec2, rem @ ..] if sec1.is_ascii_digit() && sec2.is_ascii_digit() => {
                 let second = decode_decimal(Self::TAG, *sec1, *sec2)?;
-                (second, rem)
+                (second, true, rem)
             }
-            _ => (0, rem),
+            _ => (0, false, rem),
         };
         if month > 12 || day > 31 || hour > 23 || minute > 59 || second > 59 {
             return Err(Self::TAG.invalid_value("time components with invalid values"));
@@ -56,28 +137,19 @@ impl UtcTime {
         }
         let tz = match rem {
             [b'Z'] => ASN1TimeZone::Z,
-            [b'+', h1, h2, m1, m2] => {
+            [sign @ b'+', h1, h2, m1, m2] | [sign @ b'-', h1, h2, m1, m2] => {
                 let hh = decode_decimal(Self::TAG, *h1, *h2)?;
                 let mm = decode_decimal(Self::TAG, *m1, *m2)?;
-                ASN1TimeZone::Offset(hh as i8, mm as i8)
-            }
-            [b'-', h1, h2, m1, m2] => {
-                let hh = decode_decimal(Self::TAG, *h1, *h2)?;
-                let mm = decode_decimal(Self::TAG, *m1, *m2)?;
-                ASN1TimeZone::Offset(-(hh as i8), mm as i8)
+                ASN1TimeZone::from_offset(*sign == b'-', hh, mm)
             }
             _ => return Err(Self::TAG.invalid_value("malformed time string: no time zone")),
         };
-        Ok(UtcTime(ASN1DateTime::new(
-            year as u32,
-            month,
-            day,
-            hour,
-            minute,
-            second,
-            None,
-            tz,
-        )))
+        let format = ASN1TimeFormat {
+            seconds: has_seconds,
+            ..ASN1TimeFormat::new()
+        };
+        let dt = ASN1DateTime::new(year as u32, month, day, hour, minute, second, None, tz);
+        Ok(UtcTime(dt, Some(format)))
         // match *bytes {
         //     [year1, year2, mon1, mon2, day1, day2, hour1, hour2, min1, min2, sec1, sec2, b'Z'] => {
         //         let year = decode_decimal(Self::TAG, year1, year2)?;
@@ -96,6 +168,205 @@ impl UtcTime {
         // }
     }
 
+    /// Return the same instant, converted to UTC (see [`ASN1DateTime::to_utc`])
+    ///
+    /// The two-digit year is interpreted following RFC 5280 (`YY` >= 50 is `19YY`, otherwise
+    /// `20YY`). Returns [`Error::InvalidDateTime`] if the converted year is not in the range
+    /// 1950 to 2049, since it cannot be represented.
+    pub fn to_utc(&self) -> Result<UtcTime> {
+        if !matches!(self.0.tz, ASN1TimeZone::Offset(..)) {
+            return Ok(self.clone());
+        }
+        let two_digits = self.0.year < 100;
+        let mut dt = self.to_full_datetime().to_utc()?;
+        if two_digits {
+            if !(1950..=2049).contains(&dt.year) {
+                return Err(Error::InvalidDateTime);
+            }
+            dt.year %= 100;
+        }
+        Ok(UtcTime(dt, self.1))
+    }
+
+    /// Return the full year, interpreting a two-digit year relative to `now`
+    ///
+    /// Contrary to [`to_utc`](Self::to_utc), which uses the fixed window of RFC 5280 (1950 to
+    /// 2049), the two-digit year is interpreted in a sliding window of 100 years, from 50 years
+    /// before the year of `now` to 49 years after. Full years are returned unchanged.
+    ///
+    /// `now` must have a full year, and is provided by the caller: the system clock is never read.
+    /// An error is returned if the full year cannot be represented (year of `now` close to
+    /// `u32::MAX`).
+    ///
+    /// ```rust
+    /// use asn1_rs::{ASN1DateTime, ASN1TimeZone, UtcTime};
+    ///
+    /// let now = ASN1DateTime::new(2080, 1, 1, 0, 0, 0, None, ASN1TimeZone::Z);
+    /// let t = UtcTime::from_bytes(b"450101000000Z").expect("invalid time");
+    /// assert_eq!(t.full_year_at(&now), Ok(2045));
+    /// let t = UtcTime::from_bytes(b"100101000000Z").expect("invalid time");
+    /// assert_eq!(t.full_year_at(&now), Ok(2110));
+    /// ```
+    pub fn full_year_at(&self, now: &ASN1DateTime) -> Result<u32> {
+        let year = self.0.year;
+        if year > 99 {
+            return Ok(year);
+        }
+        let start = now.year.saturating_sub(50);
+        let full_year = (start - start % 100)
+            .checked_add(year)
+            .ok_or(Error::InvalidDateTime)?;
+        if full_year < start {
+            full_year.checked_add(100).ok_or(Error::InvalidDateTime)
+        } else {
+            Ok(full_year)
+        }
+    }
+
+    /// Return the date and time, with the full year interpreted relative to `now` (see
+    /// [`full_year_at`](Self::full_year_at))
+    pub fn to_datetime_at(&self, now: &ASN1DateTime) -> Result<ASN1DateTime> {
+        Ok(ASN1DateTime {
+            year: self.full_year_at(now)?,
+            ..self.0.clone()
+        })
+    }
+
+    /// Return true if this time, used as an expiry time, is strictly before `now`
+    ///
+    /// The two-digit year is interpreted relative to `now` (see
+    /// [`full_year_at`](Self::full_year_at)). If the full year cannot be represented, it is after
+    /// the year of `now`, so the time is not expired.
+    pub fn is_expired_at(&self, now: &ASN1DateTime) -> bool {
+        match self.to_datetime_at(now) {
+            Ok(dt) => dt.is_expired_at(now),
+            // the full year is larger than u32::MAX
+            Err(_) => false,
+        }
+    }
+
+    /// Serialize this object using BER, keeping the time zone offset
+    ///
+    /// Contrary to [`ToDer`], which converts the time to UTC (X.690 section 11.8.1), an offset
+    /// time zone is written as `+hhmm` or `-hhmm`, after the seconds.
+    ///
+    /// The year is always written using two digits, so it must be in the range 1950 to 2049 (or
+    /// be a two-digit year).
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn write_ber(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        self.write_ber_format(ASN1TimeFormat::new(), writer)
+    }
+
+    /// Serialize this object using BER, with the time string representation of `format`
+    #[cfg(feature = "std")]
+    fn write_ber_format(
+        &self,
+        format: ASN1TimeFormat,
+        writer: &mut dyn std::io::Write,
+    ) -> SerializeResult<usize> {
+        let dt = &self.0;
+        let tz = match dt.tz {
+            ASN1TimeZone::Offset(..) => dt.tz.offset_string(),
+            ASN1TimeZone::Z | ASN1TimeZone::Undefined => "Z".to_string(),
+        };
+        let mut content = encode_decimal(Self::TAG, der_utc_year(dt)? as u8)?.to_vec();
+        content.extend_from_slice(&encode_time_fields(Self::TAG, dt, format.seconds)?);
+        content.extend_from_slice(tz.as_bytes());
+        let sz = write_header(Self::TAG, content.len(), writer)?;
+        writer.write_all(&content)?;
+        Ok(sz + content.len())
+    }
+
+    /// Serialize this object using BER to a new `Vec<u8>`, keeping the time zone offset
+    ///
+    /// See [`UtcTime::write_ber`].
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn to_ber_vec(&self) -> SerializeResult<Vec<u8>> {
+        let mut v = Vec::new();
+        self.write_ber(&mut v)?;
+        Ok(v)
+    }
+
+    /// Return the full year, interpreting a two-digit year following RFC 5280
+    ///
+    /// `YY` >= 50 is `19YY`, otherwise `20YY`. Full years are returned unchanged.
+    ///
+    /// ```rust
+    /// use asn1_rs::UtcTime;
+    ///
+    /// let t = UtcTime::from_bytes(b"491231235959Z").expect("invalid time");
+    /// assert_eq!(t.full_year(), 2049);
+    /// let t = UtcTime::from_bytes(b"500101000000Z").expect("invalid time");
+    /// assert_eq!(t.full_year(), 1950);
+    /// ```
+    pub const fn full_year(&self) -> u32 {
+        match self.0.year {
+            y @ 0..=49 => y + 2000,
+            y @ 50..=99 => y + 1900,
+            y => y,
+        }
+    }
+
+    /// Return the date and time, with the full year (see [`full_year`](Self::full_year))
+    pub fn to_full_datetime(&self) -> ASN1DateTime {
+        ASN1DateTime {
+            year: self.full_year(),
+            ..self.0.clone()
+        }
+    }
+
+    /// Return the number of milliseconds since the Unix epoch
+    ///
+    /// The two-digit year is interpreted following RFC 5280 (see
+    /// [`full_year`](Self::full_year)).
+    pub fn unix_timestamp_millis(&self) -> i128 {
+        self.to_full_datetime().unix_timestamp_millis()
+    }
+
+    /// Return true if `self` is in the validity window `[not_before, not_after]` (inclusive)
+    ///
+    /// The two-digit year is interpreted following RFC 5280 (see
+    /// [`full_year`](Self::full_year)).
+    pub fn is_within(&self, not_before: &ASN1DateTime, not_after: &ASN1DateTime) -> bool {
+        self.to_full_datetime().is_within(not_before, not_after)
+    }
+
+    /// Serialize this object using BER, with the format of the original time string
+    ///
+    /// If the object was parsed (or if a format was set using [`UtcTime::with_format`]), the
+    /// time string is written using the same representation, so parsing and serializing an
+    /// object gives the same encoding. Otherwise, this is the same as [`UtcTime::write_ber`].
+    /// The year is always written using two digits.
+    ///
+    /// ```rust
+    /// use asn1_rs::{FromBer, UtcTime};
+    ///
+    /// // no seconds
+    /// let input = &[0x17, 0x0b, 0x32, 0x31, 0x30, 0x31, 0x30, 0x31, 0x31, 0x32, 0x30, 0x30, 0x5a];
+    /// let (_, t) = UtcTime::from_ber(input).expect("parsing failed");
+    /// assert!(!t.format().expect("no format").seconds);
+    /// assert_eq!(t.to_ber_vec_original().expect("serialization failed"), input);
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn write_ber_original(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        self.write_ber_format(self.1.unwrap_or_default(), writer)
+    }
+
+    /// Serialize this object using BER to a new `Vec<u8>`, with the format of the original time
+    /// string
+    ///
+    /// See [`UtcTime::write_ber_original`].
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn to_ber_vec_original(&self) -> SerializeResult<Vec<u8>> {
+        let mut v = Vec::new();
+        self.write_ber_original(&mut v)?;
+        Ok(v)
+    }
+
     /// Return a ISO 8601 combined date and time with time zone.
     #[cfg(feature = "datetime")]
     #[cfg_attr(docsrs, doc(cfg(feature = "datetime")))]
@@ -165,18 +436,43 @@ impl fmt::Display for UtcTime {
                 "{:04}-{:02}-{:02} {:02}:{:02}:{:02}Z",
                 dt.year, dt.month, dt.day, dt.hour, dt.minute, dt.second
             ),
-            ASN1TimeZone::Offset(hh, mm) => {
-                let (s, hh) = if hh > 0 { ('+', hh) } else { ('-', -hh) };
-                write!(
-                    f,
-                    "{:04}-{:02}-{:02} {:02}:{:02}:{:02}{}{:02}{:02}",
-                    dt.year, dt.month, dt.day, dt.hour, dt.minute, dt.second, s, hh, mm
-                )
-            }
+            ASN1TimeZone::Offset(..) => write!(
+                f,
+                "{:04}-{:02}-{:02} {:02}:{:02}:{:02}{}",
+                dt.year,
+                dt.month,
+                dt.day,
+                dt.hour,
+                dt.minute,
+                dt.second,
+                dt.tz.offset_string()
+            ),
         }
     }
 }
 
+impl PartialEq for UtcTime {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for UtcTime {}
+
+impl PartialOrd for UtcTime {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for UtcTime {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.to_full_datetime()
+            .cmp(&other.to_full_datetime())
+            .then_with(|| self.0.cmp(&other.0))
+    }
+}
+
 impl CheckDerConstraints for UtcTime {
     fn check_constraints(_any: &Any) -> Result<()> {
         Ok(())
@@ -189,6 +485,19 @@ impl Tagged for UtcTime {
     const TAG: Tag = Tag::UtcTime;
 }
 
+/// Return the two-digit year of `dt`, as written in a `UTCTime`
+#[cfg(feature = "std")]
+fn der_utc_year(dt: &ASN1DateTime) -> SerializeResult<u32> {
+    match dt.year {
+        y @ 0..=99 => Ok(y),
+        y @ 1950..=2049 => Ok(y % 100),
+        _ => Err(SerializeError::ValueOutOfRange {
+            tag: Tag::UtcTime,
+            msg: "year must be in the range 1950 to 2049",
+        }),
+    }
+}
+
 #[cfg(feature = "std")]
 impl ToDer for UtcTime {
     fn to_der_len(&self) -> Result<usize> {
@@ -197,26 +506,23 @@ impl ToDer for UtcTime {
         // - 6 for hhmmss in DER (X.690 section 11.8.2)
         // - 1 for the character Z in DER (X.690 section 11.8.1)
         // data length: 13
-        //
-        // thus, length will always be on 1 byte (short length) and
-        // class+structure+tag also on 1
-        //
-        // total: 15 = 1 (class+constructed+tag) + 1 (length) + 13
-        Ok(15)
+        primitive_der_len(Self::TAG, 13)
     }
 
     fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        // fail before writing anything if the year cannot be represented
+        der_utc_year(&self.to_utc()?.0)?;
         // see above for length value
-        writer.write(&[Self::TAG.0 as u8, 13]).map_err(Into::into)
+        write_header(Self::TAG, 13, writer)
     }
 
     fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
-        write!(
-            writer,
-            "{:02}{:02}{:02}{:02}{:02}{:02}Z",
-            self.0.year, self.0.month, self.0.day, self.0.hour, self.0.minute, self.0.second,
-        )?;
-        // write_fmt returns (), see above for length value
-        Ok(13)
+        // DER requires UTC (X.690 section 11.8.1): convert if the time zone is an offset
+        let dt = self.to_utc()?.0;
+        let mut content = encode_decimal(Self::TAG, der_utc_year(&dt)? as u8)?.to_vec();
+        content.extend_from_slice(&encode_time_fields(Self::TAG, &dt, true)?);
+        content.push(b'Z');
+        writer.write_all(&content)?;
+        Ok(content.len())
     }
 }
@@ -0,0 +1,285 @@
+//! Representative workloads, for benchmarks and performance regression tests
+//!
+//! This module provides input generators and workloads (parsing and serialization), so
+//! benchmarks of this crate and of downstream crates can measure comparable operations. Each
+//! [`Workload`] also declares the maximum number of heap allocations of a single run, which can
+//! be checked using a counting global allocator (see `bench/benches/workloads.rs` in the repository
+//! for an example using `criterion`).
+//!
+//! # Examples
+//!
+//! ```rust
+//! use asn1_rs::bench::workloads;
+//!
+//! for w in workloads() {
+//!     let input = w.input().expect("input generation failed");
+//!     let n = w.run(&input).expect("workload failed");
+//!     assert!(n > 0);
+//! }
+//! ```
+
+use crate::ber::MAX_RECURSION;
+use crate::{
+    Any, Class, Error, FromBer, FromDer, Header, Length, OctetString, Result, Sequence,
+    SerializeResult, Tag, ToDer,
+};
+use std::borrow::Cow;
+
+/// Number of certificates in certificate parsing workloads
+pub const NUM_CERTIFICATES: usize = 10_000;
+
+/// Nesting depth of the deep sequence serialization workload
+pub const SEQUENCE_DEPTH: usize = 50;
+
+/// Size of the content of the big OCTET STRING workloads
+pub const BIG_OCTET_STRING_SIZE: usize = 1 << 20;
+
+/// A benchmark workload
+#[derive(Clone, Copy, Debug)]
+pub struct Workload {
+    /// Short name, usable as benchmark identifier
+    pub name: &'static str,
+    /// Function generating the input (not measured)
+    pub input: fn() -> SerializeResult<Vec<u8>>,
+    /// Function to measure, returning a workload-specific count (objects, bytes)
+    pub run: fn(&[u8]) -> SerializeResult<usize>,
+    /// Maximum number of heap allocations of a single run, if bounded
+    pub max_allocations: Option<usize>,
+}
+
+impl Workload {
+    /// Generate the input of this workload
+    pub fn input(&self) -> SerializeResult<Vec<u8>> {
+        (self.input)()
+    }
+
+    /// Run this workload on `input`
+    pub fn run(&self, input: &[u8]) -> SerializeResult<usize> {
+        (self.run)(input)
+    }
+
+    /// Panic if `allocations` (the number of heap allocations measured for a single run)
+    /// exceeds the maximum for this workload
+    pub fn assert_allocations(&self, allocations: usize) {
+        if let Some(max) = self.max_allocations {
+            assert!(
+                allocations <= max,
+                "workload {}: {} allocations (maximum: {})",
+                self.name,
+                allocations,
+                max
+            );
+        }
+    }
+}
+
+/// Return the list of workloads
+pub fn workloads() -> Vec<Workload> {
+    vec![
+        Workload {
+            name: "parse_certificates_ber",
+            input: || certificates(NUM_CERTIFICATES),
+            run: |i| parse_tlvs_ber(i).map_err(Into::into),
+            max_allocations: Some(0),
+        },
+        Workload {
+            name: "parse_certificates_der",
+            input: || certificates(NUM_CERTIFICATES),
+            run: |i| parse_tlvs_der(i).map_err(Into::into),
+            max_allocations: Some(0),
+        },
+        Workload {
+            name: "serialize_deep_sequence",
+            input: || Ok(Vec::new()),
+            run: |_| serialize_deep_sequence(SEQUENCE_DEPTH).map(|v| v.len()),
+            max_allocations: None,
+        },
+        Workload {
+            name: "parse_big_octet_string",
+            input: || big_octet_string(BIG_OCTET_STRING_SIZE),
+            run: |i| parse_big_octet_string(i).map_err(Into::into),
+            max_allocations: Some(0),
+        },
+        Workload {
+            name: "serialize_big_octet_string",
+            input: || Ok(vec![0xa5; BIG_OCTET_STRING_SIZE]),
+            run: |i| serialize_octet_string(i).map(|v| v.len()),
+            max_allocations: Some(1),
+        },
+    ]
+}
+
+fn tlv(class: Class, constructed: bool, tag: Tag, content: &[u8]) -> SerializeResult<Vec<u8>> {
+    let header = Header::new(class, constructed, tag, Length::Definite(content.len()));
+    Any::new(header, content).to_der_vec()
+}
+
+fn seq(parts: &[&[u8]]) -> SerializeResult<Vec<u8>> {
+    tlv(Class::Universal, true, Tag::Sequence, &parts.concat())
+}
+
+/// BIT STRING with no unused bits, and `len` bytes of content
+fn bits(byte: u8, len: usize) -> SerializeResult<Vec<u8>> {
+    let mut content = vec![byte; len + 1];
+    content[0] = 0;
+    tlv(Class::Universal, false, Tag::BitString, &content)
+}
+
+/// Generate `count` DER-encoded objects with the structure of a X.509 certificate
+///
+/// Objects are valid DER (but not valid certificates: values are arbitrary).
+pub fn certificates(count: usize) -> SerializeResult<Vec<u8>> {
+    let null = tlv(Class::Universal, false, Tag::Null, &[])?;
+    let oid = tlv(
+        Class::Universal,
+        false,
+        Tag::Oid,
+        &[42, 134, 72, 134, 247, 13, 1, 1, 11],
+    )?;
+    let alg_id = seq(&[&oid, &null])?;
+    let cn = tlv(Class::Universal, false, Tag::Oid, &[85, 4, 3])?;
+    let value = tlv(Class::Universal, false, Tag::Utf8String, b"www.example.com")?;
+    let rdn = tlv(Class::Universal, true, Tag::Set, &seq(&[&cn, &value])?)?;
+    let name = seq(&[&rdn, &rdn, &rdn])?;
+    let time = tlv(Class::Universal, false, Tag::UtcTime, b"220101000000Z")?;
+    let validity = seq(&[&time, &time])?;
+    let key = bits(0x5a, 270)?;
+    let spki = seq(&[&alg_id, &key])?;
+    let version = tlv(Class::Universal, false, Tag::Integer, &[2])?;
+    let version = tlv(Class::ContextSpecific, true, Tag(0), &version)?;
+    let ext_value = tlv(
+        Class::Universal,
+        false,
+        Tag::OctetString,
+        &[0x30, 0x03, 1, 1, 0xff],
+    )?;
+    let ext = seq(&[&cn, &ext_value])?;
+    let exts = tlv(
+        Class::ContextSpecific,
+        true,
+        Tag(3),
+        &seq(&[&ext, &ext, &ext])?,
+    )?;
+    let signature = bits(0xa5, 256)?;
+    let mut v = Vec::new();
+    for i in 0..count {
+        let serial = (i as u64 + 1).to_der_vec()?;
+        let tbs = seq(&[
+            &version, &serial, &alg_id, &name, &validity, &name, &spki, &exts,
+        ])?;
+        v.extend_from_slice(&seq(&[&tbs, &alg_id, &signature])?);
+    }
+    Ok(v)
+}
+
+/// Parse all BER objects from input (recursively), and return the number of objects
+///
+/// This function does not allocate.
+pub fn parse_tlvs_ber(input: &[u8]) -> Result<usize> {
+    walk(input, MAX_RECURSION, false)
+}
+
+/// Parse all DER objects from input (recursively), and return the number of objects
+///
+/// This function does not allocate.
+pub fn parse_tlvs_der(input: &[u8]) -> Result<usize> {
+    walk(input, MAX_RECURSION, true)
+}
+
+fn walk(input: &[u8], max_depth: usize, der: bool) -> Result<usize> {
+    if max_depth == 0 {
+        return Err(Error::BerMaxDepth);
+    }
+    let mut count = 0;
+    let mut i = input;
+    while !i.is_empty() {
+        let (rem, any) = if der {
+            Any::from_der(i)?
+        } else {
+            Any::from_ber(i)?
+        };
+        count += 1;
+        if any.header.is_constructed() {
+            count += walk(any.data, max_depth - 1, der)?;
+        }
+        i = rem;
+    }
+    Ok(count)
+}
+
+/// Serialize `depth` nested SEQUENCE objects, the innermost containing a NULL object
+pub fn serialize_deep_sequence(depth: usize) -> SerializeResult<Vec<u8>> {
+    let mut v = ().to_der_vec()?;
+    for _ in 0..depth {
+        v = Sequence::new(Cow::Owned(v)).to_der_vec()?;
+    }
+    Ok(v)
+}
+
+/// Generate a DER-encoded OCTET STRING object with `size` bytes of content
+pub fn big_octet_string(size: usize) -> SerializeResult<Vec<u8>> {
+    let data = vec![0xa5; size];
+    OctetString::from(&data[..]).to_der_vec()
+}
+
+/// Parse an OCTET STRING object, and return the length of its content
+///
+/// This function does not allocate (the content is borrowed).
+pub fn parse_big_octet_string(input: &[u8]) -> Result<usize> {
+    let (_, obj) = OctetString::from_der(input)?;
+    Ok(obj.as_ref().len())
+}
+
+/// Serialize `data` as an OCTET STRING object, using a single allocation
+pub fn serialize_octet_string(data: &[u8]) -> SerializeResult<Vec<u8>> {
+    let obj = OctetString::from(data);
+    let mut v = Vec::with_capacity(obj.to_der_len()?);
+    obj.write_der(&mut v)?;
+    Ok(v)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bench_certificates() {
+        let input = certificates(3).expect("input generation failed");
+        // 57 objects per certificate
+        assert_eq!(parse_tlvs_ber(&input), Ok(3 * 57));
+        assert_eq!(parse_tlvs_der(&input), Ok(3 * 57));
+        // BIT STRING objects have no unused bits
+        fn unused_bits(mut i: &[u8], unused: &mut Vec<u8>) {
+            while !i.is_empty() {
+                let (rem, any) = Any::from_der(i).expect("parsing failed");
+                if any.header.is_constructed() {
+                    unused_bits(any.data, unused);
+                } else if any.tag() == Tag::BitString {
+                    unused.push(any.data[0]);
+                }
+                i = rem;
+            }
+        }
+        let mut unused = Vec::new();
+        unused_bits(&input, &mut unused);
+        assert_eq!(unused, vec![0; 3 * 2]);
+    }
+
+    #[test]
+    fn bench_octet_string_deep_sequence() {
+        let input = big_octet_string(1000).expect("input generation failed");
+        assert_eq!(parse_big_octet_string(&input), Ok(1000));
+        let v = serialize_octet_string(&[1; 1000]).expect("serialization failed");
+        assert_eq!(v.capacity(), v.len());
+        let v = serialize_deep_sequence(10).expect("serialization failed");
+        assert_eq!(parse_tlvs_der(&v), Ok(11));
+    }
+
+    #[test]
+    #[should_panic]
+    fn bench_assert_allocations() {
+        let w = workloads()[0];
+        w.assert_allocations(0);
+        w.assert_allocations(1);
+    }
+}
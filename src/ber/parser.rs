@@ -1,8 +1,9 @@
 use crate::error::*;
+use crate::fast;
 use crate::header::*;
-use crate::{BerParser, DerParser, FromBer, Length, Tag};
+use crate::{BerParser, Class, DerParser, Length, Tag, TlvTokenizer};
 use nom::bytes::streaming::take;
-use nom::{Err, Needed, Offset};
+use nom::{Err, Needed};
 use rusticata_macros::custom_check;
 
 /// Default maximum recursion limit
@@ -28,17 +29,32 @@ impl GetObjectContent for BerParser {
         hdr: &'_ Header,
         max_depth: usize,
     ) -> ParseResult<'a, &'a [u8]> {
-        let start_i = i;
-        let (i, _) = ber_skip_object_content(i, hdr, max_depth)?;
-        let len = start_i.offset(i);
-        let (content, i) = start_i.split_at(len);
-        // if len is indefinite, there are 2 extra bytes for EOC
-        if hdr.length == Length::Indefinite {
-            let len = content.len();
-            assert!(len >= 2);
-            Ok((i, &content[..len - 2]))
-        } else {
-            Ok((i, content))
+        if max_depth == 0 {
+            return Err(Err::Error(Error::BerMaxDepth));
+        }
+        if hdr.is_universal() && hdr.tag == Tag::EndOfContent {
+            // X.690 section 8.1.5: end-of-contents octets are 00 00
+            if !hdr.is_end_of_content() {
+                return Err(Err::Error(Error::InvalidEndOfContent));
+            }
+            return Ok((i, &[]));
+        }
+        match hdr.length {
+            Length::Definite(l) => take(l)(i),
+            Length::Indefinite => {
+                hdr.assert_constructed()?;
+                // read nested objects until the end-of-content marker
+                let mut tokenizer = TlvTokenizer::for_indefinite_content(i, max_depth);
+                tokenizer.skip_content().map_err(|e| match e {
+                    Error::Incomplete(n) => Err::Incomplete(n),
+                    e => Err::Error(e),
+                })?;
+                // the tokenizer offset is at most `i.len()`, and includes the 2 bytes of the
+                // end-of-content marker
+                let (content, rem) = fast::split_at(i, tokenizer.offset());
+                // the content does not include the 2 bytes of the end-of-content marker
+                Ok((rem, fast::prefix(content, content.len() - 2)))
+            }
         }
     }
 }
@@ -61,41 +77,6 @@ impl GetObjectContent for DerParser {
     }
 }
 
-/// Skip object content, and return true if object was End-Of-Content
-fn ber_skip_object_content<'a>(
-    i: &'a [u8],
-    hdr: &Header,
-    max_depth: usize,
-) -> ParseResult<'a, bool> {
-    if max_depth == 0 {
-        return Err(Err::Error(Error::BerMaxDepth));
-    }
-    match hdr.length {
-        Length::Definite(l) => {
-            if l == 0 && hdr.tag == Tag::EndOfContent {
-                return Ok((i, true));
-            }
-            let (i, _) = take(l)(i)?;
-            Ok((i, false))
-        }
-        Length::Indefinite => {
-            hdr.assert_constructed()?;
-            // read objects until EndOfContent (00 00)
-            // this is recursive
-            let mut i = i;
-            loop {
-                let (i2, header2) = Header::from_ber(i)?;
-                let (i3, eoc) = ber_skip_object_content(i2, &header2, max_depth - 1)?;
-                if eoc {
-                    // return false, since top object was not EndOfContent
-                    return Ok((i3, false));
-                }
-                i = i3;
-            }
-        }
-    }
-}
-
 /// Try to parse input bytes as u64
 #[inline]
 pub(crate) fn bytes_to_u64(s: &[u8]) -> core::result::Result<u64, Error> {
@@ -110,13 +91,15 @@ pub(crate) fn bytes_to_u64(s: &[u8]) -> core::result::Result<u64, Error> {
     Ok(u)
 }
 
-pub(crate) fn parse_identifier(i: &[u8]) -> ParseResult<(u8, u8, u32, &[u8])> {
+pub(crate) fn parse_identifier(i: &[u8]) -> ParseResult<'_, (Class, u8, u32, &[u8])> {
     if i.is_empty() {
         Err(Err::Incomplete(Needed::new(1)))
     } else {
-        let a = i[0] >> 6;
-        let b = if i[0] & 0b0010_0000 != 0 { 1 } else { 0 };
-        let mut c = u32::from(i[0] & 0b0001_1111);
+        // `i` is not empty
+        let first = fast::byte_at(i, 0);
+        let a = Class::from_identifier_octet(first);
+        let b = if first & 0b0010_0000 != 0 { 1 } else { 0 };
+        let mut c = u32::from(first & 0b0001_1111);
 
         let mut tag_byte_count = 1;
 
@@ -129,9 +112,13 @@ pub(crate) fn parse_identifier(i: &[u8]) -> ParseResult<(u8, u8, u32, &[u8])> {
                 // With tag defined as u32 the most we can fit in is four tag bytes.
                 // (X.690 doesn't actually specify maximum tag width.)
                 custom_check!(i, tag_byte_count > 5, Error::InvalidTag)?;
+                // Tag number must not overflow (the last byte could have too many bits)
+                custom_check!(i, c > (u32::MAX >> 7), Error::InvalidTag)?;
 
-                c = (c << 7) | (u32::from(i[tag_byte_count]) & 0x7f);
-                let done = i[tag_byte_count] & 0x80 == 0;
+                // `tag_byte_count < i.len()` was checked above
+                let byte = fast::byte_at(i, tag_byte_count);
+                c = (c << 7) | (u32::from(byte) & 0x7f);
+                let done = byte & 0x80 == 0;
                 tag_byte_count += 1;
                 if done {
                     break;
@@ -139,20 +126,22 @@ pub(crate) fn parse_identifier(i: &[u8]) -> ParseResult<(u8, u8, u32, &[u8])> {
             }
         }
 
-        let (raw_tag, rem) = i.split_at(tag_byte_count);
+        // `tag_byte_count <= i.len()`: the last tag byte was read from `i`
+        let (raw_tag, rem) = fast::split_at(i, tag_byte_count);
 
         Ok((rem, (a, b, c, raw_tag)))
     }
 }
 
 /// Return the MSB and the rest of the first byte, or an error
-pub(crate) fn parse_ber_length_byte(i: &[u8]) -> ParseResult<(u8, u8)> {
+pub(crate) fn parse_ber_length_byte(i: &[u8]) -> ParseResult<'_, (u8, u8)> {
     if i.is_empty() {
         Err(Err::Incomplete(Needed::new(1)))
     } else {
-        let a = i[0] >> 7;
-        let b = i[0] & 0b0111_1111;
-        Ok((&i[1..], (a, b)))
+        // `i` is not empty
+        let (first, rem) = fast::split_at(i, 1);
+        let first = fast::byte_at(first, 0);
+        Ok((rem, (first >> 7, first & 0b0111_1111)))
     }
 }
 
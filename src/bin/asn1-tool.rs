@@ -0,0 +1,62 @@
+//! Command-line interface to the operations of `asn1_rs::tools`
+//!
+//! Usage: `asn1-tool <command> [args] [FILE]`, reading from standard input if `FILE` is not
+//! provided or is `-`.
+
+use asn1_rs::tools;
+use std::error::Error;
+use std::io::{self, Read, Write};
+use std::{env, fs, process};
+
+const USAGE: &str = "Usage: asn1-tool <command> [FILE]
+
+Commands:
+    dump                Print the structure of BER/DER data
+    validate-der        Check that data is valid DER
+    ber2der             Re-encode BER data using DER (written to standard output)
+    extract-path PATH   Extract the object at PATH (raw encoding, written to standard output)";
+
+fn read_input(path: Option<&String>) -> io::Result<Vec<u8>> {
+    match path.map(String::as_str) {
+        None | Some("-") => {
+            let mut v = Vec::new();
+            io::stdin().read_to_end(&mut v)?;
+            Ok(v)
+        }
+        Some(path) => fs::read(path),
+    }
+}
+
+fn run(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let mut stdout = io::stdout();
+    match args.first().map(String::as_str) {
+        Some("dump") => {
+            let input = read_input(args.get(1))?;
+            print!("{}", tools::dump(&input)?);
+        }
+        Some("validate-der") => {
+            let input = read_input(args.get(1))?;
+            let count = tools::validate_der(&input)?;
+            println!("OK ({} objects)", count);
+        }
+        Some("ber2der") => {
+            let input = read_input(args.get(1))?;
+            stdout.write_all(&tools::ber_to_der(&input)?)?;
+        }
+        Some("extract-path") => {
+            let path = args.get(1).ok_or(USAGE)?;
+            let input = read_input(args.get(2))?;
+            stdout.write_all(tools::extract_path(&input, path)?)?;
+        }
+        _ => return Err(USAGE.into()),
+    }
+    Ok(())
+}
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    if let Err(e) = run(&args) {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
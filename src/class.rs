@@ -5,7 +5,8 @@ use core::fmt;
 pub struct BerClassFromIntError(pub(crate) ());
 
 /// BER Object class of tag
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum Class {
     /// `Universal` class of tags (`0b00`)
@@ -28,6 +29,59 @@ impl Class {
     /// `Private` class of tags (`0b11`)
     pub const PRIVATE: u8 = 0b11;
 
+    /// Build a class from its 2-bit value, or return `None` if `bits` is larger than `0b11`
+    ///
+    /// This is similar to `TryFrom<u8>`, but can be used in const contexts (for ex. to convert
+    /// the `CLASS` parameter of [`TaggedValue`](crate::TaggedValue)).
+    pub const fn from_bits(bits: u8) -> Option<Class> {
+        match bits {
+            0b00 => Some(Class::Universal),
+            0b01 => Some(Class::Application),
+            0b10 => Some(Class::ContextSpecific),
+            0b11 => Some(Class::Private),
+            _ => None,
+        }
+    }
+
+    /// Return the class encoded in the first identifier octet of an object (bits 8 and 7)
+    #[inline]
+    pub const fn from_identifier_octet(b0: u8) -> Class {
+        match b0 >> 6 {
+            0b00 => Class::Universal,
+            0b01 => Class::Application,
+            0b10 => Class::ContextSpecific,
+            _ => Class::Private,
+        }
+    }
+
+    /// Test if class is Universal
+    #[inline]
+    pub const fn is_universal(&self) -> bool {
+        matches!(self, Class::Universal)
+    }
+
+    /// Test if class is Application
+    #[inline]
+    pub const fn is_application(&self) -> bool {
+        matches!(self, Class::Application)
+    }
+
+    /// Test if class is Context-specific
+    #[inline]
+    pub const fn is_context_specific(&self) -> bool {
+        matches!(self, Class::ContextSpecific)
+    }
+
+    /// Test if class is Private
+    #[inline]
+    pub const fn is_private(&self) -> bool {
+        matches!(self, Class::Private)
+    }
+
+    /// Return an error if class is not `class`
+    ///
+    /// The error is [`Error::UnexpectedClass`](crate::Error::UnexpectedClass), holding both
+    /// classes.
     pub const fn assert_eq(&self, class: Class) -> Result<(), crate::error::Error> {
         if *self as u8 == class as u8 {
             Ok(())
@@ -91,4 +145,27 @@ mod tests {
         assert!(Class::try_from(0b11).is_ok());
         assert!(Class::try_from(4).is_err());
     }
+
+    #[test]
+    fn class_helpers() {
+        assert_eq!(Class::from_bits(0b01), Some(Class::Application));
+        assert_eq!(Class::from_bits(0b11), Some(Class::Private));
+        assert_eq!(Class::from_bits(4), None);
+        assert_eq!(Class::from_identifier_octet(0x30), Class::Universal);
+        assert_eq!(Class::from_identifier_octet(0x61), Class::Application);
+        assert_eq!(Class::from_identifier_octet(0xa0), Class::ContextSpecific);
+        assert_eq!(Class::from_identifier_octet(0xdf), Class::Private);
+        assert!(Class::Universal.is_universal());
+        assert!(Class::Application.is_application());
+        assert!(Class::ContextSpecific.is_context_specific());
+        assert!(Class::Private.is_private());
+        assert!(!Class::Private.is_application());
+        assert_eq!(
+            Class::Private.assert_eq(Class::Application),
+            Err(crate::Error::UnexpectedClass {
+                expected: Some(Class::Application),
+                actual: Class::Private,
+            })
+        );
+    }
 }
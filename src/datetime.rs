@@ -1,11 +1,17 @@
-use crate::{Result, Tag};
+use crate::{Error, Result, Tag};
 use alloc::format;
-use alloc::string::ToString;
+use alloc::string::{String, ToString};
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::convert::TryFrom;
 use core::fmt;
+use core::time::Duration;
 #[cfg(feature = "datetime")]
 use time::OffsetDateTime;
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum ASN1TimeZone {
     /// No timezone provided
     Undefined,
@@ -13,11 +19,146 @@ pub enum ASN1TimeZone {
     Z,
     /// Local zone, with offset to coordinated universal time
     ///
-    /// `(offset_hour, offset_minute)`
+    /// `(offset_hour, offset_minute)`. The offset is negative if either field is negative: the
+    /// sign is carried by the hour (`-0130` is `Offset(-1, 30)`), or by the minute if the hour
+    /// is zero (`-0030` is `Offset(0, -30)`).
     Offset(i8, i8),
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+impl ASN1TimeZone {
+    /// Build an offset time zone from its sign and absolute value (`hh` hours and `mm` minutes)
+    pub(crate) fn from_offset(negative: bool, hh: u8, mm: u8) -> Self {
+        let (hh, mm) = (hh as i8, mm as i8);
+        match (negative, hh) {
+            (false, _) => ASN1TimeZone::Offset(hh, mm),
+            (true, 0) => ASN1TimeZone::Offset(0, -mm),
+            (true, _) => ASN1TimeZone::Offset(-hh, mm),
+        }
+    }
+
+    /// Return the offset to UTC, in minutes (0 if this is not an offset)
+    pub(crate) fn offset_minutes(&self) -> i64 {
+        match *self {
+            ASN1TimeZone::Offset(h, m) => {
+                let minutes = i64::from(h.unsigned_abs()) * 60 + i64::from(m.unsigned_abs());
+                if h < 0 || m < 0 {
+                    -minutes
+                } else {
+                    minutes
+                }
+            }
+            ASN1TimeZone::Undefined | ASN1TimeZone::Z => 0,
+        }
+    }
+
+    /// Return the offset to UTC, formatted as `+hhmm` or `-hhmm` (empty if this is not an
+    /// offset)
+    pub(crate) fn offset_string(&self) -> String {
+        match *self {
+            ASN1TimeZone::Offset(..) => {
+                let minutes = self.offset_minutes();
+                let s = if minutes < 0 { '-' } else { '+' };
+                format!("{}{:02}{:02}", s, minutes.abs() / 60, minutes.abs() % 60)
+            }
+            ASN1TimeZone::Undefined | ASN1TimeZone::Z => String::new(),
+        }
+    }
+}
+
+/// Resolution of the offset to UTC of local times
+///
+/// `GeneralizedTime` values can be encoded as local time, without time zone
+/// ([`ASN1TimeZone::Undefined`]). The corresponding instant depends on the time zone of the
+/// encoder, which is not part of the value: conversions usually assume UTC. Implement this trait
+/// to provide the offset instead (for ex. from a time zone database), see
+/// [`ASN1DateTime::resolve_local`].
+///
+/// This trait is implemented for [`FixedOffset`], and for closures
+/// `Fn(&ASN1DateTime) -> Option<(i8, i8)>`.
+pub trait LocalTimeZone {
+    /// Return the offset to UTC `(offset_hour, offset_minute)` of the local time `dt`, or
+    /// `None` if it cannot be resolved (for ex. a time skipped by a daylight saving change)
+    fn offset_for(&self, dt: &ASN1DateTime) -> Option<(i8, i8)>;
+}
+
+/// A [`LocalTimeZone`] with a fixed offset to UTC `(offset_hour, offset_minute)`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FixedOffset(pub i8, pub i8);
+
+impl LocalTimeZone for FixedOffset {
+    fn offset_for(&self, _dt: &ASN1DateTime) -> Option<(i8, i8)> {
+        Some((self.0, self.1))
+    }
+}
+
+impl<F> LocalTimeZone for F
+where
+    F: Fn(&ASN1DateTime) -> Option<(i8, i8)>,
+{
+    fn offset_for(&self, dt: &ASN1DateTime) -> Option<(i8, i8)> {
+        self(dt)
+    }
+}
+
+/// Representation of a parsed time string
+///
+/// BER allows several encodings of the same time value (for ex. seconds can be omitted, and
+/// fractional seconds can have trailing zeros). This records the choices made by the encoder,
+/// so an object can be written back using the exact same representation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ASN1TimeFormat {
+    /// True if the seconds element is present
+    pub seconds: bool,
+    /// Number of digits of the fractional seconds (0 if absent)
+    pub fraction_digits: u8,
+    /// True if the decimal separator is a comma (`,`) instead of a point (`.`)
+    pub decimal_comma: bool,
+}
+
+impl ASN1TimeFormat {
+    /// Format with seconds, and without fractional seconds
+    pub const fn new() -> Self {
+        ASN1TimeFormat {
+            seconds: true,
+            fraction_digits: 0,
+            decimal_comma: false,
+        }
+    }
+
+    /// Return the fractional seconds of `millisecond`, with the separator and number of digits
+    /// of this format (empty if there are no digits)
+    ///
+    /// Returns an error if `millisecond` cannot be represented exactly (see
+    /// [`encode_fraction_millis`]).
+    #[cfg(feature = "std")]
+    pub(crate) fn fraction(&self, tag: Tag, millisecond: Option<u16>) -> Result<Vec<u8>> {
+        let ms = match (millisecond, self.fraction_digits) {
+            (Some(ms), d) if d > 0 => ms,
+            _ => return Ok(Vec::new()),
+        };
+        let digits = encode_fraction_millis(tag, ms, self.fraction_digits)?;
+        let mut v = Vec::with_capacity(1 + digits.len());
+        v.push(if self.decimal_comma { b',' } else { b'.' });
+        v.extend_from_slice(&digits[..usize::from(self.fraction_digits)]);
+        Ok(v)
+    }
+}
+
+impl Default for ASN1TimeFormat {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Date and time, as encoded in ASN.1 time types
+///
+/// Objects are ordered by the instant they represent, using their time zone to convert them to
+/// UTC (an undefined time zone is considered as UTC). Objects representing the same instant in
+/// different time zones are then ordered by their fields, so ordering is consistent with
+/// equality.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct ASN1DateTime {
     pub year: u32,
     pub month: u8,
@@ -53,11 +194,190 @@ impl ASN1DateTime {
         }
     }
 
+    /// Create a new `ASN1DateTime`, checking that all fields are in range and that the date
+    /// exists in the calendar (see [`ASN1DateTime::validate`])
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_new(
+        year: u32,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+        millisecond: Option<u16>,
+        tz: ASN1TimeZone,
+    ) -> Result<Self> {
+        let dt = Self::new(year, month, day, hour, minute, second, millisecond, tz);
+        dt.validate()?;
+        Ok(dt)
+    }
+
+    /// Check that all fields are in range, and that the date exists in the calendar
+    ///
+    /// The date is checked using the proleptic Gregorian calendar (number of days per month,
+    /// leap years). Returns [`Error::InvalidCalendarDate`] if the date does not exist (for ex.
+    /// February 31st, or February 29th of a non-leap year), and [`Error::InvalidDateTime`] if the
+    /// time or time zone is out of range.
+    ///
+    /// For `UTCTime` objects, `year` is the two-digit year: year `00` is considered a leap year
+    /// (2000, following RFC 5280 section 4.1.2.5.1).
+    ///
+    /// ```rust
+    /// use asn1_rs::{ASN1DateTime, ASN1TimeZone, Error};
+    ///
+    /// let dt = ASN1DateTime::try_new(2024, 2, 29, 12, 0, 0, None, ASN1TimeZone::Z);
+    /// assert!(dt.is_ok());
+    /// let dt = ASN1DateTime::try_new(2023, 2, 29, 12, 0, 0, None, ASN1TimeZone::Z);
+    /// assert_eq!(dt, Err(Error::InvalidCalendarDate { year: 2023, month: 2, day: 29 }));
+    /// ```
+    pub fn validate(&self) -> Result<()> {
+        check_calendar_date(self.year, self.month, self.day)?;
+        if self.hour > 23 || self.minute > 59 || self.second > 59 {
+            return Err(Error::InvalidDateTime);
+        }
+        if matches!(self.millisecond, Some(ms) if ms > 999) {
+            return Err(Error::InvalidDateTime);
+        }
+        if let ASN1TimeZone::Offset(h, m) = self.tz {
+            if h.unsigned_abs() > 23 || m.unsigned_abs() > 59 {
+                return Err(Error::InvalidDateTime);
+            }
+        }
+        Ok(())
+    }
+
+    /// Return the number of milliseconds since the Unix epoch (1970-01-01 00:00:00 UTC)
+    ///
+    /// The date is not validated: out-of-range fields (for ex. month 13) are accumulated.
+    pub fn unix_timestamp_millis(&self) -> i128 {
+        let days = days_from_civil(i128::from(self.year), self.month, self.day);
+        let seconds = days * 86_400
+            + i128::from(self.hour) * 3600
+            + i128::from(self.minute) * 60
+            + i128::from(self.second)
+            - i128::from(self.tz.offset_minutes()) * 60;
+        seconds * 1000 + i128::from(self.millisecond.unwrap_or(0))
+    }
+
+    /// Return the same instant, converted to UTC
+    ///
+    /// If the time zone is an offset, date and time are shifted and the time zone is set to
+    /// [`ASN1TimeZone::Z`]. Otherwise, the object is returned unchanged (an undefined time zone is
+    /// considered as UTC, but kept).
+    ///
+    /// Returns [`Error::InvalidDateTime`] if the resulting year is negative.
+    ///
+    /// ```rust
+    /// use asn1_rs::{ASN1DateTime, ASN1TimeZone};
+    ///
+    /// let dt = ASN1DateTime::new(2022, 1, 1, 1, 30, 0, None, ASN1TimeZone::Offset(2, 0));
+    /// let utc = dt.to_utc().expect("invalid date");
+    /// assert_eq!(utc, ASN1DateTime::new(2021, 12, 31, 23, 30, 0, None, ASN1TimeZone::Z));
+    /// ```
+    pub fn to_utc(&self) -> Result<Self> {
+        if !matches!(self.tz, ASN1TimeZone::Offset(..)) {
+            return Ok(self.clone());
+        }
+        let days = days_from_civil(i128::from(self.year), self.month, self.day);
+        let minutes = days * 1440 + i128::from(self.hour) * 60 + i128::from(self.minute)
+            - i128::from(self.tz.offset_minutes());
+        let (year, month, day) = civil_from_days(minutes.div_euclid(1440));
+        let year = u32::try_from(year).map_err(|_| Error::InvalidDateTime)?;
+        let minutes = minutes.rem_euclid(1440);
+        Ok(ASN1DateTime {
+            year,
+            month,
+            day,
+            hour: (minutes / 60) as u8,
+            minute: (minutes % 60) as u8,
+            second: self.second,
+            millisecond: self.millisecond,
+            tz: ASN1TimeZone::Z,
+        })
+    }
+
+    /// Return the same value, with the time zone of local times resolved using `zone`
+    ///
+    /// If the time zone is [`ASN1TimeZone::Undefined`], it is set to the offset returned by
+    /// `zone`. Otherwise, the object is returned unchanged.
+    ///
+    /// Returns [`Error::InvalidDateTime`] if the offset cannot be resolved, or is invalid.
+    ///
+    /// ```rust
+    /// use asn1_rs::{ASN1DateTime, ASN1TimeZone, FixedOffset};
+    ///
+    /// let dt = ASN1DateTime::new(2022, 1, 1, 1, 30, 0, None, ASN1TimeZone::Undefined);
+    /// let local = dt.resolve_local(&FixedOffset(2, 0)).expect("invalid offset");
+    /// assert_eq!(local.tz, ASN1TimeZone::Offset(2, 0));
+    /// ```
+    pub fn resolve_local<Z>(&self, zone: &Z) -> Result<Self>
+    where
+        Z: LocalTimeZone + ?Sized,
+    {
+        if self.tz != ASN1TimeZone::Undefined {
+            return Ok(self.clone());
+        }
+        let (h, m) = zone.offset_for(self).ok_or(Error::InvalidDateTime)?;
+        if h.unsigned_abs() > 23 || m.unsigned_abs() > 59 {
+            return Err(Error::InvalidDateTime);
+        }
+        Ok(ASN1DateTime {
+            tz: ASN1TimeZone::Offset(h, m),
+            ..self.clone()
+        })
+    }
+
+    /// Return the same instant, converted to UTC, resolving local times using `zone`
+    ///
+    /// This is the same as [`to_utc`](Self::to_utc), except that local times are not assumed to
+    /// be UTC. See [`resolve_local`](Self::resolve_local).
+    pub fn to_utc_with<Z>(&self, zone: &Z) -> Result<Self>
+    where
+        Z: LocalTimeZone + ?Sized,
+    {
+        self.resolve_local(zone)?.to_utc()
+    }
+
+    /// Return the duration elapsed from `earlier` to `self`
+    ///
+    /// Returns `None` if `earlier` is after `self`.
+    pub fn duration_since(&self, earlier: &ASN1DateTime) -> Option<Duration> {
+        let diff = self.unix_timestamp_millis() - earlier.unix_timestamp_millis();
+        if diff < 0 {
+            return None;
+        }
+        let diff = u64::try_from(diff).ok()?;
+        Some(Duration::from_millis(diff))
+    }
+
+    /// Return true if `self` is in the validity window `[not_before, not_after]` (inclusive)
+    ///
+    /// This follows the validity period of X.509 certificates (RFC 5280 section 4.1.2.5).
+    pub fn is_within(&self, not_before: &ASN1DateTime, not_after: &ASN1DateTime) -> bool {
+        let t = self.unix_timestamp_millis();
+        not_before.unix_timestamp_millis() <= t && t <= not_after.unix_timestamp_millis()
+    }
+
+    /// Return true if `self`, used as an expiry time, is strictly before `now`
+    ///
+    /// The current time is never read from the system clock: `now` must be provided by the
+    /// caller, which makes validity checks deterministic (for ex. when replaying data).
+    ///
+    /// ```rust
+    /// use asn1_rs::{ASN1DateTime, ASN1TimeZone};
+    ///
+    /// let not_after = ASN1DateTime::new(2024, 1, 1, 0, 0, 0, None, ASN1TimeZone::Z);
+    /// let now = ASN1DateTime::new(2024, 1, 1, 1, 30, 0, None, ASN1TimeZone::Offset(2, 0));
+    /// assert!(!not_after.is_expired_at(&now));
+    /// ```
+    pub fn is_expired_at(&self, now: &ASN1DateTime) -> bool {
+        now.unix_timestamp_millis() > self.unix_timestamp_millis()
+    }
+
     #[cfg(feature = "datetime")]
     fn to_time_datetime(
         &self,
     ) -> core::result::Result<OffsetDateTime, time::error::ComponentRange> {
-        use std::convert::TryFrom;
         use time::{Date, Month, PrimitiveDateTime, Time, UtcOffset};
 
         let month = Month::try_from(self.month as u8)?;
@@ -69,10 +389,7 @@ impl ASN1DateTime {
             self.millisecond.unwrap_or(0),
         )?;
         let primitive_date = PrimitiveDateTime::new(date, time);
-        let offset = match self.tz {
-            ASN1TimeZone::Offset(h, m) => UtcOffset::from_hms(h, m, 0)?,
-            ASN1TimeZone::Undefined | ASN1TimeZone::Z => UtcOffset::UTC,
-        };
+        let offset = UtcOffset::from_whole_seconds(self.tz.offset_minutes() as i32 * 60)?;
         Ok(primitive_date.assume_offset(offset))
     }
 
@@ -84,6 +401,41 @@ impl ASN1DateTime {
     }
 }
 
+impl PartialOrd for ASN1DateTime {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ASN1DateTime {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.unix_timestamp_millis()
+            .cmp(&other.unix_timestamp_millis())
+            .then_with(|| {
+                (
+                    self.year,
+                    self.month,
+                    self.day,
+                    self.hour,
+                    self.minute,
+                    self.second,
+                    self.millisecond,
+                    self.tz,
+                )
+                    .cmp(&(
+                        other.year,
+                        other.month,
+                        other.day,
+                        other.hour,
+                        other.minute,
+                        other.second,
+                        other.millisecond,
+                        other.tz,
+                    ))
+            })
+    }
+}
+
 impl fmt::Display for ASN1DateTime {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let fractional = match self.millisecond {
@@ -98,11 +450,541 @@ impl fmt::Display for ASN1DateTime {
     }
 }
 
-/// Decode 2-digit decimal value
-pub(crate) fn decode_decimal(tag: Tag, hi: u8, lo: u8) -> Result<u8> {
-    if (b'0'..=b'9').contains(&hi) && (b'0'..=b'9').contains(&lo) {
-        Ok((hi - b'0') as u8 * 10 + (lo - b'0') as u8)
-    } else {
-        Err(tag.invalid_value("expected digit"))
+/// Return the number of days since the Unix epoch of a civil date (proleptic Gregorian calendar)
+///
+/// Out-of-range months and days are accumulated.
+fn days_from_civil(year: i128, month: u8, day: u8) -> i128 {
+    let (m, d) = (i128::from(month), i128::from(day));
+    let y = year - if m <= 2 { 1 } else { 0 };
+    let era = (if y >= 0 { y } else { y - 399 }) / 400;
+    let yoe = y - era * 400;
+    let mp = (m + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Return the civil date `(year, month, day)` of a number of days since the Unix epoch
+fn civil_from_days(days: i128) -> (i128, u8, u8) {
+    let z = days + 719_468;
+    let era = (if z >= 0 { z } else { z - 146_096 }) / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u8;
+    let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u8;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// Return true if `year` is a leap year (proleptic Gregorian calendar)
+pub const fn is_leap_year(year: u32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+/// Return the number of days of `month` (1 to 12) in `year`, or `None` if `month` is invalid
+pub const fn days_in_month(year: u32, month: u8) -> Option<u8> {
+    let days = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        _ => return None,
+    };
+    Some(days)
+}
+
+/// Check that `day` of `month` exists in `year`
+pub(crate) fn check_calendar_date(year: u32, month: u8, day: u8) -> Result<()> {
+    match days_in_month(year, month) {
+        Some(n) if day >= 1 && day <= n => Ok(()),
+        _ => Err(Error::InvalidCalendarDate { year, month, day }),
+    }
+}
+
+/// Decode `N` ASCII decimal digits
+fn decode_digits<const N: usize>(tag: Tag, digits: &[u8; N]) -> Result<u16> {
+    digits.iter().try_fold(0u16, |acc, &d| {
+        if d.is_ascii_digit() {
+            Ok(acc * 10 + u16::from(d - b'0'))
+        } else {
+            Err(tag.invalid_value("expected digit"))
+        }
+    })
+}
+
+/// Encode `value` using exactly `N` ASCII decimal digits
+fn encode_digits<const N: usize>(tag: Tag, value: u16) -> Result<[u8; N]> {
+    let mut out = [b'0'; N];
+    let mut v = value;
+    for d in out.iter_mut().rev() {
+        *d = b'0' + (v % 10) as u8;
+        v /= 10;
+    }
+    if v != 0 {
+        return Err(tag.invalid_value("value too large for the number of digits"));
+    }
+    Ok(out)
+}
+
+/// Decode a 2-digit decimal value (for ex. month, day, hour, minute or second)
+///
+/// Returns an error (for object type `tag`) if a character is not a digit.
+///
+/// ```rust
+/// use asn1_rs::{decode_decimal, Tag};
+///
+/// assert_eq!(decode_decimal(Tag::GeneralizedTime, b'0', b'7'), Ok(7));
+/// assert!(decode_decimal(Tag::GeneralizedTime, b'0', b'Z').is_err());
+/// ```
+pub fn decode_decimal(tag: Tag, hi: u8, lo: u8) -> Result<u8> {
+    decode_digits(tag, &[hi, lo]).map(|v| v as u8)
+}
+
+/// Decode a 3-digit decimal value (for ex. milliseconds)
+///
+/// See [`decode_decimal`].
+pub fn decode_decimal3(tag: Tag, digits: &[u8; 3]) -> Result<u16> {
+    decode_digits(tag, digits)
+}
+
+/// Decode a 4-digit decimal value (for ex. a year)
+///
+/// See [`decode_decimal`].
+pub fn decode_decimal4(tag: Tag, digits: &[u8; 4]) -> Result<u16> {
+    decode_digits(tag, digits)
+}
+
+/// Encode `value` as 2 decimal digits, with a leading zero if needed
+///
+/// Returns an error (for object type `tag`) if `value` is greater than 99.
+///
+/// ```rust
+/// use asn1_rs::{encode_decimal, Tag};
+///
+/// assert_eq!(encode_decimal(Tag::UtcTime, 7), Ok(*b"07"));
+/// assert!(encode_decimal(Tag::UtcTime, 100).is_err());
+/// ```
+pub fn encode_decimal(tag: Tag, value: u8) -> Result<[u8; 2]> {
+    encode_digits(tag, u16::from(value))
+}
+
+/// Encode `value` as 3 decimal digits, with leading zeroes if needed
+///
+/// See [`encode_decimal`].
+pub fn encode_decimal3(tag: Tag, value: u16) -> Result<[u8; 3]> {
+    encode_digits(tag, value)
+}
+
+/// Encode `value` as 4 decimal digits, with leading zeroes if needed
+///
+/// See [`encode_decimal`].
+pub fn encode_decimal4(tag: Tag, value: u16) -> Result<[u8; 4]> {
+    encode_digits(tag, value)
+}
+
+/// Decode the digits of fractional seconds (without the separator) as milliseconds
+///
+/// `digits` must contain 1 to 3 digits, which are scaled: `"5"` is 500 milliseconds, `"05"` is
+/// 50 milliseconds. More digits are rejected, since they cannot be represented.
+///
+/// ```rust
+/// use asn1_rs::{decode_fraction_millis, Tag};
+///
+/// assert_eq!(decode_fraction_millis(Tag::GeneralizedTime, b"5"), Ok(500));
+/// assert_eq!(decode_fraction_millis(Tag::GeneralizedTime, b"123"), Ok(123));
+/// assert!(decode_fraction_millis(Tag::GeneralizedTime, b"1234").is_err());
+/// assert!(decode_fraction_millis(Tag::GeneralizedTime, b"").is_err());
+/// ```
+pub fn decode_fraction_millis(tag: Tag, digits: &[u8]) -> Result<u16> {
+    match *digits {
+        [] => Err(tag.invalid_value("expected fractional digits")),
+        [d1] => decode_digits(tag, &[d1]).map(|v| v * 100),
+        [d1, d2] => decode_digits(tag, &[d1, d2]).map(|v| v * 10),
+        [d1, d2, d3] => decode_digits(tag, &[d1, d2, d3]),
+        _ => Err(tag.invalid_value("expected 1 to 3 fractional digits")),
+    }
+}
+
+/// Encode `millisecond` as `digits` (1 to 3) digits of fractional seconds (without separator)
+///
+/// Only the first `digits` bytes of the returned array are used. Returns an error if
+/// `millisecond` is not less than 1000, if `digits` is not between 1 and 3, or if the value
+/// cannot be represented exactly with `digits` digits.
+///
+/// ```rust
+/// use asn1_rs::{encode_fraction_millis, Tag};
+///
+/// let d = encode_fraction_millis(Tag::GeneralizedTime, 500, 1).unwrap();
+/// assert_eq!(&d[..1], b"5");
+/// assert!(encode_fraction_millis(Tag::GeneralizedTime, 550, 1).is_err());
+/// ```
+pub fn encode_fraction_millis(tag: Tag, millisecond: u16, digits: u8) -> Result<[u8; 3]> {
+    if millisecond >= 1000 || digits == 0 || digits > 3 {
+        return Err(tag.invalid_value("invalid fractional seconds"));
+    }
+    let scale = 10u16.pow(3 - u32::from(digits));
+    if millisecond % scale != 0 {
+        return Err(tag.invalid_value("fractional seconds do not fit in the number of digits"));
+    }
+    let mut out = [b'0'; 3];
+    let value = millisecond / scale;
+    out[..usize::from(digits)]
+        .copy_from_slice(&encode_digits::<3>(tag, value)?[3 - usize::from(digits)..]);
+    Ok(out)
+}
+
+/// Encode the fields of `dt` following the year (`MMDDhhmm`, followed by `ss` if `seconds` is
+/// true), using 2 digits for each field
+#[cfg(feature = "std")]
+pub(crate) fn encode_time_fields(tag: Tag, dt: &ASN1DateTime, seconds: bool) -> Result<Vec<u8>> {
+    let fields = [dt.month, dt.day, dt.hour, dt.minute, dt.second];
+    let fields = if seconds { &fields[..] } else { &fields[..4] };
+    let mut v = Vec::with_capacity(2 * fields.len());
+    for &field in fields {
+        v.extend_from_slice(&encode_decimal(tag, field)?);
+    }
+    Ok(v)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use core::time::Duration;
+
+    #[test]
+    fn datetime_ord_timezones() {
+        let utc = ASN1DateTime::new(2022, 3, 1, 12, 0, 0, None, ASN1TimeZone::Z);
+        // 12:00Z == 13:30+0130 == 10:00-0200
+        let plus = ASN1DateTime::new(2022, 3, 1, 13, 30, 0, None, ASN1TimeZone::Offset(1, 30));
+        let minus = ASN1DateTime::new(2022, 3, 1, 10, 0, 0, None, ASN1TimeZone::Offset(-2, 0));
+        assert_eq!(utc.unix_timestamp_millis(), 1_646_136_000_000);
+        assert_eq!(utc.unix_timestamp_millis(), plus.unix_timestamp_millis());
+        assert_eq!(utc.unix_timestamp_millis(), minus.unix_timestamp_millis());
+        // 11:00-0200 is after 12:00Z, even if fields are smaller
+        let later = ASN1DateTime::new(2022, 3, 1, 11, 0, 0, None, ASN1TimeZone::Offset(-2, 0));
+        assert!(later > utc);
+        assert!(later > plus);
+        // day change, across a leap year February
+        let prev = ASN1DateTime::new(2024, 2, 29, 23, 0, 0, None, ASN1TimeZone::Z);
+        let next = ASN1DateTime::new(2024, 3, 1, 0, 30, 0, None, ASN1TimeZone::Offset(1, 0));
+        assert!(prev < next);
+    }
+
+    #[test]
+    fn datetime_decimal_helpers() {
+        let tag = Tag::GeneralizedTime;
+        assert_eq!(decode_decimal4(tag, b"2024"), Ok(2024));
+        assert_eq!(decode_decimal3(tag, b"099"), Ok(99));
+        assert!(decode_decimal4(tag, b"20a4").is_err());
+        assert_eq!(encode_decimal4(tag, 987), Ok(*b"0987"));
+        assert_eq!(encode_decimal3(tag, 5), Ok(*b"005"));
+        assert!(encode_decimal3(tag, 1000).is_err());
+        // round-trip
+        for v in 0..=99 {
+            let [hi, lo] = encode_decimal(tag, v).unwrap();
+            assert_eq!(decode_decimal(tag, hi, lo), Ok(v));
+        }
+        // fractional seconds
+        assert_eq!(decode_fraction_millis(tag, b"05"), Ok(50));
+        assert!(decode_fraction_millis(tag, b"").is_err());
+        assert!(decode_fraction_millis(tag, b"5Z").is_err());
+        assert_eq!(encode_fraction_millis(tag, 50, 2), Ok(*b"050"));
+        assert_eq!(&encode_fraction_millis(tag, 50, 2).unwrap()[..2], b"05");
+        assert_eq!(encode_fraction_millis(tag, 123, 3), Ok(*b"123"));
+        assert!(encode_fraction_millis(tag, 123, 2).is_err());
+        assert!(encode_fraction_millis(tag, 1000, 3).is_err());
+        assert!(encode_fraction_millis(tag, 0, 4).is_err());
+    }
+
+    #[test]
+    fn datetime_duration_validity() {
+        let not_before = ASN1DateTime::new(2021, 12, 31, 23, 59, 59, None, ASN1TimeZone::Z);
+        let not_after = ASN1DateTime::new(2022, 1, 1, 0, 0, 1, Some(500), ASN1TimeZone::Z);
+        assert_eq!(
+            not_after.duration_since(&not_before),
+            Some(Duration::from_millis(2500))
+        );
+        assert_eq!(not_before.duration_since(&not_after), None);
+        let t = ASN1DateTime::new(2022, 1, 1, 0, 0, 0, None, ASN1TimeZone::Z);
+        assert!(t.is_within(&not_before, &not_after));
+        // bounds are inclusive
+        assert!(not_before.is_within(&not_before, &not_after));
+        assert!(not_after.is_within(&not_before, &not_after));
+        let t = ASN1DateTime::new(2022, 1, 1, 1, 0, 0, None, ASN1TimeZone::Offset(1, 0));
+        assert!(t.is_within(&not_before, &not_after));
+        let t = ASN1DateTime::new(2022, 1, 1, 0, 0, 2, None, ASN1TimeZone::Z);
+        assert!(!t.is_within(&not_before, &not_after));
+    }
+
+    #[test]
+    fn utctime_rfc5280_year() {
+        let t49 = UtcTime::from_bytes(b"491231235959Z").unwrap();
+        let t50 = UtcTime::from_bytes(b"500101000000Z").unwrap();
+        // 2049 is after 1950
+        assert!(t49 > t50);
+        assert!(t49.unix_timestamp_millis() > t50.unix_timestamp_millis());
+        let not_before = ASN1DateTime::new(2049, 1, 1, 0, 0, 0, None, ASN1TimeZone::Z);
+        let not_after = ASN1DateTime::new(2050, 1, 1, 0, 0, 0, None, ASN1TimeZone::Z);
+        assert!(t49.is_within(&not_before, &not_after));
+        assert!(!t50.is_within(&not_before, &not_after));
+        // same instant, with a full year: ordered, but not equal
+        let full = UtcTime::new(ASN1DateTime {
+            year: 2049,
+            ..t49.0.clone()
+        });
+        assert_eq!(full.unix_timestamp_millis(), t49.unix_timestamp_millis());
+        assert_ne!(full, t49);
+        assert_ne!(full.cmp(&t49), core::cmp::Ordering::Equal);
+    }
+
+    #[test]
+    fn datetime_to_utc() {
+        let dt = |y, mo, d, h, mi, tz| ASN1DateTime::new(y, mo, d, h, mi, 0, None, tz);
+        let utc = dt(2024, 2, 29, 3, 15, ASN1TimeZone::Z);
+        // east of UTC, crossing a leap day
+        let t = dt(2024, 3, 1, 0, 45, ASN1TimeZone::Offset(21, 30));
+        assert_eq!(t.to_utc(), Ok(utc.clone()));
+        let t = dt(2024, 3, 1, 4, 45, ASN1TimeZone::Offset(1, 30));
+        assert_eq!(t.to_utc(), Ok(dt(2024, 3, 1, 3, 15, ASN1TimeZone::Z)));
+        // west of UTC, crossing a year
+        let t = dt(2023, 12, 31, 23, 0, ASN1TimeZone::Offset(-5, 0));
+        assert_eq!(t.to_utc(), Ok(dt(2024, 1, 1, 4, 0, ASN1TimeZone::Z)));
+        // unchanged
+        assert_eq!(utc.to_utc(), Ok(utc.clone()));
+        let t = dt(2024, 2, 29, 3, 15, ASN1TimeZone::Undefined);
+        assert_eq!(t.to_utc(), Ok(t.clone()));
+        // negative year
+        let t = dt(0, 1, 1, 0, 0, ASN1TimeZone::Offset(1, 0));
+        assert_eq!(t.to_utc(), Err(Error::InvalidDateTime));
+        // negative offsets of less than one hour
+        let t = UtcTime::from_bytes(b"210101120000-0030").expect("parsing failed");
+        assert_eq!(t.0.tz, ASN1TimeZone::Offset(0, -30));
+        assert_eq!(
+            t.to_utc().map(|t| t.0),
+            Ok(dt(21, 1, 1, 12, 30, ASN1TimeZone::Z))
+        );
+        let t = GeneralizedTime::from_bytes(b"20210101120000-0030").expect("parsing failed");
+        assert_eq!(t.0.to_utc(), Ok(dt(2021, 1, 1, 12, 30, ASN1TimeZone::Z)));
+        let t = GeneralizedTime::from_bytes(b"20210101120000-0130").expect("parsing failed");
+        assert_eq!(t.0.tz, ASN1TimeZone::Offset(-1, 30));
+        assert_eq!(t.0.to_utc(), Ok(dt(2021, 1, 1, 13, 30, ASN1TimeZone::Z)));
+    }
+
+    #[test]
+    fn datetime_local_time_zone() {
+        let local = ASN1DateTime::new(2024, 1, 1, 1, 30, 0, None, ASN1TimeZone::Undefined);
+        let utc = ASN1DateTime::new(2023, 12, 31, 23, 30, 0, None, ASN1TimeZone::Z);
+        assert_eq!(local.to_utc_with(&FixedOffset(2, 0)), Ok(utc.clone()));
+        // callback, depending on the date
+        let zone = |dt: &ASN1DateTime| match dt.month {
+            4..=9 => Some((2, 0)),
+            _ => Some((1, 0)),
+        };
+        let t = local.to_utc_with(&zone).expect("invalid offset");
+        assert_eq!(t.hour, 0);
+        let t = ASN1DateTime {
+            month: 7,
+            ..local.clone()
+        };
+        assert_eq!(t.to_utc_with(&zone).map(|t| t.hour), Ok(23));
+        // unresolved or invalid offset
+        let none = |_: &ASN1DateTime| None;
+        assert_eq!(local.resolve_local(&none), Err(Error::InvalidDateTime));
+        assert_eq!(
+            local.resolve_local(&FixedOffset(24, 0)),
+            Err(Error::InvalidDateTime)
+        );
+        // values with a time zone are unchanged
+        assert_eq!(utc.resolve_local(&none), Ok(utc.clone()));
+        let t = ASN1DateTime::new(2024, 1, 1, 1, 0, 0, None, ASN1TimeZone::Offset(1, 0));
+        assert_eq!(t.to_utc_with(&FixedOffset(5, 0)).map(|t| t.hour), Ok(0));
+    }
+
+    #[test]
+    fn datetime_calendar() {
+        assert!(is_leap_year(2024));
+        assert!(is_leap_year(2000));
+        assert!(!is_leap_year(1900));
+        assert!(!is_leap_year(2023));
+        assert_eq!(days_in_month(2023, 2), Some(28));
+        assert_eq!(days_in_month(2024, 2), Some(29));
+        assert_eq!(days_in_month(2024, 4), Some(30));
+        assert_eq!(days_in_month(2024, 13), None);
+        let invalid = |year, month, day| Err(Error::InvalidCalendarDate { year, month, day });
+        let dt = |year, month, day| {
+            ASN1DateTime::try_new(year, month, day, 0, 0, 0, None, ASN1TimeZone::Z)
+        };
+        assert!(dt(2024, 12, 31).is_ok());
+        assert_eq!(dt(2021, 2, 31), invalid(2021, 2, 31));
+        assert_eq!(dt(2021, 4, 31), invalid(2021, 4, 31));
+        assert_eq!(dt(2021, 0, 1), invalid(2021, 0, 1));
+        assert_eq!(dt(2021, 1, 0), invalid(2021, 1, 0));
+        let res = ASN1DateTime::try_new(2021, 1, 1, 24, 0, 0, None, ASN1TimeZone::Z);
+        assert_eq!(res, Err(Error::InvalidDateTime));
+    }
+
+    #[test]
+    fn datetime_calendar_parse() {
+        // 31st of February
+        let res = UtcTime::from_bytes(b"210231120000Z");
+        assert_eq!(
+            res,
+            Err(Error::InvalidCalendarDate {
+                year: 21,
+                month: 2,
+                day: 31
+            })
+        );
+        let t = UtcTime::from_bytes_lenient(b"210231120000Z").expect("parsing failed");
+        assert_eq!(t.0.day, 31);
+        // 2000 (UTCTime 00) is a leap year
+        assert!(UtcTime::from_bytes(b"000229120000Z").is_ok());
+        assert!(UtcTime::from_bytes(b"010229120000Z").is_err());
+        let res = GeneralizedTime::from_bytes(b"19000229120000Z");
+        assert_eq!(
+            res,
+            Err(Error::InvalidCalendarDate {
+                year: 1900,
+                month: 2,
+                day: 29
+            })
+        );
+        assert!(GeneralizedTime::from_bytes_lenient(b"19000229120000Z").is_ok());
+        assert!(GeneralizedTime::from_bytes(b"20000229120000Z").is_ok());
+    }
+
+    #[test]
+    fn datetime_checked_constructors() {
+        let t = UtcTime::try_new(21, 3, 1, 12, 0, 0, ASN1TimeZone::Z).expect("invalid time");
+        assert_eq!(t.0.year, 21);
+        assert!(UtcTime::try_new(1950, 1, 1, 0, 0, 0, ASN1TimeZone::Z).is_ok());
+        assert!(UtcTime::try_new(1949, 12, 31, 0, 0, 0, ASN1TimeZone::Z).is_err());
+        // invalid fields
+        let res = UtcTime::try_new(2023, 2, 29, 0, 0, 0, ASN1TimeZone::Z);
+        assert!(matches!(res, Err(Error::InvalidCalendarDate { .. })));
+        let res = UtcTime::try_new(2023, 1, 1, 0, 60, 0, ASN1TimeZone::Z);
+        assert_eq!(res, Err(Error::InvalidDateTime));
+        let res = UtcTime::try_new(2023, 1, 1, 0, 0, 0, ASN1TimeZone::Offset(24, 0));
+        assert_eq!(res, Err(Error::InvalidDateTime));
+        // two-digit year leaving the window after conversion to UTC
+        let res = UtcTime::try_new(49, 12, 31, 23, 0, 0, ASN1TimeZone::Offset(-2, 0));
+        assert_eq!(res, Err(Error::InvalidDateTime));
+        assert!(GeneralizedTime::try_new(9999, 12, 31, 0, 0, 0, None, ASN1TimeZone::Z).is_ok());
+        let res = GeneralizedTime::try_new(10000, 1, 1, 0, 0, 0, None, ASN1TimeZone::Z);
+        assert_eq!(res, Err(Error::InvalidDateTime));
+    }
+
+    #[test]
+    fn datetime_explicit_now() {
+        let now = ASN1DateTime::new(2024, 6, 1, 12, 0, 0, None, ASN1TimeZone::Z);
+        // sliding window: 1974 to 2073
+        let t = UtcTime::from_bytes(b"740101000000Z").expect("parsing failed");
+        assert_eq!(t.full_year_at(&now), Ok(1974));
+        let t = UtcTime::from_bytes(b"730101000000Z").expect("parsing failed");
+        assert_eq!(t.full_year_at(&now), Ok(2073));
+        assert!(!t.is_expired_at(&now));
+        let t = UtcTime::from_bytes(b"240601115959Z").expect("parsing failed");
+        assert_eq!(t.to_datetime_at(&now).map(|d| d.year), Ok(2024));
+        assert!(t.is_expired_at(&now));
+        let t = UtcTime::from_bytes(b"240601140000+0200").expect("parsing failed");
+        assert!(!t.is_expired_at(&now));
+        // full years are unchanged
+        let t = UtcTime::new(ASN1DateTime::new(
+            1960,
+            1,
+            1,
+            0,
+            0,
+            0,
+            None,
+            ASN1TimeZone::Z,
+        ));
+        assert_eq!(t.full_year_at(&now), Ok(1960));
+        // the full year cannot be represented
+        let far = ASN1DateTime::new(u32::MAX, 1, 1, 0, 0, 0, None, ASN1TimeZone::Z);
+        let t = UtcTime::from_bytes(b"990101000000Z").expect("parsing failed");
+        assert_eq!(t.full_year_at(&far), Err(Error::InvalidDateTime));
+        assert!(!t.is_expired_at(&far));
+        let t = GeneralizedTime::from_bytes(b"20240601120000.001Z").expect("parsing failed");
+        assert!(!t.is_expired_at(&now));
+        assert!(!now.is_expired_at(&now));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn datetime_format_roundtrip() {
+        fn roundtrip_utc(content: &[u8]) -> Vec<u8> {
+            let t = UtcTime::from_bytes(content).expect("parsing failed");
+            let v = t.to_ber_vec_original().expect("serialization failed");
+            assert_eq!(v[1] as usize, content.len());
+            v[2..].to_vec()
+        }
+        fn roundtrip_generalized(content: &[u8]) -> Vec<u8> {
+            let t = GeneralizedTime::from_bytes(content).expect("parsing failed");
+            let v = t.to_ber_vec_original().expect("serialization failed");
+            assert_eq!(v[1] as usize, content.len());
+            v[2..].to_vec()
+        }
+        for s in [
+            &b"2101011200Z"[..],
+            b"210101120000Z",
+            b"210101120000-0130",
+            b"210101120000-0030",
+            b"2101011200+0100",
+        ] {
+            assert_eq!(roundtrip_utc(s), s);
+        }
+        for s in [
+            &b"202401011230Z"[..],
+            b"20240101123000",
+            b"20240101123000.5Z",
+            b"20240101123000,50Z",
+            b"20240101123000.050+0100",
+            b"202401011230+0100",
+            b"20240101123000-0030",
+        ] {
+            assert_eq!(roundtrip_generalized(s), s);
+        }
+        // digits after milliseconds cannot be represented
+        assert!(GeneralizedTime::from_bytes(b"20240101123000.1234").is_err());
+        assert!(GeneralizedTime::from_bytes(b"20240101123000.1234Z").is_err());
+        // format is recorded, but ignored when comparing
+        let t1 = GeneralizedTime::from_bytes(b"20240101123000.5Z").unwrap();
+        let t2 = GeneralizedTime::from_bytes(b"20240101123000.500Z").unwrap();
+        assert_eq!(t1, t2);
+        assert_eq!(t1.format().map(|f| f.fraction_digits), Some(1));
+        assert_eq!(t2.format().map(|f| f.fraction_digits), Some(3));
+        // objects which were not parsed
+        let dt = ASN1DateTime::new(2024, 1, 1, 12, 30, 0, Some(5), ASN1TimeZone::Z);
+        let t = GeneralizedTime::new(dt);
+        assert_eq!(t.format(), None);
+        assert_eq!(
+            &t.to_ber_vec_original().unwrap()[2..],
+            b"20240101123000.005Z"
+        );
+        let format = ASN1TimeFormat {
+            seconds: false,
+            ..ASN1TimeFormat::new()
+        };
+        let t = UtcTime::from_bytes(b"210101120000Z")
+            .unwrap()
+            .with_format(format);
+        assert_eq!(&t.to_ber_vec_original().unwrap()[2..], b"2101011200Z");
+        // format is kept when converting to UTC
+        let t = UtcTime::from_bytes(b"2101011230+0100").unwrap();
+        let v = t.to_utc().unwrap().to_ber_vec_original().unwrap();
+        assert_eq!(&v[2..], b"2101011130Z");
+        let t = GeneralizedTime::from_bytes(b"20240101123000.50+0100").unwrap();
+        let v = t.to_utc().unwrap().to_ber_vec_original().unwrap();
+        assert_eq!(&v[2..], b"20240101113000.50Z");
+        // a UTCTime with a four-digit year is written using two digits
+        let dt = ASN1DateTime::new(2021, 1, 1, 12, 0, 0, None, ASN1TimeZone::Offset(0, -30));
+        let v = UtcTime::new(dt)
+            .with_format(format)
+            .to_ber_vec_original()
+            .unwrap();
+        assert_eq!(&v[2..], b"2101011200-0030");
     }
 }
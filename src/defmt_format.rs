@@ -0,0 +1,249 @@
+//! Implementations of `defmt::Format`, for logging on embedded targets
+//!
+//! Simple types (for ex. [`Tag`], [`Class`], [`Length`]) derive `defmt::Format` in their own
+//! module. This module implements it for types containing borrowed or owned data, displaying
+//! values compactly (content bytes in hexadecimal, strings as `str`).
+
+use crate::*;
+use defmt::{write, Format, Formatter};
+
+impl Format for Header<'_> {
+    fn format(&self, f: Formatter) {
+        write!(
+            f,
+            "Header {{ class: {}, constructed: {=bool}, tag: {}, length: {} }}",
+            self.class(),
+            self.is_constructed(),
+            self.tag(),
+            self.length()
+        )
+    }
+}
+
+impl Format for Any<'_> {
+    fn format(&self, f: Formatter) {
+        write!(
+            f,
+            "Any {{ header: {}, data: {=[u8]:x} }}",
+            self.header, self.data
+        )
+    }
+}
+
+// not derived: the `defmt::Format` derive does not allow deprecated variants
+impl Format for DerConstraint {
+    #[allow(deprecated)]
+    fn format(&self, f: Formatter) {
+        match self {
+            DerConstraint::IndefiniteLength => write!(f, "IndefiniteLength"),
+            DerConstraint::Constructed => write!(f, "Constructed"),
+            DerConstraint::NotConstructed => write!(f, "NotConstructed"),
+            DerConstraint::MissingTimeZone => write!(f, "MissingTimeZone"),
+            DerConstraint::MissingSeconds => write!(f, "MissingSeconds"),
+            DerConstraint::InvalidDecimalPoint => write!(f, "InvalidDecimalPoint"),
+            DerConstraint::UnusedBitsNotZero => write!(f, "UnusedBitsNotZero"),
+            DerConstraint::EmptyBitStringUnusedBits => write!(f, "EmptyBitStringUnusedBits"),
+            DerConstraint::InvalidBoolean => write!(f, "InvalidBoolean"),
+            DerConstraint::NonCanonicalBoolean => write!(f, "NonCanonicalBoolean"),
+            DerConstraint::IntegerEmpty => write!(f, "IntegerEmpty"),
+            DerConstraint::IntegerLeadingZeroes => write!(f, "IntegerLeadingZeroes"),
+            DerConstraint::IntegerLeadingFF => write!(f, "IntegerLeadingFF"),
+            DerConstraint::SetNotSorted => write!(f, "SetNotSorted"),
+            DerConstraint::NonMinimalLength => write!(f, "NonMinimalLength"),
+            DerConstraint::DefaultValueEncoded => write!(f, "DefaultValueEncoded"),
+        }
+    }
+}
+
+impl Format for Error {
+    fn format(&self, f: Formatter) {
+        match self {
+            Error::BerTypeError => write!(f, "BerTypeError"),
+            Error::BerValueError => write!(f, "BerValueError"),
+            Error::InvalidLength => write!(f, "InvalidLength"),
+            Error::LengthTooLarge => write!(f, "LengthTooLarge"),
+            Error::InvalidValue { tag, msg } => {
+                write!(
+                    f,
+                    "InvalidValue {{ tag: {}, msg: {=str} }}",
+                    tag,
+                    msg.as_str()
+                )
+            }
+            Error::InvalidTag => write!(f, "InvalidTag"),
+            Error::UnknownTag(t) => write!(f, "UnknownTag({=u32})", t),
+            Error::UnexpectedTag { expected, actual } => write!(
+                f,
+                "UnexpectedTag {{ expected: {}, actual: {} }}",
+                expected, actual
+            ),
+            Error::UnexpectedClass { expected, actual } => write!(
+                f,
+                "UnexpectedClass {{ expected: {}, actual: {} }}",
+                expected, actual
+            ),
+            Error::IndefiniteLengthUnexpected => write!(f, "IndefiniteLengthUnexpected"),
+            Error::UnexpectedEndOfContent => write!(f, "UnexpectedEndOfContent"),
+            Error::InvalidEndOfContent => write!(f, "InvalidEndOfContent"),
+            Error::ConstructExpected => write!(f, "ConstructExpected"),
+            Error::ConstructUnexpected => write!(f, "ConstructUnexpected"),
+            Error::IntegerTooLarge => write!(f, "IntegerTooLarge"),
+            Error::IntegerNegative => write!(f, "IntegerNegative"),
+            Error::IntegerLengthLimit { len, max } => write!(
+                f,
+                "IntegerLengthLimit {{ len: {=usize}, max: {=usize} }}",
+                len, max
+            ),
+            Error::LengthLimit { len, max } => write!(
+                f,
+                "LengthLimit {{ len: {=usize}, max: {=usize} }}",
+                len, max
+            ),
+            Error::IntegerOutOfRange { bits, max } => write!(
+                f,
+                "IntegerOutOfRange {{ bits: {=usize}, max: {=usize} }}",
+                bits, max
+            ),
+            Error::BerMaxDepth => write!(f, "BerMaxDepth"),
+            Error::TrailingContent { tag, remaining } => write!(
+                f,
+                "TrailingContent {{ tag: {}, remaining: {=usize} }}",
+                tag, remaining
+            ),
+            Error::StringInvalidCharset => write!(f, "StringInvalidCharset"),
+            Error::StringInvalidCharacters(v) => {
+                write!(f, "StringInvalidCharacters({=[?]})", v.as_slice())
+            }
+            Error::InvalidDateTime => write!(f, "InvalidDateTime"),
+            Error::InvalidCalendarDate { year, month, day } => write!(
+                f,
+                "InvalidCalendarDate {{ year: {=u32}, month: {=u8}, day: {=u8} }}",
+                year, month, day
+            ),
+            Error::InvalidHexString => write!(f, "InvalidHexString"),
+            Error::DerConstraintFailed(c) => write!(f, "DerConstraintFailed({})", c),
+            Error::MissingField(s) => write!(f, "MissingField({=str})", s.as_str()),
+            Error::DuplicateField(s) => write!(f, "DuplicateField({=str})", s.as_str()),
+            Error::UnorderedField(s) => write!(f, "UnorderedField({=str})", s.as_str()),
+            Error::UnexpectedField { class, tag } => {
+                write!(f, "UnexpectedField {{ class: {}, tag: {} }}", class, tag)
+            }
+            Error::Field { index, error } => write!(
+                f,
+                "Field {{ index: {=usize}, error: {} }}",
+                index,
+                error.as_ref()
+            ),
+            Error::InvalidPath(s) => write!(f, "InvalidPath({=str})", s.as_str()),
+            Error::PathNotFound(n) => write!(f, "PathNotFound({=usize})", n),
+            Error::LifetimeError => write!(f, "LifetimeError"),
+            Error::Unsupported => write!(f, "Unsupported"),
+            Error::Incomplete(Needed::Unknown) => write!(f, "Incomplete(Unknown)"),
+            Error::Incomplete(Needed::Size(n)) => write!(f, "Incomplete({=usize})", n.get()),
+            Error::NomError(kind) => write!(f, "NomError({=str})", kind.description()),
+        }
+    }
+}
+
+impl Format for Boolean {
+    fn format(&self, f: Formatter) {
+        write!(f, "Boolean({=bool})", self.bool())
+    }
+}
+
+impl Format for Null {
+    fn format(&self, f: Formatter) {
+        write!(f, "Null")
+    }
+}
+
+impl Format for Integer<'_> {
+    fn format(&self, f: Formatter) {
+        write!(f, "Integer({=[u8]:x})", self.as_ref())
+    }
+}
+
+impl Format for BitString<'_> {
+    fn format(&self, f: Formatter) {
+        write!(
+            f,
+            "BitString {{ unused_bits: {=u8}, data: {=[u8]:x} }}",
+            self.unused_bits,
+            self.data.as_ref()
+        )
+    }
+}
+
+impl Format for OctetString<'_> {
+    fn format(&self, f: Formatter) {
+        write!(f, "OctetString({=[u8]:x})", self.as_ref())
+    }
+}
+
+impl Format for Oid<'_> {
+    fn format(&self, f: Formatter) {
+        match self.iter() {
+            Some(arcs) => {
+                let name = if self.is_relative() {
+                    "RelativeOid"
+                } else {
+                    "Oid"
+                };
+                write!(f, "{=str}(", name);
+                for (i, arc) in arcs.enumerate() {
+                    if i > 0 {
+                        write!(f, ".");
+                    }
+                    write!(f, "{=u64}", arc);
+                }
+                write!(f, ")");
+            }
+            // arcs do not fit in u64
+            None => write!(f, "Oid({=[u8]:x})", self.as_bytes()),
+        }
+    }
+}
+
+impl Format for UtcTime {
+    fn format(&self, f: Formatter) {
+        write!(f, "UtcTime({})", self.0)
+    }
+}
+
+impl Format for GeneralizedTime {
+    fn format(&self, f: Formatter) {
+        write!(f, "GeneralizedTime({})", self.0)
+    }
+}
+
+impl Format for AnyString<'_> {
+    fn format(&self, f: Formatter) {
+        write!(f, "AnyString({=str})", self.as_str())
+    }
+}
+
+macro_rules! impl_format_string {
+    ($($name:ident),*) => {
+        $(
+            impl Format for $name<'_> {
+                fn format(&self, f: Formatter) {
+                    write!(f, "{=str}({=str})", stringify!($name), self.as_ref())
+                }
+            }
+        )*
+    };
+}
+
+impl_format_string!(
+    BmpString,
+    GeneralString,
+    GraphicString,
+    Ia5String,
+    NumericString,
+    PrintableString,
+    TeletexString,
+    UniversalString,
+    Utf8String,
+    VideotexString,
+    VisibleString
+);
@@ -0,0 +1,149 @@
+//! Conversions to and from the types of the RustCrypto [`der`] crate
+//!
+//! The conversions borrow data when possible, so objects can be shared between both crates
+//! without re-encoding. Conversions to `der` types can fail, since `der` only accepts DER
+//! encodings (and tag numbers up to 30); the error type is [`der::Error`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! use asn1_rs::{oid, Oid};
+//! use der::asn1::ObjectIdentifier;
+//! use std::convert::TryFrom;
+//!
+//! let oid = oid!(1.2.840.113549.1.1.11);
+//! let der_oid = ObjectIdentifier::try_from(&oid).expect("invalid OID");
+//! assert_eq!(der_oid.to_string(), "1.2.840.113549.1.1.11");
+//! let oid2: Oid = (&der_oid).into();
+//! assert_eq!(oid2, oid);
+//! ```
+
+use crate::{Any, Class, Header, Integer, Length, OctetString, Oid, Tag};
+use alloc::borrow::Cow;
+use core::convert::TryFrom;
+use der::asn1::{AnyRef, IntRef, ObjectIdentifier, OctetStringRef};
+use der::{ErrorKind, Tagged};
+
+impl<'a> From<&'a ObjectIdentifier> for Oid<'a> {
+    fn from(oid: &'a ObjectIdentifier) -> Self {
+        Oid::new(Cow::Borrowed(oid.as_bytes()))
+    }
+}
+
+impl<'a, 'b> TryFrom<&'b Oid<'a>> for ObjectIdentifier {
+    type Error = der::Error;
+
+    fn try_from(oid: &'b Oid<'a>) -> Result<Self, Self::Error> {
+        // relative OIDs are not supported
+        if oid.is_relative() {
+            return Err(ErrorKind::OidMalformed.into());
+        }
+        ObjectIdentifier::from_bytes(oid.as_bytes()).map_err(Into::into)
+    }
+}
+
+impl<'a> From<IntRef<'a>> for Integer<'a> {
+    fn from(i: IntRef<'a>) -> Self {
+        Integer::new(i.as_bytes())
+    }
+}
+
+impl<'a, 'b> TryFrom<&'b Integer<'a>> for IntRef<'b> {
+    type Error = der::Error;
+
+    fn try_from(i: &'b Integer<'a>) -> Result<Self, Self::Error> {
+        IntRef::new(i.as_ref())
+    }
+}
+
+impl<'a> From<OctetStringRef<'a>> for OctetString<'a> {
+    fn from(s: OctetStringRef<'a>) -> Self {
+        OctetString::new(s.as_bytes())
+    }
+}
+
+impl<'a, 'b> TryFrom<&'b OctetString<'a>> for OctetStringRef<'b> {
+    type Error = der::Error;
+
+    fn try_from(s: &'b OctetString<'a>) -> Result<Self, Self::Error> {
+        OctetStringRef::new(s.as_ref())
+    }
+}
+
+impl<'a> From<AnyRef<'a>> for Any<'a> {
+    fn from(any: AnyRef<'a>) -> Self {
+        // tag numbers are always encoded in the identifier octet
+        let b = u8::from(any.tag());
+        let class = Class::from_identifier_octet(b);
+        let constructed = b & 0b0010_0000 != 0;
+        let tag = Tag(u32::from(b & 0b0001_1111));
+        let data = any.value();
+        let header = Header::new(class, constructed, tag, Length::Definite(data.len()));
+        Any::new(header, data)
+    }
+}
+
+impl<'a, 'b> TryFrom<&'b Any<'a>> for AnyRef<'a> {
+    type Error = der::Error;
+
+    fn try_from(any: &'b Any<'a>) -> Result<Self, Self::Error> {
+        if any.tag().0 > 30 {
+            return Err(ErrorKind::TagNumberInvalid.into());
+        }
+        let b = ((any.class() as u8) << 6)
+            | if any.header.is_constructed() {
+                0b0010_0000
+            } else {
+                0
+            }
+            | (any.tag().0 as u8);
+        let tag = der::Tag::try_from(b)?;
+        AnyRef::new(tag, any.data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use core::convert::TryFrom;
+    use der::asn1::{AnyRef, IntRef, ObjectIdentifier, OctetStringRef};
+    use hex_literal::hex;
+
+    #[test]
+    fn der_interop_oid() {
+        let oid = oid!(2.5.4 .3);
+        let der_oid = ObjectIdentifier::try_from(&oid).expect("invalid OID");
+        assert_eq!(der_oid, ObjectIdentifier::new_unwrap("2.5.4.3"));
+        let oid2: Oid = (&der_oid).into();
+        assert_eq!(oid2, oid);
+        // relative OIDs are not supported
+        assert!(ObjectIdentifier::try_from(&oid!(rel 1.2)).is_err());
+    }
+
+    #[test]
+    fn der_interop_integer_octetstring() {
+        let (_, i) = Integer::from_der(&hex!("02 02 00 ff")).expect("parsing failed");
+        let der_int = IntRef::try_from(&i).expect("invalid integer");
+        assert_eq!(Integer::from(der_int).as_u32(), Ok(255));
+        let s = OctetString::new(b"abc");
+        let der_s = OctetStringRef::try_from(&s).expect("invalid octet string");
+        assert_eq!(der_s.as_bytes(), b"abc");
+        assert_eq!(OctetString::from(der_s), s);
+    }
+
+    #[test]
+    fn der_interop_any() {
+        let input = &hex!("a1 03 02 01 02");
+        let (_, any) = Any::from_der(input).expect("parsing failed");
+        let der_any = AnyRef::try_from(&any).expect("invalid object");
+        assert_eq!(der_any.value(), &input[2..]);
+        let any2 = Any::from(der_any);
+        assert_eq!(any2.class(), Class::ContextSpecific);
+        assert_eq!(any2.tag(), Tag(1));
+        assert!(any2.header.is_constructed());
+        assert_eq!(any2.data, any.data);
+        // high tag numbers are not supported
+        let (_, any) = Any::from_der(&hex!("9f 40 00")).expect("parsing failed");
+        assert!(AnyRef::try_from(&any).is_err());
+    }
+}
@@ -320,3 +320,156 @@ pub use asn1_rs_derive::BerAlias;
 /// struct S(pub u32);
 /// ```
 pub use asn1_rs_derive::DerAlias;
+
+/// # BerEnumerated custom derive
+///
+/// `BerEnumerated` is a custom derive attribute, to map the values of an `ENUMERATED` object to
+/// the variants of a Rust enum.
+///
+/// This attribute will automatically derive implementations for the following traits:
+///   - [`TryFrom<Any>`](super::Any), also providing [`FromBer`](super::FromBer)
+///   - [`Tagged`](super::Tagged)
+///   - `TryFrom<u32>` for the enum, and `From<&E>` for `u32` (`i64` for `INTEGER` values)
+///
+/// `DerEnumerated` implies `BerEnumerated`, and will conflict with this custom derive.
+///
+/// Variants values are given by the discriminant, or by the `#[asn1(value = n)]` attribute
+/// (required for enums having a variant with fields). Unknown values are rejected, unless one
+/// variant is marked `#[asn1(other)]`: this variant must have a single `u32` field, holding the
+/// raw value, so unknown (for ex. future) values can be parsed and encoded again. Encoding
+/// this variant fails if it holds the value of another variant, since it would be parsed as the
+/// other variant.
+///
+/// To map values of an `INTEGER` object instead, add the `#[asn1(integer)]` attribute to the enum.
+/// Values are then `i64` (and can be negative), and the `#[asn1(other)]` field must have type
+/// `i64`.
+///
+/// See [`derive`](crate::doc::derive) documentation for more examples and documentation.
+///
+/// ## Examples
+///
+/// To parse the following ASN.1 object:
+/// <pre>
+/// Color ::= ENUMERATED { red(0), green(1), blue(2), ... }
+/// </pre>
+///
+/// Define an enum and add the `BerEnumerated` derive:
+///
+/// ```rust
+/// use asn1_rs::*;
+///
+/// #[derive(Debug, PartialEq, BerEnumerated)]
+/// enum Color {
+///     Red,
+///     Green,
+///     #[asn1(value = 2)]
+///     Blue,
+///     #[asn1(other)]
+///     Unknown(u32),
+/// }
+///
+/// let (_, color) = Color::from_ber(&[0x0a, 0x01, 0x01]).expect("parsing failed");
+/// assert_eq!(color, Color::Green);
+/// let (_, color) = Color::from_ber(&[0x0a, 0x01, 0x07]).expect("parsing failed");
+/// assert_eq!(color, Color::Unknown(7));
+/// ```
+pub use asn1_rs_derive::BerEnumerated;
+
+/// # DerEnumerated custom derive
+///
+/// `DerEnumerated` is a custom derive attribute, to map the values of an `ENUMERATED` object to
+/// the variants of a Rust enum, for BER and DER.
+///
+/// This attribute will automatically derive implementations for the following traits:
+///   - [`TryFrom<Any>`](super::Any), also providing [`FromBer`](super::FromBer)
+///   - [`Tagged`](super::Tagged)
+///   - [`CheckDerConstraints`](super::CheckDerConstraints)
+///   - [`FromDer`](super::FromDer)
+///   - `TryFrom<u32>` for the enum, and `From<&E>` for `u32` (`i64` for `INTEGER` values)
+///
+/// `DerEnumerated` implies `BerEnumerated`, and will conflict with this custom derive.
+/// Attributes are the same as for [`BerEnumerated`].
+///
+/// The [`ToDer`](macro@ToDer) custom derive can be added to encode the enum value.
+///
+/// See [`derive`](crate::doc::derive) documentation for more examples and documentation.
+///
+/// ## Examples
+///
+/// ```rust
+/// use asn1_rs::*;
+///
+/// #[derive(Debug, PartialEq, DerEnumerated)]
+/// #[asn1(integer)]
+/// enum Version {
+///     V1 = 0,
+///     V2 = 1,
+///     V3 = 2,
+/// }
+///
+/// let (_, version) = Version::from_der(&[0x02, 0x01, 0x02]).expect("parsing failed");
+/// assert_eq!(version, Version::V3);
+/// assert!(Version::from_der(&[0x02, 0x01, 0x03]).is_err());
+/// ```
+pub use asn1_rs_derive::DerEnumerated;
+
+/// # ToDer custom derive
+///
+/// `ToDer` is a custom derive attribute, to derive a DER encoder ([`ToDer`](super::ToDer))
+/// automatically from the structure definition.
+///
+/// The structure must also implement [`Tagged`](super::Tagged), usually using one of the
+/// `Sequence` or `Set` parser custom derives. Every field type must implement the
+/// [`ToDer`](super::ToDer) trait. Field attributes (tags, `OPTIONAL`, `DEFAULT`,
+/// `skip_serializing_if`) are the same as for parsers.
+///
+/// See [`derive`](crate::doc::derive) documentation for more examples and documentation.
+///
+/// ## Examples
+///
+/// ```rust
+/// use asn1_rs::*;
+///
+/// #[derive(DerSequence, ToDer)]
+/// struct S {
+///   a: u32,
+///   #[asn1(tag = "0", optional)]
+///   b: Option<u16>,
+/// }
+///
+/// let s = S { a: 1, b: Some(2) };
+/// let v = s.to_der_vec().expect("serialization failed");
+/// assert_eq!(&v, &[0x30, 0x08, 0x02, 0x01, 0x01, 0xa0, 0x03, 0x02, 0x01, 0x02]);
+/// ```
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use asn1_rs_derive::ToDer;
+
+/// # ToStatic custom derive
+///
+/// `ToStatic` is a custom derive attribute, to implement [`ToStatic`](super::ToStatic) for a
+/// structure or an enum, converting it to the same type with a `'static` lifetime.
+///
+/// Every field type must implement [`ToStatic`](super::ToStatic), and type parameters must
+/// implement it too. Borrowed references (for ex. `&'a str`) are not supported: use owned or
+/// copy-on-write types (for ex. [`Utf8String`](super::Utf8String)) in fields.
+///
+/// ## Examples
+///
+/// ```rust
+/// use asn1_rs::*;
+///
+/// #[derive(Debug, PartialEq, DerSequence, ToStatic)]
+/// struct S<'a> {
+///   a: u32,
+///   b: OctetString<'a>,
+/// }
+///
+/// let s = {
+///     let input = vec![0x30, 0x07, 0x02, 0x01, 0x01, 0x04, 0x02, 0x61, 0x62];
+///     let (_, s) = S::from_der(&input).expect("parsing failed");
+///     s.to_static()
+/// };
+/// assert_eq!(s.b.as_ref(), b"ab");
+/// ```
+pub use asn1_rs_derive::ToStatic;
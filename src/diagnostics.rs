@@ -0,0 +1,324 @@
+//! Hints for common misuse, attached to type mismatch errors
+//!
+//! When the input does not have the expected type, the error only contains the expected and
+//! actual tags. Often, the input is valid, but the parser does not match the ASN.1 definition
+//! (for ex. a tagged field parsed as the inner type). [`parse_ber_hinted`] and
+//! [`parse_der_hinted`] parse one object, and on a type mismatch inspect it to recognize common
+//! patterns, and attach a [`Hint`] to the error.
+//!
+//! Hints are only computed for expected `Universal` types.
+
+use crate::{Any, Class, Error, FromBer, FromDer, Tag, Tagged};
+use core::fmt;
+
+/// A suggestion explaining a type mismatch
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Hint {
+    /// The object is a constructed tagged value, containing one object of the expected type
+    ///
+    /// The field is probably tagged EXPLICIT: parse it using `TaggedExplicit`, or
+    /// [`Any::explicit_inner`].
+    ExplicitTagged {
+        expected: Tag,
+        class: Class,
+        tag: Tag,
+    },
+    /// The object is a tagged value, with the form (primitive or constructed) of the expected
+    /// type
+    ///
+    /// The field may be tagged IMPLICIT: parse it using `TaggedImplicit`.
+    ImplicitTagged {
+        expected: Tag,
+        class: Class,
+        tag: Tag,
+    },
+    /// A `SET` was found instead of a `SEQUENCE`, or the opposite
+    SequenceSetMismatch { expected: Tag, actual: Tag },
+    /// The object is an `OCTET STRING`, whose content is one object of the expected type
+    ///
+    /// Some values are DER-encoded inside an `OCTET STRING` (for ex. X.509 extension values):
+    /// parse the content of the `OCTET STRING`.
+    EncapsulatedInOctetString { expected: Tag },
+    /// The object is a `BIT STRING` (without unused bits), whose content is one object of the
+    /// expected type
+    ///
+    /// Some values are DER-encoded inside a `BIT STRING` (for ex. X.509 public keys): parse the
+    /// content of the `BIT STRING`.
+    EncapsulatedInBitString { expected: Tag },
+}
+
+/// Display the name of a universal tag, for messages
+struct TagName(Tag);
+
+impl fmt::Display for TagName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.universal_name() {
+            Some(name) => f.write_str(name),
+            None => write!(f, "UNIVERSAL {}", self.0 .0),
+        }
+    }
+}
+
+impl fmt::Display for Hint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Hint::ExplicitTagged {
+                expected,
+                class,
+                tag,
+            } => write!(
+                f,
+                "found {} constructed, wrapping the expected {}; did you forget EXPLICIT unwrapping?",
+                tag.display_with_class(class),
+                TagName(expected)
+            ),
+            Hint::ImplicitTagged {
+                expected,
+                class,
+                tag,
+            } => write!(
+                f,
+                "found {} with the form of the expected {}; is the field tagged IMPLICIT?",
+                tag.display_with_class(class),
+                TagName(expected)
+            ),
+            Hint::SequenceSetMismatch { expected, actual } => write!(
+                f,
+                "found {} instead of {}; check the ASN.1 definition",
+                TagName(actual),
+                TagName(expected)
+            ),
+            Hint::EncapsulatedInOctetString { expected } => write!(
+                f,
+                "found OCTET STRING wrapping the expected {}; parse the content of the OCTET STRING",
+                TagName(expected)
+            ),
+            Hint::EncapsulatedInBitString { expected } => write!(
+                f,
+                "found BIT STRING wrapping the expected {}; parse the content of the BIT STRING",
+                TagName(expected)
+            ),
+        }
+    }
+}
+
+/// An error, with an optional hint explaining it
+///
+/// The `Display` implementation shows the error, followed by the hint (if any):
+///
+/// ```text
+/// Unexpected Tag (expected: Some(Tag(2)), actual: Tag(0)); hint: found [CONTEXT 0] ...
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct HintedError {
+    /// The error
+    pub error: Error,
+    /// A suggestion explaining the error, if a common misuse pattern was recognized
+    pub hint: Option<Hint>,
+}
+
+impl fmt::Display for HintedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.error)?;
+        if let Some(hint) = &self.hint {
+            write!(f, "; hint: {}", hint)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for HintedError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl From<HintedError> for Error {
+    fn from(e: HintedError) -> Self {
+        e.error
+    }
+}
+
+/// Return true if `error` is a type mismatch, for which a hint can be computed
+fn is_mismatch(error: &Error) -> bool {
+    matches!(
+        error,
+        Error::UnexpectedTag { .. }
+            | Error::UnexpectedClass { .. }
+            | Error::ConstructExpected
+            | Error::ConstructUnexpected
+            | Error::BerTypeError
+    )
+}
+
+/// Return true if `data` contains exactly one universal object with tag `expected`
+fn contains_one(data: &[u8], expected: Tag) -> bool {
+    match Any::from_ber(data) {
+        Ok((rem, inner)) => {
+            rem.is_empty() && inner.class() == Class::Universal && inner.tag() == expected
+        }
+        Err(_) => false,
+    }
+}
+
+/// Inspect the first object of `input`, and return a hint if it matches a common misuse pattern
+/// when an object of universal type `expected` was expected
+///
+/// Returns `None` if the object cannot be parsed, if it has the expected type, or if no pattern
+/// was recognized.
+///
+/// ```rust
+/// use asn1_rs::{diagnose, Hint, Class, Tag};
+///
+/// // [0] { INTEGER 5 }
+/// let hint = diagnose(&[0xa0, 0x03, 0x02, 0x01, 0x05], Tag::Integer);
+/// assert_eq!(
+///     hint,
+///     Some(Hint::ExplicitTagged {
+///         expected: Tag::Integer,
+///         class: Class::ContextSpecific,
+///         tag: Tag(0)
+///     })
+/// );
+/// ```
+pub fn diagnose(input: &[u8], expected: Tag) -> Option<Hint> {
+    let (_, any) = Any::from_ber(input).ok()?;
+    let (class, tag) = (any.class(), any.tag());
+    if class == Class::Universal && tag == expected {
+        return None;
+    }
+    if class != Class::Universal {
+        if any.header.is_constructed() && contains_one(any.data, expected) {
+            return Some(Hint::ExplicitTagged {
+                expected,
+                class,
+                tag,
+            });
+        }
+        let expected_constructed = matches!(expected, Tag::Sequence | Tag::Set);
+        if any.header.is_constructed() == expected_constructed {
+            return Some(Hint::ImplicitTagged {
+                expected,
+                class,
+                tag,
+            });
+        }
+        return None;
+    }
+    match (tag, expected) {
+        (Tag::Sequence, Tag::Set) | (Tag::Set, Tag::Sequence) => Some(Hint::SequenceSetMismatch {
+            expected,
+            actual: tag,
+        }),
+        (Tag::OctetString, _) if any.header.is_primitive() && contains_one(any.data, expected) => {
+            Some(Hint::EncapsulatedInOctetString { expected })
+        }
+        (Tag::BitString, _) if any.header.is_primitive() => match any.data {
+            [0, content @ ..] if contains_one(content, expected) => {
+                Some(Hint::EncapsulatedInBitString { expected })
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Attach a hint to `error`, if it is a type mismatch
+fn hinted<T: Tagged>(input: &[u8], error: Error) -> HintedError {
+    let hint = if is_mismatch(&error) {
+        diagnose(input, T::TAG)
+    } else {
+        None
+    };
+    HintedError { error, hint }
+}
+
+/// Parse one BER object, attaching a hint to type mismatch errors
+///
+/// This is the same as [`FromBer::from_ber`], except for the error type. See [`diagnose`] for
+/// the recognized patterns.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{parse_ber_hinted, Hint, Tag};
+///
+/// // OCTET STRING { INTEGER 5 }
+/// let bytes = &[0x04, 0x03, 0x02, 0x01, 0x05];
+/// let err = parse_ber_hinted::<u32>(bytes).unwrap_err();
+/// assert_eq!(err.hint, Some(Hint::EncapsulatedInOctetString { expected: Tag::Integer }));
+/// ```
+pub fn parse_ber_hinted<'a, T>(input: &'a [u8]) -> Result<(&'a [u8], T), HintedError>
+where
+    T: FromBer<'a> + Tagged,
+{
+    T::from_ber(input).map_err(|e| hinted::<T>(input, e.into()))
+}
+
+/// Parse one DER object, attaching a hint to type mismatch errors
+///
+/// This is the same as [`FromDer::from_der`], except for the error type. See [`diagnose`] for
+/// the recognized patterns.
+pub fn parse_der_hinted<'a, T>(input: &'a [u8]) -> Result<(&'a [u8], T), HintedError>
+where
+    T: FromDer<'a> + Tagged,
+{
+    T::from_der(input).map_err(|e| hinted::<T>(input, e.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn diagnostics_hints() {
+        // [0] EXPLICIT INTEGER
+        let input = &hex!("a0 03 02 01 05");
+        let err = parse_der_hinted::<u32>(input).unwrap_err();
+        assert!(matches!(err.hint, Some(Hint::ExplicitTagged { .. })));
+        assert!(
+            format!("{}", err).contains("[CONTEXT 0] constructed, wrapping the expected INTEGER")
+        );
+        // [1] IMPLICIT INTEGER
+        let err = parse_der_hinted::<u32>(&hex!("81 01 05")).unwrap_err();
+        assert_eq!(
+            err.hint,
+            Some(Hint::ImplicitTagged {
+                expected: Tag::Integer,
+                class: Class::ContextSpecific,
+                tag: Tag(1)
+            })
+        );
+        // [APPLICATION 2] IMPLICIT SEQUENCE
+        let hint = diagnose(&hex!("62 03 02 01 05"), Tag::Sequence);
+        assert!(matches!(hint, Some(Hint::ImplicitTagged { .. })));
+        // SET instead of SEQUENCE
+        let err = parse_ber_hinted::<Vec<u32>>(&hex!("31 03 02 01 05")).unwrap_err();
+        assert_eq!(
+            err.hint,
+            Some(Hint::SequenceSetMismatch {
+                expected: Tag::Sequence,
+                actual: Tag::Set
+            })
+        );
+        // encapsulated in BIT STRING
+        let hint = diagnose(&hex!("03 06 00 30 03 02 01 05"), Tag::Sequence);
+        assert_eq!(
+            hint,
+            Some(Hint::EncapsulatedInBitString {
+                expected: Tag::Sequence
+            })
+        );
+        // no recognized pattern
+        let err = parse_der_hinted::<u32>(&hex!("04 02 02 01")).unwrap_err();
+        assert_eq!(err.hint, None);
+        assert_eq!(diagnose(&hex!("02 01 05"), Tag::Integer), None);
+        assert_eq!(diagnose(&hex!("02 01"), Tag::Integer), None);
+        // only type mismatches get a hint
+        let err = parse_der_hinted::<u32>(&hex!("02 09 01 00 00 00 00 00 00 00 00")).unwrap_err();
+        assert_eq!(err.error, Error::IntegerOutOfRange { bits: 65, max: 32 });
+        assert_eq!(err.hint, None);
+    }
+}
@@ -0,0 +1,314 @@
+//! Encoding rules selection
+
+#[cfg(feature = "std")]
+use crate::tools::SetOrder;
+#[cfg(feature = "std")]
+use crate::{
+    der_encoding_cmp, Any, Class, Error, FromBer, Header, Length, SerializeResult, Tag, ToDer,
+};
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::io::Write;
+
+/// ASN.1 encoding rules (X.690)
+///
+/// This can be used to select the encoding at runtime, for ex. using
+/// [`ToDer::write_encoded`](crate::ToDer::write_encoded).
+///
+/// The rules differ on a few points:
+/// - DER uses the definite length form, CER uses the indefinite length form for constructed
+///   objects (X.690 section 9.1)
+/// - CER splits string types with more than 1000 contents octets into fragments (X.690
+///   section 9.2)
+/// - CER sorts the components of `SET OF` values using their CER encoding (X.690 section 9.3)
+///
+/// BER allows all forms: objects are encoded using DER, which is also valid BER.
+///
+/// For CER, the string types to fragment and the `SET OF` values to sort are identified by the
+/// type of the value: types of this crate (including implicitly tagged values, collections and
+/// derived structures) override [`ToDer::write_encoded`](crate::ToDer::write_encoded). Other
+/// types are converted from their DER encoding, where only universal tags identify the type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EncodingRules {
+    /// Basic Encoding Rules
+    Ber,
+    /// Canonical Encoding Rules
+    Cer,
+    /// Distinguished Encoding Rules
+    Der,
+}
+
+/// Maximum number of contents octets of a primitive string object in CER (X.690 section 9.2)
+#[cfg(feature = "std")]
+const CER_MAX_STRING_LEN: usize = 1000;
+
+/// Return true if object is a string type, which must be fragmented in CER
+#[cfg(feature = "std")]
+fn is_cer_string(class: Class, tag: Tag) -> bool {
+    class == Class::Universal
+        && matches!(
+            tag,
+            Tag::BitString
+                | Tag::OctetString
+                | Tag::ObjectDescriptor
+                | Tag::Utf8String
+                | Tag::NumericString
+                | Tag::PrintableString
+                | Tag::T61String
+                | Tag::VideotexString
+                | Tag::Ia5String
+                | Tag::GraphicString
+                | Tag::VisibleString
+                | Tag::GeneralString
+                | Tag::UniversalString
+                | Tag::BmpString
+        )
+}
+
+#[cfg(feature = "std")]
+fn write_primitive(
+    class: Class,
+    tag: Tag,
+    data: &[u8],
+    writer: &mut dyn Write,
+) -> SerializeResult<usize> {
+    let header = Header::new(class, false, tag, Length::Definite(data.len()));
+    let sz = header.write_der_header(writer)?;
+    writer.write_all(data)?;
+    Ok(sz + data.len())
+}
+
+/// Write a string object as a constructed object of fragments (X.690 section 9.2)
+#[cfg(feature = "std")]
+fn write_cer_fragments(tag: Tag, data: &[u8], writer: &mut dyn Write) -> SerializeResult<usize> {
+    let header = Header::new(Class::Universal, true, tag, Length::Indefinite);
+    let mut sz = header.write_der_raw(writer)?;
+    if tag == Tag::BitString {
+        // each fragment has its own initial octet, only the last one can have unused bits
+        let (unused_bits, bits) = data.split_first().ok_or(Error::InvalidLength)?;
+        let mut chunks = bits.chunks(CER_MAX_STRING_LEN - 1).peekable();
+        while let Some(chunk) = chunks.next() {
+            let unused = if chunks.peek().is_none() {
+                *unused_bits
+            } else {
+                0
+            };
+            let header = Header::new(
+                Class::Universal,
+                false,
+                Tag::BitString,
+                Length::Definite(chunk.len() + 1),
+            );
+            sz += header.write_der_header(writer)?;
+            writer.write_all(&[unused])?;
+            writer.write_all(chunk)?;
+            sz += chunk.len() + 1;
+        }
+    } else {
+        // other string types are fragmented as OCTET STRING
+        for chunk in data.chunks(CER_MAX_STRING_LEN) {
+            sz += write_primitive(Class::Universal, Tag::OctetString, chunk, writer)?;
+        }
+    }
+    writer.write_all(&[0, 0])?;
+    Ok(sz + 2)
+}
+
+/// Write a constructed object using the indefinite length form (X.690 section 9.1)
+///
+/// `write_content` must write the CER encoding of all items.
+#[cfg(feature = "std")]
+pub(crate) fn write_cer_constructed<F>(
+    class: Class,
+    tag: Tag,
+    writer: &mut dyn Write,
+    write_content: F,
+) -> SerializeResult<usize>
+where
+    F: FnOnce(&mut dyn Write) -> SerializeResult<usize>,
+{
+    let header = Header::new(class, true, tag, Length::Indefinite);
+    let mut sz = header.write_der_raw(writer)?;
+    sz += write_content(writer)?;
+    writer.write_all(&[0, 0])?;
+    Ok(sz + 2)
+}
+
+/// Write the CER encodings of the components of a `SET OF`, sorted as octet strings (X.690
+/// section 9.3)
+#[cfg(feature = "std")]
+pub(crate) fn write_cer_sorted(
+    mut items: Vec<Vec<u8>>,
+    writer: &mut dyn Write,
+) -> SerializeResult<usize> {
+    items.sort_by(|a, b| der_encoding_cmp(a, b));
+    let mut sz = 0;
+    for item in &items {
+        writer.write_all(item)?;
+        sz += item.len();
+    }
+    Ok(sz)
+}
+
+/// Write the CER encoding `encoded` of a value, replacing its tag (implicit tagging)
+///
+/// The encoding is constructed if the encoding of the value is constructed (X.690 section
+/// 8.14.3), for ex. if it is a string which was fragmented.
+#[cfg(feature = "std")]
+pub(crate) fn write_cer_retagged(
+    class: Class,
+    tag: Tag,
+    encoded: &[u8],
+    writer: &mut dyn Write,
+) -> SerializeResult<usize> {
+    let (content, header) = Header::from_ber(encoded).map_err(Error::from)?;
+    let header = Header::new(class, header.is_constructed(), tag, header.length());
+    let sz = header.write_der_raw(writer)?;
+    writer.write_all(content)?;
+    Ok(sz + content.len())
+}
+
+/// Re-encode DER objects using CER
+#[cfg(feature = "std")]
+pub(crate) fn write_cer_from_der(bytes: &[u8], writer: &mut dyn Write) -> SerializeResult<usize> {
+    let mut sz = 0;
+    let mut rem = bytes;
+    while !rem.is_empty() {
+        let (i, any) = Any::from_ber(rem).map_err(Error::from)?;
+        rem = i;
+        let Any { header, data } = any;
+        let (class, tag) = (header.class(), header.tag());
+        if header.is_constructed() && header.is_universal() && tag == Tag::Set {
+            sz += write_cer_constructed(class, tag, writer, |writer| write_cer_set(data, writer))?;
+        } else if header.is_constructed() {
+            sz += write_cer_constructed(class, tag, writer, |writer| {
+                write_cer_from_der(data, writer)
+            })?;
+        } else if data.len() > CER_MAX_STRING_LEN && is_cer_string(class, tag) {
+            sz += write_cer_fragments(tag, data, writer)?;
+        } else {
+            sz += write_primitive(class, tag, data, writer)?;
+        }
+    }
+    Ok(sz)
+}
+
+/// Re-encode the items of a DER `SET` or `SET OF` using CER
+///
+/// Items of a `SET` are ordered by tag, and items of a `SET OF` must be sorted again, since the
+/// CER and DER encodings of constructed items differ. See [`SetOrder`] for the rules used to
+/// distinguish them.
+#[cfg(feature = "std")]
+fn write_cer_set(content: &[u8], writer: &mut dyn Write) -> SerializeResult<usize> {
+    let mut tags = Vec::new();
+    let mut items = Vec::new();
+    let mut rem = content;
+    while !rem.is_empty() {
+        let (i, any) = Any::from_ber(rem).map_err(Error::from)?;
+        let mut v = Vec::new();
+        write_cer_from_der(&rem[..rem.len() - i.len()], &mut v)?;
+        tags.push((any.class(), any.tag()));
+        items.push(v);
+        rem = i;
+    }
+    match SetOrder::from_tags(&tags) {
+        SetOrder::Encoding => return write_cer_sorted(items, writer),
+        SetOrder::Tag => {
+            let mut tagged: Vec<_> = tags.into_iter().zip(items).collect();
+            tagged.sort_by_key(|(tag, _)| *tag);
+            items = tagged.into_iter().map(|(_, item)| item).collect();
+        }
+        SetOrder::Unknown => (),
+    }
+    let mut sz = 0;
+    for item in &items {
+        writer.write_all(item)?;
+        sz += item.len();
+    }
+    Ok(sz)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn encoding_rules_sequence() {
+        let v = vec![2, 3];
+        let der = v.to_encoded_vec(EncodingRules::Der).unwrap();
+        assert_eq!(&der, &hex!("30 06 02 01 02 02 01 03"));
+        let ber = v.to_encoded_vec(EncodingRules::Ber).unwrap();
+        assert_eq!(&ber, &der);
+        let cer = v.to_encoded_vec(EncodingRules::Cer).unwrap();
+        assert_eq!(&cer, &hex!("30 80 02 01 02 02 01 03 00 00"));
+        // CER output is valid BER
+        let (_, v2) = <Vec<u32>>::from_ber(&cer).expect("parsing failed");
+        assert_eq!(v2, vec![2, 3]);
+    }
+
+    #[test]
+    fn encoding_rules_string_fragments() {
+        let data = vec![0xaa; 2500];
+        let obj = OctetString::from(&data[..]);
+        let cer = obj.to_encoded_vec(EncodingRules::Cer).unwrap();
+        assert_eq!(&cer[..2], &[0x24, 0x80]);
+        // 3 fragments (1000, 1000, 500)
+        assert_eq!(&cer[2..6], &hex!("04 82 03 e8"));
+        assert_eq!(&cer[1006..1010], &hex!("04 82 03 e8"));
+        assert_eq!(&cer[2010..2014], &hex!("04 82 01 f4"));
+        assert_eq!(cer.len(), 2014 + 500 + 2);
+        assert_eq!(&cer[cer.len() - 2..], &[0, 0]);
+        // short strings are not fragmented
+        let obj = OctetString::from(&data[..1000]);
+        let cer = obj.to_encoded_vec(EncodingRules::Cer).unwrap();
+        assert_eq!(cer, obj.to_der_vec().unwrap());
+    }
+
+    #[test]
+    fn encoding_rules_bitstring_fragments() {
+        let data = vec![0xf0; 1500];
+        let obj = BitString::new(4, &data);
+        let cer = obj.to_encoded_vec(EncodingRules::Cer).unwrap();
+        assert_eq!(&cer[..2], &[0x23, 0x80]);
+        // first fragment: 999 bytes, no unused bits
+        assert_eq!(&cer[2..7], &hex!("03 82 03 e8 00"));
+        // last fragment: 501 bytes, with unused bits
+        assert_eq!(&cer[1006..1011], &hex!("03 82 01 f6 04"));
+        assert_eq!(cer.len(), 1011 + 501 + 2);
+    }
+
+    #[test]
+    fn encoding_rules_implicit_string_fragments() {
+        let data = vec![0xaa; 1500];
+        let obj = TaggedImplicit::<_, Error, 1>::implicit(OctetString::from(&data[..]));
+        let der = obj.to_der_vec().unwrap();
+        assert_eq!(&der[..4], &hex!("81 82 05 dc"));
+        let cer = obj.to_encoded_vec(EncodingRules::Cer).unwrap();
+        // constructed, since the base encoding is constructed (X.690 section 8.14.3)
+        assert_eq!(&cer[..2], &[0xa1, 0x80]);
+        assert_eq!(&cer[2..6], &hex!("04 82 03 e8"));
+        assert_eq!(&cer[1006..1010], &hex!("04 82 01 f4"));
+        assert_eq!(cer.len(), 1010 + 500 + 2);
+        // short strings are only retagged
+        let obj = TaggedImplicit::<_, Error, 1>::implicit(OctetString::from(&data[..3]));
+        let cer = obj.to_encoded_vec(EncodingRules::Cer).unwrap();
+        assert_eq!(&cer, &hex!("81 03 aa aa aa"));
+    }
+
+    #[test]
+    fn encoding_rules_set_of_order() {
+        // DER order: SEQUENCE { 2 } < SEQUENCE { 1, 5 } (compared by length octet)
+        let set: SetOf<Vec<u32>> = vec![vec![2], vec![1, 5]].into_iter().collect();
+        let der = set.to_der_vec().unwrap();
+        assert_eq!(&der, &hex!("31 0d 30 03 02 01 02 30 06 02 01 01 02 01 05"));
+        // CER order: SEQUENCE { 1, 5 } < SEQUENCE { 2 } (X.690 section 9.3)
+        let expected = &hex!("31 80 30 80 02 01 01 02 01 05 00 00 30 80 02 01 02 00 00 00 00");
+        let cer = set.to_encoded_vec(EncodingRules::Cer).unwrap();
+        assert_eq!(&cer, expected);
+        // same order when converting the DER encoding
+        let (_, any) = Any::from_der(&der).unwrap();
+        assert_eq!(&any.to_encoded_vec(EncodingRules::Cer).unwrap(), expected);
+    }
+}
@@ -1,7 +1,9 @@
 use crate::{Class, Tag};
+use alloc::boxed::Box;
 use alloc::str;
 use alloc::string;
 use alloc::string::String;
+use alloc::vec::Vec;
 use displaydoc::Display;
 use nom::error::{ErrorKind, FromExternalError, ParseError};
 use nom::IResult;
@@ -15,6 +17,11 @@ impl std::error::Error for DerConstraint {}
 
 #[derive(Clone, Copy, Debug, Display, PartialEq, Eq)]
 /// Error types for DER constraints
+///
+/// These errors are returned when the input is valid BER, but violates one of the additional
+/// constraints of DER (X.690 sections 10 and 11). Callers can use this to decide whether to
+/// reject the input, or accept it (for ex. with a warning).
+#[allow(deprecated)]
 pub enum DerConstraint {
     /// Indefinite length not allowed
     IndefiniteLength,
@@ -26,16 +33,29 @@ pub enum DerConstraint {
     MissingTimeZone,
     /// DateTime object is missing seconds
     MissingSeconds,
+    /// DateTime decimal point must be a period ('.')
+    InvalidDecimalPoint,
     /// Bitstring unused bits must be set to zero
     UnusedBitsNotZero,
-    /// Boolean value must be 0x00 of 0xff
+    /// Empty bitstring must be encoded as a single 0x00 octet (no unused bits)
+    EmptyBitStringUnusedBits,
+    /// Boolean value must be 0x00 or 0xff
+    #[deprecated(note = "parsers now return `DerConstraint::NonCanonicalBoolean`")]
     InvalidBoolean,
+    /// Boolean value must be 0x00 or 0xff
+    NonCanonicalBoolean,
     /// Integer must not be empty
     IntegerEmpty,
     /// Leading zeroes in Integer encoding
     IntegerLeadingZeroes,
     /// Leading 0xff in negative Integer encoding
     IntegerLeadingFF,
+    /// Items of a SET must be sorted by encoding
+    SetNotSorted,
+    /// Length must be encoded with the minimum number of octets
+    NonMinimalLength,
+    /// Value equal to its DEFAULT must not be encoded
+    DefaultValueEncoded,
 }
 
 // XXX
@@ -56,6 +76,8 @@ pub enum Error {
     BerValueError,
     /// Invalid Length
     InvalidLength,
+    /// Length is too large to be represented on this platform
+    LengthTooLarge,
     /// Invalid Value when parsing object with tag {tag:?} {msg:}
     InvalidValue { tag: Tag, msg: String },
     /// Invalid Tag
@@ -72,6 +94,10 @@ pub enum Error {
 
     /// Indefinite length not allowed
     IndefiniteLengthUnexpected,
+    /// End-of-contents marker outside of indefinite length content
+    UnexpectedEndOfContent,
+    /// Invalid end-of-contents marker (must be primitive, with zero length)
+    InvalidEndOfContent,
 
     /// DER object was expected to be constructed (and found to be primitive)
     ConstructExpected,
@@ -82,17 +108,47 @@ pub enum Error {
     IntegerTooLarge,
     /// BER integer is negative, while an unsigned integer was requested
     IntegerNegative,
+    /// Integer content length ({len}) exceeds the maximum ({max})
+    IntegerLengthLimit { len: usize, max: usize },
+    /// Object content length ({len}) exceeds the maximum ({max})
+    LengthLimit { len: usize, max: usize },
+    /// Integer value requires {bits} bits, but the target type has {max} bits
+    IntegerOutOfRange { bits: usize, max: usize },
     /// BER recursive parsing reached maximum depth
     BerMaxDepth,
+    /// Content of object with tag {tag:?} was not fully consumed ({remaining} bytes remaining)
+    TrailingContent { tag: Tag, remaining: usize },
 
     /// Invalid encoding or forbidden characters in string
     StringInvalidCharset,
+    /// Invalid characters for string type: {0:?}
+    StringInvalidCharacters(Vec<char>),
     /// Invalid Date or Time
     InvalidDateTime,
+    /// Date does not exist in calendar (year: {year}, month: {month}, day: {day})
+    InvalidCalendarDate { year: u32, month: u8, day: u8 },
+    /// Invalid hexadecimal string
+    InvalidHexString,
 
     /// DER Failed constraint
     DerConstraintFailed(DerConstraint),
 
+    /// Missing required field {0}
+    MissingField(String),
+    /// Duplicate field {0}
+    DuplicateField(String),
+    /// Field {0} is not in the expected order
+    UnorderedField(String),
+    /// Unexpected field (class: {class:?}, tag: {tag:?})
+    UnexpectedField { class: Class, tag: Tag },
+    /// Parsing field {index} failed: {error}
+    Field { index: usize, error: Box<Error> },
+
+    /// Invalid path segment: {0}
+    InvalidPath(String),
+    /// No object matching path segment {0}
+    PathNotFound(usize),
+
     /// Requesting borrowed data from a temporary object
     LifetimeError,
     /// Feature is not yet implemented
@@ -123,6 +179,23 @@ impl Error {
     pub const fn unexpected_tag(expected: Option<Tag>, actual: Tag) -> Self {
         Self::UnexpectedTag { expected, actual }
     }
+
+    /// Return the DER constraint, if this error is a DER violation
+    ///
+    /// If this function returns `Some`, the input is valid BER but not valid DER.
+    #[inline]
+    pub const fn der_constraint(&self) -> Option<DerConstraint> {
+        match self {
+            Self::DerConstraintFailed(c) => Some(*c),
+            _ => None,
+        }
+    }
+
+    /// Return true if this error is a DER violation (the input being valid BER)
+    #[inline]
+    pub const fn is_der_violation(&self) -> bool {
+        matches!(self, Self::DerConstraintFailed(_))
+    }
 }
 
 impl<'a> ParseError<&'a [u8]> for Error {
@@ -180,6 +253,10 @@ pub type ParseResult<'a, T, E = Error> = IResult<&'a [u8], T, E>;
 pub type Result<T, E = Error> = core::result::Result<T, E>;
 
 /// The error type for serialization operations of the [`ToDer`](crate::ToDer) trait.
+///
+/// Only [`SerializeError::IOError`] and [`SerializeError::BufferTooSmall`] are caused by the
+/// writer: when writing to an infallible sink (for ex. a `Vec<u8>`), all other variants describe
+/// a value which cannot be encoded.
 #[cfg(feature = "std")]
 #[derive(Debug, Error)]
 pub enum SerializeError {
@@ -192,10 +269,49 @@ pub enum SerializeError {
     #[error("Invalid Length")]
     InvalidLength,
 
+    /// The value cannot be represented by the ASN.1 type (for ex. a `UTCTime` year after 2049)
+    #[error("Value out of range for {tag:?}: {msg}")]
+    ValueOutOfRange { tag: Tag, msg: &'static str },
+
+    /// The length of the encoding does not fit in a `usize`
+    #[error("Length overflow")]
+    LengthOverflow,
+
+    /// The string contains characters which are not allowed by its type
+    #[error("Invalid characters for string type {tag:?}: {chars:?}")]
+    InvalidCharset { tag: Tag, chars: Vec<char> },
+
+    /// An indefinite length was used, which is not allowed in DER (X.690 section 10.1)
+    #[error("Indefinite length is not allowed in DER")]
+    UnsupportedIndefiniteInDer,
+
+    /// The output buffer is too small, `needed` bytes are required
+    #[error("Buffer too small ({needed} bytes needed)")]
+    BufferTooSmall { needed: usize },
+
     #[error("I/O error: {0:?}")]
     IOError(#[from] io::Error),
 }
 
+#[cfg(feature = "std")]
+impl SerializeError {
+    /// Return true if this error was returned by the writer
+    #[inline]
+    pub fn is_io_error(&self) -> bool {
+        matches!(self, Self::IOError(_))
+    }
+
+    /// Build an error from the result of the charset check of a string of type `tag`
+    ///
+    /// [`Error::StringInvalidCharacters`] is converted to [`SerializeError::InvalidCharset`].
+    pub fn from_charset_error(tag: Tag, e: Error) -> Self {
+        match e {
+            Error::StringInvalidCharacters(chars) => Self::InvalidCharset { tag, chars },
+            e => Self::ASN1Error(e),
+        }
+    }
+}
+
 #[cfg(feature = "std")]
 /// Holds the result of BER/DER encoding functions
 pub type SerializeResult<T> = std::result::Result<T, SerializeError>;
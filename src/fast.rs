@@ -0,0 +1,67 @@
+//! Slice accesses on hot parsing paths
+//!
+//! By default, these functions use checked slice accesses and the crate is compiled with
+//! `#![forbid(unsafe_code)]`. With the `unsafe-fast` feature, bounds checks are skipped: each
+//! function documents the invariant that callers must guarantee (callers are all in this crate,
+//! and state why the invariant holds). Invariants are still checked using `debug_assert!`.
+#![cfg_attr(feature = "unsafe-fast", allow(unsafe_code))]
+
+/// Return the byte at `index`
+///
+/// Invariant: `index < i.len()`
+#[inline]
+pub(crate) fn byte_at(i: &[u8], index: usize) -> u8 {
+    debug_assert!(index < i.len());
+    #[cfg(feature = "unsafe-fast")]
+    // SAFETY: `index < i.len()` is guaranteed by the caller
+    unsafe {
+        *i.get_unchecked(index)
+    }
+    #[cfg(not(feature = "unsafe-fast"))]
+    i[index]
+}
+
+/// Return the first `len` bytes of `i`
+///
+/// Invariant: `len <= i.len()`
+#[inline]
+pub(crate) fn prefix(i: &[u8], len: usize) -> &[u8] {
+    debug_assert!(len <= i.len());
+    #[cfg(feature = "unsafe-fast")]
+    // SAFETY: `len <= i.len()` is guaranteed by the caller
+    unsafe {
+        i.get_unchecked(..len)
+    }
+    #[cfg(not(feature = "unsafe-fast"))]
+    &i[..len]
+}
+
+/// Divide `i` into two slices at `mid`
+///
+/// Invariant: `mid <= i.len()`
+#[inline]
+pub(crate) fn split_at(i: &[u8], mid: usize) -> (&[u8], &[u8]) {
+    debug_assert!(mid <= i.len());
+    #[cfg(feature = "unsafe-fast")]
+    // SAFETY: `mid <= i.len()` is guaranteed by the caller
+    unsafe {
+        (i.get_unchecked(..mid), i.get_unchecked(mid..))
+    }
+    #[cfg(not(feature = "unsafe-fast"))]
+    i.split_at(mid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fast_accessors() {
+        let i = &[1, 2, 3];
+        assert_eq!(byte_at(i, 2), 3);
+        assert_eq!(prefix(i, 0), &[]);
+        assert_eq!(prefix(i, 3), i);
+        assert_eq!(split_at(i, 1), (&i[..1], &i[1..]));
+        assert_eq!(split_at(i, 3), (&i[..], &[][..]));
+    }
+}
@@ -0,0 +1,229 @@
+//! Streaming access to the content of string objects
+//!
+//! The content of a large `OCTET STRING` (for ex. a firmware image inside a CMS message) can be
+//! split into fragments, using the constructed form (BER only, X.690 section 8.7.3). The types
+//! of this module give access to the content without copying or reassembling it: fragments are
+//! returned as sub-slices of the input, in order, so consumers can hash the content or write it
+//! to disk incrementally.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use asn1_rs::{Any, FromBer};
+//! use std::io::Read;
+//!
+//! // constructed OCTET STRING, with 2 fragments
+//! let bytes = &[0x24, 0x80, 0x04, 0x02, 0x61, 0x62, 0x04, 0x01, 0x63, 0x00, 0x00];
+//! let (_, any) = Any::from_ber(bytes).expect("parsing failed");
+//! for fragment in any.string_fragments() {
+//!     let fragment = fragment.expect("invalid fragment");
+//!     assert!(!fragment.is_empty());
+//! }
+//! let mut content = Vec::new();
+//! any.string_content_reader().read_to_end(&mut content).expect("invalid fragments");
+//! assert_eq!(&content, b"abc");
+//! ```
+
+use crate::ber::MAX_RECURSION;
+use crate::{Any, Class, Error, FromBer, Result, Tag};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+
+/// Iterator over the fragments of the content of a string object
+///
+/// Created by [`Any::string_fragments`]. Fragments are borrowed from the input, and empty
+/// fragments are skipped. After returning an error, the iterator returns `None`.
+#[derive(Clone, Debug)]
+pub struct StringFragments<'a> {
+    /// Content of a primitive object, or error, not yet returned
+    pending: Option<Result<&'a [u8]>>,
+    /// Remaining content of each constructed object being read
+    stack: Vec<&'a [u8]>,
+}
+
+impl<'a> StringFragments<'a> {
+    fn new(any: &Any<'a>) -> Self {
+        if any.header.is_constructed() {
+            StringFragments {
+                pending: None,
+                stack: vec![any.data],
+            }
+        } else {
+            StringFragments {
+                pending: Some(Ok(any.data)),
+                stack: Vec::new(),
+            }
+        }
+    }
+
+    fn fail(&mut self, e: Error) -> Option<Result<&'a [u8]>> {
+        self.stack.clear();
+        Some(Err(e))
+    }
+}
+
+impl<'a> Iterator for StringFragments<'a> {
+    type Item = Result<&'a [u8]>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(pending) = self.pending.take() {
+            return Some(pending);
+        }
+        loop {
+            let top = self.stack.last_mut()?;
+            if top.is_empty() {
+                self.stack.pop();
+                continue;
+            }
+            let fragment = match Any::from_ber(top) {
+                Ok((rem, fragment)) => {
+                    *top = rem;
+                    fragment
+                }
+                Err(e) => return self.fail(e.into()),
+            };
+            if let Err(e) = fragment
+                .header
+                .assert_class(Class::Universal)
+                .and_then(|_| fragment.tag().assert_eq(Tag::OctetString))
+            {
+                return self.fail(e);
+            }
+            if fragment.header.is_constructed() {
+                if self.stack.len() >= MAX_RECURSION {
+                    return self.fail(Error::BerMaxDepth);
+                }
+                self.stack.push(fragment.data);
+            } else if !fragment.data.is_empty() {
+                return Some(Ok(fragment.data));
+            }
+        }
+    }
+}
+
+impl FusedIterator for StringFragments<'_> {}
+
+/// Reader over the content of a string object
+///
+/// Created by [`Any::string_content_reader`]. Invalid fragments are reported as errors of kind
+/// [`InvalidData`](std::io::ErrorKind::InvalidData), wrapping the parsing error.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[derive(Clone, Debug)]
+pub struct StringContentReader<'a> {
+    fragments: StringFragments<'a>,
+    current: &'a [u8],
+}
+
+#[cfg(feature = "std")]
+impl std::io::Read for StringContentReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        while self.current.is_empty() {
+            match self.fragments.next() {
+                Some(Ok(fragment)) => self.current = fragment,
+                Some(Err(e)) => {
+                    return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+                }
+                None => return Ok(0),
+            }
+        }
+        let n = core::cmp::min(buf.len(), self.current.len());
+        let (head, tail) = self.current.split_at(n);
+        buf[..n].copy_from_slice(head);
+        self.current = tail;
+        Ok(n)
+    }
+}
+
+impl<'a> Any<'a> {
+    /// Return an iterator over the fragments of the content of this string object
+    ///
+    /// If the object is primitive, its content is returned as a single fragment. If the object is
+    /// constructed (BER only, X.690 section 8.7.3), fragments must be `OCTET STRING` objects
+    /// (possibly constructed).
+    ///
+    /// This function cannot be used for `BIT STRING` objects, since each fragment has its own
+    /// unused bits: the iterator returns [`Error::Unsupported`].
+    pub fn string_fragments(&self) -> StringFragments<'a> {
+        if self.tag() == Tag::BitString {
+            return StringFragments {
+                pending: Some(Err(Error::Unsupported)),
+                stack: Vec::new(),
+            };
+        }
+        StringFragments::new(self)
+    }
+
+    /// Return a reader over the content of this string object, reassembling fragments if the
+    /// object is constructed
+    ///
+    /// See [`Any::string_fragments`] for the accepted objects. Data is not copied until read.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn string_content_reader(&self) -> StringContentReader<'a> {
+        StringContentReader {
+            fragments: self.string_fragments(),
+            current: &[],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn string_fragments_nested() {
+        // constructed OCTET STRING { "ab", "" , constructed { "c", "de" } }
+        let bytes = &hex!("24 80 04 02 61 62 04 00 24 07 04 01 63 04 02 64 65 00 00");
+        let (_, any) = Any::from_ber(bytes).expect("parsing failed");
+        let v = any
+            .string_fragments()
+            .collect::<Result<Vec<_>>>()
+            .expect("invalid fragments");
+        assert_eq!(v, vec![&b"ab"[..], b"c", b"de"]);
+        // primitive
+        let (_, any) = Any::from_ber(&hex!("04 03 61 62 63")).expect("parsing failed");
+        let v = any.string_fragments().collect::<Vec<_>>();
+        assert_eq!(v, vec![Ok(&b"abc"[..])]);
+    }
+
+    #[test]
+    fn string_fragments_errors() {
+        // fragment with wrong tag
+        let (_, any) = Any::from_ber(&hex!("24 06 04 01 61 02 01 01")).expect("parsing failed");
+        let mut it = any.string_fragments();
+        assert_eq!(it.next(), Some(Ok(&b"a"[..])));
+        assert!(matches!(it.next(), Some(Err(Error::UnexpectedTag { .. }))));
+        assert_eq!(it.next(), None);
+        // BIT STRING
+        let (_, any) = Any::from_ber(&hex!("03 02 00 61")).expect("parsing failed");
+        let v = any.string_fragments().collect::<Vec<_>>();
+        assert_eq!(v, vec![Err(Error::Unsupported)]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn string_content_reader() {
+        use std::io::Read;
+
+        let bytes = &hex!("24 80 04 02 61 62 24 07 04 01 63 04 02 64 65 00 00");
+        let (_, any) = Any::from_ber(bytes).expect("parsing failed");
+        let mut reader = any.string_content_reader();
+        let mut buf = [0u8; 3];
+        assert_eq!(reader.read(&mut buf).unwrap(), 2);
+        assert_eq!(&buf[..2], b"ab");
+        let mut rest = Vec::new();
+        reader.read_to_end(&mut rest).unwrap();
+        assert_eq!(&rest, b"cde");
+        // error
+        let (_, any) = Any::from_ber(&hex!("24 03 02 01 01")).expect("parsing failed");
+        let err = any
+            .string_content_reader()
+            .read_to_end(&mut rest)
+            .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+}
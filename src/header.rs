@@ -3,7 +3,9 @@ use crate::der_constraint_fail_if;
 use crate::error::*;
 #[cfg(feature = "std")]
 use crate::ToDer;
-use crate::{BerParser, Class, DerParser, DynTagged, FromBer, FromDer, Length, Tag, ToStatic};
+use crate::{
+    BerParser, Class, DerParser, DynTagged, FromBer, FromDer, Length, LengthEncoding, Tag, ToStatic,
+};
 use alloc::borrow::Cow;
 use core::convert::TryFrom;
 use nom::bytes::streaming::take;
@@ -25,6 +27,9 @@ pub struct Header<'a> {
     /// This is useful in some cases, where different representations of the same
     /// BER tags have different meanings (BER only)
     pub(crate) raw_tag: Option<Cow<'a, [u8]>>,
+
+    /// Optionally, the encoding form of the length, if header was parsed
+    pub(crate) length_encoding: Option<LengthEncoding>,
 }
 
 impl<'a> Header<'a> {
@@ -36,6 +41,7 @@ impl<'a> Header<'a> {
             class,
             length,
             raw_tag: None,
+            length_encoding: None,
         }
     }
 
@@ -70,7 +76,11 @@ impl<'a> Header<'a> {
     /// Set the length of this `Header`
     #[inline]
     pub fn with_length(self, length: Length) -> Self {
-        Self { length, ..self }
+        Self {
+            length,
+            length_encoding: None,
+            ..self
+        }
     }
 
     /// Update header to add reference to raw tag
@@ -103,6 +113,26 @@ impl<'a> Header<'a> {
         self.length
     }
 
+    /// Return the encoding form of the length of this header.
+    ///
+    /// If the header was parsed, this returns the form used in the input (short, long with the
+    /// number of length octets, or indefinite). Otherwise, this returns the form that would be
+    /// used to encode the length in DER.
+    #[inline]
+    pub fn length_encoding(&self) -> LengthEncoding {
+        self.length_encoding
+            .unwrap_or_else(|| LengthEncoding::canonical(self.length))
+    }
+
+    /// Return the number of octets used to encode this header in DER
+    ///
+    /// This is the number of identifier octets (see [`Tag::encoded_len`]) plus the number of
+    /// length octets (see [`Length::encoded_len`]). The raw tag, if present, is ignored.
+    #[inline]
+    pub const fn encoded_len(&self) -> usize {
+        self.tag.encoded_len() + self.length.encoded_len()
+    }
+
     /// Return the raw tag encoding, if it was stored in this object
     #[inline]
     pub fn raw_tag(&self) -> Option<&[u8]> {
@@ -121,6 +151,39 @@ impl<'a> Header<'a> {
         self.constructed
     }
 
+    /// Test if object is an end-of-contents marker (`00 00`, X.690 section 8.1.5)
+    ///
+    /// The marker must be universal and primitive, with tag 0 and a (definite) length of 0.
+    #[inline]
+    pub fn is_end_of_content(&self) -> bool {
+        self.is_universal()
+            && self.is_primitive()
+            && self.tag == Tag::EndOfContent
+            && self.length == Length::Definite(0)
+    }
+
+    /// Check that the constructed flag is allowed for this tag
+    ///
+    /// Some `Universal` types must use the primitive form (for ex. `BOOLEAN`, `INTEGER`, `NULL`,
+    /// `OBJECT IDENTIFIER`), and some must use the constructed form (`SEQUENCE`, `SET`,
+    /// `EXTERNAL`, `EMBEDDED PDV` and `CHARACTER STRING`), see X.690 section 8. Returns
+    /// [`Error::ConstructUnexpected`] or [`Error::ConstructExpected`] if the flag does not match.
+    ///
+    /// Other classes, and types allowing both forms (strings), are not checked.
+    pub fn check_form(&self) -> Result<()> {
+        if !self.is_universal() {
+            return Ok(());
+        }
+        match self.tag.0 {
+            // EOC, BOOLEAN, INTEGER, NULL, OID, REAL, ENUMERATED, RELATIVE-OID, TIME, and
+            // DATE/TIME-OF-DAY/DATE-TIME/DURATION/OID-IRI/RELATIVE-OID-IRI
+            0 | 1 | 2 | 5 | 6 | 9 | 10 | 13 | 14 | 31..=36 => self.assert_primitive(),
+            // EXTERNAL, EMBEDDED PDV, SEQUENCE, SET, CHARACTER STRING
+            8 | 11 | 16 | 17 | 29 => self.assert_constructed(),
+            _ => Ok(()),
+        }
+    }
+
     /// Return error if class is not the expected class
     #[inline]
     pub const fn assert_class(&self, class: Class) -> Result<()> {
@@ -143,6 +206,34 @@ impl<'a> Header<'a> {
         }
     }
 
+    /// Return a DER constraint error if object is not primitive
+    ///
+    /// String types can use the constructed form in BER, but not in DER (X.690 section 10.2).
+    #[inline]
+    pub const fn assert_der_primitive(&self) -> Result<()> {
+        if self.is_primitive() {
+            Ok(())
+        } else {
+            Err(Error::DerConstraintFailed(DerConstraint::Constructed))
+        }
+    }
+
+    /// Return error if some content remains after parsing the content of this object
+    ///
+    /// `rem` is the unparsed part of the content. This is used when converting a constructed
+    /// object, to make sure that all of its content was read.
+    #[inline]
+    pub const fn assert_consumed(&self, rem: &[u8]) -> Result<()> {
+        if rem.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::TrailingContent {
+                tag: self.tag,
+                remaining: rem.len(),
+            })
+        }
+    }
+
     /// Return error if object is primitive
     #[inline]
     pub const fn assert_constructed(&self) -> Result<()> {
@@ -156,22 +247,22 @@ impl<'a> Header<'a> {
     /// Test if object class is Universal
     #[inline]
     pub const fn is_universal(&self) -> bool {
-        self.class as u8 == Class::Universal as u8
+        self.class.is_universal()
     }
     /// Test if object class is Application
     #[inline]
     pub const fn is_application(&self) -> bool {
-        self.class as u8 == Class::Application as u8
+        self.class.is_application()
     }
     /// Test if object class is Context-specific
     #[inline]
     pub const fn is_contextspecific(&self) -> bool {
-        self.class as u8 == Class::ContextSpecific as u8
+        self.class.is_context_specific()
     }
     /// Test if object class is Private
     #[inline]
     pub const fn is_private(&self) -> bool {
-        self.class as u8 == Class::Private as u8
+        self.class.is_private()
     }
 
     /// Return error if object length is definite
@@ -200,6 +291,51 @@ impl<'a> Header<'a> {
     }
 }
 
+impl<'a> Header<'a> {
+    /// Maximum size of a DER header supported by [`Header::from_der_bounded`], in bytes
+    ///
+    /// This is 6 bytes for the identifier (tag numbers up to `u32::MAX`), and 9 bytes for the
+    /// length (up to 8 length bytes in the long form).
+    pub const MAX_DER_LEN: usize = 15;
+
+    /// Parse a DER header, reading at most [`Header::MAX_DER_LEN`] bytes
+    ///
+    /// This function is a stable, single-pass API to parse only the header of an object (for ex.
+    /// to filter messages by tag before parsing them completely):
+    /// - it reads at most `MAX_DER_LEN` bytes of input, whatever the input length
+    /// - it does not allocate memory: the returned header borrows the input
+    /// - it does not parse or check the object content
+    ///
+    /// On success, it returns the remaining input (starting with the object content) and the
+    /// header. If the input is too short, `Error::Incomplete` is returned. If the header is
+    /// longer than `MAX_DER_LEN`, `Error::InvalidLength` is returned.
+    ///
+    /// Unlike [`FromDer::from_der`], errors are not wrapped in `nom::Err`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use asn1_rs::{Header, Length, Tag};
+    ///
+    /// let bytes = &[0x30, 0x82, 0x01, 0x00 /* ... content ... */];
+    /// let (rem, header) = Header::from_der_bounded(bytes).expect("invalid header");
+    /// assert_eq!(header.tag(), Tag::Sequence);
+    /// assert_eq!(header.length(), Length::Definite(256));
+    /// assert!(rem.is_empty());
+    /// ```
+    pub fn from_der_bounded(bytes: &'a [u8]) -> Result<(&'a [u8], Header<'a>)> {
+        let n = bytes.len().min(Self::MAX_DER_LEN);
+        match Header::from_der(&bytes[..n]) {
+            Ok((rem, header)) => {
+                let consumed = n - rem.len();
+                Ok((&bytes[consumed..], header))
+            }
+            Err(nom::Err::Incomplete(_)) if bytes.len() > n => Err(Error::InvalidLength),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
 impl From<Tag> for Header<'_> {
     #[inline]
     fn from(tag: Tag) -> Self {
@@ -220,22 +356,20 @@ impl<'a> ToStatic for Header<'a> {
             class: self.class,
             length: self.length,
             raw_tag,
+            length_encoding: self.length_encoding,
         }
     }
 }
 
 impl<'a> FromBer<'a> for Header<'a> {
-    fn from_ber(bytes: &'a [u8]) -> ParseResult<Self> {
+    fn from_ber(bytes: &'a [u8]) -> ParseResult<'a, Self> {
         let (i1, el) = parse_identifier(bytes)?;
-        let class = match Class::try_from(el.0) {
-            Ok(c) => c,
-            Err(_) => unreachable!(), // Cannot fail, we have read exactly 2 bits
-        };
+        let class = el.0;
         let (i2, len) = parse_ber_length_byte(i1)?;
-        let (i3, len) = match (len.0, len.1) {
+        let (i3, len, encoding) = match (len.0, len.1) {
             (0, l1) => {
                 // Short form: MSB is 0, the rest encodes the length (which can be 0) (8.1.3.4)
-                (i2, Length::Definite(usize::from(l1)))
+                (i2, Length::Definite(usize::from(l1)), LengthEncoding::Short)
             }
             (_, 0) => {
                 // Indefinite form: MSB is 1, the rest is 0 (8.1.3.6)
@@ -243,7 +377,7 @@ impl<'a> FromBer<'a> for Header<'a> {
                 if el.1 == 0 {
                     return Err(nom::Err::Error(Error::ConstructExpected));
                 }
-                (i2, Length::Indefinite)
+                (i2, Length::Indefinite, LengthEncoding::Indefinite)
             }
             (_, l1) => {
                 // if len is 0xff -> error (8.1.3.5)
@@ -254,8 +388,8 @@ impl<'a> FromBer<'a> for Header<'a> {
                 match bytes_to_u64(llen) {
                     Ok(l) => {
                         let l =
-                            usize::try_from(l).or(Err(::nom::Err::Error(Error::InvalidLength)))?;
-                        (i3, Length::Definite(l))
+                            usize::try_from(l).or(Err(::nom::Err::Error(Error::LengthTooLarge)))?;
+                        (i3, Length::Definite(l), LengthEncoding::Long(l1))
                     }
                     Err(_) => {
                         return Err(::nom::Err::Error(Error::InvalidLength));
@@ -265,22 +399,23 @@ impl<'a> FromBer<'a> for Header<'a> {
         };
         let constructed = el.1 != 0;
         let hdr = Header::new(class, constructed, Tag(el.2), len).with_raw_tag(Some(el.3.into()));
+        let hdr = Header {
+            length_encoding: Some(encoding),
+            ..hdr
+        };
         Ok((i3, hdr))
     }
 }
 
 impl<'a> FromDer<'a> for Header<'a> {
-    fn from_der(bytes: &'a [u8]) -> ParseResult<Self> {
+    fn from_der(bytes: &'a [u8]) -> ParseResult<'a, Self> {
         let (i1, el) = parse_identifier(bytes)?;
-        let class = match Class::try_from(el.0) {
-            Ok(c) => c,
-            Err(_) => unreachable!(), // Cannot fail, we have read exactly 2 bits
-        };
+        let class = el.0;
         let (i2, len) = parse_ber_length_byte(i1)?;
-        let (i3, len) = match (len.0, len.1) {
+        let (i3, len, encoding) = match (len.0, len.1) {
             (0, l1) => {
                 // Short form: MSB is 0, the rest encodes the length (which can be 0) (8.1.3.4)
-                (i2, Length::Definite(usize::from(l1)))
+                (i2, Length::Definite(usize::from(l1)), LengthEncoding::Short)
             }
             (_, 0) => {
                 // Indefinite form is not allowed in DER (10.1)
@@ -305,8 +440,8 @@ impl<'a> FromDer<'a> for Header<'a> {
                         // DER: should have been encoded in short form (< 127)
                         // XXX der_constraint_fail_if!(i, l < 127);
                         let l =
-                            usize::try_from(l).or(Err(::nom::Err::Error(Error::InvalidLength)))?;
-                        (i3, Length::Definite(l))
+                            usize::try_from(l).or(Err(::nom::Err::Error(Error::LengthTooLarge)))?;
+                        (i3, Length::Definite(l), LengthEncoding::Long(l1))
                     }
                     Err(_) => {
                         return Err(::nom::Err::Error(Error::InvalidLength));
@@ -316,6 +451,10 @@ impl<'a> FromDer<'a> for Header<'a> {
         };
         let constructed = el.1 != 0;
         let hdr = Header::new(class, constructed, Tag(el.2), len).with_raw_tag(Some(el.3.into()));
+        let hdr = Header {
+            length_encoding: Some(encoding),
+            ..hdr
+        };
         Ok((i3, hdr))
     }
 }
@@ -330,21 +469,7 @@ impl DynTagged for (Class, bool, Tag) {
 impl ToDer for (Class, bool, Tag) {
     fn to_der_len(&self) -> Result<usize> {
         let (_, _, tag) = self;
-        match tag.0 {
-            0..=30 => Ok(1),
-            t => {
-                let mut sz = 1;
-                let mut val = t;
-                loop {
-                    if val <= 127 {
-                        return Ok(sz + 1);
-                    } else {
-                        val >>= 7;
-                        sz += 1;
-                    }
-                }
-            }
-        }
+        Ok(tag.encoded_len())
     }
 
     fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
@@ -353,18 +478,20 @@ impl ToDer for (Class, bool, Tag) {
         let b0 = b0 | if *constructed { 0b10_0000 } else { 0 };
         if tag.0 > 30 {
             let b0 = b0 | 0b1_1111;
-            let mut sz = writer.write(&[b0])?;
+            // base-128 encoding, most significant group first (X.690 section 8.1.2.4.2)
+            let mut buf = [0u8; 5];
+            let mut idx = buf.len() - 1;
             let mut val = tag.0;
-            loop {
-                if val <= 127 {
-                    sz += writer.write(&[val as u8])?;
-                    return Ok(sz);
-                } else {
-                    let b = (val & 0b0111_1111) as u8 | 0b1000_0000;
-                    sz += writer.write(&[b])?;
-                    val >>= 7;
-                }
+            buf[idx] = (val & 0b0111_1111) as u8;
+            val >>= 7;
+            while val > 0 {
+                idx -= 1;
+                buf[idx] = (val & 0b0111_1111) as u8 | 0b1000_0000;
+                val >>= 7;
             }
+            let sz = writer.write(&[b0])?;
+            let sz = sz + writer.write(&buf[idx..])?;
+            Ok(sz)
         } else {
             let b0 = b0 | (tag.0 as u8);
             let sz = writer.write(&[b0])?;
@@ -386,9 +513,7 @@ impl DynTagged for Header<'_> {
 #[cfg(feature = "std")]
 impl ToDer for Header<'_> {
     fn to_der_len(&self) -> Result<usize> {
-        let tag_len = (self.class, self.constructed, self.tag).to_der_len()?;
-        let len_len = self.length.to_der_len()?;
-        Ok(tag_len + len_len)
+        Ok(self.encoded_len())
     }
 
     fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
@@ -401,17 +526,89 @@ impl ToDer for Header<'_> {
         Ok(0)
     }
 
+    /// Similar to using `to_der`, but accepts the indefinite length form
     fn write_der_raw(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
         // use raw_tag if present
         let sz = match &self.raw_tag {
             Some(t) => writer.write(t)?,
             None => (self.class, self.constructed, self.tag).write_der_header(writer)?,
         };
-        let sz = sz + self.length.write_der_header(writer)?;
+        let sz = sz + self.length.write_ber(writer)?;
         Ok(sz)
     }
 }
 
+#[cfg(feature = "std")]
+impl Header<'_> {
+    /// Write this header using BER, preserving the original encoding
+    ///
+    /// The raw tag (if present) is written as is, and the length is written using the form it
+    /// was parsed with (see [`Header::length_encoding`]): short, long with the same number of
+    /// length octets (including leading zeroes), or indefinite. This allows re-emitting a
+    /// parsed header byte-for-byte, while [`ToDer::to_der_vec`] always uses the minimal form.
+    ///
+    /// Returns an error if the length does not fit in the original form.
+    pub fn write_ber_original(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        let sz = match &self.raw_tag {
+            Some(t) => writer.write(t)?,
+            None => (self.class, self.constructed, self.tag).write_der_header(writer)?,
+        };
+        let sz = sz
+            + match (self.length, self.length_encoding()) {
+                (Length::Indefinite, LengthEncoding::Indefinite) => writer.write(&[0x80])?,
+                (Length::Definite(l), LengthEncoding::Short) if l <= 0x7f => {
+                    writer.write(&[l as u8])?
+                }
+                (Length::Definite(l), LengthEncoding::Long(n)) => {
+                    let b = l.to_be_bytes();
+                    let n = usize::from(n);
+                    let used = b.iter().skip_while(|&&x| x == 0).count();
+                    if used > n {
+                        return Err(Error::InvalidLength.into());
+                    }
+                    let mut sz = writer.write(&[0x80 | n as u8])?;
+                    // pad with leading zeroes, to the original number of length octets
+                    for _ in b.len()..n {
+                        sz += writer.write(&[0])?;
+                    }
+                    sz + writer.write(&b[b.len().saturating_sub(n)..])?
+                }
+                _ => return Err(Error::InvalidLength.into()),
+            };
+        Ok(sz)
+    }
+
+    /// Serialize this header using BER to a new `Vec<u8>`, preserving the original encoding
+    ///
+    /// See [`Header::write_ber_original`].
+    pub fn to_ber_original(&self) -> SerializeResult<Vec<u8>> {
+        let mut v = Vec::new();
+        self.write_ber_original(&mut v)?;
+        Ok(v)
+    }
+}
+
+/// Return the length of the encoding of a primitive universal object with `len` contents octets
+/// (header and contents)
+#[cfg(feature = "std")]
+pub(crate) fn primitive_der_len(tag: Tag, len: usize) -> Result<usize> {
+    let header = Header::new(Class::Universal, false, tag, Length::Definite(len));
+    Ok(header.to_der_len()? + len)
+}
+
+/// Write the header of a primitive universal object with `len` contents octets
+///
+/// The length is written using the short or long form, as required by DER.
+#[cfg(feature = "std")]
+pub(crate) fn write_header(
+    tag: Tag,
+    len: usize,
+    writer: &mut dyn std::io::Write,
+) -> SerializeResult<usize> {
+    let header = Header::new(Class::Universal, false, tag, Length::Definite(len));
+    header.write_der_header(writer)
+}
+
 /// Compare two BER headers. `len` fields are compared only if both objects have it set (same for `raw_tag`)
 impl<'a> PartialEq<Header<'a>> for Header<'a> {
     fn eq(&self, other: &Header) -> bool {
@@ -477,7 +674,9 @@ mod tests {
         // indefinite length
         let hdr4 = hdr3.with_length(Length::Indefinite);
         assert!(hdr4.assert_definite().is_err());
-        let xx = hdr4.to_der_vec().expect("serialize failed");
+        let e = hdr4.to_der_vec().expect_err("indefinite length in DER");
+        assert!(matches!(e, SerializeError::UnsupportedIndefiniteInDer));
+        let xx = hdr4.to_der_vec_raw().expect("serialize failed");
         assert_eq!(&xx, &[0xa2, 0x80]);
 
         // parse_*_content
@@ -487,4 +686,142 @@ mod tests {
         let (_, r) = hdr.parse_der_content(&input[2..]).unwrap();
         assert_eq!(r, &input[2..]);
     }
+
+    #[test]
+    fn header_length_encoding() {
+        // short form
+        let (_, hdr) = Header::from_ber(&hex!("04 01 00")).expect("parsing header failed");
+        assert_eq!(hdr.length_encoding(), LengthEncoding::Short);
+        // long form (non-canonical, valid in BER)
+        let (_, hdr) = Header::from_ber(&hex!("04 82 00 01 00")).expect("parsing header failed");
+        assert_eq!(hdr.length(), Length::Definite(1));
+        assert_eq!(hdr.length_encoding(), LengthEncoding::Long(2));
+        // indefinite form
+        let (_, hdr) = Header::from_ber(&hex!("30 80 00 00")).expect("parsing header failed");
+        assert_eq!(hdr.length_encoding(), LengthEncoding::Indefinite);
+        // DER
+        let (_, hdr) = Header::from_der(&hex!("04 81 80")).expect("parsing header failed");
+        assert_eq!(hdr.length_encoding(), LengthEncoding::Long(1));
+        // built headers use the canonical form
+        let hdr = Header::new_simple(Tag::OctetString).with_length(Length::Definite(0x100));
+        assert_eq!(hdr.length_encoding(), LengthEncoding::Long(2));
+        let hdr = hdr.with_length(Length::Definite(2));
+        assert_eq!(hdr.length_encoding(), LengthEncoding::Short);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn header_to_ber_original() {
+        for input in [
+            &hex!("04 01")[..],
+            &hex!("04 81 01")[..],
+            &hex!("04 83 00 01 00")[..],
+            &hex!("30 80")[..],
+            &hex!("1f 81 00 82 00 00")[..],
+        ] {
+            let (_, hdr) = Header::from_ber(input).expect("parsing header failed");
+            assert_eq!(hdr.to_ber_original().unwrap(), input);
+        }
+        // DER always uses the minimal form
+        let (_, hdr) = Header::from_ber(&hex!("04 83 00 01 00")).unwrap();
+        assert_eq!(hdr.to_der_vec().unwrap(), &hex!("04 82 01 00"));
+        // more than 8 length octets
+        let (_, hdr) = Header::from_ber(&hex!("04 8a 00 00 00 00 00 00 00 00 00 01")).unwrap();
+        assert_eq!(
+            hdr.to_ber_original().unwrap(),
+            &hex!("04 8a 00 00 00 00 00 00 00 00 00 01")
+        );
+        // built headers use the canonical form
+        let hdr = Header::new_simple(Tag::OctetString).with_length(Length::Definite(0x100));
+        assert_eq!(hdr.to_ber_original().unwrap(), &hex!("04 82 01 00"));
+        let hdr = Header::new_simple(Tag::Sequence)
+            .with_constructed(true)
+            .with_length(Length::Indefinite);
+        assert_eq!(hdr.to_ber_original().unwrap(), &hex!("30 80"));
+    }
+
+    #[test]
+    fn header_high_tag_number() {
+        // [PRIVATE 16384]
+        let input = &hex!("df 81 80 00 00");
+        let (rem, hdr) = Header::from_ber(input).expect("parsing header failed");
+        assert!(rem.is_empty());
+        assert_eq!(hdr.class(), Class::Private);
+        assert_eq!(hdr.tag(), Tag(0x4000));
+        // largest supported tag number
+        let (_, hdr) = Header::from_ber(&hex!("9f 8f ff ff ff 7f 00")).expect("parsing failed");
+        assert_eq!(hdr.tag(), Tag(u32::MAX));
+        // tag number does not fit in u32
+        let res = Header::from_ber(&hex!("9f 9f ff ff ff 7f 00"));
+        assert!(res.is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn header_high_tag_number_to_der() {
+        let hdr = Header::new(Class::Private, false, Tag(0x4000), Length::Definite(0));
+        assert_eq!(hdr.to_der_len(), Ok(5));
+        let v = hdr.to_der_vec().expect("serialization failed");
+        assert_eq!(&v, &hex!("df 81 80 00 00"));
+        let hdr = Header::new(
+            Class::ContextSpecific,
+            true,
+            Tag(u32::MAX),
+            Length::Definite(0),
+        );
+        let v = hdr.to_der_vec().expect("serialization failed");
+        assert_eq!(&v, &hex!("bf 8f ff ff ff 7f 00"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn header_write_header_long_length() {
+        let mut v = Vec::new();
+        assert_eq!(write_header(Tag::OctetString, 0x7f, &mut v).unwrap(), 2);
+        assert_eq!(&v, &hex!("04 7f"));
+        v.clear();
+        assert_eq!(write_header(Tag::OctetString, 0x100, &mut v).unwrap(), 4);
+        assert_eq!(&v, &hex!("04 82 01 00"));
+        assert_eq!(primitive_der_len(Tag::OctetString, 0x100), Ok(0x104));
+        // fixed-size types use the same helper
+        assert_eq!(true.to_der_vec().unwrap(), &hex!("01 01 ff"));
+        assert_eq!(Null::new().to_der_vec().unwrap(), &hex!("05 00"));
+    }
+
+    #[test]
+    fn header_encoded_len() {
+        assert_eq!(Tag(30).encoded_len(), 1);
+        assert_eq!(Tag(31).encoded_len(), 2);
+        assert_eq!(Tag(127).encoded_len(), 2);
+        assert_eq!(Tag(128).encoded_len(), 3);
+        assert_eq!(Tag(0x3fff).encoded_len(), 3);
+        assert_eq!(Tag(0x4000).encoded_len(), 4);
+        assert_eq!(Tag(u32::MAX).encoded_len(), 6);
+        for &tag in &[Tag(1), Tag(31), Tag(200), Tag(0x4000), Tag(u32::MAX)] {
+            for &len in &[0, 0x7f, 0x80, 0x1_0000] {
+                let header = Header::new(Class::ContextSpecific, true, tag, Length::Definite(len));
+                let v = header.to_der_vec().expect("serialization failed");
+                assert_eq!(header.encoded_len(), v.len());
+                assert_eq!(header.to_der_len(), Ok(v.len()));
+            }
+        }
+    }
+
+    #[test]
+    fn header_from_der_bounded() {
+        let input = &hex!("a1 03 02 01 02");
+        let (rem, hdr) = Header::from_der_bounded(input).expect("parsing header failed");
+        assert_eq!(rem, &input[2..]);
+        assert_eq!(hdr.class(), Class::ContextSpecific);
+        assert_eq!(hdr.tag(), Tag(1));
+        assert!(hdr.is_constructed());
+        // incomplete
+        let res = Header::from_der_bounded(&hex!("04 82 01"));
+        assert!(matches!(res, Err(Error::Incomplete(_))));
+        // header too long (long form with 15 length bytes)
+        let input = &hex!("04 8f 00 00 00 00 00 00 00 00 00 00 00 00 00 00 01 00");
+        assert_eq!(Header::from_der_bounded(input), Err(Error::InvalidLength));
+        // indefinite length is not DER
+        assert!(Header::from_der_bounded(&hex!("30 80 00 00")).is_err());
+    }
 }
@@ -0,0 +1,159 @@
+//! Hexadecimal representation of encoded objects
+//!
+//! This is mostly useful to embed encodings in configuration files, logs, or tests.
+
+use crate::{Error, FromDer, Result};
+use alloc::vec::Vec;
+use core::fmt;
+use std::io;
+
+/// A writer adapter, writing bytes as hexadecimal characters to a [`fmt::Write`] object
+///
+/// This can be used to serialize objects directly to a `String` (or any formatter), for ex.
+/// using [`ToDer::write_der`](crate::ToDer::write_der). Each byte is written as 2 characters,
+/// without separator.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{HexWriter, ToDer};
+///
+/// let mut s = String::new();
+/// let mut writer = HexWriter::uppercase(&mut s);
+/// let _ = 0x2au8.write_der(&mut writer).expect("serialization failed");
+/// assert_eq!(s, "02012A");
+/// ```
+#[derive(Debug)]
+pub struct HexWriter<W: fmt::Write> {
+    inner: W,
+    uppercase: bool,
+}
+
+impl<W: fmt::Write> HexWriter<W> {
+    /// Build a new `HexWriter`, using lowercase characters
+    pub const fn new(inner: W) -> Self {
+        HexWriter {
+            inner,
+            uppercase: false,
+        }
+    }
+
+    /// Build a new `HexWriter`, using uppercase characters
+    pub const fn uppercase(inner: W) -> Self {
+        HexWriter {
+            inner,
+            uppercase: true,
+        }
+    }
+
+    /// Get a reference to the underlying writer
+    #[inline]
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Return the underlying writer
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: fmt::Write> io::Write for HexWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        for b in buf {
+            let res = if self.uppercase {
+                write!(self.inner, "{:02X}", b)
+            } else {
+                write!(self.inner, "{:02x}", b)
+            };
+            res.map_err(|_| io::Error::new(io::ErrorKind::Other, "formatter error"))?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Decode a hexadecimal string. Whitespace is ignored.
+fn decode_hex(s: &str) -> Result<Vec<u8>> {
+    let digits = s
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| {
+            c.to_digit(16)
+                .map(|d| d as u8)
+                .ok_or(Error::InvalidHexString)
+        })
+        .collect::<Result<Vec<u8>>>()?;
+    if digits.len() % 2 != 0 {
+        return Err(Error::InvalidHexString);
+    }
+    let bytes = digits.chunks(2).map(|c| (c[0] << 4) | c[1]).collect();
+    Ok(bytes)
+}
+
+/// Parse a DER object from its hexadecimal representation
+///
+/// Whitespace is ignored, and both lowercase and uppercase characters are accepted. The input
+/// must contain exactly one object: trailing data is an error (`Error::InvalidLength`).
+///
+/// Since the decoded bytes are temporary, this function can only be used for types that do not
+/// borrow the input (for ex. integers or `String`).
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::from_der_hex;
+///
+/// let v: Vec<u32> = from_der_hex("30 06 02 01 02 02 01 03").expect("parsing failed");
+/// assert_eq!(v, vec![2, 3]);
+/// ```
+pub fn from_der_hex<T>(s: &str) -> Result<T>
+where
+    T: for<'a> FromDer<'a>,
+{
+    let bytes = decode_hex(s)?;
+    let (rem, obj) = T::from_der(&bytes)?;
+    if !rem.is_empty() {
+        return Err(Error::InvalidLength);
+    }
+    Ok(obj)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn to_der_hex() {
+        let v = vec![2u32, 3];
+        assert_eq!(v.to_der_hex().as_deref().ok(), Some("3006020102020103"));
+        let s = "abc".to_der_hex().expect("serialization failed");
+        assert_eq!(s, "0c03616263");
+    }
+
+    #[test]
+    fn from_der_hex_roundtrip() {
+        let s = String::from("Hello");
+        let hex = s.to_der_hex().expect("serialization failed");
+        let s2: String = from_der_hex(&hex).expect("parsing failed");
+        assert_eq!(s2, s);
+        // uppercase and whitespace
+        let b: bool = from_der_hex("01 01 FF").expect("parsing failed");
+        assert!(b);
+        // errors
+        assert!(from_der_hex::<u32>("02 01").is_err());
+        assert_eq!(from_der_hex::<u32>("02 01 0"), Err(Error::InvalidHexString));
+        assert_eq!(
+            from_der_hex::<u32>("02 01 0g"),
+            Err(Error::InvalidHexString)
+        );
+        assert_eq!(
+            from_der_hex::<u32>("02 01 00 00"),
+            Err(Error::InvalidLength)
+        );
+    }
+}
@@ -0,0 +1,201 @@
+//! Interning of repeated values
+//!
+//! Workloads parsing many similar objects (for ex. millions of certificates) decode the same
+//! OIDs and short strings over and over. Keeping owned copies of all these values uses a lot of
+//! memory. A [`ParseSession`] owns tables of interned values: identical OIDs and strings are
+//! mapped to a single shared instance (using `Arc`), which can be cheaply cloned and kept after
+//! the input is released.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use asn1_rs::ParseSession;
+//! use std::sync::Arc;
+//!
+//! let mut session = ParseSession::new();
+//! // OID 2.5.4.3 (commonName), twice
+//! let input = &[0x06, 0x03, 0x55, 0x04, 0x03, 0x06, 0x03, 0x55, 0x04, 0x03];
+//! let (rem, oid1) = session.parse_der_oid(input).expect("parsing failed");
+//! let (_, oid2) = session.parse_der_oid(rem).expect("parsing failed");
+//! assert_eq!(oid1.to_id_string(), "2.5.4.3");
+//! assert!(Arc::ptr_eq(&oid1, &oid2));
+//! assert_eq!(session.num_oids(), 1);
+//! ```
+
+use crate::*;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// Default maximum length (in bytes) of interned strings
+pub const DEFAULT_INTERN_MAX_STRING_LEN: usize = 64;
+
+/// Tables of interned OIDs and strings
+///
+/// Only strings of at most [`ParseSession::max_string_len`] bytes are interned: longer strings
+/// are rarely repeated, and are returned in a new `Arc` without being stored.
+///
+/// Interned values are kept until the session is dropped or [cleared](ParseSession::clear).
+#[derive(Debug)]
+pub struct ParseSession {
+    oids: HashMap<Box<[u8]>, Arc<Oid<'static>>>,
+    relative_oids: HashMap<Box<[u8]>, Arc<Oid<'static>>>,
+    strings: HashSet<Arc<str>>,
+    max_string_len: usize,
+}
+
+impl ParseSession {
+    /// Build a new session with empty tables
+    pub fn new() -> Self {
+        Self::with_max_string_len(DEFAULT_INTERN_MAX_STRING_LEN)
+    }
+
+    /// Build a new session, interning strings of at most `max_string_len` bytes
+    pub fn with_max_string_len(max_string_len: usize) -> Self {
+        ParseSession {
+            oids: HashMap::new(),
+            relative_oids: HashMap::new(),
+            strings: HashSet::new(),
+            max_string_len,
+        }
+    }
+
+    /// Return the maximum length (in bytes) of interned strings
+    pub fn max_string_len(&self) -> usize {
+        self.max_string_len
+    }
+
+    /// Return the shared instance of `oid`, interning it if needed
+    pub fn intern_oid(&mut self, oid: &Oid) -> Arc<Oid<'static>> {
+        let table = if oid.is_relative() {
+            &mut self.relative_oids
+        } else {
+            &mut self.oids
+        };
+        if let Some(shared) = table.get(oid.as_bytes()) {
+            return Arc::clone(shared);
+        }
+        let shared = Arc::new(oid.to_owned());
+        table.insert(oid.as_bytes().into(), Arc::clone(&shared));
+        shared
+    }
+
+    /// Return the shared instance of `s`, interning it if it is short enough
+    pub fn intern_str(&mut self, s: &str) -> Arc<str> {
+        if let Some(shared) = self.strings.get(s) {
+            return Arc::clone(shared);
+        }
+        let shared: Arc<str> = Arc::from(s);
+        if s.len() <= self.max_string_len {
+            self.strings.insert(Arc::clone(&shared));
+        }
+        shared
+    }
+
+    /// Parse a DER-encoded `OBJECT IDENTIFIER`, and return its shared instance
+    pub fn parse_der_oid<'a>(&mut self, bytes: &'a [u8]) -> ParseResult<'a, Arc<Oid<'static>>> {
+        let (rem, oid) = Oid::from_der(bytes)?;
+        Ok((rem, self.intern_oid(&oid)))
+    }
+
+    /// Parse a DER-encoded string, and return its shared instance
+    ///
+    /// Accepted types are the types of [`AnyString`] (`TeletexString`, `PrintableString`,
+    /// `UniversalString`, `UTF8String` and `BMPString`), `IA5String`, `VisibleString` and
+    /// `NumericString`.
+    pub fn parse_der_string<'a>(&mut self, bytes: &'a [u8]) -> ParseResult<'a, Arc<str>> {
+        let (_, header) = Header::from_der(bytes)?;
+        let (rem, s) = match (header.class(), header.tag()) {
+            (Class::Universal, Tag::Ia5String) => {
+                let (rem, s) = Ia5String::from_der(bytes)?;
+                (rem, self.intern_str(s.as_str()))
+            }
+            (Class::Universal, Tag::VisibleString) => {
+                let (rem, s) = VisibleString::from_der(bytes)?;
+                (rem, self.intern_str(s.as_str()))
+            }
+            (Class::Universal, Tag::NumericString) => {
+                let (rem, s) = NumericString::from_der(bytes)?;
+                (rem, self.intern_str(s.as_str()))
+            }
+            _ => {
+                let (rem, s) = AnyString::from_der(bytes)?;
+                (rem, self.intern_str(s.as_str()))
+            }
+        };
+        Ok((rem, s))
+    }
+
+    /// Return the number of interned OIDs (absolute and relative)
+    pub fn num_oids(&self) -> usize {
+        self.oids.len() + self.relative_oids.len()
+    }
+
+    /// Return the number of interned strings
+    pub fn num_strings(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Remove all interned values
+    ///
+    /// Values previously returned are still valid, but will not be shared with values returned
+    /// after this call.
+    pub fn clear(&mut self) {
+        self.oids.clear();
+        self.relative_oids.clear();
+        self.strings.clear();
+    }
+}
+
+impl Default for ParseSession {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use hex_literal::hex;
+    use std::sync::Arc;
+
+    #[test]
+    fn parse_session_intern() {
+        let mut session = ParseSession::with_max_string_len(4);
+        // OIDs
+        let oid = oid!(1.2.840 .113549);
+        let a = session.intern_oid(&oid);
+        let b = session.intern_oid(&oid.to_owned());
+        assert!(Arc::ptr_eq(&a, &b));
+        assert_eq!(*a, oid);
+        let rel = Oid::new_relative(oid.as_bytes().into());
+        let c = session.intern_oid(&rel);
+        assert!(c.is_relative());
+        assert!(!Arc::ptr_eq(&a, &c));
+        assert_eq!(session.num_oids(), 2);
+        // strings
+        let (rem, s1) = session
+            .parse_der_string(&hex!("13 02 46 52 0c 02 46 52"))
+            .expect("parsing failed");
+        let (_, s2) = session.parse_der_string(rem).expect("parsing failed");
+        assert_eq!(&*s1, "FR");
+        assert!(Arc::ptr_eq(&s1, &s2));
+        // IA5String, VisibleString and NumericString
+        let (rem, s3) = session
+            .parse_der_string(&hex!("16 02 46 52 1a 02 46 52 12 01 31"))
+            .expect("parsing failed");
+        assert!(Arc::ptr_eq(&s1, &s3));
+        let (rem, s4) = session.parse_der_string(rem).expect("parsing failed");
+        assert!(Arc::ptr_eq(&s1, &s4));
+        let (_, s5) = session.parse_der_string(rem).expect("parsing failed");
+        assert_eq!(&*s5, "1");
+        assert!(session.parse_der_string(&hex!("04 01 31")).is_err());
+        // long strings are not interned
+        let l1 = session.intern_str("abcdef");
+        let l2 = session.intern_str("abcdef");
+        assert!(!Arc::ptr_eq(&l1, &l2));
+        assert_eq!(session.num_strings(), 2);
+        session.clear();
+        assert_eq!(session.num_oids(), 0);
+        assert_eq!(session.num_strings(), 0);
+    }
+}
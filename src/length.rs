@@ -1,10 +1,12 @@
 use crate::{DynTagged, Error, Result, Tag};
 #[cfg(feature = "std")]
-use crate::{SerializeResult, ToDer};
+use crate::{SerializeError, SerializeResult, ToDer};
+use core::mem::size_of;
 use core::ops;
 
 /// BER Object Length
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Length {
     /// Definite form (X.690 8.1.3.3)
     Definite(usize),
@@ -12,6 +14,35 @@ pub enum Length {
     Indefinite,
 }
 
+/// Encoding form of a BER length
+///
+/// This describes how a length was encoded in the input, which is useful to report
+/// non-canonical (but valid in BER) encodings.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum LengthEncoding {
+    /// Short form, on a single octet (X.690 8.1.3.4)
+    Short,
+    /// Long form (X.690 8.1.3.5), with the number of subsequent length octets
+    Long(u8),
+    /// Indefinite form (X.690 8.1.3.6)
+    Indefinite,
+}
+
+impl LengthEncoding {
+    /// Return the minimal (DER) encoding form for the given length
+    pub(crate) fn canonical(length: Length) -> Self {
+        match length {
+            Length::Definite(l) if l <= 0x7f => LengthEncoding::Short,
+            Length::Definite(l) => {
+                let octets = size_of::<usize>() - (l.leading_zeros() / 8) as usize;
+                LengthEncoding::Long(octets as u8)
+            }
+            Length::Indefinite => LengthEncoding::Indefinite,
+        }
+    }
+}
+
 impl Length {
     /// Return true if length is definite and equal to 0
     #[inline]
@@ -34,6 +65,20 @@ impl Length {
         matches!(self, Length::Definite(_))
     }
 
+    /// Return the number of octets used to encode this length in DER
+    ///
+    /// The short form (1 octet) is used for lengths up to 127. Larger lengths use the long form
+    /// (X.690 section 8.1.3.5): one octet for the number of subsequent octets, followed by the
+    /// length with no leading zero octets. The indefinite form is encoded on 1 octet.
+    pub const fn encoded_len(&self) -> usize {
+        match *self {
+            Length::Definite(l) if l > 0x7f => {
+                1 + size_of::<usize>() - (l.leading_zeros() / 8) as usize
+            }
+            _ => 1,
+        }
+    }
+
     /// Return error if length is not definite
     #[inline]
     pub const fn assert_definite(&self) -> Result<()> {
@@ -93,19 +138,25 @@ impl DynTagged for Length {
 #[cfg(feature = "std")]
 impl ToDer for Length {
     fn to_der_len(&self) -> Result<usize> {
-        match self {
-            Length::Indefinite => Ok(1),
-            Length::Definite(l) => match l {
-                0..=0x7f => Ok(1),
-                0x80..=0xff => Ok(2),
-                0x100..=0xffff => Ok(3),
-                0x1_0000..=0xffff_ffff => Ok(4),
-                _ => Err(Error::InvalidLength),
-            },
-        }
+        Ok(self.encoded_len())
     }
 
     fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        match *self {
+            Length::Indefinite => Err(SerializeError::UnsupportedIndefiniteInDer),
+            Length::Definite(_) => self.write_ber(writer),
+        }
+    }
+
+    fn write_der_content(&self, _writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        Ok(0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Length {
+    /// Write this length using BER (the indefinite form is allowed)
+    pub(crate) fn write_ber(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
         match *self {
             Length::Indefinite => {
                 let sz = writer.write(&[0b1000_0000])?;
@@ -119,12 +170,8 @@ impl ToDer for Length {
                 } else {
                     // Long form
                     let b = l.to_be_bytes();
-                    // skip leading zeroes
-                    // we do not have to test for length, l cannot be 0
-                    let mut idx = 0;
-                    while b[idx] == 0 {
-                        idx += 1;
-                    }
+                    // skip leading zeroes (l cannot be 0, so at least one byte remains)
+                    let idx = b.iter().take_while(|&&x| x == 0).count();
                     let b = &b[idx..];
                     // first byte: 0x80 + length of length
                     let b0 = 0x80 | (b.len() as u8);
@@ -135,10 +182,6 @@ impl ToDer for Length {
             }
         }
     }
-
-    fn write_der_content(&self, _writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
-        Ok(0)
-    }
 }
 
 #[cfg(test)]
@@ -177,4 +220,54 @@ mod tests {
         l += 2;
         assert_eq!(l, Length::Indefinite);
     }
+
+    #[test]
+    fn to_der_len_long_form() {
+        assert_eq!(Length::Definite(0x7f).to_der_len(), Ok(1));
+        assert_eq!(Length::Definite(0x80).to_der_len(), Ok(2));
+        assert_eq!(Length::Definite(0xffff).to_der_len(), Ok(3));
+        assert_eq!(Length::Definite(0x1_0000).to_der_len(), Ok(4));
+        assert_eq!(Length::Definite(0x100_0000).to_der_len(), Ok(5));
+        for l in [0x80, 0xffff, 0x1_0000, 0x100_0000] {
+            let v = Length::Definite(l)
+                .to_der_vec()
+                .expect("serialization failed");
+            assert_eq!(Length::Definite(l).to_der_len(), Ok(v.len()));
+        }
+    }
+
+    #[test]
+    fn length_encoded_len() {
+        assert_eq!(Length::Indefinite.encoded_len(), 1);
+        assert_eq!(Length::Definite(0).encoded_len(), 1);
+        assert_eq!(Length::Definite(0x7f).encoded_len(), 1);
+        assert_eq!(Length::Definite(0x80).encoded_len(), 2);
+        assert_eq!(Length::Definite(0xff).encoded_len(), 2);
+        assert_eq!(Length::Definite(0x100).encoded_len(), 3);
+        assert_eq!(Length::Definite(0xffff).encoded_len(), 3);
+        assert_eq!(Length::Definite(0x1_0000).encoded_len(), 4);
+    }
+
+    /// Lengths larger than 4 GiB are supported on 64-bit targets
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn length_64bit() {
+        let l = Length::Definite(0x1_4000_0000);
+        assert_eq!(l.to_der_len(), Ok(6));
+        let v = l.to_der_vec().expect("serialization failed");
+        assert_eq!(&v, &[0x85, 0x01, 0x40, 0x00, 0x00, 0x00]);
+        let input = &[0x04, 0x85, 0x01, 0x40, 0x00, 0x00, 0x00];
+        let (rem, header) = Header::from_ber(input).expect("parsing failed");
+        assert!(rem.is_empty());
+        assert_eq!(header.length(), l);
+    }
+
+    /// Lengths larger than 4 GiB cannot be represented on 32-bit targets
+    #[cfg(target_pointer_width = "32")]
+    #[test]
+    fn length_64bit() {
+        let input = &[0x04, 0x85, 0x01, 0x40, 0x00, 0x00, 0x00];
+        let res = Header::from_ber(input);
+        assert_eq!(res, Err(Err::Error(Error::LengthTooLarge)));
+    }
 }
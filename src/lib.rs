@@ -42,10 +42,14 @@
 //! combination to objects and methods from this crate. Reading the nom documentation may
 //! help understanding how to write and combine parsers and use the output.
 //!
+//! The [`prelude`] module can be glob-imported to get the main traits, types and macros.
+//!
 //! **Minimum Supported Rust Version**: 1.53.0
 //!
 //! Note: if the `bits` feature is enabled, MSRV is 1.56.0 (due to `bitvec` 1.0)
 //!
+//! Note: if the `der` feature is enabled, MSRV is 1.65.0 (due to `der` 0.7)
+//!
 //! # Recipes
 //!
 //! See [doc::recipes] and [doc::derive] for more examples and recipes.
@@ -88,7 +92,7 @@
 //! ```
 //!
 //! If the parsing succeeds, but the integer cannot fit into the expected type, the method will return
-//! an `IntegerTooLarge` error.
+//! an `IntegerOutOfRange` error.
 //!
 //! # BER/DER encoders
 //!
@@ -135,7 +139,7 @@
 //! ```
 //!
 //! If the parsing succeeds, but the integer cannot fit into the expected type, the method will return
-//! an `IntegerTooLarge` error.
+//! an `IntegerOutOfRange` error.
 //!
 //! ## Changes
 //!
@@ -159,7 +163,9 @@
     unused_qualifications,
     // unreachable_pub
 )]
-#![forbid(unsafe_code)]
+// unchecked accesses are only allowed in the `fast` module, with the `unsafe-fast` feature
+#![cfg_attr(not(feature = "unsafe-fast"), forbid(unsafe_code))]
+#![cfg_attr(feature = "unsafe-fast", deny(unsafe_code))]
 #![warn(
 /* missing_docs,
 rust_2018_idioms,*/
@@ -180,26 +186,108 @@ extern crate core;
 // #[cfg(feature = "alloc")]
 extern crate alloc;
 
+#[macro_use]
+mod trace;
+
+#[cfg(feature = "allocator-copy")]
+#[cfg_attr(docsrs, doc(cfg(feature = "allocator-copy")))]
+pub mod allocator;
+#[cfg(feature = "bumpalo")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bumpalo")))]
+mod arena;
 mod asn1_types;
+#[cfg(feature = "bench")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bench")))]
+pub mod bench;
 mod ber;
 mod class;
 mod datetime;
+#[cfg(feature = "defmt")]
+#[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
+mod defmt_format;
+#[cfg(feature = "der")]
+#[cfg_attr(docsrs, doc(cfg(feature = "der")))]
+mod der_interop;
 mod derive;
+mod diagnostics;
+mod encoding;
 mod error;
+mod fast;
+mod fragments;
 mod header;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+mod hex;
+#[cfg(feature = "intern")]
+#[cfg_attr(docsrs, doc(cfg(feature = "intern")))]
+mod intern;
 mod length;
+mod limit;
+mod located;
+#[cfg(feature = "mmap")]
+#[cfg_attr(docsrs, doc(cfg(feature = "mmap")))]
+mod mmap;
+#[cfg(feature = "rayon")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+mod parallel;
+pub mod path;
+pub mod prelude;
+mod profile;
+mod recovery;
+mod registry;
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+mod serde_format;
+#[cfg(feature = "stats")]
+#[cfg_attr(docsrs, doc(cfg(feature = "stats")))]
+mod stats;
 mod tag;
+#[cfg(feature = "test-vectors")]
+#[cfg_attr(docsrs, doc(cfg(feature = "test-vectors")))]
+pub mod test_vectors;
+mod tokenizer;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod tools;
 mod traits;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+mod writer;
 
+#[cfg(feature = "allocator-copy")]
+pub use allocator::{AllocCow, AllocVec};
+#[cfg(feature = "bumpalo")]
+pub use arena::*;
 pub use asn1_types::*;
 pub use class::*;
 pub use datetime::*;
 pub use derive::*;
+pub use diagnostics::*;
+pub use encoding::EncodingRules;
 pub use error::*;
+pub use fragments::*;
 pub use header::*;
+#[cfg(feature = "std")]
+pub use hex::*;
+#[cfg(feature = "intern")]
+pub use intern::*;
 pub use length::*;
+pub use limit::*;
+pub use located::*;
+#[cfg(feature = "mmap")]
+pub use mmap::*;
+#[cfg(feature = "rayon")]
+pub use parallel::*;
+pub use profile::*;
+pub use recovery::*;
+pub use registry::*;
+#[cfg(feature = "stats")]
+pub use stats::*;
 pub use tag::*;
+pub use tokenizer::*;
 pub use traits::*;
+#[cfg(feature = "std")]
+pub use writer::*;
 
 pub use nom;
 pub use nom::{Err, IResult, Needed};
@@ -0,0 +1,106 @@
+//! Parser adapters limiting the length of parsed objects
+
+use crate::ber::{GetObjectContent, MAX_RECURSION};
+use crate::{BerParser, Error, FromBer, Header, Length, ParseResult};
+
+/// Wrap `parser`, rejecting objects whose content is longer than `max` bytes
+///
+/// The header of the next object is read first, and if the declared length exceeds `max`,
+/// [`Error::LengthLimit`] is returned without calling `parser`. This avoids allocating or
+/// recursing on large (possibly malicious) objects, and can be used per field to set limits
+/// smaller than the input size.
+///
+/// Objects using the indefinite length form have no declared length: their content is located
+/// (without allocating), and its length is checked before calling `parser`. At most `max` bytes
+/// of content (plus the end-of-contents marker) are scanned: if the content is longer,
+/// [`Error::LengthLimit`] is returned with `len` set to `max + 1` (the real length is unknown).
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{with_length_limit, Err, Error, FromDer, OctetString};
+///
+/// let mut parser = with_length_limit(4, OctetString::from_der);
+///
+/// let (_, s) = parser(&[0x04, 0x02, 0x01, 0x02]).expect("parsing failed");
+/// assert_eq!(s.as_cow().as_ref(), &[0x01, 0x02]);
+///
+/// let res = parser(&[0x04, 0x05, 0x01, 0x02, 0x03, 0x04, 0x05]);
+/// assert_eq!(res, Err(Err::Error(Error::LengthLimit { len: 5, max: 4 })));
+/// ```
+pub fn with_length_limit<'a, T, E, F>(
+    max: usize,
+    mut parser: F,
+) -> impl FnMut(&'a [u8]) -> ParseResult<'a, T, E>
+where
+    F: FnMut(&'a [u8]) -> ParseResult<'a, T, E>,
+    E: From<Error>,
+{
+    move |input| {
+        let (i, header) = Header::from_ber(input).map_err(nom::Err::convert)?;
+        let len = match header.length() {
+            Length::Definite(len) => len,
+            Length::Indefinite => {
+                // the content and the end-of-contents marker must fit in the first max + 2 bytes
+                let window = max.saturating_add(2);
+                let truncated = i.len() > window;
+                let i = if truncated { &i[..window] } else { i };
+                match BerParser::get_object_content(i, &header, MAX_RECURSION) {
+                    Ok((_, content)) => content.len(),
+                    Err(nom::Err::Incomplete(_)) if truncated => max + 1,
+                    Err(e) => return Err(nom::Err::convert(e)),
+                }
+            }
+        };
+        if len > max {
+            return Err(nom::Err::Error(Error::LengthLimit { len, max }.into()));
+        }
+        parser(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn length_limit() {
+        let mut parser = with_length_limit(3, u32::from_ber);
+        assert_eq!(parser(&hex!("02 01 05")), Ok((&[][..], 5)));
+        assert_eq!(parser(&hex!("02 03 01 00 00")), Ok((&[][..], 0x10000)));
+        // the declared length is checked, even if the content is missing
+        assert_eq!(
+            parser(&hex!("02 84 10 00 00 00")),
+            Err(Err::Error(Error::LengthLimit {
+                len: 0x1000_0000,
+                max: 3
+            }))
+        );
+        // indefinite length
+        let mut large = hex!("30 80 04 82 ff ff").to_vec();
+        large.resize(0x1_0006, 0);
+        let mut parser = with_length_limit(4, Any::from_ber);
+        assert!(parser(&hex!("30 80 05 00 00 00")).is_ok());
+        assert_eq!(
+            parser(&hex!("30 80 04 03 01 02 03 00 00")).map(|(_, any)| any.data.len()),
+            Err(Err::Error(Error::LengthLimit { len: 5, max: 4 }))
+        );
+        // the scan stops after max bytes of content
+        assert_eq!(
+            parser(&hex!("30 80 04 03 01 02 03 05 00 00 00")).map(|(_, any)| any.data.len()),
+            Err(Err::Error(Error::LengthLimit { len: 5, max: 4 }))
+        );
+        assert_eq!(
+            parser(&large).map(|(_, any)| any.data.len()),
+            Err(Err::Error(Error::LengthLimit { len: 5, max: 4 }))
+        );
+        // incomplete content within the limit
+        assert!(matches!(
+            parser(&hex!("30 80 05 00")),
+            Err(Err::Incomplete(_))
+        ));
+        // incomplete header
+        assert!(parser(&hex!("30")).is_err());
+    }
+}
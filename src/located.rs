@@ -0,0 +1,172 @@
+//! Parsing functions returning the location of errors
+//!
+//! Errors returned by parsers do not contain the position of the invalid object in the input.
+//! [`parse_ber_any`] and [`parse_der_any`] parse one object, and on error return a
+//! [`LocatedError`] with the absolute offset of the object in error, and the bytes surrounding
+//! it, so that error reports (for ex. from logs) can be analyzed without the original input.
+
+use crate::{Any, Error, FromBer, FromDer, TlvTokenizer};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Maximum number of bytes before the error offset, included in [`LocatedError::snippet`]
+pub const SNIPPET_BEFORE: usize = 8;
+
+/// Maximum number of bytes starting at the error offset, included in
+/// [`LocatedError::snippet`]
+pub const SNIPPET_AFTER: usize = 8;
+
+/// An error, with its location in the input
+///
+/// The `Display` implementation shows the error, the offset and the snippet in hexadecimal, with
+/// the byte at the error offset between brackets:
+///
+/// ```text
+/// incomplete data, missing: Size(3) at offset 2: 30 80 [02] 05 01 02
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct LocatedError {
+    /// The error
+    pub error: Error,
+    /// Offset of the object in error, relative to the start of the input
+    ///
+    /// If the object in error cannot be determined more precisely, this is the offset of the
+    /// parsed object (0).
+    pub offset: usize,
+    /// Offset of the first byte of `snippet`, relative to the start of the input
+    pub snippet_offset: usize,
+    /// Bytes surrounding the error offset (at most [`SNIPPET_BEFORE`] bytes before, and
+    /// [`SNIPPET_AFTER`] bytes starting at the offset)
+    pub snippet: Vec<u8>,
+}
+
+impl LocatedError {
+    /// Build an error located at `offset` in `input`, copying the surrounding bytes
+    pub fn new(input: &[u8], offset: usize, error: Error) -> Self {
+        let offset = offset.min(input.len());
+        let snippet_offset = offset.saturating_sub(SNIPPET_BEFORE);
+        let end = offset.saturating_add(SNIPPET_AFTER).min(input.len());
+        LocatedError {
+            error,
+            offset,
+            snippet_offset,
+            snippet: input[snippet_offset..end].to_vec(),
+        }
+    }
+}
+
+impl fmt::Display for LocatedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at offset {}:", self.error, self.offset)?;
+        for (i, b) in self.snippet.iter().enumerate() {
+            if self.snippet_offset + i == self.offset {
+                write!(f, " [{:02x}]", b)?;
+            } else {
+                write!(f, " {:02x}", b)?;
+            }
+        }
+        if self.snippet_offset + self.snippet.len() == self.offset {
+            // error at end of input
+            f.write_str(" []")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LocatedError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl From<LocatedError> for Error {
+    fn from(e: LocatedError) -> Self {
+        e.error
+    }
+}
+
+/// Return the offset of the first structural error in the first object of `input`
+///
+/// Returns 0 (the offset of the object) if the error cannot be located more precisely.
+fn locate_error(input: &[u8]) -> usize {
+    let mut tokenizer = TlvTokenizer::new(input);
+    loop {
+        match tokenizer.next() {
+            Some(Ok(_)) if tokenizer.depth() > 0 => (),
+            Some(Err(_)) => return tokenizer.offset(),
+            _ => return 0,
+        }
+    }
+}
+
+/// Parse one BER object, returning the location of the error on failure
+///
+/// This is the same as [`Any::from_ber`], except for the error type. Incomplete input is
+/// reported as [`Error::Incomplete`].
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::parse_ber_any;
+///
+/// // SEQUENCE (indefinite length) { INTEGER (truncated) }
+/// let bytes = &[0x30, 0x80, 0x02, 0x05, 0x01, 0x02];
+/// let err = parse_ber_any(bytes).unwrap_err();
+/// assert_eq!(err.offset, 2);
+/// assert_eq!(err.snippet, bytes);
+/// ```
+pub fn parse_ber_any(input: &[u8]) -> Result<(&[u8], Any<'_>), LocatedError> {
+    Any::from_ber(input).map_err(|e| LocatedError::new(input, locate_error(input), e.into()))
+}
+
+/// Parse one DER object, returning the location of the error on failure
+///
+/// This is the same as [`Any::from_der`], except for the error type. Incomplete input is
+/// reported as [`Error::Incomplete`].
+pub fn parse_der_any(input: &[u8]) -> Result<(&[u8], Any<'_>), LocatedError> {
+    Any::from_der(input).map_err(|e| LocatedError::new(input, locate_error(input), e.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn located_errors() {
+        // SEQUENCE { OCTET STRING, SEQUENCE { NULL (invalid length form) } }
+        let mut input = hex!("30 80 04 10").to_vec();
+        input.extend_from_slice(&[0xaa; 16]);
+        input.extend_from_slice(&hex!("30 80 05 80 00 00 00 00"));
+        let err = parse_ber_any(&input).unwrap_err();
+        assert_eq!(err.error, Error::ConstructExpected);
+        assert_eq!(err.offset, 22);
+        assert_eq!(err.snippet_offset, 14);
+        assert_eq!(err.snippet, &input[14..]);
+        assert_eq!(
+            format!("{}", err),
+            format!(
+                "{} at offset 22: aa aa aa aa aa aa 30 80 [05] 80 00 00 00 00",
+                Error::ConstructExpected
+            )
+        );
+        // errors at the end of input
+        let err = parse_ber_any(&hex!("30 80 05 00")).unwrap_err();
+        assert_eq!(err.offset, 4);
+        assert!(format!("{}", err).ends_with("05 00 []"));
+        // indefinite length child overflowing its parent
+        assert_eq!(super::locate_error(&hex!("30 02 30 80 05 00 00 00")), 4);
+        // DER constraints of the parsed object
+        let err = parse_der_any(&hex!("30 80 05 00 00 00")).unwrap_err();
+        assert_eq!(
+            err.error,
+            Error::DerConstraintFailed(DerConstraint::IndefiniteLength)
+        );
+        assert_eq!(err.offset, 0);
+        // success
+        let (rem, any) = parse_der_any(&hex!("05 00 ff")).expect("parsing failed");
+        assert_eq!(rem, &[0xff]);
+        assert_eq!(any.tag(), Tag::Null);
+    }
+}
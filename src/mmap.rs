@@ -0,0 +1,69 @@
+//! Helpers to parse memory-mapped files
+//!
+//! Memory-mapping a file avoids reading its whole content before parsing, which is useful for
+//! very large objects (for ex. CMS signatures of disk images).
+//!
+//! Creating a mapping is an `unsafe` operation (the underlying file could be modified while
+//! mapped), and this crate forbids unsafe code: the caller is responsible for creating the
+//! [`Mmap`] object, and these functions only provide parsing helpers on top of it.
+
+use crate::{Error, FromBer, FromDer, Header, ParseResult};
+pub use memmap2::Mmap;
+
+/// Parse a BER object from the content of a memory-mapped file
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use asn1_rs::{from_ber_mmap, Any, Mmap};
+/// use std::fs::File;
+///
+/// let file = File::open("large.ber").expect("could not open file");
+/// // SAFETY: the file must not be modified while mapped
+/// let map = unsafe { Mmap::map(&file) }.expect("could not map file");
+/// let (rem, any) = from_ber_mmap::<Any>(&map).expect("parsing failed");
+/// ```
+pub fn from_ber_mmap<'a, T>(map: &'a Mmap) -> ParseResult<'a, T>
+where
+    T: FromBer<'a>,
+{
+    T::from_ber(map.as_ref())
+}
+
+/// Parse a DER object from the content of a memory-mapped file
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use asn1_rs::{from_der_mmap, Any, Mmap};
+/// use std::fs::File;
+///
+/// let file = File::open("large.der").expect("could not open file");
+/// // SAFETY: the file must not be modified while mapped
+/// let map = unsafe { Mmap::map(&file) }.expect("could not map file");
+/// let (rem, any) = from_der_mmap::<Any>(&map).expect("parsing failed");
+/// ```
+pub fn from_der_mmap<'a, T>(map: &'a Mmap) -> ParseResult<'a, T>
+where
+    T: FromDer<'a>,
+{
+    T::from_der(map.as_ref())
+}
+
+/// Parse a DER object from the content of a memory-mapped file, and check that the
+/// file does not contain trailing data (`Error::TrailingContent`)
+pub fn from_der_mmap_complete<'a, T>(map: &'a Mmap) -> Result<T, Error>
+where
+    T: FromDer<'a>,
+{
+    let (rem, obj) = T::from_der(map.as_ref())?;
+    if !rem.is_empty() {
+        // `T` could be parsed, so the header is valid
+        let (_, header) = Header::from_der(map.as_ref())?;
+        return Err(Error::TrailingContent {
+            tag: header.tag(),
+            remaining: rem.len(),
+        });
+    }
+    Ok(obj)
+}
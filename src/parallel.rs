@@ -0,0 +1,118 @@
+//! Parallel parsing of concatenated records
+//!
+//! Bulk inputs (for ex. certificates extracted from Certificate Transparency logs) are often
+//! stored as a buffer of concatenated DER objects. Records are independent, so they can be
+//! parsed in parallel once their boundaries are known: only the top-level headers are read
+//! sequentially, and the contents are then parsed using `rayon`.
+
+use crate::{Any, Error, FromDer, Header, Result};
+use alloc::vec::Vec;
+use nom::Needed;
+use rayon::prelude::*;
+
+/// Split `input` into the raw encodings of its top-level DER objects
+///
+/// Only the headers are parsed: contents are skipped using their length. Returns an error if
+/// a header is invalid, if a length is indefinite, or if the last object is incomplete.
+///
+/// ```rust
+/// use asn1_rs::split_der_records;
+///
+/// let input = &[0x02, 0x01, 0x01, 0x30, 0x03, 0x02, 0x01, 0x02];
+/// let records = split_der_records(input).expect("invalid input");
+/// assert_eq!(records, vec![&input[..3], &input[3..]]);
+/// ```
+pub fn split_der_records(input: &[u8]) -> Result<Vec<&[u8]>> {
+    let mut records = Vec::new();
+    let mut i = input;
+    while !i.is_empty() {
+        let (rem, header) = Header::from_der(i)?;
+        let len = header.length().definite()?;
+        if rem.len() < len {
+            return Err(Error::Incomplete(Needed::new(len - rem.len())));
+        }
+        let record_len = i.len() - rem.len() + len;
+        records.push(&i[..record_len]);
+        i = &i[record_len..];
+    }
+    Ok(records)
+}
+
+/// Parse a buffer of concatenated DER records in parallel
+///
+/// `input` is split at top-level boundaries (see [`split_der_records`]), and each record is
+/// parsed as a `T` by the `rayon` thread pool. The returned iterator is indexed: records keep
+/// their order when collected, and `enumerate` gives the index of a failing record. Each record
+/// must be fully consumed by the parser.
+///
+/// Returns an error if the input cannot be split; errors in a record only affect this record.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::par_parse_iter;
+/// use rayon::prelude::*;
+///
+/// let input = &[0x02, 0x01, 0x01, 0x02, 0x01, 0x02, 0x02, 0x01, 0x03];
+/// let values = par_parse_iter::<u32>(input)
+///     .expect("invalid input")
+///     .collect::<Result<Vec<_>, _>>()
+///     .expect("invalid record");
+/// assert_eq!(values, vec![1, 2, 3]);
+/// ```
+pub fn par_parse_iter<'a, T>(
+    input: &'a [u8],
+) -> Result<impl IndexedParallelIterator<Item = Result<T>> + 'a>
+where
+    T: FromDer<'a> + Send + 'a,
+{
+    let records = split_der_records(input)?;
+    Ok(records.into_par_iter().map(parse_record))
+}
+
+/// Parse one record, which must be fully consumed
+fn parse_record<'a, T>(record: &'a [u8]) -> Result<T>
+where
+    T: FromDer<'a>,
+{
+    let (rem, value) = T::from_der(record)?;
+    if !rem.is_empty() {
+        let (_, any) = Any::from_der(record)?;
+        return Err(Error::TrailingContent {
+            tag: any.tag(),
+            remaining: rem.len(),
+        });
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use hex_literal::hex;
+    use rayon::prelude::*;
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn par_parse_records() {
+        let mut input = Vec::new();
+        for n in 0..1000u32 {
+            let _ = n.write_der(&mut input).expect("serialization failed");
+        }
+        let values: Vec<u32> = par_parse_iter(&input)
+            .expect("invalid input")
+            .collect::<Result<_>>()
+            .expect("invalid record");
+        assert_eq!(values, (0..1000).collect::<Vec<_>>());
+        // errors are reported per record
+        let input = &hex!("02 01 01 01 01 ff 02 01 03");
+        let res: Vec<_> = par_parse_iter::<u32>(input).unwrap().enumerate().collect();
+        assert_eq!(res[0], (0, Ok(1)));
+        assert!(matches!(res[1], (1, Err(Error::UnexpectedTag { .. }))));
+        assert_eq!(res[2], (2, Ok(3)));
+        // invalid split
+        assert!(split_der_records(&hex!("02 01 01 30 80 00 00")).is_err());
+        assert!(split_der_records(&hex!("02 01 01 02 02 01")).is_err());
+        assert_eq!(split_der_records(&[]), Ok(vec![]));
+    }
+}
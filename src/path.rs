@@ -0,0 +1,330 @@
+//! Selective extraction of objects by path
+//!
+//! A path selects a single object inside a BER/DER tree, without decoding the other objects:
+//! siblings are skipped using their length, so extracting a field is much faster than parsing
+//! the full structure.
+//!
+//! A path is a list of segments separated by `/`. The first object of the input is the root, and
+//! each segment selects one child of the current (constructed) object:
+//! - `N` (a decimal number) selects the child at index `N` (starting at 0)
+//! - `[N]` selects the first child with context-specific tag `N`
+//! - `[APPLICATION N]`, `[PRIVATE N]` or `[UNIVERSAL N]` selects the first child with this class
+//!   and tag
+//! - a type name (for ex. `oid`, `int`, `seq`, see [`Segment`]) selects the first child of this
+//!   universal type
+//!
+//! The empty path selects the root object.
+//!
+//! # Examples
+//!
+//! Extract the serial number of a X.509 certificate (field 1 of `tbsCertificate`, itself
+//! field 0 of `Certificate`):
+//!
+//! ```rust
+//! use asn1_rs::path::Path;
+//! use asn1_rs::Integer;
+//! use hex_literal::hex;
+//! use std::convert::TryFrom;
+//!
+//! // Certificate { tbsCertificate { [0] { 2 }, serial 0x1234, ... }, ... }
+//! let cert = &hex!("30 0d 30 09 a0 03 02 01 02 02 02 12 34 05 00");
+//! let path: Path = "0/1".parse().expect("invalid path");
+//! // the path can be reused for many inputs
+//! let any = path.extract(cert).expect("extraction failed");
+//! assert_eq!(Integer::try_from(any).unwrap().as_u32(), Ok(0x1234));
+//! // get the raw encoding instead
+//! let raw = asn1_rs::path::extract_raw(cert, "0/[0]/int").expect("extraction failed");
+//! assert_eq!(raw, &hex!("02 01 02"));
+//! ```
+
+use crate::{Any, Class, Error, FromBer, Header, Result, Tag};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+/// A segment of a [`Path`]
+///
+/// Type names accepted when parsing segments are (case-insensitive): `bool`, `int`,
+/// `bitstring`, `octetstring`, `null`, `oid`, `enum`, `utf8`, `seq`, `set`, `printable`,
+/// `ia5`, `utctime`, `gentime`, `bmp`, as well as the full ASN.1 names without spaces (for ex.
+/// `boolean`, `integer`, `sequence`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Segment {
+    /// Select the child at this index
+    Index(usize),
+    /// Select the first child with this class and tag
+    Tagged(Class, Tag),
+}
+
+impl Segment {
+    fn matches(&self, index: usize, header: &Header) -> bool {
+        match *self {
+            Segment::Index(n) => n == index,
+            Segment::Tagged(class, tag) => header.class() == class && header.tag() == tag,
+        }
+    }
+}
+
+impl FromStr for Segment {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || Error::InvalidPath(s.to_string());
+        if let Ok(n) = s.parse::<usize>() {
+            return Ok(Segment::Index(n));
+        }
+        if let Some(inner) = s.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+            let mut it = inner.split_whitespace();
+            let (class, n) = match (it.next(), it.next(), it.next()) {
+                (Some(n), None, None) => (Class::ContextSpecific, n),
+                (Some(c), Some(n), None) if c.eq_ignore_ascii_case("APPLICATION") => {
+                    (Class::Application, n)
+                }
+                (Some(c), Some(n), None) if c.eq_ignore_ascii_case("PRIVATE") => {
+                    (Class::Private, n)
+                }
+                (Some(c), Some(n), None) if c.eq_ignore_ascii_case("UNIVERSAL") => {
+                    (Class::Universal, n)
+                }
+                _ => return Err(invalid()),
+            };
+            let n = n.parse::<u32>().map_err(|_| invalid())?;
+            return Ok(Segment::Tagged(class, Tag(n)));
+        }
+        let tag = match s.to_ascii_lowercase().as_str() {
+            "bool" | "boolean" => Tag::Boolean,
+            "int" | "integer" => Tag::Integer,
+            "bitstring" => Tag::BitString,
+            "octetstring" => Tag::OctetString,
+            "null" => Tag::Null,
+            "oid" | "objectidentifier" => Tag::Oid,
+            "enum" | "enumerated" => Tag::Enumerated,
+            "utf8" | "utf8string" => Tag::Utf8String,
+            "seq" | "sequence" => Tag::Sequence,
+            "set" => Tag::Set,
+            "printable" | "printablestring" => Tag::PrintableString,
+            "ia5" | "ia5string" => Tag::Ia5String,
+            "utctime" => Tag::UtcTime,
+            "gentime" | "generalizedtime" => Tag::GeneralizedTime,
+            "bmp" | "bmpstring" => Tag::BmpString,
+            _ => return Err(invalid()),
+        };
+        Ok(Segment::Tagged(Class::Universal, tag))
+    }
+}
+
+impl fmt::Display for Segment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Segment::Index(n) => write!(f, "{}", n),
+            Segment::Tagged(Class::ContextSpecific, tag) => write!(f, "[{}]", tag.0),
+            Segment::Tagged(Class::Application, tag) => write!(f, "[APPLICATION {}]", tag.0),
+            Segment::Tagged(Class::Private, tag) => write!(f, "[PRIVATE {}]", tag.0),
+            Segment::Tagged(Class::Universal, tag) => match tag {
+                Tag::Boolean => f.write_str("bool"),
+                Tag::Integer => f.write_str("int"),
+                Tag::BitString => f.write_str("bitstring"),
+                Tag::OctetString => f.write_str("octetstring"),
+                Tag::Null => f.write_str("null"),
+                Tag::Oid => f.write_str("oid"),
+                Tag::Enumerated => f.write_str("enum"),
+                Tag::Utf8String => f.write_str("utf8"),
+                Tag::Sequence => f.write_str("seq"),
+                Tag::Set => f.write_str("set"),
+                Tag::PrintableString => f.write_str("printable"),
+                Tag::Ia5String => f.write_str("ia5"),
+                Tag::UtcTime => f.write_str("utctime"),
+                Tag::GeneralizedTime => f.write_str("gentime"),
+                Tag::BmpString => f.write_str("bmp"),
+                _ => write!(f, "[UNIVERSAL {}]", tag.0),
+            },
+        }
+    }
+}
+
+/// A path to an object inside a BER/DER tree
+///
+/// See the [module documentation](crate::path) for the syntax.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Path {
+    segments: Vec<Segment>,
+}
+
+impl Path {
+    /// Build a path from a list of segments
+    pub fn new(segments: Vec<Segment>) -> Self {
+        Path { segments }
+    }
+
+    /// Return the segments of this path
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// Return the object at this path in `input`
+    ///
+    /// Returns [`Error::PathNotFound`] with the index of the segment if there is no matching
+    /// object, and [`Error::ConstructExpected`] if a segment applies to a primitive object.
+    pub fn extract<'a>(&self, input: &'a [u8]) -> Result<Any<'a>> {
+        self.walk(input).map(|(_, any)| any)
+    }
+
+    /// Return the raw encoding (header and content) of the object at this path in `input`
+    ///
+    /// See [`Path::extract`] for errors.
+    pub fn extract_raw<'a>(&self, input: &'a [u8]) -> Result<&'a [u8]> {
+        self.walk(input).map(|(raw, _)| raw)
+    }
+
+    fn walk<'a>(&self, input: &'a [u8]) -> Result<(&'a [u8], Any<'a>)> {
+        let (mut raw, mut any) = next_object(input)?;
+        for (depth, segment) in self.segments.iter().enumerate() {
+            if !any.header.is_constructed() {
+                return Err(Error::ConstructExpected);
+            }
+            let mut i = any.data;
+            let mut index = 0;
+            loop {
+                if i.is_empty() {
+                    return Err(Error::PathNotFound(depth));
+                }
+                let (obj_raw, obj) = next_object(i)?;
+                i = &i[obj_raw.len()..];
+                if segment.matches(index, &obj.header) {
+                    raw = obj_raw;
+                    any = obj;
+                    break;
+                }
+                index += 1;
+            }
+        }
+        Ok((raw, any))
+    }
+}
+
+/// Parse the next object, and return its raw encoding and the object
+fn next_object<'a>(i: &'a [u8]) -> Result<(&'a [u8], Any<'a>)> {
+    let (rem, any) = Any::from_ber(i)?;
+    let raw = &i[..i.len() - rem.len()];
+    Ok((raw, any))
+}
+
+impl FromStr for Path {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let segments = s
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .map(|s| s.trim().parse())
+            .collect::<Result<_>>()?;
+        Ok(Path { segments })
+    }
+}
+
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = self
+            .segments
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<String>>()
+            .join("/");
+        f.write_str(&s)
+    }
+}
+
+/// Return the object at `path` in `input`
+///
+/// To extract the same path from many inputs, parse the path once using [`Path::from_str`] and
+/// use [`Path::extract`].
+pub fn extract<'a>(input: &'a [u8], path: &str) -> Result<Any<'a>> {
+    path.parse::<Path>()?.extract(input)
+}
+
+/// Return the raw encoding (header and content) of the object at `path` in `input`
+pub fn extract_raw<'a>(input: &'a [u8], path: &str) -> Result<&'a [u8]> {
+    path.parse::<Path>()?.extract_raw(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+
+    // SEQUENCE { [0] { INTEGER 2 }, INTEGER 0x1234, SEQUENCE { OID 1.2.3, NULL } }
+    const DATA: &[u8] = &hex!("30 11 a0 03 02 01 02 02 02 12 34 30 06 06 02 2a 03 05 00");
+
+    #[test]
+    fn path_parse_display() {
+        let path: Path = "[0]/1/oid/[APPLICATION 3]".parse().expect("invalid path");
+        assert_eq!(
+            path.segments(),
+            &[
+                Segment::Tagged(Class::ContextSpecific, Tag(0)),
+                Segment::Index(1),
+                Segment::Tagged(Class::Universal, Tag::Oid),
+                Segment::Tagged(Class::Application, Tag(3)),
+            ]
+        );
+        assert_eq!(path.to_string(), "[0]/1/oid/[APPLICATION 3]");
+        assert!(""
+            .parse::<Path>()
+            .expect("invalid path")
+            .segments()
+            .is_empty());
+        assert_eq!(
+            "0/foo".parse::<Path>(),
+            Err(Error::InvalidPath("foo".to_string()))
+        );
+        assert!("[x]".parse::<Path>().is_err());
+        assert_eq!(
+            "[UNIVERSAL 29]".parse::<Segment>(),
+            Ok(Segment::Tagged(Class::Universal, Tag(29)))
+        );
+    }
+
+    #[test]
+    fn path_display_roundtrip() {
+        let segments = [
+            Segment::Index(3),
+            Segment::Tagged(Class::ContextSpecific, Tag(0)),
+            Segment::Tagged(Class::Application, Tag(3)),
+            Segment::Tagged(Class::Private, Tag(128)),
+            Segment::Tagged(Class::Universal, Tag::Sequence),
+            Segment::Tagged(Class::Universal, Tag::BmpString),
+            Segment::Tagged(Class::Universal, Tag(29)),
+            Segment::Tagged(Class::Universal, Tag::RelativeOid),
+        ];
+        for segment in &segments {
+            let s = segment.to_string();
+            assert_eq!(s.parse::<Segment>().as_ref(), Ok(segment), "segment {}", s);
+        }
+        let path = Path::new(segments.to_vec());
+        assert_eq!(path.to_string().parse::<Path>(), Ok(path));
+    }
+
+    #[test]
+    fn path_extract() {
+        assert_eq!(extract_raw(DATA, ""), Ok(DATA));
+        assert_eq!(extract_raw(DATA, "1"), Ok(&hex!("02 02 12 34")[..]));
+        assert_eq!(extract_raw(DATA, "[0]/int"), Ok(&hex!("02 01 02")[..]));
+        assert_eq!(extract_raw(DATA, "seq/oid"), Ok(&hex!("06 02 2a 03")[..]));
+        let any = extract(DATA, "2/1").expect("extraction failed");
+        assert_eq!(any.tag(), Tag::Null);
+        assert_eq!(extract(DATA, "3"), Err(Error::PathNotFound(0)));
+        assert_eq!(extract(DATA, "seq/bool"), Err(Error::PathNotFound(1)));
+        assert_eq!(extract(DATA, "1/0"), Err(Error::ConstructExpected));
+    }
+
+    #[test]
+    fn path_extract_indefinite() {
+        // SEQUENCE (indefinite) { INTEGER 1, OCTET STRING (constructed, indefinite) { "ab" } }
+        let data = &hex!("30 80 02 01 01 24 80 04 02 61 62 00 00 00 00");
+        let raw = extract_raw(data, "1/0").expect("extraction failed");
+        assert_eq!(raw, &hex!("04 02 61 62"));
+        let raw = extract_raw(data, "octetstring").expect("extraction failed");
+        assert_eq!(raw, &hex!("24 80 04 02 61 62 00 00"));
+    }
+}
@@ -0,0 +1,31 @@
+//! The `asn1_rs` prelude
+//!
+//! This module provides a minimal set of imports for common use of this crate: the
+//! parsing and serialization traits, the most common ASN.1 types, and the macros.
+//!
+//! Contrary to `use asn1_rs::*`, it does not shadow the `Result` type from the standard library.
+//! Implementation details (for ex. `DerAutoDerive`, used by the derive macros, or `DynTagged`)
+//! and low-level helpers (for ex. the `AsTaggedExplicit` extension trait, implemented for all
+//! types) are not included.
+//!
+//! ```rust
+//! use asn1_rs::prelude::*;
+//!
+//! let (_, oid) = Oid::from_der(&[0x06, 0x03, 0x2a, 0x03, 0x04]).expect("parsing failed");
+//! assert_eq!(oid, oid!(1.2.3.4));
+//! ```
+
+pub use crate::{CheckDerConstraints, FromBer, FromDer, Tagged, ToStatic};
+#[cfg(feature = "std")]
+pub use crate::{SerializeError, SerializeResult, ToDer};
+
+pub use crate::{
+    Any, BitString, Boolean, Class, Enumerated, Error, Explicit, GeneralizedTime, Header,
+    Ia5String, Implicit, Integer, Length, Null, OctetString, Oid, ParseResult, PrintableString,
+    Sequence, SequenceOf, Set, SetOf, Tag, TaggedExplicit, TaggedImplicit, TaggedValue, UtcTime,
+    Utf8String,
+};
+
+pub use crate::{BerAlias, BerSequence, BerSet, DerAlias, DerSequence, DerSet};
+
+pub use crate::{asn1_alias, int, oid};
@@ -0,0 +1,344 @@
+//! Parsing profiles, selecting which encodings are accepted
+
+use crate::ber::{GetObjectContent, MAX_RECURSION};
+use crate::*;
+use core::convert::TryFrom;
+
+/// A set of rules describing which encodings are accepted when parsing
+///
+/// BER allows many encodings for the same value, and DER restricts them to a single one. In
+/// practice, parsers often need something in between: for ex. certificates are supposed to be
+/// DER, but some producers use non-canonical encodings that are widely accepted. A profile
+/// bundles all these choices, so they can be selected in one place and passed to
+/// [`ParserProfile::parse`].
+///
+/// The profile is applied to the complete object: all nested objects are checked, not only the
+/// top-level one. Checks done by the target type (for ex. the charset of a `PrintableString`)
+/// are always applied.
+///
+/// Presets are provided for common cases:
+/// - [`ParserProfile::der_strict`]: DER, as described in X.690 sections 10 and 11
+/// - [`ParserProfile::ber`]: all BER encodings (the default)
+/// - [`ParserProfile::x509_webpki`]: DER, as accepted by common X.509 validators (charsets of
+///   nested strings are not checked)
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{DerConstraint, Error, Integer, ParserProfile};
+///
+/// // INTEGER 1, with a non-minimal length encoding
+/// let bytes = &[0x02, 0x81, 0x01, 0x01];
+/// let (_, i) = ParserProfile::ber().parse::<Integer>(bytes).expect("parsing failed");
+/// assert_eq!(i.as_u32(), Ok(1));
+/// let res = ParserProfile::der_strict().parse::<Integer>(bytes);
+/// assert_eq!(
+///     res,
+///     Err(nom::Err::Error(Error::DerConstraintFailed(DerConstraint::NonMinimalLength)))
+/// );
+/// // profiles can be adjusted
+/// let profile = ParserProfile {
+///     non_minimal_length: true,
+///     ..ParserProfile::der_strict()
+/// };
+/// assert!(profile.parse::<Integer>(bytes).is_ok());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParserProfile {
+    /// Maximum nesting depth of constructed objects
+    pub max_depth: usize,
+    /// Accept the indefinite length form (X.690 section 8.1.3.6)
+    pub indefinite_length: bool,
+    /// Accept lengths not encoded with the minimum number of octets (X.690 section 10.1)
+    pub non_minimal_length: bool,
+    /// Accept the constructed form for string types (X.690 section 10.2)
+    pub constructed_strings: bool,
+    /// Check the DER constraints of universal types (for ex. canonical booleans, minimal
+    /// integers, unused bits of bit strings), except time types
+    pub der_constraints: bool,
+    /// Accept time types with a local time or an offset time zone, instead of `Z` (X.690
+    /// sections 11.7.1 and 11.8.1)
+    pub time_zone_offsets: bool,
+    /// Accept time types without seconds (X.690 sections 11.7.2 and 11.8.2)
+    pub time_without_seconds: bool,
+    /// Check the charset of all nested string objects
+    pub string_charsets: bool,
+    /// Accept trailing data after the top-level object (otherwise, `Error::TrailingContent` is
+    /// returned)
+    pub trailing_data: bool,
+    /// Maximum length of the content of all nested `INTEGER` objects
+    ///
+    /// Conversions to integer types also apply the default limit [`MAX_INTEGER_LEN`], so this
+    /// can only lower it.
+    pub max_integer_len: usize,
+}
+
+impl ParserProfile {
+    /// DER, as described in X.690 sections 10 and 11
+    pub const fn der_strict() -> Self {
+        ParserProfile {
+            max_depth: MAX_RECURSION,
+            indefinite_length: false,
+            non_minimal_length: false,
+            constructed_strings: false,
+            der_constraints: true,
+            time_zone_offsets: false,
+            time_without_seconds: false,
+            string_charsets: true,
+            trailing_data: false,
+            max_integer_len: MAX_INTEGER_LEN,
+        }
+    }
+
+    /// All BER encodings
+    pub const fn ber() -> Self {
+        ParserProfile {
+            max_depth: MAX_RECURSION,
+            indefinite_length: true,
+            non_minimal_length: true,
+            constructed_strings: true,
+            der_constraints: false,
+            time_zone_offsets: true,
+            time_without_seconds: true,
+            string_charsets: false,
+            trailing_data: true,
+            max_integer_len: MAX_INTEGER_LEN,
+        }
+    }
+
+    /// DER, as accepted by common X.509 validators
+    ///
+    /// This is [`ParserProfile::der_strict`], without checking the charsets of nested strings:
+    /// certificates often contain, for ex., `PrintableString` values with forbidden characters
+    /// such as `*` or `@`. Time types must use `Z` and have seconds (RFC 5280 section 4.1.2.5).
+    pub const fn x509_webpki() -> Self {
+        ParserProfile {
+            string_charsets: false,
+            ..Self::der_strict()
+        }
+    }
+
+    /// Parse an object, checking it against this profile
+    pub fn parse_any<'a>(&self, bytes: &'a [u8]) -> ParseResult<'a, Any<'a>> {
+        let (i, header) = Header::from_ber(bytes)?;
+        let (rem, data) = BerParser::get_object_content(i, &header, self.max_depth)?;
+        let any = Any::new(header, data);
+        self.check(&any)?;
+        if !self.trailing_data && !rem.is_empty() {
+            return Err(Err::Error(Error::TrailingContent {
+                tag: any.tag(),
+                remaining: rem.len(),
+            }));
+        }
+        Ok((rem, any))
+    }
+
+    /// Parse an object of type `T`, checking it against this profile
+    ///
+    /// If [`ParserProfile::der_constraints`] is set, the DER constraints of `T` are also checked.
+    pub fn parse<'a, T>(&self, bytes: &'a [u8]) -> ParseResult<'a, T>
+    where
+        T: TryFrom<Any<'a>, Error = Error>,
+        T: CheckDerConstraints,
+    {
+        let (rem, any) = self.parse_any(bytes)?;
+        if self.der_constraints {
+            T::check_constraints(&any)?;
+        }
+        let obj = T::try_from(any)?;
+        Ok((rem, obj))
+    }
+
+    /// Check an object, and all nested objects, against this profile
+    pub fn check(&self, any: &Any) -> Result<()> {
+        self.check_rec(any, DerCheckContext::with_max_depth(self.max_depth))
+    }
+
+    fn check_rec(&self, any: &Any, ctx: DerCheckContext) -> Result<()> {
+        let header = &any.header;
+        if !self.indefinite_length && !header.length().is_definite() {
+            return Err(Error::DerConstraintFailed(DerConstraint::IndefiniteLength));
+        }
+        if !self.non_minimal_length
+            && header.length_encoding() != LengthEncoding::canonical(header.length())
+        {
+            return Err(Error::DerConstraintFailed(DerConstraint::NonMinimalLength));
+        }
+        if header.is_universal() {
+            let tag = header.tag();
+            if header.is_constructed() {
+                if !self.constructed_strings && is_string(tag) {
+                    return Err(Error::DerConstraintFailed(DerConstraint::Constructed));
+                }
+            } else if tag == Tag::UtcTime || tag == Tag::GeneralizedTime {
+                self.check_time(tag, any.data)?;
+            } else {
+                if tag == Tag::Integer {
+                    check_int_len(any, self.max_integer_len)?;
+                }
+                if self.der_constraints {
+                    check_universal_der(any)?;
+                }
+                if self.string_charsets && is_string(tag) {
+                    any.decode_universal()?;
+                }
+            }
+        }
+        if header.is_constructed() {
+            let ctx = ctx.enter()?;
+            let mut i = any.data;
+            while !i.is_empty() {
+                let (rem, item) = Any::from_ber(i)?;
+                self.check_rec(&item, ctx)?;
+                i = rem;
+            }
+        }
+        Ok(())
+    }
+
+    fn check_time(&self, tag: Tag, data: &[u8]) -> Result<()> {
+        if !self.time_zone_offsets && data.last() != Some(&b'Z') {
+            return Err(Error::DerConstraintFailed(DerConstraint::MissingTimeZone));
+        }
+        // seconds follow YYMMDDhhmm (UTCTime) or YYYYMMDDhhmm (GeneralizedTime)
+        let offset = if tag == Tag::UtcTime { 10 } else { 12 };
+        let has_seconds = matches!(data.get(offset..offset + 2), Some([a, b]) if a.is_ascii_digit() && b.is_ascii_digit());
+        if !self.time_without_seconds && !has_seconds {
+            return Err(Error::DerConstraintFailed(DerConstraint::MissingSeconds));
+        }
+        Ok(())
+    }
+}
+
+impl Default for ParserProfile {
+    fn default() -> Self {
+        Self::ber()
+    }
+}
+
+/// Return true if universal type is a string type, which can be constructed in BER
+pub(crate) fn is_string(tag: Tag) -> bool {
+    matches!(
+        tag,
+        Tag::BitString
+            | Tag::OctetString
+            | Tag::ObjectDescriptor
+            | Tag::Utf8String
+            | Tag::NumericString
+            | Tag::PrintableString
+            | Tag::T61String
+            | Tag::VideotexString
+            | Tag::Ia5String
+            | Tag::UtcTime
+            | Tag::GeneralizedTime
+            | Tag::GraphicString
+            | Tag::VisibleString
+            | Tag::GeneralString
+            | Tag::UniversalString
+            | Tag::BmpString
+    )
+}
+
+/// Check the DER constraints of universal types
+pub(crate) fn check_universal_der(any: &Any) -> Result<()> {
+    match any.tag() {
+        Tag::EndOfContent => Err(Error::DerConstraintFailed(DerConstraint::IndefiniteLength)),
+        Tag::Boolean => Boolean::check_constraints(any),
+        Tag::Integer => Integer::check_constraints(any),
+        Tag::BitString => BitString::check_constraints(any),
+        Tag::OctetString => OctetString::check_constraints(any),
+        Tag::Null => Null::check_constraints(any),
+        Tag::Oid => Oid::check_constraints(any),
+        Tag::Enumerated => Enumerated::check_constraints(any),
+        Tag::Utf8String => Utf8String::check_constraints(any),
+        Tag::PrintableString => PrintableString::check_constraints(any),
+        Tag::Ia5String => Ia5String::check_constraints(any),
+        Tag::UtcTime => UtcTime::check_constraints(any),
+        Tag::GeneralizedTime => GeneralizedTime::check_constraints(any),
+        Tag::BmpString => BmpString::check_constraints(any),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn profile_presets() {
+        let der = ParserProfile::der_strict();
+        let ber = ParserProfile::ber();
+        // SEQUENCE (indefinite) { BOOLEAN 01 }
+        let input = &hex!("30 80 01 01 01 00 00");
+        assert!(ber.parse_any(input).is_ok());
+        let err = Err(Err::Error(Error::DerConstraintFailed(
+            DerConstraint::IndefiniteLength,
+        )));
+        assert_eq!(der.parse_any(input), err);
+        // nested non-canonical BOOLEAN
+        let input = &hex!("30 03 01 01 01");
+        assert!(ber.parse::<Sequence>(input).is_ok());
+        assert!(der.parse::<Sequence>(input).is_err());
+        // constructed string
+        let input = &hex!("24 04 04 02 61 62");
+        assert!(ber.parse::<OctetString>(input).is_ok());
+        assert!(der.parse::<OctetString>(input).is_err());
+        // trailing data
+        assert!(ber.parse_any(&hex!("05 00 ff")).is_ok());
+        assert_eq!(
+            der.parse_any(&hex!("05 00 ff")),
+            Err(Err::Error(Error::TrailingContent {
+                tag: Tag::Null,
+                remaining: 1
+            }))
+        );
+    }
+
+    #[test]
+    fn profile_times_charsets() {
+        let der = ParserProfile::der_strict();
+        let webpki = ParserProfile::x509_webpki();
+        // UTCTime with offset, without seconds
+        let input = &hex!("17 0f 32 31 30 31 30 31 31 32 30 30 2b 30 31 30 30");
+        assert!(ParserProfile::ber().parse::<UtcTime>(input).is_ok());
+        assert_eq!(
+            der.parse::<UtcTime>(input),
+            Err(Err::Error(Error::DerConstraintFailed(
+                DerConstraint::MissingTimeZone
+            )))
+        );
+        let profile = ParserProfile {
+            time_zone_offsets: true,
+            ..der
+        };
+        assert_eq!(
+            profile.check(&Any::from_ber(input).unwrap().1),
+            Err(Error::DerConstraintFailed(DerConstraint::MissingSeconds))
+        );
+        // SEQUENCE { PrintableString "*" }
+        let input = &hex!("30 03 13 01 2a");
+        assert!(der.parse::<Sequence>(input).is_err());
+        assert!(webpki.parse::<Sequence>(input).is_ok());
+    }
+
+    #[test]
+    fn profile_max_integer_len() {
+        let profile = ParserProfile {
+            max_integer_len: 2,
+            ..ParserProfile::ber()
+        };
+        // SEQUENCE { INTEGER 0x010001 }
+        let input = &hex!("30 05 02 03 01 00 01");
+        assert!(ParserProfile::ber().parse::<Sequence>(input).is_ok());
+        assert_eq!(
+            profile.parse::<Sequence>(input),
+            Err(Err::Error(Error::IntegerLengthLimit { len: 3, max: 2 }))
+        );
+        assert!(profile.parse::<u16>(&input[2..]).is_err());
+        assert_eq!(
+            profile.parse::<u16>(&hex!("02 02 01 00")),
+            Ok((&[][..], 0x100))
+        );
+    }
+}
@@ -0,0 +1,247 @@
+//! Lenient BER parsing, recovering from errors
+//!
+//! Parsers of this crate stop at the first error. When analyzing corrupted or truncated data
+//! (for ex. network captures), it is often more useful to get all objects that can be parsed,
+//! and the list of errors. [`parse_ber_recover`] parses objects recursively: when an object
+//! cannot be parsed, the error is recorded and parsing continues with the next sibling, using
+//! the length from the object header.
+
+use crate::ber::MAX_RECURSION;
+use crate::{Any, Class, Error, FromBer, Header, Length};
+use alloc::vec::Vec;
+use nom::Needed;
+
+/// An object parsed in recovery mode
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecoveredNode<'a> {
+    /// Object header
+    pub header: Header<'a>,
+    /// Object content (truncated if the input was too short)
+    pub data: &'a [u8],
+    /// Offset of the object header, relative to the start of the input
+    pub offset: usize,
+    /// Sub-objects, if the object is constructed
+    pub children: Vec<RecoveredNode<'a>>,
+}
+
+/// An error encountered in recovery mode
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecoveryError {
+    /// Offset of the object in error, relative to the start of the input
+    pub offset: usize,
+    /// Nesting depth of the object in error (top-level objects have depth 1)
+    pub depth: usize,
+    /// The error
+    pub error: Error,
+}
+
+/// The result of parsing in recovery mode: a partial tree of objects, and a list of errors
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RecoveredTree<'a> {
+    /// Top-level objects
+    pub nodes: Vec<RecoveredNode<'a>>,
+    /// Errors, in input order
+    pub errors: Vec<RecoveryError>,
+}
+
+impl<'a> RecoveredTree<'a> {
+    /// Return true if input was parsed without errors
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Parse all BER objects from input (recursively), recording errors instead of failing
+///
+/// For each object:
+/// - if the header cannot be parsed, the error is recorded and the rest of the enclosing object
+///   (or input) is skipped, since the position of the next sibling is unknown
+/// - if the length exceeds the available data, the object is kept with truncated content, and
+///   an error is recorded
+/// - if the content of a primitive universal object is invalid (for ex. invalid UTF-8 in a
+///   `UTF8String`), the object is kept and an error is recorded
+///
+/// Constructed objects are parsed recursively, up to the default recursion limit.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{parse_ber_recover, Error};
+///
+/// // SEQUENCE { BOOLEAN (invalid length), INTEGER 2 }
+/// let bytes = &[0x30, 0x07, 0x01, 0x02, 0xff, 0xff, 0x02, 0x01, 0x02];
+/// let tree = parse_ber_recover(bytes);
+/// assert_eq!(tree.nodes[0].children.len(), 2);
+/// assert_eq!(tree.errors.len(), 1);
+/// assert_eq!(tree.errors[0].offset, 2);
+/// assert_eq!(tree.errors[0].error, Error::InvalidLength);
+/// ```
+pub fn parse_ber_recover(bytes: &[u8]) -> RecoveredTree<'_> {
+    let mut tree = RecoveredTree::default();
+    let (nodes, _, _) = walk(bytes, 0, 1, false, &mut tree.errors);
+    tree.nodes = nodes;
+    tree
+}
+
+fn push_error(errors: &mut Vec<RecoveryError>, offset: usize, depth: usize, error: Error) {
+    errors.push(RecoveryError {
+        offset,
+        depth,
+        error,
+    });
+}
+
+/// Parse sibling objects, until the end of input or (if `indefinite`) an end-of-content marker
+///
+/// Returns the parsed objects, the number of bytes consumed, and true if the end-of-content
+/// marker was found
+fn walk<'a>(
+    bytes: &'a [u8],
+    offset: usize,
+    depth: usize,
+    indefinite: bool,
+    errors: &mut Vec<RecoveryError>,
+) -> (Vec<RecoveredNode<'a>>, usize, bool) {
+    let mut nodes = Vec::new();
+    let mut pos = 0;
+    while pos < bytes.len() {
+        let i = &bytes[pos..];
+        if indefinite && i.starts_with(&[0, 0]) {
+            return (nodes, pos + 2, true);
+        }
+        let (rem, header) = match Header::from_ber(i) {
+            Ok(res) => res,
+            Err(e) => {
+                push_error(errors, offset + pos, depth, e.into());
+                return (nodes, bytes.len(), false);
+            }
+        };
+        let hdr_len = i.len() - rem.len();
+        let mut node = RecoveredNode {
+            header,
+            data: &[],
+            offset: offset + pos,
+            children: Vec::new(),
+        };
+        let content_offset = node.offset + hdr_len;
+        let consumed = match node.header.length() {
+            Length::Definite(len) => {
+                let len = if len > rem.len() {
+                    let needed = Needed::new(len - rem.len());
+                    push_error(errors, node.offset, depth, Error::Incomplete(needed));
+                    rem.len()
+                } else {
+                    len
+                };
+                node.data = &rem[..len];
+                if node.header.is_constructed() {
+                    if depth >= MAX_RECURSION {
+                        push_error(errors, node.offset, depth, Error::BerMaxDepth);
+                    } else {
+                        let (children, _, _) =
+                            walk(node.data, content_offset, depth + 1, false, errors);
+                        node.children = children;
+                    }
+                } else if node.header.class() == Class::Universal {
+                    let any = Any::new(node.header.clone(), node.data);
+                    if let Err(e) = any.decode_universal() {
+                        push_error(errors, node.offset, depth, e);
+                    }
+                }
+                len
+            }
+            Length::Indefinite => {
+                if !node.header.is_constructed() {
+                    // X.690 section 8.1.3.2
+                    push_error(errors, node.offset, depth, Error::ConstructExpected);
+                    return (nodes, bytes.len(), false);
+                }
+                if depth >= MAX_RECURSION {
+                    push_error(errors, node.offset, depth, Error::BerMaxDepth);
+                    return (nodes, bytes.len(), false);
+                }
+                let (children, used, terminated) =
+                    walk(rem, content_offset, depth + 1, true, errors);
+                node.children = children;
+                // content does not include the end-of-content marker
+                node.data = if terminated {
+                    &rem[..used - 2]
+                } else {
+                    &rem[..used]
+                };
+                used
+            }
+        };
+        nodes.push(node);
+        pos += hdr_len + consumed;
+    }
+    if indefinite {
+        // missing end-of-content marker
+        push_error(
+            errors,
+            offset + pos,
+            depth,
+            Error::Incomplete(Needed::new(2)),
+        );
+    }
+    (nodes, pos, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use hex_literal::hex;
+    use nom::Needed;
+
+    #[test]
+    fn recover_valid_input() {
+        let input = &hex!("30 80 02 01 01 04 02 aa bb 00 00 05 00");
+        let tree = parse_ber_recover(input);
+        assert!(tree.is_complete());
+        assert_eq!(tree.nodes.len(), 2);
+        assert_eq!(tree.nodes[0].children.len(), 2);
+        assert_eq!(tree.nodes[0].data, &input[2..9]);
+        assert_eq!(tree.nodes[0].children[1].offset, 5);
+        assert_eq!(tree.nodes[1].header.tag(), Tag::Null);
+        assert_eq!(tree.nodes[1].offset, 11);
+    }
+
+    #[test]
+    fn recover_invalid_siblings() {
+        // SEQUENCE { UTF8String (invalid), SEQUENCE { NULL (invalid) }, INTEGER 5 }, NULL
+        let input = &hex!("30 0b 0c 01 ff 30 03 05 01 00 02 01 05 05 00");
+        let tree = parse_ber_recover(input);
+        assert_eq!(tree.nodes.len(), 2);
+        let seq = &tree.nodes[0];
+        assert_eq!(seq.children.len(), 3);
+        assert_eq!(seq.children[2].data, &[5]);
+        assert_eq!(tree.errors.len(), 2);
+        assert_eq!(tree.errors[0].offset, 2);
+        assert_eq!(tree.errors[0].depth, 2);
+        assert_eq!(tree.errors[1].offset, 7);
+        assert_eq!(tree.errors[1].depth, 3);
+        assert_eq!(tree.errors[1].error, Error::InvalidLength);
+    }
+
+    #[test]
+    fn recover_truncated() {
+        // SEQUENCE (length 10, truncated) { INTEGER 1, OCTET STRING (truncated) }
+        let input = &hex!("30 0a 02 01 01 04 05 aa");
+        let tree = parse_ber_recover(input);
+        assert_eq!(tree.nodes.len(), 1);
+        let seq = &tree.nodes[0];
+        assert_eq!(seq.data, &input[2..]);
+        assert_eq!(seq.children.len(), 2);
+        assert_eq!(seq.children[1].data, &[0xaa]);
+        assert_eq!(tree.errors.len(), 2);
+        assert_eq!(tree.errors[0].offset, 0);
+        assert_eq!(tree.errors[0].error, Error::Incomplete(Needed::new(4)));
+        assert_eq!(tree.errors[1].offset, 5);
+        // missing end-of-content
+        let input = &hex!("30 80 02 01 01");
+        let tree = parse_ber_recover(input);
+        assert_eq!(tree.nodes[0].children.len(), 1);
+        assert_eq!(tree.errors.len(), 1);
+        assert_eq!(tree.errors[0].offset, 5);
+    }
+}
@@ -0,0 +1,170 @@
+//! Registry of user-defined decoders for universal tags
+//!
+//! Generic layers (for ex. [`tools::dump`](crate::tools::dump) or
+//! [`Any::decode_universal`]) only know how to decode the universal types supported by this
+//! crate. Applications handling other universal tags (for ex. `CHARACTER STRING`, or tags used
+//! by a private profile) can register a decoder for them in a [`TagRegistry`], so their content
+//! is displayed meaningfully instead of as raw bytes.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use asn1_rs::{Any, FromBer, Tag, TagRegistry};
+//!
+//! fn decode_latin1(data: &[u8]) -> asn1_rs::Result<String> {
+//!     Ok(data.iter().map(|&b| b as char).collect())
+//! }
+//!
+//! let mut registry = TagRegistry::new();
+//! registry.register(Tag(29), "CHARACTER STRING", decode_latin1);
+//!
+//! let (_, any) = Any::from_ber(&[0x1d, 0x02, 0x61, 0xe9]).expect("parsing failed");
+//! match registry.decode(&any) {
+//!     Some(Ok(registered)) => {
+//!         assert_eq!(registered.name, "CHARACTER STRING");
+//!         assert_eq!(registered.value, "aé");
+//!     }
+//!     // not registered: fall back to `any.decode_universal()`
+//!     _ => panic!("unexpected value"),
+//! }
+//! ```
+
+use crate::*;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// A function decoding the content of an object to a displayable value
+pub type TagDecoder = fn(&[u8]) -> Result<String>;
+
+/// A decoder registered for a universal tag
+#[derive(Clone, Copy, Debug)]
+pub struct TagHandler {
+    /// Name of the type, used instead of the tag number
+    pub name: &'static str,
+    /// Function decoding the content of the object
+    pub decode: TagDecoder,
+}
+
+/// An object decoded by a handler of a [`TagRegistry`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegisteredValue<'a> {
+    /// Name of the type, from the handler
+    pub name: &'static str,
+    /// Value returned by the handler
+    pub value: String,
+    /// The decoded object
+    pub any: Any<'a>,
+}
+
+/// A set of decoders for universal tags
+///
+/// Handlers only apply to objects of the `Universal` class. A handler registered for a tag
+/// supported by this crate replaces the default decoding.
+#[derive(Clone, Debug, Default)]
+pub struct TagRegistry {
+    handlers: Vec<(Tag, TagHandler)>,
+}
+
+impl TagRegistry {
+    /// Build an empty registry
+    pub const fn new() -> Self {
+        TagRegistry {
+            handlers: Vec::new(),
+        }
+    }
+
+    /// Register a decoder for the universal tag `tag`, replacing any previous one
+    pub fn register(&mut self, tag: Tag, name: &'static str, decode: TagDecoder) -> &mut Self {
+        let handler = TagHandler { name, decode };
+        match self.handlers.iter_mut().find(|(t, _)| *t == tag) {
+            Some(entry) => entry.1 = handler,
+            None => self.handlers.push((tag, handler)),
+        }
+        self
+    }
+
+    /// Return the handler registered for the universal tag `tag`, if any
+    pub fn get(&self, tag: Tag) -> Option<&TagHandler> {
+        self.handlers
+            .iter()
+            .find(|(t, _)| *t == tag)
+            .map(|(_, handler)| handler)
+    }
+
+    /// Return the handler for the object described by `header`, if any
+    pub(crate) fn handler_for(&self, header: &Header) -> Option<&TagHandler> {
+        if header.is_universal() {
+            self.get(header.tag())
+        } else {
+            None
+        }
+    }
+
+    /// Decode `any` using the handler registered for its tag
+    ///
+    /// Return `None` if `any` is not of the `Universal` class, or if no handler is registered
+    /// for its tag. In that case, [`Any::decode_universal`] can be used instead.
+    pub fn decode<'a>(&self, any: &Any<'a>) -> Option<Result<RegisteredValue<'a>>> {
+        let handler = self.handler_for(&any.header)?;
+        let res = (handler.decode)(any.data).map(|value| RegisteredValue {
+            name: handler.name,
+            value,
+            any: any.clone(),
+        });
+        Some(res)
+    }
+
+    /// Return true if no handler is registered
+    pub fn is_empty(&self) -> bool {
+        self.handlers.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use hex_literal::hex;
+
+    fn decode_upper(data: &[u8]) -> Result<String> {
+        let s = core::str::from_utf8(data)?;
+        Ok(s.to_uppercase())
+    }
+
+    fn decode_fail(_: &[u8]) -> Result<String> {
+        Err(Error::BerValueError)
+    }
+
+    #[test]
+    fn tag_registry() {
+        let mut registry = TagRegistry::new();
+        assert!(registry.is_empty());
+        registry.register(Tag(29), "CHARACTER STRING", decode_fail);
+        registry.register(Tag(29), "CHARACTER STRING", decode_upper);
+        assert_eq!(
+            registry.get(Tag(29)).map(|h| h.name),
+            Some("CHARACTER STRING")
+        );
+        assert!(registry.get(Tag(30)).is_none());
+        // registered universal tag
+        let (_, any) = Any::from_ber(&hex!("1d 02 61 62")).expect("parsing failed");
+        assert_eq!(
+            registry.decode(&any),
+            Some(Ok(RegisteredValue {
+                name: "CHARACTER STRING",
+                value: "AB".to_string(),
+                any: any.clone(),
+            }))
+        );
+        assert_eq!(
+            any.decode_universal(),
+            Ok(UniversalValue::Other(any.clone()))
+        );
+        // only the universal class is affected
+        let (_, any) = Any::from_ber(&hex!("9d 02 61 62")).expect("parsing failed");
+        assert_eq!(registry.decode(&any), None);
+        // errors are returned
+        registry.register(Tag::Null, "NULL", decode_fail);
+        let (_, any) = Any::from_ber(&hex!("05 00")).expect("parsing failed");
+        assert_eq!(registry.decode(&any), Some(Err(Error::BerValueError)));
+    }
+}
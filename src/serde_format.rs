@@ -0,0 +1,181 @@
+//! Implementations of `serde::Serialize` for errors, for machine-readable reports
+//!
+//! Errors are serialized as maps, so reports can be aggregated without parsing messages:
+//! - `kind`: the name of the error variant (for ex. `UnexpectedTag`)
+//! - `message`: the error message, as displayed
+//! - `context`: the stack of field indices (outermost first), from nested [`Error::Field`]
+//!   errors. `kind` and `message` describe the innermost error.
+//! - variant-specific values, for ex. `tag` and `expected_tag` for `UnexpectedTag`
+//!
+//! Tags are serialized as numbers, classes and DER constraints as their names.
+
+use crate::*;
+use alloc::vec::Vec;
+use core::fmt;
+use nom::Needed;
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+/// Serialize a value using its `Display` implementation
+struct DisplayStr<'a, T: fmt::Display>(&'a T);
+
+impl<T: fmt::Display> Serialize for DisplayStr<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self.0)
+    }
+}
+
+impl Serialize for Tag {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        serializer.serialize_u32(self.0)
+    }
+}
+
+impl Serialize for Class {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl Serialize for DerConstraint {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_args!("{:?}", self))
+    }
+}
+
+/// Return the name of the error variant
+fn kind_name(error: &Error) -> &'static str {
+    match error {
+        Error::BerTypeError => "BerTypeError",
+        Error::BerValueError => "BerValueError",
+        Error::InvalidLength => "InvalidLength",
+        Error::LengthTooLarge => "LengthTooLarge",
+        Error::InvalidValue { .. } => "InvalidValue",
+        Error::InvalidTag => "InvalidTag",
+        Error::UnknownTag(_) => "UnknownTag",
+        Error::UnexpectedTag { .. } => "UnexpectedTag",
+        Error::UnexpectedClass { .. } => "UnexpectedClass",
+        Error::IndefiniteLengthUnexpected => "IndefiniteLengthUnexpected",
+        Error::UnexpectedEndOfContent => "UnexpectedEndOfContent",
+        Error::InvalidEndOfContent => "InvalidEndOfContent",
+        Error::ConstructExpected => "ConstructExpected",
+        Error::ConstructUnexpected => "ConstructUnexpected",
+        Error::IntegerTooLarge => "IntegerTooLarge",
+        Error::IntegerNegative => "IntegerNegative",
+        Error::IntegerLengthLimit { .. } => "IntegerLengthLimit",
+        Error::IntegerOutOfRange { .. } => "IntegerOutOfRange",
+        Error::LengthLimit { .. } => "LengthLimit",
+        Error::BerMaxDepth => "BerMaxDepth",
+        Error::TrailingContent { .. } => "TrailingContent",
+        Error::StringInvalidCharset => "StringInvalidCharset",
+        Error::StringInvalidCharacters(_) => "StringInvalidCharacters",
+        Error::InvalidDateTime => "InvalidDateTime",
+        Error::InvalidCalendarDate { .. } => "InvalidCalendarDate",
+        Error::InvalidHexString => "InvalidHexString",
+        Error::DerConstraintFailed(_) => "DerConstraintFailed",
+        Error::MissingField(_) => "MissingField",
+        Error::DuplicateField(_) => "DuplicateField",
+        Error::UnorderedField(_) => "UnorderedField",
+        Error::UnexpectedField { .. } => "UnexpectedField",
+        Error::Field { .. } => "Field",
+        Error::InvalidPath(_) => "InvalidPath",
+        Error::PathNotFound(_) => "PathNotFound",
+        Error::LifetimeError => "LifetimeError",
+        Error::Unsupported => "Unsupported",
+        Error::Incomplete(_) => "Incomplete",
+        Error::NomError(_) => "NomError",
+    }
+}
+
+impl Serialize for Error {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        // unwrap the context stack
+        let mut context = Vec::new();
+        let mut error = self;
+        while let Error::Field {
+            index,
+            error: inner,
+        } = error
+        {
+            context.push(*index);
+            error = inner;
+        }
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("kind", kind_name(error))?;
+        map.serialize_entry("message", &DisplayStr(error))?;
+        map.serialize_entry("context", &context)?;
+        match error {
+            Error::InvalidValue { tag, .. } => map.serialize_entry("tag", tag)?,
+            Error::UnknownTag(tag) => map.serialize_entry("tag", tag)?,
+            Error::UnexpectedTag { expected, actual } => {
+                map.serialize_entry("expected_tag", expected)?;
+                map.serialize_entry("tag", actual)?;
+            }
+            Error::UnexpectedClass { expected, actual } => {
+                map.serialize_entry("expected_class", expected)?;
+                map.serialize_entry("class", actual)?;
+            }
+            Error::IntegerLengthLimit { len, max } | Error::LengthLimit { len, max } => {
+                map.serialize_entry("len", len)?;
+                map.serialize_entry("max", max)?;
+            }
+            Error::IntegerOutOfRange { bits, max } => {
+                map.serialize_entry("bits", bits)?;
+                map.serialize_entry("max", max)?;
+            }
+            Error::TrailingContent { tag, remaining } => {
+                map.serialize_entry("tag", tag)?;
+                map.serialize_entry("remaining", remaining)?;
+            }
+            Error::StringInvalidCharacters(v) => map.serialize_entry("characters", v)?,
+            Error::InvalidCalendarDate { year, month, day } => {
+                map.serialize_entry("year", year)?;
+                map.serialize_entry("month", month)?;
+                map.serialize_entry("day", day)?;
+            }
+            Error::DerConstraintFailed(c) => map.serialize_entry("constraint", c)?,
+            Error::MissingField(s) | Error::DuplicateField(s) | Error::UnorderedField(s) => {
+                map.serialize_entry("field", s)?
+            }
+            Error::UnexpectedField { class, tag } => {
+                map.serialize_entry("class", class)?;
+                map.serialize_entry("tag", tag)?;
+            }
+            Error::InvalidPath(s) => map.serialize_entry("path", s)?,
+            Error::PathNotFound(n) => map.serialize_entry("segment", n)?,
+            Error::Incomplete(Needed::Size(n)) => map.serialize_entry("needed", &n.get())?,
+            Error::NomError(kind) => map.serialize_entry("nom_error", kind.description())?,
+            _ => (),
+        }
+        map.end()
+    }
+}
+
+impl Serialize for LocatedError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(4))?;
+        map.serialize_entry("error", &self.error)?;
+        map.serialize_entry("offset", &self.offset)?;
+        map.serialize_entry("snippet_offset", &self.snippet_offset)?;
+        map.serialize_entry("snippet", &self.snippet)?;
+        map.end()
+    }
+}
+
+impl Serialize for RecoveryError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(3))?;
+        map.serialize_entry("error", &self.error)?;
+        map.serialize_entry("offset", &self.offset)?;
+        map.serialize_entry("depth", &self.depth)?;
+        map.end()
+    }
+}
+
+impl Serialize for HintedError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("error", &self.error)?;
+        map.serialize_entry("hint", &self.hint.as_ref().map(DisplayStr))?;
+        map.end()
+    }
+}
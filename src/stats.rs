@@ -0,0 +1,147 @@
+//! Parsing statistics
+//!
+//! This module provides a collector for statistics on parsed objects (number of objects per
+//! tag, nesting depth, sizes). This is useful to understand the workload of a parser, and to
+//! choose limits (for ex. maximum depth or object size) before parsing untrusted data.
+
+use crate::ber::MAX_RECURSION;
+use crate::{Any, Class, Error, FromBer, FromDer, Header, Result, Tag};
+use alloc::collections::BTreeMap;
+
+/// Statistics on parsed objects
+///
+/// Statistics can be collected on a complete input using [`ParseStats::collect_ber`] or
+/// [`ParseStats::collect_der`], which parse all objects recursively (including the contents of
+/// constructed objects). Custom parsers can also use [`ParseStats::record`] to record each
+/// parsed object, and [`ParseStats::merge`] to aggregate statistics of multiple inputs.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{Class, ParseStats, Tag};
+///
+/// // SEQUENCE { INTEGER 1, SEQUENCE { INTEGER 2, NULL } }
+/// let bytes = &[0x30, 0x0a, 0x02, 0x01, 0x01, 0x30, 0x05, 0x02, 0x01, 0x02, 0x05, 0x00];
+/// let stats = ParseStats::collect_der(bytes).expect("parsing failed");
+/// assert_eq!(stats.total_elements, 5);
+/// assert_eq!(stats.max_depth, 3);
+/// assert_eq!(stats.count(Class::Universal, Tag::Integer), 2);
+/// assert_eq!(stats.largest_element, 10);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ParseStats {
+    /// Number of objects, per class and tag
+    pub tags: BTreeMap<(Class, Tag), usize>,
+    /// Maximum nesting depth (top-level objects have depth 1)
+    pub max_depth: usize,
+    /// Total number of objects
+    pub total_elements: usize,
+    /// Size of the content of the largest object, in bytes
+    pub largest_element: usize,
+}
+
+impl ParseStats {
+    /// Build a new, empty, statistics collector
+    pub fn new() -> Self {
+        ParseStats::default()
+    }
+
+    /// Record a parsed object, with the size of its content and its nesting depth
+    pub fn record(&mut self, header: &Header, content_len: usize, depth: usize) {
+        *self.tags.entry((header.class(), header.tag())).or_insert(0) += 1;
+        self.max_depth = self.max_depth.max(depth);
+        self.total_elements += 1;
+        self.largest_element = self.largest_element.max(content_len);
+    }
+
+    /// Add statistics from another collector
+    pub fn merge(&mut self, other: &ParseStats) {
+        for (k, v) in &other.tags {
+            *self.tags.entry(*k).or_insert(0) += v;
+        }
+        self.max_depth = self.max_depth.max(other.max_depth);
+        self.total_elements += other.total_elements;
+        self.largest_element = self.largest_element.max(other.largest_element);
+    }
+
+    /// Return the number of objects recorded with this class and tag
+    pub fn count(&self, class: Class, tag: Tag) -> usize {
+        self.tags.get(&(class, tag)).copied().unwrap_or(0)
+    }
+
+    /// Parse all BER objects from input (recursively), and collect statistics
+    ///
+    /// Parsing fails if objects are nested deeper than the default recursion limit.
+    pub fn collect_ber(bytes: &[u8]) -> Result<ParseStats> {
+        let mut stats = ParseStats::new();
+        stats.walk(bytes, 1, false)?;
+        Ok(stats)
+    }
+
+    /// Parse all DER objects from input (recursively), and collect statistics
+    ///
+    /// Parsing fails if objects are nested deeper than the default recursion limit.
+    pub fn collect_der(bytes: &[u8]) -> Result<ParseStats> {
+        let mut stats = ParseStats::new();
+        stats.walk(bytes, 1, true)?;
+        Ok(stats)
+    }
+
+    fn walk(&mut self, bytes: &[u8], depth: usize, der: bool) -> Result<()> {
+        if depth > MAX_RECURSION {
+            return Err(Error::BerMaxDepth);
+        }
+        let mut i = bytes;
+        while !i.is_empty() {
+            let (rem, any) = if der {
+                Any::from_der(i)?
+            } else {
+                Any::from_ber(i)?
+            };
+            self.record(&any.header, any.data.len(), depth);
+            if any.header.is_constructed() {
+                self.walk(any.data, depth + 1, der)?;
+            }
+            i = rem;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use alloc::vec::Vec;
+    use hex_literal::hex;
+
+    #[test]
+    fn parse_stats_ber() {
+        // indefinite length SEQUENCE { [0] { INTEGER 5 }, OCTET STRING }
+        let input = &hex!("30 80 a0 03 02 01 05 04 02 aa bb 00 00");
+        let stats = ParseStats::collect_ber(input).expect("parsing failed");
+        assert_eq!(stats.total_elements, 4);
+        assert_eq!(stats.max_depth, 3);
+        assert_eq!(stats.count(Class::ContextSpecific, Tag(0)), 1);
+        assert_eq!(stats.count(Class::Universal, Tag::OctetString), 1);
+        assert_eq!(stats.count(Class::Universal, Tag::Null), 0);
+        // indefinite length is not DER
+        assert!(ParseStats::collect_der(input).is_err());
+        // merge
+        let mut total = stats.clone();
+        total.merge(&stats);
+        assert_eq!(total.total_elements, 8);
+        assert_eq!(total.max_depth, 3);
+        assert_eq!(total.count(Class::Universal, Tag::Integer), 2);
+    }
+
+    #[test]
+    fn parse_stats_max_depth() {
+        // 60 nested SEQUENCE
+        let mut input = Vec::new();
+        for i in 0..60 {
+            input.extend_from_slice(&[0x30, (2 * (59 - i)) as u8]);
+        }
+        let res = ParseStats::collect_der(&input);
+        assert_eq!(res, Err(Error::BerMaxDepth));
+    }
+}
@@ -1,12 +1,16 @@
-use crate::{Error, Result};
+use crate::{Class, Error, Result};
 use alloc::string::ToString;
+use core::convert::TryFrom;
+use core::fmt;
+use core::str::FromStr;
 use rusticata_macros::newtype_enum;
 
 /// BER/DER Tag as defined in X.680 section 8.4
 ///
 /// X.690 doesn't specify the maximum tag size so we're assuming that people
 /// aren't going to need anything more than a u32.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct Tag(pub u32);
 
 newtype_enum! {
@@ -25,6 +29,7 @@ impl display Tag {
     EmbeddedPdv = 11,
     Utf8String = 12,
     RelativeOid = 13,
+    Time = 14,
 
     Sequence = 16,
     Set = 17,
@@ -43,7 +48,15 @@ impl display Tag {
     GeneralString = 27,
 
     UniversalString = 28,
+    CharacterString = 29,
     BmpString = 30,
+
+    Date = 31,
+    TimeOfDay = 32,
+    DateTime = 33,
+    Duration = 34,
+    OidIri = 35,
+    RelativeOidIri = 36,
 }
 }
 
@@ -65,6 +78,126 @@ impl Tag {
             msg: msg.to_string(),
         }
     }
+
+    /// Build a tag from a tag number, checking that it is in the supported range
+    ///
+    /// Tag numbers are encoded using the high tag number form (X.690 section 8.1.2.4) without
+    /// size limits, but this crate supports only numbers that fit in a `u32`. This function
+    /// returns `Error::InvalidTag` for larger values.
+    pub fn try_from_u64(n: u64) -> Result<Tag> {
+        u32::try_from(n).map(Tag).or(Err(Error::InvalidTag))
+    }
+
+    /// Return the number of identifier octets used to encode this tag number
+    ///
+    /// Tag numbers up to 30 are encoded on a single octet. Larger numbers use the high tag
+    /// number form (X.690 section 8.1.2.4): one leading octet, followed by the number in base
+    /// 128, 7 bits per octet.
+    pub const fn encoded_len(&self) -> usize {
+        if self.0 <= 30 {
+            return 1;
+        }
+        let mut sz = 2;
+        let mut val = self.0 >> 7;
+        while val > 0 {
+            val >>= 7;
+            sz += 1;
+        }
+        sz
+    }
+
+    /// Return true if this tag number is reserved for future use in the `Universal` class
+    /// (X.680 section 8.6)
+    pub const fn is_universal_reserved(&self) -> bool {
+        self.0 == 15 || self.0 >= 37
+    }
+
+    /// Return the ASN.1 name of the `Universal` type with this tag number, if known
+    ///
+    /// Names are the ones used in X.680 (for ex. `OBJECT IDENTIFIER`, `UTF8String`).
+    pub const fn universal_name(&self) -> Option<&'static str> {
+        let s = match self.0 {
+            0 => "EOC",
+            1 => "BOOLEAN",
+            2 => "INTEGER",
+            3 => "BIT STRING",
+            4 => "OCTET STRING",
+            5 => "NULL",
+            6 => "OBJECT IDENTIFIER",
+            7 => "ObjectDescriptor",
+            8 => "EXTERNAL",
+            9 => "REAL",
+            10 => "ENUMERATED",
+            11 => "EMBEDDED PDV",
+            12 => "UTF8String",
+            13 => "RELATIVE-OID",
+            14 => "TIME",
+            16 => "SEQUENCE",
+            17 => "SET",
+            18 => "NumericString",
+            19 => "PrintableString",
+            20 => "TeletexString",
+            21 => "VideotexString",
+            22 => "IA5String",
+            23 => "UTCTime",
+            24 => "GeneralizedTime",
+            25 => "GraphicString",
+            26 => "VisibleString",
+            27 => "GeneralString",
+            28 => "UniversalString",
+            29 => "CHARACTER STRING",
+            30 => "BMPString",
+            31 => "DATE",
+            32 => "TIME-OF-DAY",
+            33 => "DATE-TIME",
+            34 => "DURATION",
+            35 => "OID-IRI",
+            36 => "RELATIVE-OID-IRI",
+            _ => return None,
+        };
+        Some(s)
+    }
+
+    /// Return an object displaying this tag with its class, in the style of `dumpasn1`
+    ///
+    /// ```rust
+    /// use asn1_rs::{Class, Tag};
+    ///
+    /// let s = Tag::Sequence.display_with_class(Class::Universal).to_string();
+    /// assert_eq!(s, "UNIVERSAL 16 (SEQUENCE)");
+    /// let s = Tag(3).display_with_class(Class::ContextSpecific).to_string();
+    /// assert_eq!(s, "[CONTEXT 3]");
+    /// ```
+    pub const fn display_with_class(self, class: Class) -> TagDisplay {
+        TagDisplay { class, tag: self }
+    }
+}
+
+/// Display a tag with its class, in the style of `dumpasn1`
+///
+/// `Universal` tags are displayed with their number and name (for ex. `UNIVERSAL 16 (SEQUENCE)`),
+/// other tags using the class and number (for ex. `[CONTEXT 3]` or `[APPLICATION 1]`).
+///
+/// See [`Tag::display_with_class`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TagDisplay {
+    class: Class,
+    tag: Tag,
+}
+
+impl fmt::Display for TagDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let n = self.tag.0;
+        match self.class {
+            Class::Universal => match self.tag.universal_name() {
+                Some(name) => write!(f, "UNIVERSAL {} ({})", n, name),
+                None => write!(f, "UNIVERSAL {}", n),
+            },
+            Class::Application => write!(f, "[APPLICATION {}]", n),
+            Class::ContextSpecific => write!(f, "[CONTEXT {}]", n),
+            Class::Private => write!(f, "[PRIVATE {}]", n),
+        }
+    }
 }
 
 impl From<u32> for Tag {
@@ -72,3 +205,80 @@ impl From<u32> for Tag {
         Tag(v)
     }
 }
+
+impl FromStr for Tag {
+    type Err = Error;
+
+    /// Parse the ASN.1 name of a `Universal` type, as returned by [`Tag::universal_name`]
+    ///
+    /// Names are matched ignoring ASCII case, so `OBJECT IDENTIFIER` and `Object Identifier` are
+    /// both accepted. `T61String` is accepted as an alias of `TeletexString`. Returns
+    /// `Error::InvalidTag` if the name is unknown.
+    ///
+    /// ```rust
+    /// use asn1_rs::Tag;
+    ///
+    /// assert_eq!("OBJECT IDENTIFIER".parse::<Tag>(), Ok(Tag::Oid));
+    /// assert_eq!("utf8string".parse::<Tag>(), Ok(Tag::Utf8String));
+    /// ```
+    fn from_str(s: &str) -> Result<Tag> {
+        if s.eq_ignore_ascii_case("T61String") {
+            return Ok(Tag::T61String);
+        }
+        (0..=36)
+            .map(Tag)
+            .find(|tag| matches!(tag.universal_name(), Some(name) if name.eq_ignore_ascii_case(s)))
+            .ok_or(Error::InvalidTag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tag_try_from_u64() {
+        assert_eq!(Tag::try_from_u64(16), Ok(Tag::Sequence));
+        assert_eq!(Tag::try_from_u64(u64::from(u32::MAX)), Ok(Tag(u32::MAX)));
+        assert_eq!(
+            Tag::try_from_u64(u64::from(u32::MAX) + 1),
+            Err(Error::InvalidTag)
+        );
+    }
+
+    #[test]
+    fn tag_reserved() {
+        assert!(!Tag::Sequence.is_universal_reserved());
+        assert!(!Tag(14).is_universal_reserved());
+        assert!(Tag(15).is_universal_reserved());
+        assert!(!Tag(36).is_universal_reserved());
+        assert!(Tag(37).is_universal_reserved());
+    }
+
+    #[test]
+    fn tag_from_str() {
+        for n in 0..=36 {
+            let tag = Tag(n);
+            if let Some(name) = tag.universal_name() {
+                assert_eq!(name.parse::<Tag>(), Ok(tag));
+            }
+        }
+        assert_eq!("SEQUENCE".parse::<Tag>(), Ok(Tag::Sequence));
+        assert_eq!("bit string".parse::<Tag>(), Ok(Tag::BitString));
+        assert_eq!("T61String".parse::<Tag>(), Ok(Tag::TeletexString));
+        assert_eq!("RELATIVE-OID-IRI".parse::<Tag>(), Ok(Tag::RelativeOidIri));
+        assert_eq!("SEQUENCE OF".parse::<Tag>(), Err(Error::InvalidTag));
+        assert_eq!("".parse::<Tag>(), Err(Error::InvalidTag));
+    }
+
+    #[test]
+    fn tag_display_with_class() {
+        let d = |class, n| Tag(n).display_with_class(class).to_string();
+        assert_eq!(d(Class::Universal, 16), "UNIVERSAL 16 (SEQUENCE)");
+        assert_eq!(d(Class::Universal, 6), "UNIVERSAL 6 (OBJECT IDENTIFIER)");
+        assert_eq!(d(Class::Universal, 15), "UNIVERSAL 15");
+        assert_eq!(d(Class::ContextSpecific, 3), "[CONTEXT 3]");
+        assert_eq!(d(Class::Application, 1), "[APPLICATION 1]");
+        assert_eq!(d(Class::Private, 1000), "[PRIVATE 1000]");
+    }
+}
@@ -0,0 +1,454 @@
+//! Shared corpus of encodings, for interoperability tests
+//!
+//! This module exposes encoded objects taken from specifications and common protocols, with
+//! the value they are expected to decode to. Downstream crates (other parsers or encoders) can
+//! use them to check that they agree with this crate on a shared corpus.
+//!
+//! Vectors are grouped by source:
+//! - [`X690_VECTORS`]: examples from X.690 (simple types, sequences and tagging)
+//! - [`RFC5280_VECTORS`]: parts of X.509 certificates (algorithm identifiers, names, validity)
+//! - [`KERBEROS_VECTORS`]: Kerberos types (RFC 4120)
+//! - [`SNMP_VECTORS`]: SNMP messages (RFC 1157)
+//!
+//! # Examples
+//!
+//! ```rust
+//! use asn1_rs::test_vectors;
+//!
+//! for vector in test_vectors::all() {
+//!     // decode `vector.encoding` using another implementation, and compare with
+//!     // `vector.expected`. Here, check that this crate decodes it as expected:
+//!     assert!(vector.check().is_ok(), "vector {} failed", vector.name);
+//! }
+//! ```
+
+use crate::*;
+use core::convert::TryFrom;
+
+/// Expected decoded value of an encoded object
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Expected {
+    Boolean(bool),
+    Integer(i64),
+    BitString {
+        unused_bits: u8,
+        data: &'static [u8],
+    },
+    OctetString(&'static [u8]),
+    Null,
+    /// An `OBJECT IDENTIFIER`, in dotted form
+    Oid(&'static str),
+    Utf8String(&'static str),
+    PrintableString(&'static str),
+    Ia5String(&'static str),
+    VisibleString(&'static str),
+    GeneralString(&'static str),
+    /// A `UTCTime`, as the string content
+    UtcTime(&'static str),
+    /// A `GeneralizedTime`, as the string content
+    GeneralizedTime(&'static str),
+    Sequence(&'static [Expected]),
+    Set(&'static [Expected]),
+    /// A constructed object of any class (for ex. an explicit tag, or an implicitly tagged
+    /// `SEQUENCE`), with its sub-objects
+    Constructed {
+        class: Class,
+        tag: u32,
+        items: &'static [Expected],
+    },
+    /// A primitive object of any class (for ex. an implicitly tagged string), with its content
+    Primitive {
+        class: Class,
+        tag: u32,
+        data: &'static [u8],
+    },
+}
+
+/// An encoded object, with its source and expected value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TestVector {
+    /// Short name of this vector
+    pub name: &'static str,
+    /// Source of this vector (specification and section)
+    pub reference: &'static str,
+    /// The encoded object
+    pub encoding: &'static [u8],
+    /// True if the encoding is valid DER (otherwise, it is only valid BER)
+    pub is_der: bool,
+    /// The expected decoded value
+    pub expected: Expected,
+}
+
+impl TestVector {
+    /// Check that this crate decodes the vector to the expected value
+    ///
+    /// The encoding is parsed using DER if [`is_der`](TestVector::is_der) is true, otherwise
+    /// using BER. All input must be consumed.
+    pub fn check(&self) -> Result<()> {
+        let (rem, any) = if self.is_der {
+            Any::from_der(self.encoding)?
+        } else {
+            Any::from_ber(self.encoding)?
+        };
+        if !rem.is_empty() {
+            return Err(Error::TrailingContent {
+                tag: any.tag(),
+                remaining: rem.len(),
+            });
+        }
+        self.expected.check(&any)
+    }
+}
+
+impl Expected {
+    /// Check that `any` has the expected type and value
+    pub fn check(&self, any: &Any) -> Result<()> {
+        let ok = match *self {
+            Expected::Boolean(b) => bool::try_from(any)? == b,
+            Expected::Integer(i) => i64::try_from(any)? == i,
+            Expected::BitString { unused_bits, data } => {
+                let b = BitString::try_from(any)?;
+                b.unused_bits == unused_bits && &*b.data == data
+            }
+            Expected::OctetString(data) => OctetString::try_from(any)?.as_ref() == data,
+            Expected::Null => Null::try_from(any).is_ok(),
+            Expected::Oid(s) => {
+                any.tag().assert_eq(Tag::Oid)?;
+                Oid::try_from(any)?.to_id_string() == s
+            }
+            Expected::Utf8String(s) => Utf8String::try_from(any)?.as_ref() == s,
+            Expected::PrintableString(s) => PrintableString::try_from(any)?.as_ref() == s,
+            Expected::Ia5String(s) => Ia5String::try_from(any)?.as_ref() == s,
+            Expected::VisibleString(s) => VisibleString::try_from(any)?.as_ref() == s,
+            Expected::GeneralString(s) => GeneralString::try_from(any)?.as_ref() == s,
+            Expected::UtcTime(s) => {
+                UtcTime::try_from(any)?;
+                any.data == s.as_bytes()
+            }
+            Expected::GeneralizedTime(s) => {
+                GeneralizedTime::try_from(any)?;
+                any.data == s.as_bytes()
+            }
+            Expected::Sequence(items) => {
+                return check_constructed(any, Class::Universal, Tag::Sequence, items)
+            }
+            Expected::Set(items) => {
+                return check_constructed(any, Class::Universal, Tag::Set, items)
+            }
+            Expected::Constructed { class, tag, items } => {
+                return check_constructed(any, class, Tag(tag), items)
+            }
+            Expected::Primitive { class, tag, data } => {
+                any.header.assert_class(class)?;
+                any.tag().assert_eq(Tag(tag))?;
+                any.header.assert_primitive()?;
+                any.data == data
+            }
+        };
+        if ok {
+            Ok(())
+        } else {
+            Err(any.tag().invalid_value("value differs from test vector"))
+        }
+    }
+}
+
+fn check_constructed(any: &Any, class: Class, tag: Tag, items: &[Expected]) -> Result<()> {
+    any.header.assert_class(class)?;
+    any.tag().assert_eq(tag)?;
+    any.header.assert_constructed()?;
+    let mut data = any.data;
+    for item in items {
+        let (rem, obj) = Any::from_ber(data)?;
+        item.check(&obj)?;
+        data = rem;
+    }
+    if !data.is_empty() {
+        return Err(Error::TrailingContent {
+            tag,
+            remaining: data.len(),
+        });
+    }
+    Ok(())
+}
+
+/// Examples from X.690
+pub const X690_VECTORS: &[TestVector] = &[
+    TestVector {
+        name: "boolean-true",
+        reference: "X.690 section 11.1",
+        encoding: &[0x01, 0x01, 0xff],
+        is_der: true,
+        expected: Expected::Boolean(true),
+    },
+    TestVector {
+        name: "boolean-true-ber",
+        reference: "X.690 section 8.2.2",
+        encoding: &[0x01, 0x01, 0x01],
+        is_der: false,
+        expected: Expected::Boolean(true),
+    },
+    TestVector {
+        name: "integer-negative",
+        reference: "X.690 section 8.3",
+        encoding: &[0x02, 0x02, 0xff, 0x7f],
+        is_der: true,
+        expected: Expected::Integer(-129),
+    },
+    TestVector {
+        name: "bitstring",
+        reference: "X.690 section 8.6.4.2",
+        encoding: &[0x03, 0x07, 0x04, 0x0a, 0x3b, 0x5f, 0x29, 0x1c, 0xd0],
+        is_der: true,
+        expected: Expected::BitString {
+            unused_bits: 4,
+            data: &[0x0a, 0x3b, 0x5f, 0x29, 0x1c, 0xd0],
+        },
+    },
+    TestVector {
+        name: "null",
+        reference: "X.690 section 8.8.2",
+        encoding: &[0x05, 0x00],
+        is_der: true,
+        expected: Expected::Null,
+    },
+    TestVector {
+        name: "sequence",
+        reference: "X.690 section 8.9.3",
+        encoding: &[
+            0x30, 0x0a, 0x16, 0x05, 0x53, 0x6d, 0x69, 0x74, 0x68, 0x01, 0x01, 0xff,
+        ],
+        is_der: true,
+        expected: Expected::Sequence(&[Expected::Ia5String("Smith"), Expected::Boolean(true)]),
+    },
+    TestVector {
+        name: "tagged-type1",
+        reference: "X.690 section 8.14.3",
+        encoding: &[0x1a, 0x05, 0x4a, 0x6f, 0x6e, 0x65, 0x73],
+        is_der: true,
+        expected: Expected::VisibleString("Jones"),
+    },
+    TestVector {
+        name: "tagged-type2",
+        reference: "X.690 section 8.14.3",
+        encoding: &[0x43, 0x05, 0x4a, 0x6f, 0x6e, 0x65, 0x73],
+        is_der: true,
+        expected: Expected::Primitive {
+            class: Class::Application,
+            tag: 3,
+            data: b"Jones",
+        },
+    },
+    TestVector {
+        name: "tagged-type3",
+        reference: "X.690 section 8.14.3",
+        encoding: &[0xa2, 0x07, 0x43, 0x05, 0x4a, 0x6f, 0x6e, 0x65, 0x73],
+        is_der: true,
+        expected: Expected::Constructed {
+            class: Class::ContextSpecific,
+            tag: 2,
+            items: &[Expected::Primitive {
+                class: Class::Application,
+                tag: 3,
+                data: b"Jones",
+            }],
+        },
+    },
+    TestVector {
+        name: "tagged-type4",
+        reference: "X.690 section 8.14.3",
+        encoding: &[0x67, 0x07, 0x43, 0x05, 0x4a, 0x6f, 0x6e, 0x65, 0x73],
+        is_der: true,
+        expected: Expected::Constructed {
+            class: Class::Application,
+            tag: 7,
+            items: &[Expected::Primitive {
+                class: Class::Application,
+                tag: 3,
+                data: b"Jones",
+            }],
+        },
+    },
+    TestVector {
+        name: "tagged-type5",
+        reference: "X.690 section 8.14.3",
+        encoding: &[0x82, 0x05, 0x4a, 0x6f, 0x6e, 0x65, 0x73],
+        is_der: true,
+        expected: Expected::Primitive {
+            class: Class::ContextSpecific,
+            tag: 2,
+            data: b"Jones",
+        },
+    },
+];
+
+/// Parts of X.509 certificates (RFC 5280)
+pub const RFC5280_VECTORS: &[TestVector] = &[
+    TestVector {
+        name: "version-v3",
+        reference: "RFC 5280 section 4.1.2.1",
+        encoding: &[0xa0, 0x03, 0x02, 0x01, 0x02],
+        is_der: true,
+        expected: Expected::Constructed {
+            class: Class::ContextSpecific,
+            tag: 0,
+            items: &[Expected::Integer(2)],
+        },
+    },
+    TestVector {
+        name: "algorithm-sha256-rsa",
+        reference: "RFC 5280 section 4.1.1.2, RFC 4055 section 5",
+        encoding: &[
+            0x30, 0x0d, 0x06, 0x09, 0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0b, 0x05,
+            0x00,
+        ],
+        is_der: true,
+        expected: Expected::Sequence(&[Expected::Oid("1.2.840.113549.1.1.11"), Expected::Null]),
+    },
+    TestVector {
+        name: "rdn-common-name",
+        reference: "RFC 5280 section 4.1.2.4",
+        encoding: &[
+            0x31, 0x0d, 0x30, 0x0b, 0x06, 0x03, 0x55, 0x04, 0x03, 0x0c, 0x04, 0x74, 0x65, 0x73,
+            0x74,
+        ],
+        is_der: true,
+        expected: Expected::Set(&[Expected::Sequence(&[
+            Expected::Oid("2.5.4.3"),
+            Expected::Utf8String("test"),
+        ])]),
+    },
+    TestVector {
+        name: "rdn-country",
+        reference: "RFC 5280 section 4.1.2.4",
+        encoding: &[
+            0x31, 0x0b, 0x30, 0x09, 0x06, 0x03, 0x55, 0x04, 0x06, 0x13, 0x02, 0x46, 0x52,
+        ],
+        is_der: true,
+        expected: Expected::Set(&[Expected::Sequence(&[
+            Expected::Oid("2.5.4.6"),
+            Expected::PrintableString("FR"),
+        ])]),
+    },
+    TestVector {
+        name: "validity",
+        reference: "RFC 5280 section 4.1.2.5",
+        encoding: &[
+            0x30, 0x1e, 0x17, 0x0d, 0x32, 0x35, 0x30, 0x31, 0x30, 0x31, 0x30, 0x30, 0x30, 0x30,
+            0x30, 0x30, 0x5a, 0x17, 0x0d, 0x33, 0x35, 0x30, 0x31, 0x30, 0x31, 0x30, 0x30, 0x30,
+            0x30, 0x30, 0x30, 0x5a,
+        ],
+        is_der: true,
+        expected: Expected::Sequence(&[
+            Expected::UtcTime("250101000000Z"),
+            Expected::UtcTime("350101000000Z"),
+        ]),
+    },
+    TestVector {
+        name: "basic-constraints-ca",
+        reference: "RFC 5280 section 4.2.1.9",
+        encoding: &[0x30, 0x03, 0x01, 0x01, 0xff],
+        is_der: true,
+        expected: Expected::Sequence(&[Expected::Boolean(true)]),
+    },
+];
+
+/// Kerberos types (RFC 4120)
+pub const KERBEROS_VECTORS: &[TestVector] = &[
+    TestVector {
+        name: "realm",
+        reference: "RFC 4120 section 5.2.2",
+        encoding: &[0x1b, 0x06, 0x41, 0x54, 0x48, 0x45, 0x4e, 0x41],
+        is_der: true,
+        expected: Expected::GeneralString("ATHENA"),
+    },
+    TestVector {
+        name: "principal-name",
+        reference: "RFC 4120 section 5.2.2",
+        encoding: &[
+            0x30, 0x0f, 0xa0, 0x03, 0x02, 0x01, 0x01, 0xa1, 0x08, 0x30, 0x06, 0x1b, 0x04, 0x75,
+            0x73, 0x65, 0x72,
+        ],
+        is_der: true,
+        expected: Expected::Sequence(&[
+            Expected::Constructed {
+                class: Class::ContextSpecific,
+                tag: 0,
+                items: &[Expected::Integer(1)],
+            },
+            Expected::Constructed {
+                class: Class::ContextSpecific,
+                tag: 1,
+                items: &[Expected::Sequence(&[Expected::GeneralString("user")])],
+            },
+        ]),
+    },
+    TestVector {
+        name: "kerberos-time",
+        reference: "RFC 4120 section 5.2.3",
+        encoding: &[
+            0x18, 0x0f, 0x32, 0x30, 0x32, 0x34, 0x30, 0x31, 0x30, 0x31, 0x30, 0x30, 0x30, 0x30,
+            0x30, 0x30, 0x5a,
+        ],
+        is_der: true,
+        expected: Expected::GeneralizedTime("20240101000000Z"),
+    },
+];
+
+/// SNMP messages (RFC 1157)
+pub const SNMP_VECTORS: &[TestVector] = &[TestVector {
+    name: "get-request-sysdescr",
+    reference: "RFC 1157 section 4.1.2",
+    encoding: &[
+        0x30, 0x26, 0x02, 0x01, 0x00, 0x04, 0x06, 0x70, 0x75, 0x62, 0x6c, 0x69, 0x63, 0xa0, 0x19,
+        0x02, 0x01, 0x01, 0x02, 0x01, 0x00, 0x02, 0x01, 0x00, 0x30, 0x0e, 0x30, 0x0c, 0x06, 0x08,
+        0x2b, 0x06, 0x01, 0x02, 0x01, 0x01, 0x01, 0x00, 0x05, 0x00,
+    ],
+    is_der: true,
+    expected: Expected::Sequence(&[
+        Expected::Integer(0),
+        Expected::OctetString(b"public"),
+        Expected::Constructed {
+            class: Class::ContextSpecific,
+            tag: 0,
+            items: &[
+                Expected::Integer(1),
+                Expected::Integer(0),
+                Expected::Integer(0),
+                Expected::Sequence(&[Expected::Sequence(&[
+                    Expected::Oid("1.3.6.1.2.1.1.1.0"),
+                    Expected::Null,
+                ])]),
+            ],
+        },
+    ]),
+}];
+
+/// Iterate over all test vectors
+pub fn all() -> impl Iterator<Item = &'static TestVector> {
+    X690_VECTORS
+        .iter()
+        .chain(RFC5280_VECTORS)
+        .chain(KERBEROS_VECTORS)
+        .chain(SNMP_VECTORS)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_vectors::*;
+
+    #[test]
+    fn test_vectors_decode() {
+        for vector in all() {
+            assert_eq!(vector.check(), Ok(()), "vector {}", vector.name);
+        }
+        // BER-only vectors are rejected by DER parsers
+        assert!(bool::from_der(X690_VECTORS[1].encoding).is_err());
+        // a wrong expected value is reported
+        let mut vector = X690_VECTORS[0];
+        vector.expected = Expected::Boolean(false);
+        assert!(vector.check().is_err());
+        vector.expected = Expected::Integer(1);
+        assert!(vector.check().is_err());
+    }
+}
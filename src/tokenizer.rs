@@ -0,0 +1,355 @@
+//! Pull parser producing tag-length-value events
+//!
+//! [`TlvTokenizer`] reads BER objects from a byte slice and returns a flat sequence of events,
+//! without building objects: constructed objects produce a [`TlvEvent::BeginConstructed`] and a
+//! matching [`TlvEvent::EndConstructed`] event, with the events of their content in between.
+//!
+//! This is the lowest-level parsing API of this crate. It does not allocate (except for the
+//! stack of open constructed objects), and can be used to build alternative high-level APIs, or
+//! consumers processing objects as they are read. BER parsing of [`Any`](crate::Any) uses it to
+//! find the end of objects with an indefinite length.
+
+use crate::ber::MAX_RECURSION;
+use crate::fast;
+use crate::{Class, Error, FromBer, Header, Length, Result, Tag};
+use alloc::vec::Vec;
+use nom::Needed;
+
+/// An event returned by [`TlvTokenizer`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum TlvEvent<'a> {
+    /// Start of a constructed object (its content follows)
+    BeginConstructed(Header<'a>),
+    /// A primitive object
+    Primitive { header: Header<'a>, data: &'a [u8] },
+    /// End of the last constructed object
+    EndConstructed,
+    /// End-of-content marker (`00 00`), always followed by [`TlvEvent::EndConstructed`]
+    ///
+    /// The marker is only accepted in objects with indefinite length, otherwise the tokenizer
+    /// returns [`Error::UnexpectedEndOfContent`].
+    Eoc,
+}
+
+/// A constructed object being read
+#[derive(Clone, Copy, Debug)]
+struct Frame {
+    /// Offset of the end of the content, or `None` if length is indefinite and no
+    /// end-of-content marker was read yet
+    end: Option<usize>,
+    /// Offset that the content cannot exceed (the end of the closest enclosing object with a
+    /// definite length, or the end of the input)
+    limit: usize,
+}
+
+/// Pull parser returning tag-length-value events from BER input
+///
+/// The tokenizer implements `Iterator`. After returning an error, it returns `None`.
+///
+/// Indefinite lengths are accepted for constructed objects, and must be terminated by an
+/// end-of-content marker. The content of primitive objects is not validated.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{Tag, TlvEvent, TlvTokenizer};
+///
+/// // SEQUENCE { INTEGER 2, NULL }
+/// let bytes = &[0x30, 0x05, 0x02, 0x01, 0x02, 0x05, 0x00];
+/// let mut tokenizer = TlvTokenizer::new(bytes);
+/// assert!(matches!(tokenizer.next(), Some(Ok(TlvEvent::BeginConstructed(h))) if h.tag() == Tag::Sequence));
+/// assert!(matches!(tokenizer.next(), Some(Ok(TlvEvent::Primitive { data: &[2], .. }))));
+/// assert_eq!(tokenizer.depth(), 1);
+/// assert!(matches!(tokenizer.next(), Some(Ok(TlvEvent::Primitive { data: &[], .. }))));
+/// assert_eq!(tokenizer.next(), Some(Ok(TlvEvent::EndConstructed)));
+/// assert_eq!(tokenizer.next(), None);
+/// ```
+#[derive(Clone, Debug)]
+pub struct TlvTokenizer<'a> {
+    input: &'a [u8],
+    offset: usize,
+    stack: Vec<Frame>,
+    max_depth: usize,
+    done: bool,
+}
+
+impl<'a> TlvTokenizer<'a> {
+    /// Build a tokenizer for `input`, using the default recursion limit
+    pub const fn new(input: &'a [u8]) -> Self {
+        Self::with_max_depth(input, MAX_RECURSION)
+    }
+
+    /// Build a tokenizer for `input`, returning [`Error::BerMaxDepth`] if constructed objects
+    /// are nested more than `max_depth` times
+    pub const fn with_max_depth(input: &'a [u8], max_depth: usize) -> Self {
+        TlvTokenizer {
+            input,
+            offset: 0,
+            stack: Vec::new(),
+            max_depth,
+            done: false,
+        }
+    }
+
+    /// Build a tokenizer for the content of a constructed object with indefinite length
+    ///
+    /// The object is already open: the tokenizer ends with the [`TlvEvent::EndConstructed`]
+    /// event following its end-of-content marker.
+    pub(crate) fn for_indefinite_content(input: &'a [u8], max_depth: usize) -> Self {
+        let mut tokenizer = Self::with_max_depth(input, max_depth);
+        tokenizer.stack.push(Frame {
+            end: None,
+            limit: input.len(),
+        });
+        tokenizer
+    }
+
+    /// Skip the content of the innermost open constructed object
+    ///
+    /// The next event is the [`TlvEvent::EndConstructed`] event of this object. Objects with a
+    /// definite length are skipped without reading their content, so only the headers of
+    /// objects with an indefinite length (and of their children) are read.
+    ///
+    /// ```rust
+    /// use asn1_rs::{TlvEvent, TlvTokenizer};
+    ///
+    /// // SEQUENCE (indefinite) { SEQUENCE { NULL } }, NULL
+    /// let bytes = &[0x30, 0x80, 0x30, 0x02, 0x05, 0x00, 0x00, 0x00, 0x05, 0x00];
+    /// let mut tokenizer = TlvTokenizer::new(bytes);
+    /// assert!(matches!(tokenizer.next(), Some(Ok(TlvEvent::BeginConstructed(_)))));
+    /// tokenizer.skip_content().expect("invalid content");
+    /// assert_eq!(tokenizer.offset(), 8);
+    /// assert_eq!(tokenizer.next(), Some(Ok(TlvEvent::EndConstructed)));
+    /// assert!(matches!(tokenizer.next(), Some(Ok(TlvEvent::Primitive { .. }))));
+    /// ```
+    pub fn skip_content(&mut self) -> Result<()> {
+        let depth = self.stack.len();
+        if self.done || depth == 0 {
+            return Ok(());
+        }
+        loop {
+            if let Some(Frame { end: Some(end), .. }) = self.stack.last() {
+                // definite length, or end-of-content marker already read
+                self.offset = *end;
+                if self.stack.len() == depth {
+                    return Ok(());
+                }
+            }
+            if let Err(e) = self.next_event() {
+                self.done = true;
+                return Err(e);
+            }
+        }
+    }
+
+    /// Return the offset of the next event, relative to the start of the input
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Return the number of constructed objects currently open
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Return the unread part of the input
+    pub fn remaining(&self) -> &'a [u8] {
+        &self.input[self.offset..]
+    }
+
+    fn fail(&mut self, e: Error) -> Option<Result<TlvEvent<'a>>> {
+        self.done = true;
+        Some(Err(e))
+    }
+
+    fn next_event(&mut self) -> Result<Option<TlvEvent<'a>>> {
+        // end of the enclosing object
+        let limit = match self.stack.last() {
+            Some(Frame { end: Some(end), .. }) if *end == self.offset => {
+                self.stack.pop();
+                return Ok(Some(TlvEvent::EndConstructed));
+            }
+            Some(Frame { end: Some(end), .. }) => *end,
+            Some(Frame { end: None, limit }) => *limit,
+            None => self.input.len(),
+        };
+        if self.offset == limit {
+            if self.stack.is_empty() {
+                return Ok(None);
+            }
+            // missing end-of-content marker
+            return Err(Error::Incomplete(Needed::new(2)));
+        }
+        let i = &self.input[self.offset..limit];
+        let (rem, header) = Header::from_ber(i)?;
+        let hdr_len = i.len() - rem.len();
+        let start = self.offset;
+        self.offset += hdr_len;
+        if header.class() == Class::Universal && header.tag() == Tag::EndOfContent {
+            if !header.is_end_of_content() {
+                self.offset = start;
+                return Err(Error::InvalidEndOfContent);
+            }
+            match self.stack.last_mut() {
+                Some(frame) if frame.end.is_none() => frame.end = Some(self.offset),
+                _ => {
+                    self.offset = start;
+                    return Err(Error::UnexpectedEndOfContent);
+                }
+            }
+            return Ok(Some(TlvEvent::Eoc));
+        }
+        if header.is_constructed() {
+            if self.stack.len() >= self.max_depth {
+                self.offset = start;
+                return Err(Error::BerMaxDepth);
+            }
+            let end = match header.length() {
+                Length::Definite(l) if l > rem.len() => {
+                    self.offset = start;
+                    return Err(Error::Incomplete(Needed::new(l - rem.len())));
+                }
+                Length::Definite(l) => Some(self.offset + l),
+                Length::Indefinite => None,
+            };
+            self.stack.push(Frame {
+                end,
+                limit: end.unwrap_or(limit),
+            });
+            return Ok(Some(TlvEvent::BeginConstructed(header)));
+        }
+        let l = match header.length() {
+            Length::Definite(l) => l,
+            Length::Indefinite => {
+                self.offset = start;
+                return Err(Error::ConstructExpected);
+            }
+        };
+        if l > rem.len() {
+            self.offset = start;
+            return Err(Error::Incomplete(Needed::new(l - rem.len())));
+        }
+        // `l <= rem.len()` was checked above
+        let data = fast::prefix(rem, l);
+        self.offset += l;
+        Ok(Some(TlvEvent::Primitive { header, data }))
+    }
+}
+
+impl<'a> Iterator for TlvTokenizer<'a> {
+    type Item = Result<TlvEvent<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.next_event() {
+            Ok(Some(event)) => Some(Ok(event)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(e) => self.fail(e),
+        }
+    }
+}
+
+impl core::iter::FusedIterator for TlvTokenizer<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use hex_literal::hex;
+
+    fn tags(bytes: &[u8]) -> Vec<core::result::Result<(char, u32), Error>> {
+        TlvTokenizer::new(bytes)
+            .map(|r| {
+                r.map(|e| match e {
+                    TlvEvent::BeginConstructed(h) => ('B', h.tag().0),
+                    TlvEvent::Primitive { header, .. } => ('P', header.tag().0),
+                    TlvEvent::EndConstructed => ('E', 0),
+                    TlvEvent::Eoc => ('Z', 0),
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn tokenizer_nested() {
+        // SEQUENCE { SET { INTEGER 1 }, SEQUENCE (indefinite) { NULL } }, BOOLEAN
+        let bytes = &hex!("30 0b 31 03 02 01 01 30 80 05 00 00 00 01 01 ff");
+        assert_eq!(
+            tags(bytes),
+            vec![
+                Ok(('B', 16)),
+                Ok(('B', 17)),
+                Ok(('P', 2)),
+                Ok(('E', 0)),
+                Ok(('B', 16)),
+                Ok(('P', 5)),
+                Ok(('Z', 0)),
+                Ok(('E', 0)),
+                Ok(('E', 0)),
+                Ok(('P', 1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn tokenizer_errors() {
+        // truncated primitive
+        let bytes = &hex!("30 03 02 03 01");
+        let mut t = TlvTokenizer::new(bytes);
+        assert!(matches!(t.next(), Some(Ok(TlvEvent::BeginConstructed(_)))));
+        assert_eq!(t.next(), Some(Err(Error::Incomplete(Needed::new(2)))));
+        assert_eq!(t.offset(), 2);
+        assert_eq!(t.next(), None);
+        // missing end-of-content
+        let bytes = &hex!("30 80 05 00");
+        let res = TlvTokenizer::new(bytes).last();
+        assert_eq!(res, Some(Err(Error::Incomplete(Needed::new(2)))));
+        // child larger than parent
+        let bytes = &hex!("30 02 04 03 61 62 63");
+        let res = TlvTokenizer::new(bytes).last();
+        assert_eq!(res, Some(Err(Error::Incomplete(Needed::new(3)))));
+        // recursion limit
+        let bytes = &hex!("30 04 30 02 30 00");
+        let res = TlvTokenizer::with_max_depth(bytes, 2).last();
+        assert_eq!(res, Some(Err(Error::BerMaxDepth)));
+        // end-of-content in definite length object, or at top level
+        let bytes = &hex!("30 04 05 00 00 00");
+        let res = TlvTokenizer::new(bytes).last();
+        assert_eq!(res, Some(Err(Error::UnexpectedEndOfContent)));
+        let res = TlvTokenizer::new(&hex!("00 00")).last();
+        assert_eq!(res, Some(Err(Error::UnexpectedEndOfContent)));
+        // invalid end-of-content
+        let res = TlvTokenizer::new(&hex!("30 80 00 01 00")).last();
+        assert_eq!(res, Some(Err(Error::InvalidEndOfContent)));
+        // indefinite length child, with its end-of-content after the end of the parent
+        let bytes = &hex!("30 02 30 80 05 00 00 00");
+        let res = TlvTokenizer::new(bytes).last();
+        assert_eq!(res, Some(Err(Error::Incomplete(Needed::new(2)))));
+    }
+
+    #[test]
+    fn tokenizer_skip_content() {
+        // SEQUENCE (indefinite) { SEQUENCE { truncated INTEGER }, SET (indefinite) { NULL } }
+        let bytes = &hex!("30 80 30 02 02 05 31 80 05 00 00 00 00 00 01 01 ff");
+        let mut t = TlvTokenizer::new(bytes);
+        assert!(matches!(t.next(), Some(Ok(TlvEvent::BeginConstructed(_)))));
+        // the content of the definite length SEQUENCE is not read
+        assert_eq!(t.skip_content(), Ok(()));
+        assert_eq!((t.offset(), t.depth()), (14, 1));
+        assert_eq!(tags(t.remaining()), vec![Ok(('P', 1))]);
+        assert_eq!(t.next(), Some(Ok(TlvEvent::EndConstructed)));
+        assert!(matches!(t.next(), Some(Ok(TlvEvent::Primitive { .. }))));
+        // nothing open
+        assert_eq!(t.skip_content(), Ok(()));
+        assert_eq!(t.next(), None);
+        // missing end-of-content
+        let mut t = TlvTokenizer::new(&hex!("30 80 31 80 00 00"));
+        t.next();
+        assert_eq!(t.skip_content(), Err(Error::Incomplete(Needed::new(2))));
+        assert_eq!(t.next(), None);
+    }
+}
@@ -0,0 +1,706 @@
+//! Common operations on encoded data, for scripts and command-line tools
+//!
+//! This module provides the operations usually written as glue code around this crate:
+//! - [`dump`]: print the structure of BER/DER data, in the style of `dumpasn1` (see also
+//!   [`dump_with_registry`], to decode additional universal tags)
+//! - [`validate_der`]: check that data is valid DER
+//! - [`ber_to_der`]: re-encode BER data using DER
+//! - [`extract_path`]: extract an object using a [path]
+//! - [`der_diff`]: describe the first difference between two encodings (see also
+//!   [`assert_der_eq`])
+//!
+//! The same operations are available from the command line, using the `asn1-tool` binary
+//! (feature `cli`).
+//!
+//! # Examples
+//!
+//! ```rust
+//! use asn1_rs::tools;
+//!
+//! // SEQUENCE (indefinite) { BOOLEAN TRUE (non-canonical), INTEGER 2 }
+//! let ber = &[0x30, 0x80, 0x01, 0x01, 0x01, 0x02, 0x01, 0x02, 0x00, 0x00];
+//! assert!(tools::validate_der(ber).is_err());
+//! let der = tools::ber_to_der(ber).expect("conversion failed");
+//! assert_eq!(&der, &[0x30, 0x06, 0x01, 0x01, 0xff, 0x02, 0x01, 0x02]);
+//! assert_eq!(tools::validate_der(&der), Ok(3));
+//! print!("{}", tools::dump(&der).expect("invalid data"));
+//! ```
+
+use crate::asn1_types::check_der_tree;
+use crate::ber::MAX_RECURSION;
+use crate::profile::is_string;
+use crate::*;
+use alloc::borrow::Cow;
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::fmt::Write as _;
+use nom::Offset;
+
+/// Maximum number of content bytes displayed by [`dump`] for a primitive object
+const DUMP_MAX_BYTES: usize = 16;
+
+/// Return a textual description of the structure of BER/DER data
+///
+/// Each line contains the offset of the object, its content length, and its tag, indented
+/// according to the depth. Content of primitive objects is displayed as a string, an OID, or in
+/// hexadecimal (truncated).
+///
+/// ```rust
+/// use asn1_rs::tools::dump;
+///
+/// let s = dump(&[0x30, 0x05, 0x06, 0x03, 0x2a, 0x03, 0x04]).expect("invalid data");
+/// assert_eq!(s, "    0    5: SEQUENCE {\n    2    3:   OBJECT IDENTIFIER 1.2.3.4\n          : }\n");
+/// ```
+pub fn dump(input: &[u8]) -> Result<String> {
+    dump_with_registry(input, &TagRegistry::new())
+}
+
+/// Return a textual description of the structure of BER/DER data, using the decoders of
+/// `registry`
+///
+/// This is the same as [`dump`], except that primitive universal objects with a tag registered
+/// in `registry` are displayed using the name and the value returned by the handler. If the
+/// handler fails, the content is displayed in hexadecimal.
+///
+/// ```rust
+/// use asn1_rs::tools::dump_with_registry;
+/// use asn1_rs::{Tag, TagRegistry};
+///
+/// fn decode_ascii(data: &[u8]) -> asn1_rs::Result<String> {
+///     Ok(format!("'{}'", std::str::from_utf8(data)?))
+/// }
+///
+/// let mut registry = TagRegistry::new();
+/// registry.register(Tag(29), "CHARACTER STRING", decode_ascii);
+/// let s = dump_with_registry(&[0x1d, 0x02, 0x61, 0x62], &registry).expect("invalid data");
+/// assert_eq!(s, "    0    2: CHARACTER STRING 'ab'\n");
+/// ```
+pub fn dump_with_registry(input: &[u8], registry: &TagRegistry) -> Result<String> {
+    let mut s = String::new();
+    let mut tokenizer = TlvTokenizer::new(input);
+    loop {
+        let offset = tokenizer.offset();
+        let depth = tokenizer.depth();
+        let event = match tokenizer.next() {
+            Some(event) => event?,
+            None => break,
+        };
+        let (header, data) = match event {
+            TlvEvent::BeginConstructed(header) => (header, None),
+            TlvEvent::Primitive { header, data } => (header, Some(data)),
+            TlvEvent::EndConstructed => {
+                let _ = writeln!(s, "{:>10}: {:indent$}}}", "", "", indent = 2 * depth - 2);
+                continue;
+            }
+            TlvEvent::Eoc => continue,
+        };
+        let len = match header.length() {
+            Length::Definite(l) => l.to_string(),
+            Length::Indefinite => "inf".to_string(),
+        };
+        let _ = write!(
+            s,
+            "{:>5} {:>4}: {:indent$}",
+            offset,
+            len,
+            "",
+            indent = 2 * depth
+        );
+        let handler = registry.handler_for(&header);
+        match (handler, header.class(), header.tag().universal_name()) {
+            (Some(handler), _, _) => s.push_str(handler.name),
+            (None, Class::Universal, Some(name)) => s.push_str(name),
+            (None, class, _) => {
+                let _ = write!(s, "{}", header.tag().display_with_class(class));
+            }
+        }
+        match data {
+            None => s.push_str(" {"),
+            Some(data) => {
+                let value = handler
+                    .and_then(|h| (h.decode)(data).ok())
+                    .or_else(|| dump_value(&header, data));
+                if let Some(value) = value {
+                    s.push(' ');
+                    s.push_str(&value);
+                }
+            }
+        }
+        s.push('\n');
+    }
+    Ok(s)
+}
+
+fn dump_value(header: &Header, data: &[u8]) -> Option<String> {
+    if data.is_empty() {
+        return None;
+    }
+    if header.is_universal() {
+        match header.tag() {
+            Tag::Oid => return Some(Oid::new(Cow::Borrowed(data)).to_id_string()),
+            Tag::Utf8String
+            | Tag::NumericString
+            | Tag::PrintableString
+            | Tag::Ia5String
+            | Tag::VisibleString
+            | Tag::UtcTime
+            | Tag::GeneralizedTime => {
+                if let Ok(s) = core::str::from_utf8(data) {
+                    return Some(format!("'{}'", s));
+                }
+            }
+            _ => (),
+        }
+    }
+    let mut s = String::new();
+    for b in data.iter().take(DUMP_MAX_BYTES) {
+        let _ = write!(s, "{:02X}", b);
+    }
+    if data.len() > DUMP_MAX_BYTES {
+        s.push_str("...");
+    }
+    Some(s)
+}
+
+/// Check that input is a list of valid DER objects, and return the number of objects
+///
+/// All objects are parsed recursively. For universal types, the DER constraints of the type
+/// are checked (for ex. boolean value, integer encoding, unused bits of bit strings), and the
+/// items of `SET` and `SET OF` objects must be sorted (see [`SetOrder`] for the rules).
+pub fn validate_der(input: &[u8]) -> Result<usize> {
+    let mut count = 0;
+    let mut visit = |any: &Any| {
+        count += 1;
+        if any.header.is_universal() && any.header.is_constructed() && any.tag() == Tag::Set {
+            check_set_order(any.data)?;
+        }
+        Ok(())
+    };
+    let mut i = input;
+    while !i.is_empty() {
+        let (rem, any) = Any::from_der(i)?;
+        check_der_tree(&any, DerCheckContext::new(), &mut visit)?;
+        i = rem;
+    }
+    Ok(count)
+}
+
+/// Ordering rule of the items of a constructed object with the universal `SET` tag
+///
+/// `SET` and `SET OF` objects are encoded with the same tag, so when the ASN.1 type is not known,
+/// the rule is guessed from the tags of the items:
+/// - if all items have the same tag, the object is a `SET OF`: items are sorted by encoding
+///   (X.690 section 11.6)
+/// - if all items have different tags, the object is a `SET`: items are sorted by tag, in the
+///   canonical order of X.690 section 8.6 (X.690 section 10.3)
+/// - otherwise (for ex. a `SET OF` a `CHOICE` type), the order cannot be determined, and it is
+///   neither checked nor changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetOrder {
+    /// Items are sorted by encoding (`SET OF`)
+    Encoding,
+    /// Items are sorted by tag (`SET`)
+    Tag,
+    /// Items have different tags, some of them being equal
+    Unknown,
+}
+
+impl SetOrder {
+    /// Guess the ordering rule from the class and tag of all items
+    pub fn from_tags(tags: &[(Class, Tag)]) -> Self {
+        let mut sorted = tags.to_vec();
+        sorted.sort();
+        if sorted.first() == sorted.last() {
+            SetOrder::Encoding
+        } else if sorted.windows(2).all(|w| w[0] != w[1]) {
+            SetOrder::Tag
+        } else {
+            SetOrder::Unknown
+        }
+    }
+}
+
+/// Check that the items of a `SET` or `SET OF` are sorted
+fn check_set_order(content: &[u8]) -> Result<()> {
+    let mut items = Vec::new();
+    let mut i = content;
+    while !i.is_empty() {
+        let (rem, any) = Any::from_der(i)?;
+        items.push(((any.class(), any.tag()), &i[..i.len() - rem.len()]));
+        i = rem;
+    }
+    let tags: Vec<_> = items.iter().map(|(tag, _)| *tag).collect();
+    let sorted = match SetOrder::from_tags(&tags) {
+        SetOrder::Encoding => items
+            .windows(2)
+            .all(|w| der_encoding_cmp(w[0].1, w[1].1) != Ordering::Greater),
+        SetOrder::Tag => tags.windows(2).all(|w| w[0] < w[1]),
+        SetOrder::Unknown => true,
+    };
+    if !sorted {
+        return Err(Error::DerConstraintFailed(DerConstraint::SetNotSorted));
+    }
+    Ok(())
+}
+
+/// Re-encode BER objects using DER
+///
+/// Objects are converted recursively:
+/// - lengths are encoded using the definite form, with the minimum number of octets
+/// - constructed strings are reassembled into primitive strings (X.690 section 10.2)
+/// - `BOOLEAN` values are encoded as `00` or `FF` (X.690 section 11.1)
+/// - unused bits of `BIT STRING` values are set to zero (X.690 section 11.2.1)
+/// - items of `SET` and `SET OF` objects are sorted (see [`SetOrder`] for the rules)
+///
+/// Content of other primitive objects is copied: this function does not check that it is valid.
+pub fn ber_to_der(input: &[u8]) -> SerializeResult<Vec<u8>> {
+    let mut v = Vec::with_capacity(input.len());
+    ber_to_der_rec(input, &mut v, MAX_RECURSION)?;
+    Ok(v)
+}
+
+fn ber_to_der_rec(input: &[u8], out: &mut Vec<u8>, max_depth: usize) -> SerializeResult<()> {
+    if max_depth == 0 {
+        return Err(Error::BerMaxDepth.into());
+    }
+    let mut i = input;
+    while !i.is_empty() {
+        let (rem, any) = Any::from_ber(i).map_err(Error::from)?;
+        i = rem;
+        let header = &any.header;
+        let (class, tag) = (header.class(), header.tag());
+        if header.is_universal() && tag == Tag::EndOfContent {
+            return Err(Error::UnexpectedEndOfContent.into());
+        }
+        if header.is_constructed() && header.is_universal() && is_string(tag) {
+            let content = if tag == Tag::BitString {
+                bitstring_content(&any, max_depth)?
+            } else {
+                let mut content = Vec::new();
+                for fragment in any.string_fragments() {
+                    content.extend_from_slice(fragment?);
+                }
+                content
+            };
+            write_der_object(class, false, tag, &content, out)?;
+        } else if header.is_constructed() {
+            let mut tags = Vec::new();
+            let mut items = Vec::new();
+            let mut j = any.data;
+            while !j.is_empty() {
+                let (rem, item) = Any::from_ber(j).map_err(Error::from)?;
+                let raw = &j[..j.len() - rem.len()];
+                j = rem;
+                let mut v = Vec::new();
+                ber_to_der_rec(raw, &mut v, max_depth - 1)?;
+                tags.push((item.class(), item.tag()));
+                items.push(v);
+            }
+            if header.is_universal() && tag == Tag::Set {
+                match SetOrder::from_tags(&tags) {
+                    SetOrder::Encoding => items.sort_by(|a, b| der_encoding_cmp(a, b)),
+                    SetOrder::Tag => {
+                        let mut tagged: Vec<_> = tags.into_iter().zip(items).collect();
+                        tagged.sort_by_key(|(tag, _)| *tag);
+                        items = tagged.into_iter().map(|(_, item)| item).collect();
+                    }
+                    SetOrder::Unknown => (),
+                }
+            }
+            write_der_object(class, true, tag, &items.concat(), out)?;
+        } else if header.is_universal() && tag == Tag::Boolean {
+            let b = Boolean::try_from(&any)?;
+            write_der_object(class, false, tag, &[if b.bool() { 0xff } else { 0 }], out)?;
+        } else if header.is_universal() && tag == Tag::BitString {
+            let mut content = any.data.to_vec();
+            clear_unused_bits(&mut content)?;
+            write_der_object(class, false, tag, &content, out)?;
+        } else {
+            write_der_object(class, false, tag, any.data, out)?;
+        }
+    }
+    Ok(())
+}
+
+/// Reassemble the content of a constructed `BIT STRING` (X.690 section 8.6.4)
+fn bitstring_content(any: &Any, max_depth: usize) -> Result<Vec<u8>> {
+    let (unused, bits) = reassemble_bitstring(any, max_depth)?;
+    let mut content = vec![unused];
+    content.extend_from_slice(&bits);
+    clear_unused_bits(&mut content)?;
+    Ok(content)
+}
+
+/// Set the unused bits of `BIT STRING` content to zero
+fn clear_unused_bits(content: &mut [u8]) -> Result<()> {
+    let (unused, rest) = content.split_first_mut().ok_or(Error::InvalidLength)?;
+    if *unused > 7 || (rest.is_empty() && *unused != 0) {
+        return Err(Error::InvalidLength);
+    }
+    if let Some(last) = rest.last_mut() {
+        *last &= 0xff << *unused;
+    }
+    Ok(())
+}
+
+fn write_der_object(
+    class: Class,
+    constructed: bool,
+    tag: Tag,
+    content: &[u8],
+    out: &mut Vec<u8>,
+) -> SerializeResult<()> {
+    let header = Header::new(class, constructed, tag, Length::Definite(content.len()));
+    header.write_der_header(out)?;
+    out.extend_from_slice(content);
+    Ok(())
+}
+
+/// Return the raw encoding of the object at `path` in `input`
+///
+/// See the [`path`] module for the syntax.
+pub fn extract_path<'a>(input: &'a [u8], path: &str) -> Result<&'a [u8]> {
+    path::extract_raw(input, path)
+}
+
+/// Compare two BER/DER encodings, and describe the first difference
+///
+/// Returns `None` if the encodings are identical. Otherwise, the objects are compared
+/// recursively to find the first differing object, and the returned report contains its path
+/// (see the [`path`] module, the index of the top-level object is displayed if the
+/// input contains several objects), followed by the structure of both encodings (see [`dump`]),
+/// with the differing object marked with `>`. Inputs which cannot be parsed are displayed in
+/// hexadecimal.
+///
+/// This is used by [`assert_der_eq`].
+///
+/// ```rust
+/// use asn1_rs::tools::der_diff;
+///
+/// let left = &[0x30, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x02];
+/// let right = &[0x30, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x03];
+/// assert!(der_diff(left, left).is_none());
+/// let report = der_diff(left, right).expect("no difference");
+/// assert!(report.starts_with("first difference at path \"1\""));
+/// assert!(report.contains(">    5    1:   INTEGER 02"));
+/// ```
+pub fn der_diff(left: &[u8], right: &[u8]) -> Option<String> {
+    if left == right {
+        return None;
+    }
+    let mut path = Vec::new();
+    let (left_offset, right_offset) =
+        first_difference(left, right, (0, 0), &mut path, MAX_RECURSION);
+    let mut s = String::new();
+    let (object, segments) = path.split_first().map_or((0, &[][..]), |(o, p)| (*o, p));
+    let segments: Vec<_> = segments.iter().map(|n| n.to_string()).collect();
+    let _ = write!(s, "first difference at path \"{}\"", segments.join("/"));
+    if object > 0 {
+        let _ = write!(s, " of object {}", object);
+    }
+    let _ = writeln!(
+        s,
+        " (offset {} in left, {} in right)",
+        left_offset, right_offset
+    );
+    for (name, input, offset) in [("left", left, left_offset), ("right", right, right_offset)] {
+        let _ = writeln!(s, "{}:", name);
+        match dump(input) {
+            Ok(tree) => {
+                let marker = format!("{:>5} ", offset);
+                for line in tree.lines() {
+                    let prefix = if line.starts_with(&marker) { '>' } else { ' ' };
+                    let _ = writeln!(s, "{}{}", prefix, line);
+                }
+            }
+            Err(e) => {
+                let _ = writeln!(s, " (invalid: {})", e);
+                s.push(' ');
+                for b in input {
+                    let _ = write!(s, "{:02X}", b);
+                }
+                s.push('\n');
+            }
+        }
+    }
+    Some(s)
+}
+
+/// Find the first difference between the lists of objects `left` and `right`, starting at
+/// offsets `base`
+///
+/// The indices of the differing object (and its parents) are appended to `path`, and its offsets
+/// are returned.
+fn first_difference(
+    left: &[u8],
+    right: &[u8],
+    base: (usize, usize),
+    path: &mut Vec<usize>,
+    max_depth: usize,
+) -> (usize, usize) {
+    let (mut l, mut r) = (left, right);
+    let mut index = 0;
+    loop {
+        let offsets = (base.0 + left.offset(l), base.1 + right.offset(r));
+        if l.is_empty() || r.is_empty() {
+            path.push(index);
+            return offsets;
+        }
+        let (a, b) = match (Any::from_ber(l), Any::from_ber(r)) {
+            (Ok(a), Ok(b)) => (a, b),
+            _ => {
+                path.push(index);
+                return offsets;
+            }
+        };
+        let (raw_a, raw_b) = (&l[..l.offset(a.0)], &r[..r.offset(b.0)]);
+        if raw_a == raw_b {
+            l = a.0;
+            r = b.0;
+            index += 1;
+            continue;
+        }
+        path.push(index);
+        let (a, b) = (a.1, b.1);
+        let same_type = a.class() == b.class()
+            && a.tag() == b.tag()
+            && a.header.is_constructed() == b.header.is_constructed();
+        if !same_type || a.header.is_primitive() || max_depth == 0 {
+            return offsets;
+        }
+        if a.data == b.data {
+            // content is identical, the headers differ (for ex. length encoding)
+            return offsets;
+        }
+        let content_base = (offsets.0 + l.offset(a.data), offsets.1 + r.offset(b.data));
+        return first_difference(a.data, b.data, content_base, path, max_depth - 1);
+    }
+}
+
+/// Assert that two BER/DER encodings are equal, displaying the first difference on failure
+///
+/// Both expressions must implement `AsRef<[u8]>`. On failure, the panic message contains the
+/// report from [`der_diff`](crate::tools::der_diff): the path of the first differing object,
+/// and the annotated structure of both encodings. An optional message can be added, as for
+/// `assert_eq!`.
+///
+/// Requires the `std` feature.
+///
+/// ```rust
+/// use asn1_rs::{assert_der_eq, ToDer};
+///
+/// let v = 2u32.to_der_vec().unwrap();
+/// assert_der_eq!(v, &[0x02, 0x01, 0x02]);
+/// ```
+///
+/// ```rust,should_panic
+/// # use asn1_rs::assert_der_eq;
+/// // panics with:
+/// // first difference at path "1" (offset 5 in left, 5 in right)
+/// // left:
+/// //     0    6: SEQUENCE {
+/// //     2    1:   INTEGER 01
+/// // >   5    1:   INTEGER 02
+/// // ...
+/// assert_der_eq!(
+///     &[0x30, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x02],
+///     &[0x30, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x03],
+///     "certificate serial"
+/// );
+/// ```
+#[macro_export]
+macro_rules! assert_der_eq {
+    ($left:expr, $right:expr $(,)?) => {{
+        let (left, right) = (&$left, &$right);
+        let (left, right): (&[u8], &[u8]) = (left.as_ref(), right.as_ref());
+        if let Some(report) = $crate::tools::der_diff(left, right) {
+            panic!("assertion failed: DER encodings differ\n{}", report);
+        }
+    }};
+    ($left:expr, $right:expr, $($arg:tt)+) => {{
+        let (left, right) = (&$left, &$right);
+        let (left, right): (&[u8], &[u8]) = (left.as_ref(), right.as_ref());
+        if let Some(report) = $crate::tools::der_diff(left, right) {
+            panic!(
+                "assertion failed: DER encodings differ: {}\n{}",
+                format_args!($($arg)+),
+                report
+            );
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn tools_dump() {
+        // SEQUENCE (indefinite) { [0] { INTEGER 2 }, OCTET STRING (17 bytes), UTF8String "ab" }
+        let input = &hex!(
+            "30 80 a0 03 02 01 02 04 11 00 01 02 03 04 05 06 07 08 09 0a 0b 0c 0d 0e 0f 10
+            0c 02 61 62 00 00"
+        );
+        let s = dump(input).expect("invalid data");
+        let expected = "    0  inf: SEQUENCE {
+    2    3:   [CONTEXT 0] {
+    4    1:     INTEGER 02
+          :   }
+    7   17:   OCTET STRING 000102030405060708090A0B0C0D0E0F...
+   26    2:   UTF8String 'ab'
+          : }
+";
+        assert_eq!(s, expected);
+        assert!(dump(&hex!("30 03 02 01")).is_err());
+        // indefinite length child overflowing its parent
+        assert!(dump(&hex!("30 02 30 80 05 00 00 00")).is_err());
+    }
+
+    #[test]
+    fn tools_dump_with_registry() {
+        fn decode_len(data: &[u8]) -> Result<String> {
+            Ok(format!("({} chars)", data.len()))
+        }
+        fn decode_fail(_: &[u8]) -> Result<String> {
+            Err(Error::BerValueError)
+        }
+        let mut registry = TagRegistry::new();
+        registry.register(Tag(29), "CHARACTER STRING", decode_len);
+        registry.register(Tag(31), "PrivateType", decode_fail);
+        // SEQUENCE { UNIVERSAL 29 "abc", UNIVERSAL 31 01, [CONTEXT 29] 01 }
+        let input = &hex!("30 0c 1d 03 61 62 63 1f 1f 01 01 9d 01 01");
+        let s = dump_with_registry(input, &registry).expect("invalid data");
+        let expected = "    0   12: SEQUENCE {
+    2    3:   CHARACTER STRING (3 chars)
+    7    1:   PrivateType 01
+   11    1:   [CONTEXT 29] 01
+          : }
+";
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn tools_validate_der() {
+        assert_eq!(validate_der(&hex!("30 05 01 01 ff 05 00")), Ok(3));
+        // invalid BOOLEAN
+        assert!(validate_der(&hex!("30 03 01 01 01")).is_err());
+        // non-minimal INTEGER
+        assert!(validate_der(&hex!("02 02 00 01")).is_err());
+        // indefinite length
+        assert!(validate_der(&hex!("30 80 00 00")).is_err());
+        // unsorted SET OF
+        assert_eq!(
+            validate_der(&hex!("31 06 02 01 02 02 01 01")),
+            Err(Error::DerConstraintFailed(DerConstraint::SetNotSorted))
+        );
+        // unsorted SET (items sorted by encoding, not by tag)
+        assert_eq!(
+            validate_der(&hex!("31 08 81 01 00 a0 03 02 01 05")),
+            Err(Error::DerConstraintFailed(DerConstraint::SetNotSorted))
+        );
+        // items with mixed tags: order is not checked
+        assert_eq!(
+            validate_der(&hex!("31 09 02 01 02 04 01 61 02 01 01")),
+            Ok(4)
+        );
+    }
+
+    #[test]
+    fn tools_set_order() {
+        let (u, c) = (Class::Universal, Class::ContextSpecific);
+        assert_eq!(SetOrder::from_tags(&[]), SetOrder::Encoding);
+        assert_eq!(
+            SetOrder::from_tags(&[(u, Tag::Integer), (u, Tag::Integer)]),
+            SetOrder::Encoding
+        );
+        assert_eq!(
+            SetOrder::from_tags(&[(c, Tag(1)), (u, Tag::Integer), (c, Tag(0))]),
+            SetOrder::Tag
+        );
+        assert_eq!(
+            SetOrder::from_tags(&[(u, Tag::Integer), (u, Tag::Null), (u, Tag::Integer)]),
+            SetOrder::Unknown
+        );
+    }
+
+    #[test]
+    fn tools_ber_to_der() {
+        // SET OF (indefinite) { OCTET STRING (constructed) { "ab", "c" }, OCTET STRING "a" }
+        let input = &hex!("31 80 24 80 04 02 61 62 04 01 63 00 00 04 01 61 00 00");
+        let der = ber_to_der(input).expect("conversion failed");
+        assert_eq!(&der, &hex!("31 08 04 01 61 04 03 61 62 63"));
+        assert_eq!(validate_der(&der), Ok(3));
+        // SET { [1] 00, [0] { INTEGER 5 } }: sorted by tag, not by encoding
+        let input = &hex!("31 08 81 01 00 a0 03 02 01 05");
+        let der = ber_to_der(input).expect("conversion failed");
+        assert_eq!(&der, &hex!("31 08 a0 03 02 01 05 81 01 00"));
+        assert_eq!(validate_der(&der), Ok(4));
+        // items with mixed tags (SET OF CHOICE): order is kept
+        let input = &hex!("31 09 02 01 02 04 01 61 02 01 01");
+        assert_eq!(&ber_to_der(input).expect("conversion failed"), input);
+        // constructed BIT STRING, with non-zero unused bits
+        let input = &hex!("23 09 03 02 00 aa 03 03 04 bb ff");
+        let der = ber_to_der(input).expect("conversion failed");
+        assert_eq!(&der, &hex!("03 04 04 aa bb f0"));
+        // invalid BIT STRING fragments
+        let input = &hex!("23 08 03 02 04 aa 03 02 00 bb");
+        assert!(ber_to_der(input).is_err());
+        // non-minimal length
+        let der = ber_to_der(&hex!("04 81 01 00")).expect("conversion failed");
+        assert_eq!(&der, &hex!("04 01 00"));
+    }
+
+    #[test]
+    fn tools_der_diff() {
+        let left = &hex!("30 0a 30 06 02 01 01 02 01 02 05 00");
+        assert_eq!(der_diff(left, left), None);
+        // nested value
+        let right = &hex!("30 0a 30 06 02 01 01 02 01 03 05 00");
+        let report = der_diff(left, right).expect("no difference");
+        let expected = "first difference at path \"0/1\" (offset 7 in left, 7 in right)
+left:
+     0   10: SEQUENCE {
+     2    6:   SEQUENCE {
+     4    1:     INTEGER 01
+>    7    1:     INTEGER 02
+           :   }
+    10    0:   NULL
+           : }
+right:
+     0   10: SEQUENCE {
+     2    6:   SEQUENCE {
+     4    1:     INTEGER 01
+>    7    1:     INTEGER 03
+           :   }
+    10    0:   NULL
+           : }
+";
+        assert_eq!(report, expected);
+        // missing object, and different length encoding
+        let right = &hex!("30 03 02 01 01");
+        let report = der_diff(&hex!("30 06 02 01 01 02 01 02"), right).unwrap();
+        assert!(report.starts_with("first difference at path \"1\" (offset 5 in left, 5 in right)"));
+        let report = der_diff(&hex!("04 01 00"), &hex!("04 81 01 00")).unwrap();
+        assert!(report.starts_with("first difference at path \"\" (offset 0"));
+        // malformed input (indefinite length child overflowing its parent)
+        let report = der_diff(&hex!("30 02 30 80 05 00 00 00"), &hex!("05 00")).unwrap();
+        assert!(report.starts_with("first difference at path \"\" (offset 0"));
+        // several objects, invalid input
+        let report = der_diff(&hex!("05 00 02 01 01"), &hex!("05 00 02 02 01")).unwrap();
+        assert!(report.starts_with(
+            "first difference at path \"\" of object 1 (offset 2 in left, 2 in right)"
+        ));
+        assert!(report.contains("(invalid: "));
+        // macro
+        assert_der_eq!(left.to_vec(), left);
+        let res = std::panic::catch_unwind(|| assert_der_eq!(left, right, "test {}", 1));
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn tools_extract_path() {
+        let input = &hex!("30 06 02 01 01 02 01 02");
+        assert_eq!(extract_path(input, "1"), Ok(&hex!("02 01 02")[..]));
+    }
+}
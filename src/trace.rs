@@ -0,0 +1,53 @@
+//! Internal macros for structured logging (`tracing` feature)
+//!
+//! Without the `tracing` feature, all macros expand to nothing, so instrumented code does not
+//! depend on the `tracing` crate.
+//!
+//! In spans and events, the `header_len` field is the length of the header of the object (the
+//! parsing functions do not know the position of their input in the whole encoding).
+
+/// Enter a span at `TRACE` level, until the end of the current block
+#[cfg(feature = "tracing")]
+macro_rules! enter_span {
+    ($($args:tt)*) => {
+        let _span = tracing::trace_span!($($args)*).entered();
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! enter_span {
+    ($($args:tt)*) => {};
+}
+
+/// Emit an event at `DEBUG` level
+#[cfg(feature = "tracing")]
+macro_rules! debug_event {
+    ($($args:tt)*) => {
+        tracing::debug!($($args)*);
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! debug_event {
+    ($($args:tt)*) => {};
+}
+
+/// Emit an event at `DEBUG` level if `$res` is an error, and return `$res`
+///
+/// The error is added to the event as the `error` field.
+#[cfg(feature = "tracing")]
+macro_rules! debug_on_err {
+    ($res:expr, $($args:tt)*) => {
+        $res.map_err(|e| {
+            tracing::debug!(error = ?e, $($args)*);
+            e
+        })
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! debug_on_err {
+    ($res:expr, $($args:tt)*) => {
+        $res
+    };
+}
@@ -1,5 +1,12 @@
+use crate::ber::MAX_RECURSION;
 use crate::error::*;
+#[cfg(feature = "std")]
+use crate::{encoding::write_cer_from_der, writer::SliceWriter, EncodingRules, HexWriter};
 use crate::{Any, Class, Explicit, Implicit, Tag, TaggedParser};
+use alloc::borrow::{Cow, ToOwned};
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
 use core::convert::{TryFrom, TryInto};
 #[cfg(feature = "std")]
 use std::io::Write;
@@ -50,6 +57,10 @@ where
 /// [`TryFrom<Any>`] trait,
 /// which offers greater flexibility and provides an equivalent `FromBer` implementation for free.
 ///
+/// If the conversion does not need to take ownership of the object, implement
+/// `TryFrom<&'b Any<'a>>` and make `TryFrom<Any>` call it: this allows callers to parse an
+/// object while keeping the `Any` (for ex. to re-encode the original bytes).
+///
 /// # Examples
 ///
 /// ```
@@ -83,7 +94,19 @@ where
 /// ```
 pub trait FromBer<'a, E = Error>: Sized {
     /// Attempt to parse input bytes into a BER object
-    fn from_ber(bytes: &'a [u8]) -> ParseResult<Self, E>;
+    fn from_ber(bytes: &'a [u8]) -> ParseResult<'a, Self, E>;
+
+    /// Attempt to parse input bytes into a BER object, converting the error to type `F`
+    ///
+    /// This is useful to propagate an application-specific error type from nested parsers,
+    /// for ex. to parse a field with a type of this crate (returning [`Error`]) from a parser
+    /// returning a custom error type (implementing `From<Error>`).
+    fn from_ber_into_err<F>(bytes: &'a [u8]) -> ParseResult<'a, Self, F>
+    where
+        F: From<E>,
+    {
+        Self::from_ber(bytes).map_err(nom::Err::convert)
+    }
 }
 
 impl<'a, T, E> FromBer<'a, E> for T
@@ -91,8 +114,17 @@ where
     T: TryFrom<Any<'a>, Error = E>,
     E: From<Error>,
 {
-    fn from_ber(bytes: &'a [u8]) -> ParseResult<T, E> {
+    fn from_ber(bytes: &'a [u8]) -> ParseResult<'a, T, E> {
         let (i, any) = Any::from_ber(bytes).map_err(nom::Err::convert)?;
+        enter_span!(
+            "from_ber",
+            ty = core::any::type_name::<T>(),
+            class = %any.class(),
+            tag = any.tag().0,
+            header_len = any.data.as_ptr() as usize - bytes.as_ptr() as usize,
+            length = any.data.len(),
+            input_len = bytes.len()
+        );
         let result = any.try_into().map_err(nom::Err::Error)?;
         Ok((i, result))
     }
@@ -152,7 +184,17 @@ where
 /// ```
 pub trait FromDer<'a, E = Error>: Sized {
     /// Attempt to parse input bytes into a DER object (enforcing constraints)
-    fn from_der(bytes: &'a [u8]) -> ParseResult<Self, E>;
+    fn from_der(bytes: &'a [u8]) -> ParseResult<'a, Self, E>;
+
+    /// Attempt to parse input bytes into a DER object, converting the error to type `F`
+    ///
+    /// See [`FromBer::from_ber_into_err`].
+    fn from_der_into_err<F>(bytes: &'a [u8]) -> ParseResult<'a, Self, F>
+    where
+        F: From<E>,
+    {
+        Self::from_der(bytes).map_err(nom::Err::convert)
+    }
 }
 
 /// Trait to automatically derive `FromDer`
@@ -180,19 +222,117 @@ where
     T: DerAutoDerive,
     E: From<Error>,
 {
-    fn from_der(bytes: &'a [u8]) -> ParseResult<T, E> {
+    fn from_der(bytes: &'a [u8]) -> ParseResult<'a, T, E> {
         // Note: Any::from_der checks than length is definite
         let (i, any) = Any::from_der(bytes).map_err(nom::Err::convert)?;
-        <T as CheckDerConstraints>::check_constraints(&any)
-            .map_err(|e| nom::Err::Error(e.into()))?;
+        enter_span!(
+            "from_der",
+            ty = core::any::type_name::<T>(),
+            class = %any.class(),
+            tag = any.tag().0,
+            header_len = any.data.as_ptr() as usize - bytes.as_ptr() as usize,
+            length = any.data.len(),
+            input_len = bytes.len()
+        );
+        <T as CheckDerConstraints>::check_constraints(&any).map_err(|e| {
+            debug_event!(
+                error = %e,
+                header_len = any.data.as_ptr() as usize - bytes.as_ptr() as usize,
+                "DER constraint failed"
+            );
+            nom::Err::Error(e.into())
+        })?;
         let result = any.try_into().map_err(nom::Err::Error)?;
         Ok((i, result))
     }
 }
 
 /// Verification of DER constraints
+///
+/// [`check_constraints`](CheckDerConstraints::check_constraints) checks the constraints of the
+/// object. Constructed types can also check the items they contain, by implementing
+/// [`check_constraints_ctx`](CheckDerConstraints::check_constraints_ctx): the context tracks
+/// the recursion depth, so deeply nested input cannot exhaust the stack.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{Any, CheckDerConstraints, DerConstraint, Error, FromBer, Integer, SequenceOf};
+///
+/// // SEQUENCE OF INTEGER, with a non-minimal INTEGER
+/// let bytes = &[0x30, 0x07, 0x02, 0x01, 0x01, 0x02, 0x02, 0x00, 0x01];
+/// let (_, any) = Any::from_ber(bytes).expect("parsing failed");
+/// assert_eq!(
+///     SequenceOf::<Integer>::check_der_recursive(&any),
+///     Err(Error::DerConstraintFailed(DerConstraint::IntegerLeadingZeroes))
+/// );
+/// ```
 pub trait CheckDerConstraints {
+    /// Check the DER constraints of `any`
     fn check_constraints(any: &Any) -> Result<()>;
+
+    /// Check the DER constraints of `any`, and of its items, using context `ctx`
+    ///
+    /// Implementations for constructed types should call
+    /// [`DerCheckContext::enter`] before checking their items. The default implementation
+    /// calls [`check_constraints`](CheckDerConstraints::check_constraints).
+    fn check_constraints_ctx(any: &Any, ctx: DerCheckContext) -> Result<()> {
+        let _ = ctx;
+        Self::check_constraints(any)
+    }
+
+    /// Check the DER constraints of `any`, and recursively of all its items
+    fn check_der_recursive(any: &Any) -> Result<()> {
+        Self::check_constraints_ctx(any, DerCheckContext::new())
+    }
+}
+
+/// Context of a recursive verification of DER constraints
+///
+/// See [`CheckDerConstraints::check_constraints_ctx`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DerCheckContext {
+    depth: usize,
+    max_depth: usize,
+}
+
+impl DerCheckContext {
+    /// Build a new context, using the default maximum depth
+    pub const fn new() -> Self {
+        Self::with_max_depth(MAX_RECURSION)
+    }
+
+    /// Build a new context, allowing at most `max_depth` levels of nested objects
+    pub const fn with_max_depth(max_depth: usize) -> Self {
+        DerCheckContext {
+            depth: 0,
+            max_depth,
+        }
+    }
+
+    /// Return the current depth (0 for the top-level object)
+    pub const fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Return the context for the items of the current object
+    ///
+    /// Returns [`Error::BerMaxDepth`] if the maximum depth is reached.
+    pub const fn enter(&self) -> Result<Self> {
+        if self.depth >= self.max_depth {
+            return Err(Error::BerMaxDepth);
+        }
+        Ok(DerCheckContext {
+            depth: self.depth + 1,
+            max_depth: self.max_depth,
+        })
+    }
+}
+
+impl Default for DerCheckContext {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 /// Common trait for all objects that can be encoded using the DER representation
@@ -241,6 +381,26 @@ where
         Ok(v)
     }
 
+    /// Write the DER encoded representation to a newly allocated `String`, as lowercase
+    /// hexadecimal characters.
+    ///
+    /// See [`HexWriter`](crate::HexWriter) to write uppercase characters, or to write to
+    /// another formatter, and [`from_der_hex`](crate::from_der_hex) for the reverse operation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use asn1_rs::ToDer;
+    ///
+    /// let s = 4u32.to_der_hex().expect("serialization failed");
+    /// assert_eq!(s, "020104");
+    /// ```
+    fn to_der_hex(&self) -> SerializeResult<String> {
+        let mut s = String::new();
+        let _ = self.write_der(&mut HexWriter::new(&mut s))?;
+        Ok(s)
+    }
+
     /// Similar to using `to_vec`, but uses provided values without changes.
     /// This can generate an invalid encoding for a DER object.
     fn to_der_vec_raw(&self) -> SerializeResult<Vec<u8>> {
@@ -264,11 +424,78 @@ where
     /// # assert_eq!(sz, 3);
     /// ```
     fn write_der(&self, writer: &mut dyn Write) -> SerializeResult<usize> {
+        enter_span!("write_der", ty = core::any::type_name::<Self>());
         let sz = self.write_der_header(writer)?;
         let sz = sz + self.write_der_content(writer)?;
         Ok(sz)
     }
 
+    /// Attempt to write the DER encoded representation (header and content) into a preallocated
+    /// buffer, returning the number of bytes written.
+    ///
+    /// The length is computed first: if the buffer is too small, nothing is written and
+    /// [`SerializeError::BufferTooSmall`] is returned, with the required size. The encoding is
+    /// never truncated: if the written data does not fit in the buffer (or its size differs from
+    /// [`ToDer::to_der_len`]), an error is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use asn1_rs::{SerializeError, ToDer};
+    ///
+    /// let mut buf = [0u8; 8];
+    /// let sz = 4u32.write_der_to_slice(&mut buf).expect("serialization failed");
+    /// assert_eq!(&buf[..sz], &[0x02, 0x01, 0x04]);
+    ///
+    /// let res = 4u32.write_der_to_slice(&mut buf[..2]);
+    /// assert!(matches!(res, Err(SerializeError::BufferTooSmall { needed: 3 })));
+    /// ```
+    fn write_der_to_slice(&self, buf: &mut [u8]) -> SerializeResult<usize> {
+        let needed = self.to_der_len()?;
+        if needed > buf.len() {
+            return Err(SerializeError::BufferTooSmall { needed });
+        }
+        let mut writer = SliceWriter::new(buf);
+        let sz = match self.write_der(&mut writer) {
+            Ok(sz) => sz,
+            Err(SerializeError::IOError(e)) if e.kind() == std::io::ErrorKind::WriteZero => {
+                // `to_der_len` was wrong, compute the real size
+                let needed = self.write_der(&mut std::io::sink())?;
+                return Err(SerializeError::BufferTooSmall { needed });
+            }
+            Err(e) => return Err(e),
+        };
+        if sz != needed || sz != writer.position() {
+            return Err(SerializeError::InvalidLength);
+        }
+        Ok(sz)
+    }
+
+    /// Attempt to write the DER encoded representation (header and content) into this writer,
+    /// using vectored I/O.
+    ///
+    /// Types with potentially large contents (for ex. [`OctetString`](crate::OctetString) and
+    /// [`BitString`](crate::BitString)) write the header and the content as separate buffers
+    /// using [`Write::write_vectored`], so the content is not copied to an intermediate buffer.
+    /// The default implementation calls [`ToDer::write_der`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use asn1_rs::{OctetString, ToDer};
+    ///
+    /// let data = vec![0xaa; 1000];
+    /// let obj = OctetString::from(&data[..]);
+    /// let mut writer = Vec::new();
+    /// let sz = obj.to_der_vectored(&mut writer).expect("serialization failed");
+    ///
+    /// assert_eq!(&writer[..4], &[0x04, 0x82, 0x03, 0xe8]);
+    /// # assert_eq!(sz, 1004);
+    /// ```
+    fn to_der_vectored(&self, writer: &mut dyn Write) -> SerializeResult<usize> {
+        self.write_der(writer)
+    }
+
     /// Attempt to write the DER header to this writer.
     fn write_der_header(&self, writer: &mut dyn Write) -> SerializeResult<usize>;
 
@@ -280,6 +507,43 @@ where
     fn write_der_raw(&self, writer: &mut dyn Write) -> SerializeResult<usize> {
         self.write_der(writer)
     }
+
+    /// Attempt to write the encoded representation, using the given encoding rules, into this writer.
+    ///
+    /// The default implementation writes the DER encoding for DER and BER (since DER is valid
+    /// BER), and re-encodes the DER encoding for CER. Types can override this method when
+    /// encoding rules require a specific encoding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use asn1_rs::{EncodingRules, ToDer};
+    ///
+    /// let mut writer = Vec::new();
+    /// let _ = vec![4].write_encoded(EncodingRules::Cer, &mut writer).expect("serialization failed");
+    ///
+    /// assert_eq!(&writer, &[0x30, 0x80, 0x02, 0x01, 0x04, 0x00, 0x00]);
+    /// ```
+    fn write_encoded(
+        &self,
+        rules: EncodingRules,
+        writer: &mut dyn Write,
+    ) -> SerializeResult<usize> {
+        match rules {
+            EncodingRules::Ber | EncodingRules::Der => self.write_der(writer),
+            EncodingRules::Cer => {
+                let v = self.to_der_vec()?;
+                write_cer_from_der(&v, writer)
+            }
+        }
+    }
+
+    /// Write the encoded representation, using the given encoding rules, to a newly allocated `Vec<u8>`.
+    fn to_encoded_vec(&self, rules: EncodingRules) -> SerializeResult<Vec<u8>> {
+        let mut v = Vec::new();
+        let _ = self.write_encoded(rules, &mut v)?;
+        Ok(v)
+    }
 }
 
 #[cfg(feature = "std")]
@@ -299,6 +563,69 @@ where
     fn write_der_content(&self, writer: &mut dyn Write) -> SerializeResult<usize> {
         (*self).write_der_content(writer)
     }
+
+    fn to_der_vectored(&self, writer: &mut dyn Write) -> SerializeResult<usize> {
+        (*self).to_der_vectored(writer)
+    }
+
+    fn write_encoded(
+        &self,
+        rules: EncodingRules,
+        writer: &mut dyn Write,
+    ) -> SerializeResult<usize> {
+        (*self).write_encoded(rules, writer)
+    }
+}
+
+/// Comparison of a value with the `DEFAULT` value of its type
+///
+/// In DER, a component whose value is equal to its `DEFAULT` must not be encoded (X.690 section
+/// 11.5). This trait provides the comparison for types with a natural default (`FALSE`, `0`,
+/// empty collections), so it can be used without repeating the value:
+/// - by the custom derive, using `#[asn1(default)]` (see [`DerSequence`](derive@crate::DerSequence))
+/// - as a predicate for `skip_serializing_if`: `#[asn1(skip_serializing_if = "DerDefault::is_default")]`
+///
+/// Types implementing this trait must also implement `Default`, returning the same value.
+///
+/// # Examples
+///
+/// ```
+/// use asn1_rs::DerDefault;
+///
+/// assert!(false.is_default());
+/// assert!(!3u32.is_default());
+/// assert!(Vec::<u8>::new().is_default());
+/// ```
+pub trait DerDefault: Default {
+    /// Return true if the value is equal to the `DEFAULT` value, and must not be encoded
+    fn is_default(&self) -> bool;
+}
+
+macro_rules! impl_derdefault_eq {
+    ($($t:ty),*) => {
+        $(
+            impl DerDefault for $t {
+                fn is_default(&self) -> bool {
+                    *self == <$t>::default()
+                }
+            }
+        )*
+    };
+}
+
+impl_derdefault_eq!(bool, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_derdefault_eq!(crate::Boolean);
+
+impl<T> DerDefault for Option<T> {
+    fn is_default(&self) -> bool {
+        self.is_none()
+    }
+}
+
+impl<T> DerDefault for Vec<T> {
+    fn is_default(&self) -> bool {
+        self.is_empty()
+    }
 }
 
 /// Helper trait for creating tagged EXPLICIT values
@@ -342,7 +669,73 @@ pub trait AsTaggedImplicit<'a, E = Error>: Sized {
 
 impl<'a, T, E> AsTaggedImplicit<'a, E> for T where T: Sized + 'a {}
 
+/// Conversion of a value to an equivalent value with a `'static` lifetime
+///
+/// Parsed objects usually borrow from the input buffer. This trait copies the borrowed data, so
+/// the returned value can be kept after the input is dropped (for ex. in a cache).
+///
+/// This trait can be derived for structures and enums, see [`ToStatic`](derive@crate::ToStatic).
 pub trait ToStatic {
     type Owned: 'static;
     fn to_static(&self) -> Self::Owned;
 }
+
+macro_rules! impl_tostatic_clone {
+    ($($t:ty),*) => {
+        $(
+            impl ToStatic for $t {
+                type Owned = $t;
+
+                fn to_static(&self) -> Self::Owned {
+                    self.clone()
+                }
+            }
+        )*
+    };
+}
+
+impl_tostatic_clone!(bool, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_tostatic_clone!(f32, f64, (), String);
+impl_tostatic_clone!(
+    crate::Boolean,
+    crate::EndOfContent,
+    crate::Enumerated,
+    crate::GeneralizedTime,
+    crate::Null,
+    crate::Real,
+    crate::UtcTime
+);
+
+impl<T> ToStatic for Option<T>
+where
+    T: ToStatic,
+{
+    type Owned = Option<T::Owned>;
+
+    fn to_static(&self) -> Self::Owned {
+        self.as_ref().map(ToStatic::to_static)
+    }
+}
+
+impl<T> ToStatic for Box<T>
+where
+    T: ToStatic,
+{
+    type Owned = Box<T::Owned>;
+
+    fn to_static(&self) -> Self::Owned {
+        Box::new((**self).to_static())
+    }
+}
+
+impl<B> ToStatic for Cow<'_, B>
+where
+    B: ToOwned + ?Sized + 'static,
+    B::Owned: 'static,
+{
+    type Owned = Cow<'static, B>;
+
+    fn to_static(&self) -> Cow<'static, B> {
+        Cow::Owned(ToOwned::to_owned(&**self))
+    }
+}
@@ -0,0 +1,771 @@
+//! Writer adapters for serialization
+//!
+//! The [`ToDer`](crate::ToDer) trait writes to any [`Write`] object. The adapters in this module
+//! can be used to control how the serialized bytes are sent to the underlying sink.
+
+use crate::ber::MAX_RECURSION;
+use crate::{Class, Error, FromBer, Header, Length, Tag};
+use std::io::{self, IoSlice, Write};
+
+/// A writer adapter, sending output to the underlying writer in chunks of bounded size
+///
+/// This is useful for sinks with small buffers (sockets, hardware tokens, etc.): every call to
+/// the underlying writer contains at most `chunk_size` bytes.
+///
+/// Serialized data is first stored in an internal buffer, so serialization (for ex. using
+/// [`ToDer::write_der`](crate::ToDer::write_der)) never fails because the sink is not ready.
+/// Complete chunks are sent as soon as possible. If the underlying writer returns
+/// `WouldBlock` or `Interrupted`, or accepts only part of a chunk, the remaining data is kept
+/// pending. Sent data is removed from the buffer, so unless the underlying writer blocks, at
+/// most one incomplete chunk is buffered.
+///
+/// Other errors of the underlying writer are returned by the next call to
+/// [`write`](Write::write) (which then does not accept any byte) or [`flush`](Write::flush).
+///
+/// Calling [`flush`](Write::flush) sends all pending data, including the last (possibly
+/// incomplete) chunk. If the underlying writer would block, `flush` returns the
+/// `WouldBlock` error and can be called again later to resume.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{ChunkedWriter, ToDer};
+/// use std::io::Write;
+///
+/// let mut writer = ChunkedWriter::new(Vec::new(), 4);
+/// let _ = "some data".write_der(&mut writer).expect("serialization failed");
+/// writer.flush().expect("flush failed");
+/// assert!(!writer.has_pending());
+/// assert_eq!(writer.get_ref().len(), 11);
+/// ```
+#[derive(Debug)]
+pub struct ChunkedWriter<W: Write> {
+    inner: W,
+    chunk_size: usize,
+    buffer: Vec<u8>,
+    pos: usize,
+}
+
+impl<W: Write> ChunkedWriter<W> {
+    /// Build a new `ChunkedWriter`, sending at most `chunk_size` bytes per write.
+    ///
+    /// A `chunk_size` of 0 is treated as 1.
+    pub fn new(inner: W, chunk_size: usize) -> Self {
+        ChunkedWriter {
+            inner,
+            chunk_size: chunk_size.max(1),
+            buffer: Vec::new(),
+            pos: 0,
+        }
+    }
+
+    /// Return the maximum number of bytes sent per write
+    #[inline]
+    pub const fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    /// Return the data not yet sent to the underlying writer
+    #[inline]
+    pub fn pending(&self) -> &[u8] {
+        &self.buffer[self.pos..]
+    }
+
+    /// Return true if some data was not yet sent to the underlying writer
+    #[inline]
+    pub fn has_pending(&self) -> bool {
+        self.pos < self.buffer.len()
+    }
+
+    /// Get a reference to the underlying writer
+    #[inline]
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the underlying writer
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Flush pending data, and return the underlying writer
+    ///
+    /// If pending data cannot be sent, the error is returned with this object.
+    pub fn into_inner(mut self) -> Result<W, (io::Error, Self)> {
+        match self.flush() {
+            Ok(()) => Ok(self.inner),
+            Err(e) => Err((e, self)),
+        }
+    }
+
+    /// Remove the data already sent from the buffer
+    fn compact(&mut self) {
+        if self.pos > 0 {
+            self.buffer.drain(..self.pos);
+            self.pos = 0;
+        }
+    }
+
+    /// Send pending chunks to the underlying writer.
+    ///
+    /// If `partial` is false, only complete chunks are sent.
+    fn send_pending(&mut self, partial: bool) -> io::Result<()> {
+        loop {
+            let pending = self.buffer.len() - self.pos;
+            if pending == 0 || (!partial && pending < self.chunk_size) {
+                break;
+            }
+            let end = self.pos + pending.min(self.chunk_size);
+            match self.inner.write(&self.buffer[self.pos..end]) {
+                Ok(0) => {
+                    self.compact();
+                    return Err(io::ErrorKind::WriteZero.into());
+                }
+                Ok(n) => self.pos += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => (),
+                Err(e) => {
+                    self.compact();
+                    return Err(e);
+                }
+            }
+        }
+        self.compact();
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for ChunkedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // report errors of the underlying writer before accepting new data
+        match self.send_pending(false) {
+            Ok(()) => (),
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => (),
+            Err(e) => return Err(e),
+        }
+        self.buffer.extend_from_slice(buf);
+        // data is accepted: if it cannot be sent now, it stays pending and the error (if any)
+        // is returned by the next call to `write` or `flush`
+        let _ = self.send_pending(false);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.send_pending(true)?;
+        self.inner.flush()
+    }
+}
+
+/// A writer to a fixed-size buffer, failing instead of truncating data
+///
+/// Contrary to `Cursor<&mut [u8]>`, a write which does not fit in the remaining space returns
+/// a `WriteZero` error, and nothing is written.
+#[derive(Debug)]
+pub(crate) struct SliceWriter<'a> {
+    buf: &'a mut [u8],
+    pos: usize,
+}
+
+impl<'a> SliceWriter<'a> {
+    pub(crate) fn new(buf: &'a mut [u8]) -> Self {
+        SliceWriter { buf, pos: 0 }
+    }
+
+    /// Return the number of bytes written
+    pub(crate) fn position(&self) -> usize {
+        self.pos
+    }
+}
+
+impl Write for SliceWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let end = self.pos + buf.len();
+        if end > self.buf.len() {
+            return Err(io::ErrorKind::WriteZero.into());
+        }
+        self.buf[self.pos..end].copy_from_slice(buf);
+        self.pos = end;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A writer adapter, feeding all written bytes to a hash function
+///
+/// This computes the digest of a serialized object without storing its encoding, which is
+/// useful when signing large structures. The number of bytes written is also counted.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{DigestWriter, ToDer};
+/// use sha2::{Digest, Sha256};
+///
+/// let mut writer = DigestWriter::new(Sha256::new());
+/// let _ = "some data".write_der(&mut writer).expect("serialization failed");
+/// let (hash, len) = writer.finalize();
+/// assert_eq!(len, 11);
+/// assert_eq!(hash, Sha256::digest(&"some data".to_der_vec().unwrap()));
+/// ```
+#[cfg(feature = "digest")]
+#[cfg_attr(docsrs, doc(cfg(feature = "digest")))]
+#[derive(Debug, Clone, Default)]
+pub struct DigestWriter<D: digest::Digest> {
+    digest: D,
+    len: usize,
+}
+
+#[cfg(feature = "digest")]
+impl<D: digest::Digest> DigestWriter<D> {
+    /// Build a new `DigestWriter`, updating the hash function `digest`
+    pub fn new(digest: D) -> Self {
+        DigestWriter { digest, len: 0 }
+    }
+
+    /// Return the number of bytes written
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return true if no byte was written
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Get a reference to the hash function
+    #[inline]
+    pub fn get_ref(&self) -> &D {
+        &self.digest
+    }
+
+    /// Return the hash function
+    #[inline]
+    pub fn into_inner(self) -> D {
+        self.digest
+    }
+
+    /// Compute the digest of the written bytes, and return it with the number of bytes written
+    pub fn finalize(self) -> (digest::Output<D>, usize) {
+        (self.digest.finalize(), self.len)
+    }
+}
+
+#[cfg(feature = "digest")]
+impl<D: digest::Digest> Write for DigestWriter<D> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.digest.update(buf);
+        self.len += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A writer adapter, converting BER input to DER on the fly
+///
+/// Bytes written to this object are parsed as a stream of BER objects. As soon as an object is
+/// complete, it is converted using [`tools::ber_to_der`](crate::tools::ber_to_der) and written
+/// to the underlying writer. This can be used to canonicalize data in pipes or network proxies,
+/// without storing the full stream.
+///
+/// Top-level objects are buffered until complete: DER requires the definite length of all
+/// constructed objects, and the items of `SET` objects to be sorted, so memory usage is bounded
+/// by the size of the largest top-level object. For objects with a definite length, the size
+/// is known from the header; objects with an indefinite length are scanned incrementally (only
+/// the headers of nested objects are read, and each byte is scanned once) until the
+/// end-of-contents marker is found.
+///
+/// Invalid input is reported as an `InvalidData` I/O error by [`write`](Write::write). The
+/// pending input is then discarded, so the transcoder can be used for a new stream. The bytes of
+/// a failed call are never kept: if some objects were converted before the error, only their
+/// size is returned, and the error is reported by the next call.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::Ber2DerTranscoder;
+/// use std::io::Write;
+///
+/// let mut transcoder = Ber2DerTranscoder::new(Vec::new());
+/// // SEQUENCE (indefinite) { BOOLEAN TRUE (non-canonical) }, in two parts
+/// transcoder.write_all(&[0x30, 0x80, 0x01]).expect("write failed");
+/// assert!(transcoder.get_ref().is_empty());
+/// transcoder.write_all(&[0x01, 0x01, 0x00, 0x00]).expect("write failed");
+/// let der = transcoder.into_inner().expect("incomplete input");
+/// assert_eq!(&der, &[0x30, 0x03, 0x01, 0x01, 0xff]);
+/// ```
+#[derive(Debug)]
+pub struct Ber2DerTranscoder<W: Write> {
+    inner: W,
+    buffer: Vec<u8>,
+    /// Size of the pending object, if known from its header
+    needed: Option<usize>,
+    /// Offset of the next header to read in the pending object with an indefinite length
+    scan_pos: usize,
+    /// Number of objects with an indefinite length open at `scan_pos`
+    scan_depth: usize,
+}
+
+impl<W: Write> Ber2DerTranscoder<W> {
+    /// Build a new `Ber2DerTranscoder`, writing DER objects to `inner`
+    pub fn new(inner: W) -> Self {
+        Ber2DerTranscoder {
+            inner,
+            buffer: Vec::new(),
+            needed: None,
+            scan_pos: 0,
+            scan_depth: 0,
+        }
+    }
+
+    /// Return the input data of the incomplete object, not yet converted
+    #[inline]
+    pub fn pending(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// Return true if some input data was not yet converted
+    #[inline]
+    pub fn has_pending(&self) -> bool {
+        !self.buffer.is_empty()
+    }
+
+    /// Get a reference to the underlying writer
+    #[inline]
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the underlying writer
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Return the underlying writer, after checking that the last object is complete
+    ///
+    /// If some input data was not converted, an `UnexpectedEof` error is returned with this
+    /// object.
+    pub fn into_inner(self) -> Result<W, (io::Error, Self)> {
+        if self.has_pending() {
+            let e = io::Error::new(io::ErrorKind::UnexpectedEof, "incomplete BER object");
+            return Err((e, self));
+        }
+        Ok(self.inner)
+    }
+
+    /// Return the size of the first object of the buffer, or `None` if it is incomplete
+    ///
+    /// For objects with an indefinite length, the scan state is kept between calls, so data is
+    /// not parsed again when more input is written.
+    fn object_size(&mut self) -> Result<Option<usize>, Error> {
+        loop {
+            let input = match self.buffer.get(self.scan_pos..) {
+                Some(input) if !input.is_empty() => input,
+                _ => return Ok(None),
+            };
+            let (rem, header) = match Header::from_ber(input) {
+                Ok(res) => res,
+                Err(nom::Err::Incomplete(_)) => return Ok(None),
+                Err(e) => return Err(e.into()),
+            };
+            let end = self.scan_pos + (input.len() - rem.len());
+            let is_eoc = header.tag() == Tag::EndOfContent
+                && header.class() == Class::Universal
+                && header.is_primitive();
+            match header.length() {
+                Length::Definite(0) if is_eoc && self.scan_depth > 0 => {
+                    self.scan_depth -= 1;
+                    self.scan_pos = end;
+                    if self.scan_depth == 0 {
+                        return Ok(Some(end));
+                    }
+                }
+                Length::Definite(_) if is_eoc && self.scan_depth > 0 => {
+                    return Err(Error::InvalidEndOfContent)
+                }
+                Length::Definite(l) => {
+                    let size = end.checked_add(l).ok_or(Error::InvalidLength)?;
+                    if self.scan_depth == 0 {
+                        // top-level object with a definite length
+                        return Ok(Some(size));
+                    }
+                    // skip the content, it is checked during conversion
+                    self.scan_pos = size;
+                }
+                Length::Indefinite => {
+                    header.assert_constructed()?;
+                    if self.scan_depth >= MAX_RECURSION {
+                        return Err(Error::BerMaxDepth);
+                    }
+                    self.scan_depth += 1;
+                    self.scan_pos = end;
+                }
+            }
+        }
+    }
+
+    /// Convert and write all complete objects of the buffer
+    ///
+    /// The number of bytes of the buffer converted so far is added to `converted`.
+    fn process(&mut self, converted: &mut usize) -> io::Result<()> {
+        while !self.buffer.is_empty() {
+            let needed = match self.needed {
+                Some(n) => n,
+                None => match self.object_size() {
+                    Ok(Some(n)) => n,
+                    Ok(None) => break,
+                    Err(e) => {
+                        // invalid input: discard it
+                        self.reset();
+                        return Err(io::Error::new(io::ErrorKind::InvalidData, e));
+                    }
+                },
+            };
+            if self.buffer.len() < needed {
+                // definite length: wait until the object is complete
+                self.needed = Some(needed);
+                break;
+            }
+            let der = match crate::tools::ber_to_der(&self.buffer[..needed]) {
+                Ok(der) => der,
+                Err(e) => {
+                    self.reset();
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, e));
+                }
+            };
+            self.inner.write_all(&der)?;
+            self.buffer.drain(..needed);
+            *converted += needed;
+            self.needed = None;
+            self.scan_pos = 0;
+        }
+        Ok(())
+    }
+
+    /// Discard the pending input and the scan state
+    fn reset(&mut self) {
+        self.buffer.clear();
+        self.reset_scan();
+    }
+
+    /// Discard the scan state, so the pending input is scanned again
+    fn reset_scan(&mut self) {
+        self.needed = None;
+        self.scan_pos = 0;
+        self.scan_depth = 0;
+    }
+}
+
+impl<W: Write> Write for Ber2DerTranscoder<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let pending = self.buffer.len();
+        self.buffer.extend_from_slice(buf);
+        let mut converted = 0;
+        match self.process(&mut converted) {
+            Ok(()) => Ok(buf.len()),
+            Err(e) => {
+                // keep the bytes of `buf` which were not converted out of the buffer
+                self.buffer.truncate(pending.saturating_sub(converted));
+                self.reset_scan();
+                match converted.checked_sub(pending) {
+                    Some(n) if n > 0 => Ok(n),
+                    _ => Err(e),
+                }
+            }
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Write all buffers to `writer` using vectored I/O, returning the total number of bytes written
+///
+/// This is similar to the unstable `Write::write_all_vectored`: partial writes are resumed, and
+/// `Interrupted` errors are retried.
+pub(crate) fn write_all_vectored(writer: &mut dyn Write, parts: &[&[u8]]) -> io::Result<usize> {
+    let total = parts.iter().map(|p| p.len()).sum();
+    // current position: index of buffer, and offset in this buffer
+    let (mut idx, mut offset) = (0, 0);
+    while idx < parts.len() {
+        if offset == parts[idx].len() {
+            idx += 1;
+            offset = 0;
+            continue;
+        }
+        let mut slices = Vec::with_capacity(parts.len() - idx);
+        slices.push(IoSlice::new(&parts[idx][offset..]));
+        slices.extend(parts[idx + 1..].iter().map(|p| IoSlice::new(p)));
+        let mut n = match writer.write_vectored(&slices) {
+            Ok(0) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::WriteZero,
+                    "failed to write whole buffer",
+                ))
+            }
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        while n > 0 {
+            let remaining = parts[idx].len() - offset;
+            if n >= remaining {
+                n -= remaining;
+                idx += 1;
+                offset = 0;
+            } else {
+                offset += n;
+                n = 0;
+            }
+        }
+    }
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::io::{self, Write};
+
+    /// A sink accepting at most 3 bytes per write, and blocking every other call
+    #[derive(Default)]
+    struct SlowSink {
+        data: Vec<u8>,
+        writes: Vec<usize>,
+        calls: usize,
+    }
+
+    impl Write for SlowSink {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.calls += 1;
+            if self.calls % 2 == 0 {
+                return Err(io::ErrorKind::WouldBlock.into());
+            }
+            let n = buf.len().min(3);
+            self.data.extend_from_slice(&buf[..n]);
+            self.writes.push(buf.len());
+            Ok(n)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn chunked_writer_resume() {
+        let mut writer = ChunkedWriter::new(SlowSink::default(), 4);
+        let sz = "abcdefghij"
+            .write_der(&mut writer)
+            .expect("serialization failed");
+        assert_eq!(sz, 12);
+        // resume until all data is sent
+        let mut attempts = 0;
+        while let Err(e) = writer.flush() {
+            assert_eq!(e.kind(), io::ErrorKind::WouldBlock);
+            attempts += 1;
+            assert!(attempts < 20);
+        }
+        assert!(!writer.has_pending());
+        let sink = writer.into_inner().map_err(|(e, _)| e).unwrap();
+        assert_eq!(&sink.data, &"abcdefghij".to_der_vec().unwrap());
+        assert!(sink.writes.iter().all(|&n| n <= 4));
+    }
+
+    #[test]
+    fn chunked_writer_bounded() {
+        let mut writer = ChunkedWriter::new(Vec::new(), 4);
+        for i in 0..100u8 {
+            writer.write_all(&[i; 3]).expect("write failed");
+            // only the last incomplete chunk is kept
+            assert!(writer.buffer.len() < 4);
+        }
+        writer.flush().expect("flush failed");
+        assert_eq!(writer.get_ref().len(), 300);
+        assert!(writer.buffer.is_empty());
+    }
+
+    /// A sink failing with a `BrokenPipe` error
+    struct BrokenSink;
+
+    impl Write for BrokenSink {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::ErrorKind::BrokenPipe.into())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn chunked_writer_error() {
+        let mut writer = ChunkedWriter::new(BrokenSink, 4);
+        // bytes are accepted: the error is reported by the next call
+        assert_eq!(writer.write(b"abcdef").expect("write failed"), 6);
+        let e = writer.write(b"gh").expect_err("write should fail");
+        assert_eq!(e.kind(), io::ErrorKind::BrokenPipe);
+        // rejected bytes were not buffered
+        assert_eq!(writer.pending(), b"abcdef");
+        assert!(writer.flush().is_err());
+        assert_eq!(writer.pending(), b"abcdef");
+    }
+
+    /// A sink with vectored writes, accepting at most 5 bytes per call
+    #[derive(Default)]
+    struct VectoredSink {
+        data: Vec<u8>,
+        calls: usize,
+    }
+
+    impl Write for VectoredSink {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.write_vectored(&[io::IoSlice::new(buf)])
+        }
+
+        fn write_vectored(&mut self, bufs: &[io::IoSlice<'_>]) -> io::Result<usize> {
+            self.calls += 1;
+            let mut n = 0;
+            for buf in bufs {
+                let sz = buf.len().min(5 - n);
+                self.data.extend_from_slice(&buf[..sz]);
+                n += sz;
+            }
+            Ok(n)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn to_der_vectored_partial_writes() {
+        let data = (0..200).map(|i| i as u8).collect::<Vec<_>>();
+        let obj = OctetString::from(&data[..]);
+        let mut sink = VectoredSink::default();
+        let sz = obj
+            .to_der_vectored(&mut sink)
+            .expect("serialization failed");
+        assert_eq!(sz, 203);
+        assert_eq!(sink.data, obj.to_der_vec().unwrap());
+        // header and first bytes of content are sent in the same call
+        assert_eq!(sink.calls, 41);
+        let obj = BitString::new(3, &data);
+        let mut sink = VectoredSink::default();
+        let sz = obj
+            .to_der_vectored(&mut sink)
+            .expect("serialization failed");
+        assert_eq!(sz, 204);
+        assert_eq!(sink.data, obj.to_der_vec().unwrap());
+        // default implementation
+        let mut sink = VectoredSink::default();
+        let _ = 4u32
+            .to_der_vectored(&mut sink)
+            .expect("serialization failed");
+        assert_eq!(sink.data, &[2, 1, 4]);
+    }
+
+    #[cfg(feature = "digest")]
+    #[test]
+    fn digest_writer() {
+        use sha2::{Digest, Sha256};
+
+        let value = (1234u32, "abcdef", true);
+        let mut writer = DigestWriter::new(Sha256::new());
+        assert!(writer.is_empty());
+        let sz = value.write_der(&mut writer).expect("serialization failed");
+        assert_eq!(writer.len(), sz);
+        let encoded = value.to_der_vec().unwrap();
+        let (hash, len) = writer.finalize();
+        assert_eq!(len, encoded.len());
+        assert_eq!(hash, Sha256::digest(&encoded));
+    }
+
+    #[test]
+    fn ber2der_transcoder() {
+        // SET (indefinite) { INTEGER 2, INTEGER 1 }, OCTET STRING (non-minimal length),
+        // SEQUENCE { BOOLEAN 01 }
+        let input =
+            &hex_literal::hex!("31 80 02 01 02 02 01 01 00 00 04 81 02 aa bb 30 03 01 01 01");
+        let expected = tools::ber_to_der(input).expect("conversion failed");
+        // write one byte at a time
+        let mut transcoder = Ber2DerTranscoder::new(Vec::new());
+        for (i, b) in input.iter().enumerate() {
+            transcoder.write_all(&[*b]).expect("write failed");
+            if i == 9 {
+                // first object is complete
+                assert_eq!(transcoder.get_ref().len(), 8);
+                assert!(!transcoder.has_pending());
+            }
+        }
+        assert_eq!(transcoder.into_inner().expect("incomplete input"), expected);
+        // incomplete input
+        let mut transcoder = Ber2DerTranscoder::new(Vec::new());
+        transcoder.write_all(&[0x04, 0x02, 0xaa]).unwrap();
+        assert_eq!(transcoder.pending(), &[0x04, 0x02, 0xaa]);
+        let (e, _) = transcoder.into_inner().unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::UnexpectedEof);
+        // invalid input (truncated BOOLEAN in a complete SEQUENCE)
+        let mut transcoder = Ber2DerTranscoder::new(Vec::new());
+        let e = transcoder
+            .write_all(&[0x30, 0x03, 0x01, 0x02, 0xff])
+            .unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+        // invalid input is discarded
+        assert!(!transcoder.has_pending());
+        transcoder
+            .write_all(&[0x01, 0x01, 0x01])
+            .expect("write failed");
+        assert_eq!(transcoder.get_ref(), &[0x01, 0x01, 0xff]);
+        // objects converted before an error are reported, the rejected input is not consumed
+        let mut transcoder = Ber2DerTranscoder::new(Vec::new());
+        let input = &[0x01, 0x01, 0x01, 0x30, 0x03, 0x01, 0x02, 0xff];
+        assert_eq!(transcoder.write(input).expect("write failed"), 3);
+        assert_eq!(transcoder.get_ref(), &[0x01, 0x01, 0xff]);
+        assert!(!transcoder.has_pending());
+        let e = transcoder.write(&input[3..]).unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+        assert!(!transcoder.has_pending());
+        // I/O errors keep the previous pending input only
+        let mut output = [0u8; 2];
+        let mut transcoder = Ber2DerTranscoder::new(&mut output[..]);
+        transcoder.write_all(&[0x01, 0x01]).expect("write failed");
+        let e = transcoder.write(&[0x01]).unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::WriteZero);
+        assert_eq!(transcoder.pending(), &[0x01, 0x01]);
+    }
+
+    #[test]
+    fn ber2der_transcoder_incremental() {
+        // SEQUENCE (indefinite) { SEQUENCE (indefinite) { OCTET STRING aa bb }, NULL }
+        let input = &hex_literal::hex!("30 80 30 80 04 02 aa bb 00 00 05 00 00 00");
+        let mut transcoder = Ber2DerTranscoder::new(Vec::new());
+        transcoder.write_all(&input[..6]).expect("write failed");
+        // the content of the OCTET STRING is skipped without waiting for it
+        assert_eq!((transcoder.scan_pos, transcoder.scan_depth), (8, 2));
+        transcoder.write_all(&input[6..11]).expect("write failed");
+        assert_eq!((transcoder.scan_pos, transcoder.scan_depth), (10, 1));
+        assert!(transcoder.get_ref().is_empty());
+        transcoder.write_all(&input[11..]).expect("write failed");
+        assert!(!transcoder.has_pending());
+        assert_eq!(
+            transcoder.get_ref(),
+            &tools::ber_to_der(input).expect("conversion failed")
+        );
+        // primitive object with an indefinite length
+        let mut transcoder = Ber2DerTranscoder::new(Vec::new());
+        let e = transcoder.write_all(&[0x30, 0x80, 0x04, 0x80]).unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+        assert!(!transcoder.has_pending());
+    }
+}
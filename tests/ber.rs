@@ -110,6 +110,30 @@ fn from_ber_endofcontent() {
     assert_eq!(rem, &[]);
 }
 
+#[test]
+fn from_ber_endofcontent_context() {
+    // [0] with length 0 is not an end-of-contents marker
+    let input = &hex!("30 80 80 00 02 01 01 00 00 ff");
+    let (rem, any) = Any::from_ber(input).expect("parsing failed");
+    assert_eq!(rem, &[0xff]);
+    assert_eq!(any.data, &hex!("80 00 02 01 01"));
+    // invalid markers
+    let e = Any::from_ber(&hex!("30 80 00 01 00 00 00")).expect_err("parsing should fail");
+    assert_eq!(e, Err::Error(Error::InvalidEndOfContent));
+    let e = Any::from_ber(&hex!("30 80 20 00 00 00")).expect_err("parsing should fail");
+    assert_eq!(e, Err::Error(Error::InvalidEndOfContent));
+    // marker in definite length content
+    let (_, any) = Any::from_ber(&hex!("30 05 02 01 01 00 00")).expect("parsing failed");
+    let v = SequenceIterator::<Any, BerParser>::new(any.data).collect::<Vec<_>>();
+    assert_eq!(v.len(), 2);
+    assert_eq!(v[1], Err(Error::UnexpectedEndOfContent));
+    // manual reading of indefinite length content
+    let (rem, eoc) = EndOfContent::parse_ber(&hex!("00 00 ff")).expect("parsing failed");
+    assert_eq!((rem, eoc), (&[0xff][..], Some(EndOfContent::new())));
+    let (rem, eoc) = EndOfContent::parse_ber(&hex!("80 00")).expect("parsing failed");
+    assert_eq!((rem, eoc), (&hex!("80 00")[..], None));
+}
+
 #[test]
 fn from_ber_generalizedtime() {
     let input = &hex!("18 0F 32 30 30 32 31 32 31 33 31 34 32 39 32 33 5A FF");
@@ -509,10 +533,133 @@ fn from_ber_tagged_optional_cov() {
     assert!(r.is_err());
 }
 
+#[test]
+fn from_ber_application_wrapped() {
+    // [APPLICATION 1] { OID 1.2.3.4, INTEGER 2 }
+    let input = &hex!("61 08 06 03 2a 03 04 02 01 02");
+    let (rem, wrapped) = ApplicationWrapped::<u32>::from_ber(input).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(wrapped.tag, Tag(1));
+    assert_eq!(wrapped.oid, oid!(1.2.3 .4));
+    assert_eq!(wrapped.inner, 2);
+    // wrong class
+    let input = &hex!("a1 08 06 03 2a 03 04 02 01 02");
+    let _ = ApplicationWrapped::<u32>::from_ber(input).expect_err("parsing should fail");
+}
+
+#[test]
+fn from_ber_gss_token_header() {
+    // SPNEGO OID (1.3.6.1.5.5.2), followed by a non-ASN.1 inner token
+    let input = &hex!("60 0a 06 06 2b 06 01 05 05 02 01 02");
+    let (rem, (mech, token)) = parse_gss_token_header(input).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(mech, oid!(1.3.6 .1 .5 .5 .2));
+    assert_eq!(token, &[0x01, 0x02]);
+    // wrong application tag
+    let input = &hex!("61 0a 06 06 2b 06 01 05 05 02 01 02");
+    let _ = parse_gss_token_header(input).expect_err("parsing should fail");
+}
+
 #[test]
 fn from_ber_universalstring() {
     let input = &hex!("1C 10 00000061 00000062 00000063 00000064");
     let (rem, result) = UniversalString::from_ber(input).expect("parsing failed");
     assert_eq!(result.as_ref(), "abcd");
     assert_eq!(rem, &[]);
+    // invalid length or code points
+    let input = &hex!("1C 03 00000061");
+    assert_eq!(
+        UniversalString::from_ber(input),
+        Err(Err::Error(Error::StringInvalidCharset))
+    );
+    let input = &hex!("1C 04 0000d800");
+    assert_eq!(
+        UniversalString::from_ber(input),
+        Err(Err::Error(Error::StringInvalidCharset))
+    );
+    // checked constructor from UCS-4
+    let s = UniversalString::try_new(&hex!("00000061 0001f600")).expect("valid string");
+    assert_eq!(s.as_ref(), "a\u{1f600}");
+    assert!(UniversalString::try_new(&hex!("00000061 00")).is_err());
+    assert!(UniversalString::try_new(&hex!("00110000")).is_err());
+    assert!(UniversalString::try_new(&hex!("0000dfff")).is_err());
+}
+
+#[test]
+fn from_ber_custom_error() {
+    use std::convert::TryFrom;
+
+    #[derive(Debug, PartialEq)]
+    enum MyError {
+        Asn1(Error),
+        NotPositive,
+    }
+
+    impl From<Error> for MyError {
+        fn from(e: Error) -> Self {
+            MyError::Asn1(e)
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Positive(u32);
+
+    impl<'a> TryFrom<Any<'a>> for Positive {
+        type Error = MyError;
+
+        fn try_from(any: Any<'a>) -> Result<Self, MyError> {
+            match u32::try_from(any)? {
+                0 => Err(MyError::NotPositive),
+                v => Ok(Positive(v)),
+            }
+        }
+    }
+
+    // SEQUENCE { INTEGER, Positive }, with content parsed by a custom parser
+    fn parse_pair(i: &[u8]) -> ParseResult<'_, (u32, Positive), MyError> {
+        let (i, a) = u32::from_ber_into_err::<MyError>(i)?;
+        let (i, b) = Positive::from_ber(i)?;
+        Ok((i, (a, b)))
+    }
+
+    let (_, (a, b)) = parse_pair(&hex!("02 01 01 02 01 02")).expect("parsing failed");
+    assert_eq!((a, b), (1, Positive(2)));
+    // application error is propagated
+    let res = parse_pair(&hex!("02 01 01 02 01 00"));
+    assert_eq!(res, Err(nom::Err::Error(MyError::NotPositive)));
+    // errors from this crate are converted
+    let res = parse_pair(&hex!("01 01 ff 02 01 00"));
+    assert_eq!(
+        res,
+        Err(nom::Err::Error(MyError::Asn1(Error::unexpected_tag(
+            Some(Tag::Integer),
+            Tag::Boolean
+        ))))
+    );
+}
+
+#[test]
+fn from_ber_trailing_content() {
+    // [0] EXPLICIT { INTEGER 2, NULL }: NULL is not consumed
+    let input = &hex!("a0 05 02 01 02 05 00");
+    let res = TaggedExplicit::<u32, Error, 0>::from_ber(input);
+    let expected = Error::TrailingContent {
+        tag: Tag(0),
+        remaining: 2,
+    };
+    assert_eq!(res, Err(nom::Err::Error(expected.clone())));
+    let res = TaggedExplicit::<u32, Error, 0>::from_der(input);
+    assert_eq!(res, Err(nom::Err::Error(expected)));
+    // SEQUENCE { INTEGER 1, INTEGER 2, INTEGER 3 } as a pair
+    let input = &hex!("30 09 02 01 01 02 01 02 02 01 03");
+    let res = <(u32, u32)>::from_ber(input);
+    assert_eq!(
+        res,
+        Err(nom::Err::Error(Error::TrailingContent {
+            tag: Tag::Sequence,
+            remaining: 3
+        }))
+    );
+    let (_, t) = <(u32, u32, u32)>::from_ber(input).expect("parsing failed");
+    assert_eq!(t, (1, 2, 3));
 }
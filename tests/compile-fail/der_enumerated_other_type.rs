@@ -0,0 +1,24 @@
+fn test_other_type() {
+    use asn1_rs::*;
+
+    /// Should not compile: catch-all field must be u32
+    #[derive(Debug, PartialEq, DerEnumerated)]
+    pub enum E0 {
+        A,
+        #[asn1(other)]
+        Other(u16),
+    }
+
+    /// Should not compile: catch-all field must be i64 for INTEGER values
+    #[derive(Debug, PartialEq, DerEnumerated)]
+    #[asn1(integer)]
+    pub enum E1 {
+        A,
+        #[asn1(other)]
+        Other(u32),
+    }
+}
+
+fn main() {
+    test_other_type();
+}
@@ -72,6 +72,16 @@ fn methods_error() {
     let e2 = Error::from(nom::Err::Incomplete(nom::Needed::new(2)));
     assert!(e1 != e2);
     //
+    let e = Error::DerConstraintFailed(DerConstraint::NonCanonicalBoolean);
+    assert!(e.is_der_violation());
+    assert_eq!(e.der_constraint(), Some(DerConstraint::NonCanonicalBoolean));
+    // the deprecated variant is kept for compatibility
+    #[allow(deprecated)]
+    let e = Error::DerConstraintFailed(DerConstraint::InvalidBoolean);
+    assert!(e.is_der_violation());
+    assert!(!Error::BerTypeError.is_der_violation());
+    assert_eq!(Error::BerTypeError.der_constraint(), None);
+    //
     let e = SerializeError::from(Error::BerTypeError);
     let s = format!("{}", e);
     assert!(s.starts_with("ASN.1 error:"));
@@ -83,6 +93,27 @@ fn methods_error() {
     let e = SerializeError::from(io::Error::new(io::ErrorKind::Other, "msg"));
     let s = format!("{}", e);
     assert!(s.starts_with("I/O error:"));
+    assert!(e.is_io_error());
+    //
+    let e = SerializeError::LengthOverflow;
+    assert_eq!(format!("{}", e), "Length overflow");
+    assert!(!e.is_io_error());
+    let e = SerializeError::from_charset_error(
+        Tag::Ia5String,
+        Error::StringInvalidCharacters(vec!['é']),
+    );
+    assert!(matches!(
+        e,
+        SerializeError::InvalidCharset {
+            tag: Tag::Ia5String,
+            ..
+        }
+    ));
+    let e = SerializeError::from_charset_error(Tag::Ia5String, Error::StringInvalidCharset);
+    assert!(matches!(
+        e,
+        SerializeError::ASN1Error(Error::StringInvalidCharset)
+    ));
 }
 
 #[test]
@@ -3,7 +3,7 @@ use hex_literal::hex;
 use nom::sequence::pair;
 use nom::Needed;
 use std::collections::BTreeSet;
-use std::convert::TryInto;
+use std::convert::{TryFrom, TryInto};
 
 #[test]
 fn from_der_any() {
@@ -50,6 +50,23 @@ fn from_der_bitstring() {
         )))
     );
     //
+    // empty bit string, with unused bits
+    //
+    let res = BitString::from_der(&hex!("03 01 03"));
+    assert_eq!(
+        res,
+        Err(Err::Error(Error::DerConstraintFailed(
+            DerConstraint::EmptyBitStringUnusedBits
+        )))
+    );
+    let (_, result) = BitString::from_der(&hex!("03 01 00")).expect("parsing failed");
+    assert!(result.data.is_empty());
+    //
+    // too many unused bits
+    //
+    let res = BitString::from_der(&hex!("03 02 08 00"));
+    assert_eq!(res, Err(Err::Error(Error::InvalidLength)));
+    //
     // long form of length (invalid, < 127)
     //
     // let input = &hex!("03 81 04 06 6e 5d c0");
@@ -62,7 +79,9 @@ fn from_der_bitstring_constructed() {
     let bytes: &[u8] = &hex!("23 81 0c 03 03 00 0a 3b 03 05 04 5f 29 1c d0");
     assert_eq!(
         BitString::from_der(bytes),
-        Err(Err::Error(Error::ConstructUnexpected))
+        Err(Err::Error(Error::DerConstraintFailed(
+            DerConstraint::Constructed
+        )))
     );
 }
 
@@ -93,7 +112,7 @@ fn from_der_bool() {
     assert_eq!(
         res,
         Err(Err::Error(Error::DerConstraintFailed(
-            DerConstraint::InvalidBoolean
+            DerConstraint::NonCanonicalBoolean
         )))
     );
     // bool type
@@ -291,6 +310,18 @@ fn from_der_sequence_vec() {
     assert_eq!(rem, &[]);
 }
 
+#[test]
+fn from_der_sequence_tuple() {
+    let input = &hex!("30 0a 02 03 01 00 01 0c 03 61 62 63");
+    let (rem, (a, b)) = <(u32, Utf8String)>::from_der(input).expect("parsing failed");
+    assert_eq!(a, 65537);
+    assert_eq!(b.as_ref(), "abc");
+    assert_eq!(rem, &[]);
+    // wrong tag
+    let input = &hex!("31 0a 02 03 01 00 01 0c 03 61 62 63");
+    let _ = <(u32, Utf8String)>::from_der(input).expect_err("parsing should fail");
+}
+
 #[test]
 fn from_der_iter_sequence_parse() {
     let input = &hex!("30 0a 02 03 01 00 01 02 03 01 00 01");
@@ -639,3 +670,57 @@ fn from_der_tagged_optional_cov() {
     let p = OptTaggedParser::from(Tag(1));
     let _ = format!("{:?}", p);
 }
+
+#[test]
+fn from_der_bool_invalid_length() {
+    // parsing must fail without panicking
+    assert_eq!(
+        Boolean::from_der(&hex!("01 00")),
+        Err(Err::Error(Error::InvalidLength))
+    );
+    assert_eq!(
+        bool::from_der(&hex!("01 02 ff ff")),
+        Err(Err::Error(Error::InvalidLength))
+    );
+    // Any built by hand, with a header inconsistent with data
+    let header = Header::new(Class::Universal, false, Tag::Boolean, Length::Definite(1));
+    let any = Any::new(header, &[]);
+    assert_eq!(Boolean::try_from(&any), Err(Error::InvalidLength));
+}
+
+#[test]
+fn check_der_recursive() {
+    // SEQUENCE { SEQUENCE { BOOLEAN 01 (non-canonical) } }
+    let (_, any) = Any::from_ber(&hex!("30 05 30 03 01 01 01")).expect("parsing failed");
+    assert!(Sequence::check_constraints(&any).is_ok());
+    assert_eq!(
+        Sequence::check_der_recursive(&any),
+        Err(Error::DerConstraintFailed(
+            DerConstraint::NonCanonicalBoolean
+        ))
+    );
+    // SEQUENCE OF [0] EXPLICIT INTEGER, with a non-minimal INTEGER
+    let (_, any) = Any::from_ber(&hex!("30 05 a0 03 02 01 01")).expect("parsing failed");
+    assert!(SequenceOf::<TaggedExplicit<Integer, Error, 0>>::check_der_recursive(&any).is_ok());
+    let (_, any) = Any::from_ber(&hex!("30 06 a0 04 02 02 00 01")).expect("parsing failed");
+    assert_eq!(
+        SequenceOf::<TaggedExplicit<Integer, Error, 0>>::check_der_recursive(&any),
+        Err(Error::DerConstraintFailed(
+            DerConstraint::IntegerLeadingZeroes
+        ))
+    );
+    // recursion is limited
+    let mut input = hex!("05 00").to_vec();
+    for _ in 0..4 {
+        let mut outer = vec![0x30, input.len() as u8];
+        outer.extend_from_slice(&input);
+        input = outer;
+    }
+    let (_, any) = Any::from_ber(&input).expect("parsing failed");
+    assert!(Any::check_der_recursive(&any).is_ok());
+    assert!(Any::check_constraints_ctx(&any, DerCheckContext::with_max_depth(4)).is_ok());
+    assert_eq!(
+        Any::check_constraints_ctx(&any, DerCheckContext::with_max_depth(3)),
+        Err(Error::BerMaxDepth)
+    );
+}
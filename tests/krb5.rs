@@ -106,3 +106,26 @@ fn to_der_krb5_principalname() {
     let (_, principal2) = PrincipalName::from_der(&v).expect("parsing failed");
     assert!(principal.eq(&principal2));
 }
+
+#[test]
+fn krb5_generalstring_utf8() {
+    // MIT Kerberos may encode UTF-8 in KerberosString
+    let input = &hex!("1b 06 4a c3 b6 6e 65 73");
+    assert!(GeneralString::from_der(input).is_err());
+    let (rem, s) = GeneralStringUtf8::from_der(input).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(s.as_ref(), "Jönes");
+    assert_eq!(s.warnings(), &[GeneralStringWarning::NonAsciiContent]);
+    assert!(s.to_strict().is_err());
+    // ASCII content does not raise warnings
+    let (_, s) =
+        GeneralStringUtf8::from_der(&hex!("1b 05 4a 6f 6e 65 73")).expect("parsing failed");
+    assert!(s.warnings().is_empty());
+    assert_eq!(
+        s.to_strict().expect("strict conversion failed").as_ref(),
+        "Jones"
+    );
+    // round-trip
+    let v = s.to_der_vec().expect("serialization failed");
+    assert_eq!(&v, &hex!("1b 05 4a 6f 6e 65 73"));
+}
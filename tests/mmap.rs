@@ -0,0 +1,56 @@
+#![cfg(feature = "mmap")]
+
+use asn1_rs::*;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Write `data` to a temporary file, and map it
+fn map_bytes(name: &str, data: &[u8]) -> Mmap {
+    let path: PathBuf =
+        std::env::temp_dir().join(format!("asn1-rs-{}-{}.der", name, std::process::id()));
+    File::create(&path)
+        .and_then(|mut f| f.write_all(data))
+        .expect("could not write file");
+    let file = File::open(&path).expect("could not open file");
+    // SAFETY: the file is not modified while mapped
+    let map = unsafe { Mmap::map(&file) }.expect("could not map file");
+    let _ = fs::remove_file(&path);
+    map
+}
+
+#[test]
+fn mmap_complete() {
+    let map = map_bytes("ok", &[0x02, 0x01, 0x2a]);
+    assert_eq!(from_der_mmap_complete::<u32>(&map), Ok(42));
+    let (rem, any) = from_ber_mmap::<Any>(&map).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(any.tag(), Tag::Integer);
+}
+
+#[test]
+fn mmap_trailing_data() {
+    let map = map_bytes("trailing", &[0x02, 0x01, 0x2a, 0xff]);
+    assert_eq!(
+        from_der_mmap_complete::<u32>(&map),
+        Err(Error::TrailingContent {
+            tag: Tag::Integer,
+            remaining: 1
+        })
+    );
+    let (rem, v) = from_der_mmap::<u32>(&map).expect("parsing failed");
+    assert_eq!((rem, v), (&[0xff][..], 42));
+}
+
+#[test]
+fn mmap_truncated() {
+    let map = map_bytes("truncated", &[0x02, 0x02, 0x2a]);
+    assert!(matches!(
+        from_der_mmap_complete::<u32>(&map),
+        Err(Error::Incomplete(_))
+    ));
+    assert!(matches!(
+        from_der_mmap::<u32>(&map),
+        Err(nom::Err::Incomplete(_))
+    ));
+}
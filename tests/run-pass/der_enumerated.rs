@@ -0,0 +1,92 @@
+use asn1_rs::*;
+use hex_literal::hex;
+use std::convert::TryFrom;
+
+#[derive(Debug, PartialEq, DerEnumerated, ToDer)]
+pub enum E1 {
+    A,
+    B,
+    C = 5,
+    D,
+}
+
+#[derive(Debug, PartialEq, DerEnumerated, ToDer)]
+pub enum E2 {
+    #[asn1(value = 1)]
+    A,
+    #[asn1(value = "3")]
+    B,
+    #[asn1(other)]
+    Other(u32),
+}
+
+#[derive(Debug, PartialEq, DerEnumerated, ToDer)]
+#[asn1(integer)]
+pub enum Version {
+    #[asn1(value = 0)]
+    V1,
+    V2,
+    #[asn1(other)]
+    Unknown(i64),
+}
+
+#[derive(Debug, PartialEq, DerEnumerated, ToDer)]
+#[asn1(integer)]
+pub enum Signed {
+    Minus = -1,
+    Zero,
+    #[asn1(value = "-128")]
+    Min,
+}
+
+#[derive(Debug, PartialEq, DerSequence)]
+pub struct T1 {
+    pub version: Version,
+    pub e: E2,
+}
+
+fn main() {
+    // values from discriminants
+    let (rem, e1) = E1::from_der(&hex!("0a0106")).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(e1, E1::D);
+    assert_eq!(u32::from(&E1::B), 1);
+    assert_eq!(e1.to_der_vec().expect("serialization failed"), &hex!("0a0106"));
+    // unknown value without catch-all variant
+    assert!(E1::from_der(&hex!("0a0102")).is_err());
+    // wrong tag
+    assert!(E1::from_der(&hex!("020101")).is_err());
+
+    // catch-all variant
+    let (_, e2) = E2::from_der(&hex!("0a0103")).expect("parsing failed");
+    assert_eq!(e2, E2::B);
+    let (_, e2) = E2::from_ber(&hex!("0a0107")).expect("parsing failed");
+    assert_eq!(e2, E2::Other(7));
+    assert_eq!(e2.to_der_vec().expect("serialization failed"), &hex!("0a0107"));
+    assert_eq!(E2::try_from(1u32).expect("conversion failed"), E2::A);
+    // the catch-all variant cannot hold the value of a named variant
+    assert!(E2::Other(3).to_der_vec().is_err());
+
+    // INTEGER values, in a sequence
+    let input = &hex!("3006 020102 0a0101");
+    let (rem, t1) = T1::from_der(input).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(t1.version, Version::Unknown(2));
+    assert_eq!(t1.e, E2::A);
+    let (_, v) = Version::from_der(&hex!("020101")).expect("parsing failed");
+    assert_eq!(v, Version::V2);
+    assert_eq!(v.to_der_vec().expect("serialization failed"), &hex!("020101"));
+    assert!(Version::from_der(&hex!("0a0101")).is_err());
+    // negative unknown values round-trip
+    let (_, v) = Version::from_der(&hex!("0201fe")).expect("parsing failed");
+    assert_eq!(v, Version::Unknown(-2));
+    assert_eq!(v.to_der_vec().expect("serialization failed"), &hex!("0201fe"));
+    assert!(Version::Unknown(1).to_der_vec().is_err());
+
+    // negative values
+    let (_, s) = Signed::from_der(&hex!("0201ff")).expect("parsing failed");
+    assert_eq!(s, Signed::Minus);
+    assert_eq!(i64::from(&Signed::Zero), 0);
+    assert_eq!(Signed::Min.to_der_vec().expect("serialization failed"), &hex!("020180"));
+    assert!(Signed::from_der(&hex!("020101")).is_err());
+}
@@ -0,0 +1,86 @@
+use asn1_rs::*;
+use hex_literal::hex;
+use std::convert::TryFrom;
+
+#[derive(Debug, PartialEq, DerSequence, ToDer)]
+// #[debug_derive]
+pub struct T1 {
+    a: u32,
+    #[asn1(tag = "0", explicit, optional)]
+    b: Option<u16>,
+    #[asn1(tag = "1", implicit, optional, default = "5")]
+    c: u32,
+    #[asn1(optional, default = "Vec::new()", skip_serializing_if = "Vec::is_empty")]
+    d: Vec<u16>,
+}
+
+// SET, with fields declared in a different order than the DER encoding
+#[derive(Debug, PartialEq, DerSequence, ToDer)]
+#[asn1(set)]
+pub struct T2 {
+    #[asn1(tag = "APPLICATION 2", implicit)]
+    a: u16,
+    b: bool,
+    c: u16,
+}
+
+// SET mixing a constructed ([0] EXPLICIT) and a primitive ([1] IMPLICIT) tag: components are
+// sorted by tag number, not by encoding
+#[derive(Debug, PartialEq, DerSequence, ToDer)]
+#[asn1(set)]
+pub struct T3 {
+    #[asn1(tag = "0", explicit)]
+    a: u16,
+    #[asn1(tag = "1", implicit)]
+    b: u16,
+}
+
+fn main() {
+    let input = &hex!("3008 020101 a003020102");
+    let (rem, t1) = T1::from_der(input).expect("parsing failed");
+    assert!(rem.is_empty());
+    let expected = T1 {
+        a: 1,
+        b: Some(2),
+        c: 5,
+        d: vec![],
+    };
+    assert_eq!(t1, expected);
+    // DEFAULT and empty values are omitted
+    assert_eq!(t1.to_der_vec().expect("serialization failed"), input);
+
+    let t1 = T1 {
+        a: 1,
+        b: None,
+        c: 3,
+        d: vec![4],
+    };
+    let v = t1.to_der_vec().expect("serialization failed");
+    assert_eq!(v, hex!("300b 020101 810103 3003020104"));
+
+    assert_eq!(T2::TAG, Tag::Set);
+    let t2 = T2 {
+        a: 1,
+        b: true,
+        c: 3,
+    };
+    let v = t2.to_der_vec().expect("serialization failed");
+    assert_eq!(v, hex!("3109 0101ff 020103 420101"));
+    // SET components are parsed in any order
+    let (rem, t2_parsed) = T2::from_der(&v).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(t2_parsed, t2);
+    let (rem, t2_parsed) = T2::from_der(&hex!("3109 420101 020103 0101ff")).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(t2_parsed, t2);
+    let any = Any::from_ber(&v).expect("parsing failed").1;
+    assert_eq!(T2::try_from(&any), Ok(t2));
+
+    let t3 = T3 { a: 5, b: 0 };
+    let v = t3.to_der_vec().expect("serialization failed");
+    assert_eq!(v, hex!("3108 a003020105 810100"));
+    assert_eq!(tools::validate_der(&v), Ok(4));
+    let (rem, t3_parsed) = T3::from_der(&v).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(t3_parsed, t3);
+}
@@ -0,0 +1,58 @@
+use asn1_rs::*;
+use hex_literal::hex;
+
+#[derive(Debug, PartialEq, DerSequence, ToDer)]
+// #[debug_derive]
+pub struct T0 {
+    #[asn1(default)]
+    a: bool,
+    b: u16,
+}
+
+#[derive(Debug, PartialEq, DerSequence)]
+#[asn1(strict_default)]
+// #[debug_derive]
+pub struct T1 {
+    #[asn1(tag = "0", default)]
+    a: u16,
+    #[asn1(tag = "1", default = "3")]
+    b: u16,
+}
+
+fn main() {
+    // default value absent
+    let (rem, t0) = T0::from_der(&hex!("3003 020105")).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(t0, T0 { a: false, b: 5 });
+    assert_eq!(t0.to_der_vec().unwrap(), &hex!("3003 020105"));
+
+    // default value present: accepted without strict mode, omitted on encode
+    let (_, t0) = T0::from_der(&hex!("3006 010100 020105")).expect("parsing failed");
+    assert_eq!(t0, T0 { a: false, b: 5 });
+    assert_eq!(t0.to_der_vec().unwrap(), &hex!("3003 020105"));
+
+    // non-default value
+    let t0 = T0 { a: true, b: 5 };
+    assert_eq!(t0.to_der_vec().unwrap(), &hex!("3006 0101ff 020105"));
+
+    // strict mode
+    let (_, t1) = T1::from_der(&hex!("3005 a103 020104")).expect("parsing failed");
+    assert_eq!(t1, T1 { a: 0, b: 4 });
+    let err = T1::from_der(&hex!("3005 a003 020100")).expect_err("default value accepted");
+    assert_eq!(
+        err,
+        nom::Err::Error(Error::DerConstraintFailed(
+            DerConstraint::DefaultValueEncoded
+        ))
+    );
+    let err = T1::from_der(&hex!("3005 a103 020103")).expect_err("default value accepted");
+    assert_eq!(
+        err,
+        nom::Err::Error(Error::DerConstraintFailed(
+            DerConstraint::DefaultValueEncoded
+        ))
+    );
+    // BER parsers are not affected
+    let (_, t1) = T1::from_ber(&hex!("3005 a003 020100")).expect("parsing failed");
+    assert_eq!(t1, T1 { a: 0, b: 3 });
+}
@@ -1,5 +1,6 @@
 use asn1_rs::*;
 use hex_literal::hex;
+use std::convert::TryFrom;
 
 #[derive(Debug, PartialEq, DerSequence)]
 pub struct T1 {
@@ -13,4 +14,9 @@ fn main() {
     let (rem, t1) = T1::from_der(input).expect("parsing failed");
     assert!(rem.is_empty());
     assert_eq!(t1, T1 { a: 1, b: 2, c: 3 });
+    // parse by reference, keeping the object
+    let (_, any) = Any::from_der(input).expect("parsing failed");
+    let t1 = T1::try_from(&any).expect("parsing failed");
+    assert_eq!(t1, T1 { a: 1, b: 2, c: 3 });
+    assert_eq!(any.data.len(), 9);
 }
\ No newline at end of file
@@ -106,6 +106,26 @@ fn test_tag_explicit_private() {
 
     let input1 = &hex!("3005 e103020103");
     T6::from_der(input1).expect_err("parsing tag 1 should fail");
+
+    // same tag number, different classes
+    #[derive(Debug, PartialEq, DerSequence)]
+    pub struct T7 {
+        #[tag_explicit(PRIVATE 0)]
+        #[optional]
+        a: Option<u16>,
+        #[tag_explicit(0)]
+        b: u16,
+    }
+
+    let input0 = &hex!("300a e003020103 a003020104");
+    let (rem, t7) = T7::from_der(input0).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(t7, T7 { a: Some(3), b: 4 });
+
+    let input1 = &hex!("3005 a003020104");
+    let (rem, t7) = T7::from_der(input1).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(t7, T7 { a: None, b: 4 });
 }
 
 fn main() {
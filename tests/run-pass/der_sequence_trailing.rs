@@ -0,0 +1,31 @@
+use asn1_rs::*;
+use hex_literal::hex;
+use std::convert::TryFrom;
+
+#[derive(Debug, PartialEq, DerSequence)]
+pub struct T0 {
+    a: u16,
+}
+
+// extension marker: additional elements are ignored
+#[derive(Debug, PartialEq, DerSequence)]
+#[asn1(allow_trailing)]
+pub struct T1 {
+    a: u16,
+}
+
+fn main() {
+    let input = &hex!("3006 020103 020104");
+    let err = T0::from_der(input).expect_err("parsing should fail");
+    let expected = Error::TrailingContent {
+        tag: Tag::Sequence,
+        remaining: 3,
+    };
+    assert_eq!(err, nom::Err::Error(expected.clone()));
+    let (_, any) = Any::from_der(input).expect("parsing failed");
+    assert_eq!(T0::try_from(any), Err(expected));
+
+    let (rem, t1) = T1::from_der(input).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(t1, T1 { a: 3 });
+}
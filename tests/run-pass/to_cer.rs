@@ -0,0 +1,42 @@
+use asn1_rs::*;
+use hex_literal::hex;
+
+#[derive(Debug, PartialEq, DerSequence, ToDer)]
+pub struct T0<'a> {
+    a: u32,
+    #[tag_implicit(0)]
+    b: OctetString<'a>,
+}
+
+#[derive(Debug, PartialEq, DerSet, ToDer)]
+pub struct T1 {
+    #[tag_explicit(1)]
+    a: u32,
+    #[tag_implicit(0)]
+    b: Vec<u32>,
+}
+
+fn main() {
+    let data = vec![0xaa; 1200];
+    let t0 = T0 {
+        a: 1,
+        b: OctetString::from(&data[..]),
+    };
+    let cer = t0.to_encoded_vec(EncodingRules::Cer).expect("serialization failed");
+    // implicitly tagged strings are fragmented, using the type of the field
+    assert_eq!(&cer[..7], &hex!("30 80 02 01 01 a0 80"));
+    assert_eq!(&cer[7..11], &hex!("04 82 03 e8"));
+    assert_eq!(&cer[1011..1014], &hex!("04 81 c8"));
+    assert_eq!(&cer[1214..], &hex!("00 00 00 00"));
+    // DER is not changed
+    let der = t0.to_encoded_vec(EncodingRules::Der).expect("serialization failed");
+    assert_eq!(der, t0.to_der_vec().unwrap());
+
+    // SET items are ordered by tag, not by encoding
+    let t1 = T1 { a: 1, b: vec![2] };
+    let cer = t1.to_encoded_vec(EncodingRules::Cer).expect("serialization failed");
+    assert_eq!(
+        &cer,
+        &hex!("31 80 a0 80 02 01 02 00 00 a1 80 02 01 01 00 00 00 00")
+    );
+}
@@ -0,0 +1,52 @@
+use asn1_rs::*;
+use hex_literal::hex;
+
+#[derive(Debug, PartialEq, DerSequence, ToStatic)]
+pub struct T1<'a> {
+    a: u32,
+    b: Utf8String<'a>,
+    c: Option<OctetString<'a>>,
+    d: TaggedExplicit<Vec<Integer<'a>>, Error, 0>,
+}
+
+// tuple struct with a type parameter
+#[derive(Debug, PartialEq, ToStatic)]
+pub struct T2<'a, T>(Oid<'a>, T);
+
+#[derive(Debug, PartialEq, ToStatic)]
+pub enum T3<'a> {
+    A,
+    B(BitString<'a>),
+    C { s: Utf8String<'a>, n: u16 },
+}
+
+fn parse_static(input: &[u8]) -> T1<'static> {
+    let (_, t1) = T1::from_der(input).expect("parsing failed");
+    t1.to_static()
+}
+
+fn main() {
+    let input = hex!("30 14 02 01 01 0c 02 61 62 04 01 ff a0 08 30 06 02 01 02 02 01 03").to_vec();
+    let t1 = parse_static(&input);
+    drop(input);
+    assert_eq!(t1.a, 1);
+    assert_eq!(t1.b.as_ref(), "ab");
+    assert_eq!(t1.c.as_ref().map(|o| o.as_ref()), Some(&[0xff][..]));
+    let d = t1.d.into_inner();
+    assert_eq!(d.len(), 2);
+    assert_eq!(d[1].as_u32(), Ok(3));
+
+    let t2: T2<'static, u32> = {
+        let bytes = vec![0x2a, 0x03];
+        T2(Oid::new(bytes.as_slice().into()), 5u32).to_static()
+    };
+    assert_eq!(t2.0.to_id_string(), "1.2.3");
+    assert_eq!(t2.1, 5);
+
+    let t3: T3<'static> = {
+        let s = String::from("xyz");
+        T3::C { s: Utf8String::new(&s), n: 2 }.to_static()
+    };
+    assert_eq!(t3, T3::C { s: Utf8String::from("xyz".to_string()), n: 2 });
+    assert_eq!(T3::A.to_static(), T3::A);
+}
@@ -0,0 +1,89 @@
+//! Check that public types can be cloned and shared between threads
+//!
+//! These are compile-time checks: the tests fail to build if a type stops implementing
+//! `Clone`, `Send` or `Sync`.
+use asn1_rs::*;
+use std::borrow::Cow;
+
+fn assert_clone_send_sync<T: Clone + Send + Sync>() {}
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn parsed_types_are_clone_send_sync() {
+    assert_clone_send_sync::<Any<'static>>();
+    assert_clone_send_sync::<Header<'static>>();
+    assert_clone_send_sync::<Class>();
+    assert_clone_send_sync::<Tag>();
+    assert_clone_send_sync::<Length>();
+    assert_clone_send_sync::<UniversalValue<'static>>();
+    // universal types
+    assert_clone_send_sync::<BitString<'static>>();
+    assert_clone_send_sync::<Boolean>();
+    assert_clone_send_sync::<EmbeddedPdv<'static>>();
+    assert_clone_send_sync::<EndOfContent>();
+    assert_clone_send_sync::<Enumerated>();
+    assert_clone_send_sync::<GeneralizedTime>();
+    assert_clone_send_sync::<Integer<'static>>();
+    assert_clone_send_sync::<Null>();
+    assert_clone_send_sync::<OctetString<'static>>();
+    assert_clone_send_sync::<Oid<'static>>();
+    assert_clone_send_sync::<Real>();
+    assert_clone_send_sync::<Sequence<'static>>();
+    assert_clone_send_sync::<SequenceOf<Integer<'static>>>();
+    assert_clone_send_sync::<Set<'static>>();
+    assert_clone_send_sync::<SetOf<Integer<'static>>>();
+    assert_clone_send_sync::<UtcTime>();
+    assert_clone_send_sync::<ASN1DateTime>();
+    // strings
+    assert_clone_send_sync::<AnyString<'static>>();
+    assert_clone_send_sync::<BmpString<'static>>();
+    assert_clone_send_sync::<GeneralString<'static>>();
+    assert_clone_send_sync::<GeneralStringUtf8<'static>>();
+    assert_clone_send_sync::<GraphicString<'static>>();
+    assert_clone_send_sync::<Ia5String<'static>>();
+    assert_clone_send_sync::<NumericString<'static>>();
+    assert_clone_send_sync::<ObjectDescriptor<'static>>();
+    assert_clone_send_sync::<PrintableString<'static>>();
+    assert_clone_send_sync::<TeletexString<'static>>();
+    assert_clone_send_sync::<UniversalString<'static>>();
+    assert_clone_send_sync::<Utf8String<'static>>();
+    assert_clone_send_sync::<VideotexString<'static>>();
+    assert_clone_send_sync::<VisibleString<'static>>();
+    // tagged values
+    assert_clone_send_sync::<TaggedExplicit<Integer<'static>, Error, 0>>();
+    assert_clone_send_sync::<TaggedImplicit<Integer<'static>, Error, 0>>();
+    assert_clone_send_sync::<ApplicationExplicit<Integer<'static>, Error, 0>>();
+    assert_clone_send_sync::<TaggedParser<'static, Explicit, Integer<'static>>>();
+    assert_clone_send_sync::<TaggedParser<'static, Implicit, Integer<'static>>>();
+    assert_clone_send_sync::<OptTaggedParser>();
+    // other types
+    assert_clone_send_sync::<AlgorithmIdentifier<'static>>();
+    assert_clone_send_sync::<Attribute<'static>>();
+    assert_clone_send_sync::<DerOrdered<Integer<'static>>>();
+    assert_clone_send_sync::<LocatedError>();
+    assert_clone_send_sync::<ParserProfile>();
+    assert_clone_send_sync::<RecoveredTree<'static>>();
+    assert_clone_send_sync::<TlvEvent<'static>>();
+    assert_clone_send_sync::<TlvTokenizer<'static>>();
+}
+
+#[test]
+fn errors_are_send_sync() {
+    assert_clone_send_sync::<Error>();
+    assert_clone_send_sync::<DerConstraint>();
+    assert_clone_send_sync::<OidParseError>();
+    // contains a `std::io::Error`, which cannot be cloned
+    assert_send_sync::<SerializeError>();
+}
+
+#[test]
+fn cloned_value_crosses_threads() {
+    let bytes = [0x30, 0x06, 0x02, 0x01, 0x01, 0x0c, 0x01, 0x61];
+    let (_, seq) = Sequence::from_der(&bytes).expect("parsing failed");
+    let seq = Sequence::new(Cow::Owned(seq.into_content().into_owned()));
+    let clone = seq.clone();
+    let handle = std::thread::spawn(move || clone.der_iter::<Any, Error>().count());
+    assert_eq!(handle.join().expect("thread panicked"), 2);
+    assert_eq!(seq.as_ref().len(), 6);
+}
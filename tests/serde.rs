@@ -0,0 +1,42 @@
+#![cfg(feature = "serde")]
+
+use asn1_rs::*;
+use hex_literal::hex;
+use serde_json::json;
+
+#[test]
+fn serde_errors() {
+    let e = Error::Field {
+        index: 2,
+        error: Box::new(Error::Field {
+            index: 0,
+            error: Box::new(Error::unexpected_tag(Some(Tag::Integer), Tag::Boolean)),
+        }),
+    };
+    let v = serde_json::to_value(&e).expect("serialization failed");
+    assert_eq!(
+        v,
+        json!({
+            "kind": "UnexpectedTag",
+            "message": Error::unexpected_tag(Some(Tag::Integer), Tag::Boolean).to_string(),
+            "context": [2, 0],
+            "expected_tag": 2,
+            "tag": 1,
+        })
+    );
+    let e = Error::DerConstraintFailed(DerConstraint::NonMinimalLength);
+    let v = serde_json::to_value(&e).expect("serialization failed");
+    assert_eq!(v["constraint"], "NonMinimalLength");
+    assert_eq!(v["context"], json!([]));
+    // located errors
+    let err = parse_der_any(&hex!("30 80 05 00 00 00")).unwrap_err();
+    let v = serde_json::to_value(&err).expect("serialization failed");
+    assert_eq!(v["offset"], 0);
+    assert_eq!(v["error"]["kind"], "DerConstraintFailed");
+    assert_eq!(v["error"]["constraint"], "IndefiniteLength");
+    // hints
+    let err = parse_der_hinted::<u32>(&hex!("81 01 05")).unwrap_err();
+    let v = serde_json::to_value(&err).expect("serialization failed");
+    assert_eq!(v["error"]["kind"], "UnexpectedTag");
+    assert!(v["hint"].as_str().unwrap().contains("IMPLICIT"));
+}
@@ -27,8 +27,8 @@ macro_rules! test_string_invalid_charset {
 fn to_der_length() {
     // indefinite length
     let length = Length::Indefinite;
-    let v = length.to_der_vec().expect("serialization failed");
-    assert_eq!(&v, &[0x80]);
+    let e = length.to_der_vec().expect_err("indefinite length in DER");
+    assert!(matches!(e, SerializeError::UnsupportedIndefiniteInDer));
     // definite, short form
     let length = Length::Definite(3);
     let v = length.to_der_vec().expect("serialization failed");
@@ -68,7 +68,7 @@ fn to_der_tag() {
     let v = (Class::Universal, false, Tag(0x1a1a))
         .to_der_vec()
         .expect("serialization failed");
-    assert_eq!(&v, &[0b1_1111, 0x9a, 0x34]);
+    assert_eq!(&v, &[0b1_1111, 0xb4, 0x1a]);
 }
 
 #[test]
@@ -79,7 +79,9 @@ fn to_der_header() {
     assert_eq!(&v, &[0x2, 0x0]);
     // indefinite length
     let header = Header::new(Class::Universal, false, Tag::Integer, Length::Indefinite);
-    let v = header.to_der_vec().expect("serialization failed");
+    let e = header.to_der_vec().expect_err("indefinite length in DER");
+    assert!(matches!(e, SerializeError::UnsupportedIndefiniteInDer));
+    let v = header.to_der_vec_raw().expect("serialization failed");
     assert_eq!(&v, &[0x2, 0x80]);
 }
 
@@ -175,6 +177,23 @@ fn to_der_generalizedtime() {
     assert_eq!(&v[2..], b"19991231235959Z");
     let (_, time2) = GeneralizedTime::from_der(&v).expect("decoding serialized object failed");
     assert!(time.eq(&time2));
+    // year cannot be represented
+    let dt = ASN1DateTime::new(10000, 1, 1, 0, 0, 0, None, ASN1TimeZone::Z);
+    let e = GeneralizedTime::new(dt)
+        .to_der_vec()
+        .expect_err("year out of range");
+    assert!(matches!(e, SerializeError::ValueOutOfRange { .. }));
+    // the year is also checked when writing only the content (65536 + 1999 must not wrap)
+    let dt = ASN1DateTime::new(67535, 1, 1, 0, 0, 0, None, ASN1TimeZone::Z);
+    let mut v = Vec::new();
+    let e = GeneralizedTime::new(dt.clone())
+        .write_der_content(&mut v)
+        .expect_err("year out of range");
+    assert!(matches!(e, SerializeError::ValueOutOfRange { .. }));
+    let e = GeneralizedTime::new(dt)
+        .to_ber_vec_original()
+        .expect_err("year out of range");
+    assert!(matches!(e, SerializeError::ValueOutOfRange { .. }));
     //
     // date with millisecond
     let dt = ASN1DateTime::new(1999, 12, 31, 23, 59, 59, Some(123), ASN1TimeZone::Z);
@@ -184,6 +203,40 @@ fn to_der_generalizedtime() {
     assert_eq!(&v[2..], b"19991231235959.123Z");
     let (_, time2) = GeneralizedTime::from_der(&v).expect("decoding serialized object failed");
     assert!(time.eq(&time2));
+    // leading zeros are kept, trailing zeros are removed
+    let dt = ASN1DateTime::new(1999, 12, 31, 23, 59, 59, Some(50), ASN1TimeZone::Z);
+    let v = GeneralizedTime::new(dt)
+        .to_der_vec()
+        .expect("serialization failed");
+    assert_eq!(&v[2..], b"19991231235959.05Z");
+    let dt = ASN1DateTime::new(1999, 12, 31, 23, 59, 59, Some(0), ASN1TimeZone::Z);
+    let v = GeneralizedTime::new(dt)
+        .to_der_vec()
+        .expect("serialization failed");
+    assert_eq!(&v[2..], b"19991231235959Z");
+}
+
+#[test]
+fn to_der_generalizedtime_offset() {
+    // (BER input, DER output)
+    let matrix: &[(&[u8], &[u8])] = &[
+        (b"20220101013000+0200", b"20211231233000Z"),
+        (b"20211231230000.25-0130", b"20220101003000.25Z"),
+        (b"20240301004500+0130", b"20240229231500Z"),
+        (b"19991231235959Z", b"19991231235959Z"),
+    ];
+    for (ber, der) in matrix {
+        let time = GeneralizedTime::from_bytes(ber).expect("parsing failed");
+        // DER converts to UTC, and is the same instant
+        let v = time.to_der_vec().expect("serialization failed");
+        assert_eq!(v[1] as usize, der.len());
+        assert_eq!(&v[2..], *der);
+        let (_, time2) = GeneralizedTime::from_der(&v).expect("decoding serialized object failed");
+        assert_eq!(time.to_utc(), Ok(time2));
+    }
+    // conversion out of the GeneralizedTime range (9999 -> 10000)
+    let time = GeneralizedTime::from_bytes(b"99991231230000-0200").expect("parsing failed");
+    assert!(time.to_der_vec().is_err());
 }
 
 #[test]
@@ -218,7 +271,8 @@ fn to_der_integer() {
     // signed i32 (> 0)
     encode_decode_assert_int(4, &[0x02, 0x01, 0x04]);
     // signed i32 (< 0)
-    encode_decode_assert_int(-4, &[0x02, 0x05, 0x00, 0xff, 0xff, 0xff, 0xfc]);
+    encode_decode_assert_int(-4, &[0x02, 0x01, 0xfc]);
+    encode_decode_assert_int(-129, &[0x02, 0x02, 0xff, 0x7f]);
 }
 
 #[test]
@@ -253,6 +307,29 @@ fn to_der_objectdescriptor() {
     test_string_invalid_charset!(ObjectDescriptor, "abcdéf");
 }
 
+#[test]
+fn to_der_printablestring() {
+    test_simple_string!(PrintableString, "abc def");
+    test_string_invalid_charset!(PrintableString, "abc*def");
+    // checked constructor
+    let s = PrintableString::try_new("abc def").expect("valid charset");
+    assert_eq!(s.as_ref(), "abc def");
+    let e = PrintableString::try_new("a*b@c*").expect_err("invalid charset");
+    assert_eq!(e, Error::StringInvalidCharacters(vec!['*', '@']));
+    // encoding an invalid string fails
+    let s = PrintableString::new("a*b");
+    let e = s.to_der_vec().expect_err("serialization should fail");
+    assert!(matches!(
+        e,
+        SerializeError::InvalidCharset { tag: Tag::PrintableString, ref chars } if chars == &['*']
+    ));
+    assert!(!e.is_io_error());
+    // BmpString can only contain characters from the Basic Multilingual Plane
+    assert!(BmpString::try_new("abc").is_ok());
+    let e = BmpString::try_new("a\u{1f600}").expect_err("invalid charset");
+    assert_eq!(e, Error::StringInvalidCharacters(vec!['\u{1f600}']));
+}
+
 #[test]
 fn to_der_octetstring() {
     let bytes: &[u8] = &hex!("01 02 03 04 05 06 07 08 09 0a 0b 0c 0d 0e 0f");
@@ -375,6 +452,16 @@ fn to_der_sequence() {
     assert_eq!(&v, &hex!("30 09 02 01 02 02 01 03 02 01 04"));
 }
 
+#[test]
+fn to_der_sequence_tuple() {
+    let v = (2, true, "abc").to_der_vec().expect("serialization failed");
+    assert_eq!(&v, &hex!("30 0b 02 01 02 01 01 ff 0c 03 61 62 63"));
+    let (_, (a, b, c)) = <(u32, bool, Utf8String)>::from_der(&v).expect("decoding failed");
+    assert_eq!(a, 2);
+    assert!(b);
+    assert_eq!(c.as_ref(), "abc");
+}
+
 #[test]
 fn to_der_set() {
     let it = [2u8, 3, 4].iter();
@@ -472,6 +559,50 @@ fn to_der_utctime() {
     assert_eq!(&v[2..], b"991231235959Z");
     let (_, time2) = UtcTime::from_der(&v).expect("decoding serialized object failed");
     assert!(time.eq(&time2));
+    // four-digit years are written using two digits, if in range
+    let dt = ASN1DateTime::new(1999, 12, 31, 23, 59, 59, None, ASN1TimeZone::Z);
+    let v = UtcTime::new(dt).to_der_vec().expect("serialization failed");
+    assert_eq!(&v[2..], b"991231235959Z");
+    let dt = ASN1DateTime::new(2050, 1, 1, 0, 0, 0, None, ASN1TimeZone::Z);
+    let e = UtcTime::new(dt)
+        .to_der_vec()
+        .expect_err("year out of range");
+    assert!(matches!(
+        e,
+        SerializeError::ValueOutOfRange {
+            tag: Tag::UtcTime,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn to_der_utctime_offset() {
+    // (BER input, DER output)
+    let matrix: &[(&[u8], &[u8])] = &[
+        (b"220101013000+0200", b"211231233000Z"),
+        (b"211231230000-0130", b"220101003000Z"),
+        (b"240301004500+0130", b"240229231500Z"),
+        (b"491231223000-0100", b"491231233000Z"),
+        (b"991231235959Z", b"991231235959Z"),
+    ];
+    for (ber, der) in matrix {
+        let time = UtcTime::from_bytes(ber).expect("parsing failed");
+        // BER keeps the offset
+        let v = time.to_ber_vec().expect("serialization failed");
+        assert_eq!(&v[2..], *ber);
+        let (_, time2) = UtcTime::from_ber(&v).expect("decoding serialized object failed");
+        assert_eq!(time, time2);
+        // DER converts to UTC, and is the same instant
+        let v = time.to_der_vec().expect("serialization failed");
+        assert_eq!(v[1] as usize, der.len());
+        assert_eq!(&v[2..], *der);
+        let (_, time2) = UtcTime::from_der(&v).expect("decoding serialized object failed");
+        assert_eq!(time.unix_timestamp_millis(), time2.unix_timestamp_millis());
+    }
+    // conversion out of the UTCTime range (2049 -> 2050)
+    let time = UtcTime::from_bytes(b"491231230000-0200").expect("parsing failed");
+    assert!(time.to_der_vec().is_err());
 }
 
 #[test]
@@ -493,6 +624,13 @@ fn to_der_universalstring() {
     assert_eq!(s.to_der_len(), Ok(4 + 4 * sz));
     let v = s.to_der_vec().expect("serialization failed");
     assert_eq!(v.len(), 4 + 4 * sz);
+    // non-ASCII characters are encoded using 4 bytes each
+    let s = UniversalString::new("aé\u{1f600}");
+    assert_eq!(s.to_der_len(), Ok(2 + 4 * 3));
+    let v = s.to_der_vec().expect("serialization failed");
+    assert_eq!(&v, &hex!("1c 0c 00000061 000000e9 0001f600"));
+    let (_, s2) = UniversalString::from_der(&v).expect("decoding serialized object failed");
+    assert!(s.eq(&s2));
 }
 
 #[test]
@@ -510,3 +648,86 @@ fn to_der_visiblestring() {
 fn to_der_videotexstring() {
     test_simple_string!(VideotexString, "abcdef");
 }
+
+#[test]
+fn to_der_conversions() {
+    let v = Boolean::from(true)
+        .to_der_vec()
+        .expect("serialization failed");
+    assert_eq!(&v, &hex!("01 01 ff"));
+    assert_eq!(Boolean::default(), Boolean::FALSE);
+    assert!(!bool::from(Boolean::default()));
+    //
+    let v = Null::default().to_der_vec().expect("serialization failed");
+    assert_eq!(&v, &hex!("05 00"));
+    //
+    let v = OctetString::default()
+        .to_der_vec()
+        .expect("serialization failed");
+    assert_eq!(&v, &hex!("04 00"));
+    let v = OctetString::from(vec![1, 2, 3])
+        .to_der_vec()
+        .expect("serialization failed");
+    assert_eq!(&v, &hex!("04 03 01 02 03"));
+    //
+    let s: Ia5String = "abc".parse().expect("invalid string");
+    let v = s.to_der_vec().expect("serialization failed");
+    assert_eq!(&v, &hex!("16 03 61 62 63"));
+    let res = "é".parse::<Ia5String>();
+    assert_eq!(res, Err(Error::StringInvalidCharacters(vec!['é'])));
+}
+
+#[test]
+fn to_der_slice() {
+    let seq = Sequence::from_iter_to_der([1u32, 2, 3].iter()).expect("serialization failed");
+    let expected = seq.to_der_vec().expect("serialization failed");
+    let mut buf = [0xaau8; 32];
+    let sz = seq
+        .write_der_to_slice(&mut buf)
+        .expect("serialization failed");
+    assert_eq!(&buf[..sz], &expected[..]);
+    assert!(buf[sz..].iter().all(|&b| b == 0xaa));
+    // exact size
+    let mut buf = vec![0; expected.len()];
+    assert_eq!(seq.write_der_to_slice(&mut buf).ok(), Some(expected.len()));
+    // too small: nothing is written
+    let mut buf = [0u8; 4];
+    let res = seq.write_der_to_slice(&mut buf);
+    assert!(matches!(
+        res,
+        Err(SerializeError::BufferTooSmall { needed }) if needed == expected.len()
+    ));
+    assert_eq!(buf, [0; 4]);
+    // to_der_len includes the header
+    let r = Real::from(1.5);
+    let expected = r.to_der_vec().expect("serialization failed");
+    assert_eq!(r.to_der_len(), Ok(expected.len()));
+    let mut buf = vec![0; expected.len() - 2];
+    assert!(matches!(
+        r.write_der_to_slice(&mut buf),
+        Err(SerializeError::BufferTooSmall { needed }) if needed == expected.len()
+    ));
+    // high tag numbers
+    let t: TaggedImplicit<u32, Error, 200> = TaggedValue::implicit(2);
+    let expected = t.to_der_vec().expect("serialization failed");
+    assert_eq!(&expected, &hex!("9f 81 48 01 02"));
+    assert_eq!(t.to_der_len(), Ok(expected.len()));
+    let mut buf = [0u8; 3];
+    assert!(matches!(
+        t.write_der_to_slice(&mut buf),
+        Err(SerializeError::BufferTooSmall { needed: 5 })
+    ));
+    // long form length of the inner object
+    let data = [0x5a; 300];
+    let t: TaggedImplicit<OctetString, Error, 200> = TaggedValue::implicit(OctetString::new(&data));
+    let expected = t.to_der_vec().expect("serialization failed");
+    assert_eq!(&expected[..6], &hex!("9f 81 48 82 01 2c"));
+    assert_eq!(t.to_der_len(), Ok(expected.len()));
+    let t: TaggedExplicit<u32, Error, 200> = TaggedValue::explicit(2);
+    let expected = t.to_der_vec().expect("serialization failed");
+    assert_eq!(&expected, &hex!("bf 81 48 03 02 01 02"));
+    assert_eq!(t.to_der_len(), Ok(expected.len()));
+    let mut buf = [0u8; 7];
+    assert_eq!(t.write_der_to_slice(&mut buf).ok(), Some(7));
+    assert_eq!(buf, &expected[..]);
+}
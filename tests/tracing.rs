@@ -0,0 +1,111 @@
+#![cfg(feature = "tracing")]
+
+use asn1_rs::*;
+use hex_literal::hex;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+/// A span or an event, with its fields formatted using `Debug`
+#[derive(Debug)]
+struct Recorded {
+    name: String,
+    fields: Vec<(String, String)>,
+}
+
+impl Recorded {
+    fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+impl Visit for Recorded {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.fields
+            .push((field.name().to_string(), format!("{:?}", value)));
+    }
+}
+
+#[derive(Clone, Default)]
+struct Recorder {
+    spans: Arc<Mutex<Vec<Recorded>>>,
+    events: Arc<Mutex<Vec<Recorded>>>,
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut recorded = Recorded {
+            name: span.metadata().name().to_string(),
+            fields: Vec::new(),
+        };
+        span.record(&mut recorded);
+        let mut spans = self.spans.lock().unwrap();
+        spans.push(recorded);
+        Id::from_u64(spans.len() as u64)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut recorded = Recorded {
+            name: event.metadata().name().to_string(),
+            fields: Vec::new(),
+        };
+        event.record(&mut recorded);
+        self.events.lock().unwrap().push(recorded);
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+#[test]
+fn tracing_spans_and_events() {
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), || {
+        let (_, v) = u32::from_der(&hex!("02 01 2a")).expect("parsing failed");
+        assert_eq!(v, 42);
+        // non-canonical BOOLEAN
+        assert!(bool::from_der(&hex!("01 01 01")).is_err());
+        // truncated content
+        assert!(Any::from_ber(&hex!("04 82 01 00 00")).is_err());
+    });
+
+    let spans = recorder.spans.lock().unwrap();
+    let span = spans
+        .iter()
+        .find(|s| s.name == "from_der" && s.field("ty") == Some("\"u32\""))
+        .expect("no span for u32");
+    assert_eq!(span.field("class"), Some("UNIVERSAL"));
+    assert_eq!(span.field("tag"), Some("2"));
+    assert_eq!(span.field("header_len"), Some("2"));
+    assert_eq!(span.field("length"), Some("1"));
+    assert_eq!(span.field("input_len"), Some("3"));
+
+    let events = recorder.events.lock().unwrap();
+    let event = events
+        .iter()
+        .find(|e| e.field("message") == Some("DER constraint failed"))
+        .expect("no event for DER constraint");
+    assert!(event.field("error").is_some());
+    assert_eq!(event.field("header_len"), Some("2"));
+    let event = events
+        .iter()
+        .find(|e| e.field("message") == Some("invalid BER content"))
+        .expect("no event for BER content");
+    assert_eq!(event.field("tag"), Some("4"));
+    assert_eq!(event.field("header_len"), Some("4"));
+    assert_eq!(event.field("length"), Some("Definite(256)"));
+}
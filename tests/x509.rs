@@ -4,8 +4,8 @@
 //! and that provided API is convenient.
 
 use asn1_rs::{
-    nom, Any, CheckDerConstraints, Choice, Error, FromBer, FromDer, Oid, ParseResult, Sequence,
-    SetOf, Tag, Tagged,
+    nom, oid, AlgorithmIdentifier, Any, CheckDerConstraints, Choice, Error, FromBer, FromDer, Oid,
+    ParseResult, Sequence, SetOf, Tag, Tagged, ToDer,
 };
 use hex_literal::hex;
 use nom::sequence::pair;
@@ -156,3 +156,30 @@ fn x509_decode_dn() {
     // dbg!(&dn);
     assert_eq!(dn.rdn_sequence.len(), 3);
 }
+
+#[test]
+fn x509_algorithm_identifier() {
+    // sha256WithRSAEncryption, NULL parameters
+    let input = &hex!("30 0d 06 09 2a 86 48 86 f7 0d 01 01 0b 05 00");
+    let (rem, alg) = AlgorithmIdentifier::from_der(input).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(alg.algorithm, oid!(1.2.840 .113549 .1 .1 .11));
+    assert!(alg.assert_null_params().is_ok());
+    let expected = AlgorithmIdentifier::with_null_params(oid!(1.2.840 .113549 .1 .1 .11));
+    assert_eq!(alg, expected);
+    assert_eq!(expected.to_der_vec().expect("serialization failed"), input);
+    // ecPublicKey, with named curve parameters (prime256v1)
+    let input = &hex!("30 13 06 07 2a 86 48 ce 3d 02 01 06 08 2a 86 48 ce 3d 03 01 07");
+    let (_, alg) = AlgorithmIdentifier::from_der(input).expect("parsing failed");
+    assert!(alg.assert_null_params().is_err());
+    let curve = alg.params_as::<Oid>().expect("invalid parameters");
+    assert_eq!(curve, Some(oid!(1.2.840 .10045 .3 .1 .7)));
+    assert_eq!(alg.to_der_vec().expect("serialization failed"), input);
+    // Ed25519, absent parameters
+    let input = &hex!("30 05 06 03 2b 65 70");
+    let (_, alg) = AlgorithmIdentifier::from_der(input).expect("parsing failed");
+    assert!(alg.parameters.is_none());
+    assert!(alg.assert_null_params().is_err());
+    assert_eq!(alg.params_as::<Oid>(), Ok(None));
+    assert_eq!(alg.to_der_vec().expect("serialization failed"), input);
+}